@@ -8,6 +8,8 @@ pub enum ContentType {
     Xml,
 }
 
+// `#[derive(Default)]` on enums requires Rust 1.62, which is above our MSRV.
+#[allow(clippy::derivable_impls)]
 impl Default for ContentType {
     fn default() -> Self {
         Self::Json
@@ -34,6 +36,7 @@ pub struct ApiClient {
 }
 
 impl ApiClient {
+    #[allow(clippy::new_ret_no_self)]
     pub fn new(host: impl Into<String>, key: impl Into<String>) -> ApiClientBuilder {
         ApiClientBuilder {
             host: Some(host.into()),
@@ -0,0 +1,128 @@
+//! Stable keys of builder configurations, for builders with `#[builder(derive(ConfigKey))]`.
+
+use crate::export::core::hash::{Hash, Hasher};
+
+/// A builder whose configuration can be used as a key, e.g. to memoize expensive resources
+/// such as connection pools which are built from it.
+///
+/// This is implemented by builders with `#[builder(derive(ConfigKey))]`, along with
+/// `PartialEq`, `Eq` and `Hash` over the fields which have been set.
+pub trait ConfigKey: Hash + Eq {
+    /// Hash the fields which have been set with a [`StableHasher`].
+    ///
+    /// Unlike the hashers of `std`, the key doesn't change between processes or platforms, so it
+    /// can be persisted, e.g. as the name of a cache file.
+    fn config_key(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// An FNV-1a hasher without a random seed, which writes integers as little endian bytes.
+///
+/// The hashes it produces only depend on the bytes written by the `Hash` impls, so they are the
+/// same in every process and on every platform. It isn't resistant to HashDoS attacks.
+#[derive(Debug, Clone, Copy)]
+pub struct StableHasher {
+    state: u64,
+}
+
+impl StableHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    /// Create a hasher with the FNV offset basis as its state.
+    pub const fn new() -> Self {
+        StableHasher {
+            state: Self::OFFSET_BASIS,
+        }
+    }
+}
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.state
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= u64::from(*byte);
+            self.state = self.state.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64)
+    }
+
+    fn write_i16(&mut self, i: i16) {
+        self.write_u16(i as u16)
+    }
+
+    fn write_i32(&mut self, i: i32) {
+        self.write_u32(i as u32)
+    }
+
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64)
+    }
+
+    fn write_i128(&mut self, i: i128) {
+        self.write_u128(i as u128)
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write_i64(i as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fnv1a_test_vectors() {
+        let hash = |bytes: &[u8]| {
+            let mut hasher = StableHasher::new();
+            hasher.write(bytes);
+            hasher.finish()
+        };
+
+        assert_eq!(hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(hash(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn integers_are_little_endian() {
+        let mut from_usize = StableHasher::new();
+        from_usize.write_usize(1);
+        let mut from_bytes = StableHasher::new();
+        from_bytes.write(&[1, 0, 0, 0, 0, 0, 0, 0]);
+
+        assert_eq!(from_usize.finish(), from_bytes.finish());
+    }
+}
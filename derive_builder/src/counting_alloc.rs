@@ -0,0 +1,81 @@
+//! Counting of heap allocations, for the tests generated by `#[builder(assert_no_alloc_build)]`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A global allocator which delegates to the system allocator and counts the allocations of
+/// closures passed to [`count_allocations`].
+///
+/// It must be installed as the global allocator of the test binary:
+///
+/// ```rust
+/// use derive_builder::CountingAllocator;
+///
+/// #[global_allocator]
+/// static ALLOCATOR: CountingAllocator = CountingAllocator;
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CountingAllocator;
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    // `const` initializers of thread locals need a newer compiler than the MSRV.
+    #[allow(clippy::missing_const_for_thread_local)]
+    static COUNT: Cell<Option<usize>> = Cell::new(None);
+}
+
+fn record_allocation() {
+    INSTALLED.store(true, Ordering::Relaxed);
+    // The thread local may already be destroyed while the thread exits.
+    let _ = COUNT.try_with(|count| {
+        if let Some(n) = count.get() {
+            count.set(Some(n + 1));
+        }
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record_allocation();
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record_allocation();
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record_allocation();
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// Call `f` and count the heap allocations it makes on the current thread, including
+/// reallocations.
+///
+/// # Panics
+///
+/// If [`CountingAllocator`] isn't the global allocator, as no allocations could be counted.
+pub fn count_allocations<R, F: FnOnce() -> R>(f: F) -> (R, usize) {
+    assert!(
+        INSTALLED.load(Ordering::Relaxed),
+        "`CountingAllocator` must be the `#[global_allocator]` to count allocations"
+    );
+    let outer = COUNT.with(|count| count.replace(Some(0)));
+    let result = f();
+    let allocations = COUNT.with(|count| count.replace(outer)).unwrap_or_default();
+    COUNT.with(|count| {
+        if let Some(n) = outer {
+            count.set(Some(n + allocations));
+        }
+    });
+    (result, allocations)
+}
@@ -0,0 +1,85 @@
+//! Setting the fields of builders by name at runtime, for builders with `#[builder(dyn_set)]`.
+
+use crate::export::core::any::{Any, TypeId};
+use crate::export::core::boxed::Box;
+use crate::export::core::fmt;
+use crate::export::core::string::{String, ToString};
+
+#[cfg(feature = "std")]
+use std::error::Error;
+
+/// A builder whose fields can be set by name, with values whose type is only known at runtime.
+///
+/// This is implemented by builders with `#[builder(dyn_set)]`, so plugin systems can configure
+/// structs they only know by name and `TypeId` through a `&mut dyn DynSet`.
+pub trait DynSet {
+    /// Set the field named `field` to `value`, which must hold the type the setter of the
+    /// field takes.
+    fn set(&mut self, field: &str, value: Box<dyn Any>) -> Result<(), DynSetError>;
+
+    /// Get the `TypeId` of the value the setter of the field named `field` takes, or `None` if
+    /// the builder has no such field.
+    fn field_type(&self, field: &str) -> Option<TypeId>;
+}
+
+/// Runtime error when `DynSet::set` is called with an unknown field, or with a value of
+/// another type than the field takes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynSetError {
+    field_name: String,
+    kind: DynSetErrorKind,
+}
+
+/// The kind of error `DynSet::set` failed with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DynSetErrorKind {
+    /// The builder has no field with the name.
+    UnknownField,
+    /// The value has another type than the field takes, which is the contained type name.
+    TypeMismatch(&'static str),
+}
+
+impl DynSetError {
+    /// Create a new `DynSetError` for a field name which the builder doesn't have.
+    pub fn unknown_field(field_name: &str) -> Self {
+        DynSetError {
+            field_name: field_name.to_string(),
+            kind: DynSetErrorKind::UnknownField,
+        }
+    }
+
+    /// Create a new `DynSetError` for a value which isn't of the type `expected`, which the
+    /// field takes.
+    pub fn type_mismatch(field_name: &str, expected: &'static str) -> Self {
+        DynSetError {
+            field_name: field_name.to_string(),
+            kind: DynSetErrorKind::TypeMismatch(expected),
+        }
+    }
+
+    /// Get the name of the field which was passed to `DynSet::set`
+    pub fn field_name(&self) -> &str {
+        &self.field_name
+    }
+
+    /// Get the kind of the error
+    pub fn kind(&self) -> DynSetErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for DynSetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            DynSetErrorKind::UnknownField => write!(f, "unknown field `{}`", self.field_name),
+            DynSetErrorKind::TypeMismatch(expected) => write!(
+                f,
+                "`{}` takes a value of type `{}`",
+                self.field_name, expected
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for DynSetError {}
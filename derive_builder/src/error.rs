@@ -4,6 +4,9 @@ use std::{error::Error, fmt};
 #[cfg(not(feature = "std"))]
 use core::fmt;
 
+#[cfg(any(feature = "alloc", feature = "std"))]
+use crate::export::core::{string::String, string::ToString, vec::Vec};
+
 /// Runtime error when a `build()` method is called and one or more required fields
 /// do not have a value.
 #[derive(Debug, Clone)]
@@ -30,8 +33,274 @@ impl fmt::Display for UninitializedFieldError {
 #[cfg(feature = "std")]
 impl Error for UninitializedFieldError {}
 
+/// Runtime error when a `build()` method of a builder with
+/// `#[builder(build_fn(collect_errors))]` is called and one or more required fields do not
+/// have a value.
+///
+/// Unlike [`UninitializedFieldError`], it lists all the uninitialized fields.
+#[cfg(any(feature = "alloc", feature = "std"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UninitializedFieldsError(Vec<&'static str>);
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl UninitializedFieldsError {
+    /// Create a new `UninitializedFieldsError` for the specified field names.
+    pub fn new(field_names: Vec<&'static str>) -> Self {
+        UninitializedFieldsError(field_names)
+    }
+
+    /// Get the names of the fields which weren't initialized
+    pub fn field_names(&self) -> &[&'static str] {
+        &self.0
+    }
+}
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl fmt::Display for UninitializedFieldsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Fields not initialized: ")?;
+        for (i, field_name) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", field_name)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for UninitializedFieldsError {}
+
+/// Runtime error when a `build()` method is called and a `requires` or `conflicts_with`
+/// dependency between two fields is not satisfied.
+#[derive(Debug, Clone)]
+pub struct FieldDependencyError {
+    field_name: &'static str,
+    other_field_name: &'static str,
+    kind: FieldDependencyKind,
+}
+
+/// The kind of dependency between two fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldDependencyKind {
+    /// The field can only be set if the other field is set too.
+    Requires,
+    /// The field can only be set if the other field is not set.
+    ConflictsWith,
+}
+
+impl FieldDependencyError {
+    /// Create a new `FieldDependencyError` for a field which is set although the other field
+    /// it requires is not.
+    pub fn requires(field_name: &'static str, other_field_name: &'static str) -> Self {
+        FieldDependencyError {
+            field_name,
+            other_field_name,
+            kind: FieldDependencyKind::Requires,
+        }
+    }
+
+    /// Create a new `FieldDependencyError` for a field which is set together with another field
+    /// it conflicts with.
+    pub fn conflicts_with(field_name: &'static str, other_field_name: &'static str) -> Self {
+        FieldDependencyError {
+            field_name,
+            other_field_name,
+            kind: FieldDependencyKind::ConflictsWith,
+        }
+    }
+
+    /// Get the name of the field which declared the dependency
+    pub fn field_name(&self) -> &'static str {
+        self.field_name
+    }
+
+    /// Get the name of the field which is required or conflicting
+    pub fn other_field_name(&self) -> &'static str {
+        self.other_field_name
+    }
+
+    /// Get the kind of the dependency
+    pub fn kind(&self) -> FieldDependencyKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for FieldDependencyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            FieldDependencyKind::Requires => write!(
+                f,
+                "`{}` requires `{}` to be set",
+                self.field_name, self.other_field_name
+            ),
+            FieldDependencyKind::ConflictsWith => write!(
+                f,
+                "`{}` conflicts with `{}`",
+                self.field_name, self.other_field_name
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for FieldDependencyError {}
+
+/// Error of a `#[builder(setter(array))]` setter, when the number of items does not match the
+/// length of the array field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArrayLengthError {
+    field_name: &'static str,
+    expected: usize,
+    actual: usize,
+}
+
+impl ArrayLengthError {
+    /// Create a new `ArrayLengthError` for an array field of length `expected`, which was set
+    /// from `actual` items.
+    pub fn new(field_name: &'static str, expected: usize, actual: usize) -> Self {
+        ArrayLengthError {
+            field_name,
+            expected,
+            actual,
+        }
+    }
+
+    /// Get the name of the array field
+    pub fn field_name(&self) -> &'static str {
+        self.field_name
+    }
+
+    /// Get the length of the array
+    pub fn expected(&self) -> usize {
+        self.expected
+    }
+
+    /// Get the number of items the field was set from
+    pub fn actual(&self) -> usize {
+        self.actual
+    }
+}
+
+impl fmt::Display for ArrayLengthError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "`{}` takes {} items, but {} were given",
+            self.field_name, self.expected, self.actual
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ArrayLengthError {}
+
+/// Error of a `build()` method, which tells if it failed because a required field was not
+/// initialized.
+///
+/// The generated error types implement it, so a builder with a `#[builder(delegate)]` field can
+/// name the uninitialized field of the nested builder with its path, e.g. `database.pool_size`.
+/// A custom error type of a nested builder has to implement it too.
+pub trait BuilderError: fmt::Display {
+    /// Get the name of the field which was not initialized, if that is why the build failed.
+    ///
+    /// For a field of a nested builder, this is its path, e.g. `database.pool_size`.
+    fn uninitialized_field(&self) -> Option<&str>;
+}
+
+/// Error of the nested builder of a `#[builder(delegate)]` field, which names the field.
+#[cfg(any(feature = "alloc", feature = "std"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NestedFieldError {
+    field_name: &'static str,
+    uninitialized_field: Option<String>,
+    message: String,
+}
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl NestedFieldError {
+    /// Create a new `NestedFieldError` for the `error` of the nested builder of the field
+    /// `field_name`.
+    pub fn new<E: BuilderError + ?Sized>(field_name: &'static str, error: &E) -> Self {
+        NestedFieldError {
+            field_name,
+            uninitialized_field: error
+                .uninitialized_field()
+                .map(|nested| [field_name, nested].join(".")),
+            message: error.to_string(),
+        }
+    }
+
+    /// Get the name of the field whose nested builder failed
+    pub fn field_name(&self) -> &'static str {
+        self.field_name
+    }
+
+    /// Get the message of the error of the nested builder
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl BuilderError for NestedFieldError {
+    fn uninitialized_field(&self) -> Option<&str> {
+        self.uninitialized_field.as_deref()
+    }
+}
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+impl fmt::Display for NestedFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.uninitialized_field {
+            Some(ref path) => write!(f, "`{}` must be initialized", path),
+            None => write!(f, "{}: {}", self.field_name, self.message),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for NestedFieldError {}
+
+/// The kind of error a generated `build()` method failed with, which is passed to a
+/// `#[builder(build_fn(message_fn = "..."))]` function to produce the error message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BuilderErrorKind {
+    /// A required field does not have a value.
+    UninitializedField,
+}
+
 impl From<&'static str> for UninitializedFieldError {
     fn from(field_name: &'static str) -> Self {
         Self::new(field_name)
     }
 }
+
+/// Names of the fields which are missing in a builder, paired with whether each one is
+/// missing, for the panic message of `build_or_panic()`.
+#[doc(hidden)]
+pub struct MissingFields<'a>(pub &'a [(bool, &'static str)]);
+
+impl<'a> MissingFields<'a> {
+    /// Whether any of the fields is missing.
+    pub fn any(&self) -> bool {
+        self.0.iter().any(|&(missing, _)| missing)
+    }
+}
+
+impl<'a> fmt::Display for MissingFields<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut names = self.0.iter().filter(|&&(missing, _)| missing);
+        let count = names.clone().count();
+        write!(f, "missing field{}", if count == 1 { "" } else { "s" })?;
+        if let Some(&(_, first)) = names.next() {
+            write!(f, " `{}`", first)?;
+        }
+        for &(_, name) in names {
+            write!(f, ", `{}`", name)?;
+        }
+        Ok(())
+    }
+}
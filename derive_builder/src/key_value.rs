@@ -0,0 +1,110 @@
+//! Parsing of comma-separated `key=value` lists for the `FromStr` impls of builders.
+
+use crate::export::core::fmt::{self, Write};
+use crate::export::core::str::FromStr;
+use crate::export::core::string::{String, ToString};
+
+#[cfg(feature = "std")]
+use std::error::Error;
+
+/// Runtime error when the `FromStr` impl of a builder is given a malformed `key=value` pair,
+/// an unknown key, or a value which can't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyValueError {
+    key: String,
+    message: String,
+}
+
+impl KeyValueError {
+    /// Create a new `KeyValueError` for the pair with the key `key`.
+    pub fn new(key: String, message: String) -> Self {
+        KeyValueError { key, message }
+    }
+
+    /// Get the key of the pair, or the whole pair if it has no `=`
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Get the description of what is wrong with the pair
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for KeyValueError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}`: {}", self.key, self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for KeyValueError {}
+
+/// Split a comma-separated list into its `key=value` pairs, trimming whitespace.
+pub fn pairs(s: &str) -> impl Iterator<Item = Result<(&str, &str), KeyValueError>> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) => Ok((key.trim_end(), value.trim_start())),
+            None => Err(KeyValueError::new(
+                pair.to_string(),
+                "expected `key=value`".to_string(),
+            )),
+        })
+}
+
+/// Parse the value of the pair with the key `key`.
+pub fn parse<T>(key: &str, value: &str) -> Result<T, KeyValueError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    value
+        .parse()
+        .map_err(|e: T::Err| KeyValueError::new(key.to_string(), e.to_string()))
+}
+
+/// Create the error for a key which doesn't match any of the `expected` keys.
+pub fn unknown_key(key: &str, expected: &[&str]) -> KeyValueError {
+    let mut message = String::from("unknown key");
+    for (i, expected) in expected.iter().enumerate() {
+        let separator = if i == 0 { ", expected one of " } else { ", " };
+        // Writing to a `String` can't fail.
+        let _ = write!(message, "{}`{}`", separator, expected);
+    }
+    KeyValueError::new(key.to_string(), message)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_pairs() {
+        let pairs: Result<Vec<_>, _> = pairs(" host = db,port=5432,, tls=").collect();
+        assert_eq!(
+            pairs.unwrap(),
+            vec![("host", "db"), ("port", "5432"), ("tls", "")]
+        );
+    }
+
+    #[test]
+    fn missing_value() {
+        let err = pairs("host=db,tls").nth(1).unwrap().unwrap_err();
+        assert_eq!(err.to_string(), "`tls`: expected `key=value`");
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            parse::<bool>("tls", "yes").unwrap_err().to_string(),
+            "`tls`: provided string was not `true` or `false`"
+        );
+        assert_eq!(
+            unknown_key("user", &["host", "port"]).to_string(),
+            "`user`: unknown key, expected one of `host`, `port`"
+        );
+    }
+}
@@ -0,0 +1,42 @@
+//! Values of `#[builder(setter(lazy))]` fields, which are computed by the build method.
+
+use crate::export::core::fmt;
+use crate::export::core::sync::Arc;
+
+/// A closure which computes the value of a field when the builder builds, set by the `_with`
+/// setter of a field with `#[builder(setter(lazy))]`.
+///
+/// The closure is shared by clones of the builder, and called by every build which needs the
+/// value, so it is `Fn` rather than `FnOnce`.
+pub struct LazyValue<T> {
+    f: Arc<dyn Fn() -> T + Send + Sync>,
+}
+
+impl<T> LazyValue<T> {
+    /// Create a new `LazyValue` which computes the value with `f`.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        LazyValue { f: Arc::new(f) }
+    }
+
+    /// Compute the value.
+    pub fn get(&self) -> T {
+        (self.f)()
+    }
+}
+
+impl<T> Clone for LazyValue<T> {
+    fn clone(&self) -> Self {
+        LazyValue {
+            f: Arc::clone(&self.f),
+        }
+    }
+}
+
+impl<T> fmt::Debug for LazyValue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("LazyValue(..)")
+    }
+}
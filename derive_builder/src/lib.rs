@@ -119,6 +119,29 @@
 //! * CON: If you don't chain your calls, you have to create a reference to each return value,
 //!   e.g. `builder = builder.ipsum(42)`.
 //!
+//! A failed build consumes the builder too, unless you add `#[builder(build_fn(return_builder))]`.
+//! The build method then returns the builder together with the error, so it can be fixed and
+//! built again. The values of the fields are cloned by the build method, so they must implement
+//! `Clone`.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #[derive(Builder, Debug)]
+//! #[builder(pattern = "owned", build_fn(return_builder))]
+//! struct Lorem {
+//!     ipsum: u32,
+//!     dolor: u32,
+//! }
+//!
+//! # fn main() {
+//! let (builder, error) = LoremBuilder::default().ipsum(1).build().unwrap_err();
+//! assert_eq!(error.to_string(), "`dolor` must be initialized");
+//!
+//! let lorem = builder.dolor(2).build().ok().unwrap();
+//! assert_eq!(lorem.ipsum, 1);
+//! # }
+//! ```
+//!
 //! ## Mutable, aka Non-Consuming (recommended)
 //!
 //! This pattern is recommended and active by default if you don't specify anything else.
@@ -141,6 +164,45 @@
 //! * CON: The build method _and each setter_ must clone or copy data to create something owned
 //!   out of a reference. **(*)**
 //!
+//! ## Mixing Patterns
+//!
+//! A field-level `pattern` only changes the setters of that field, so they must still chain with
+//! the builder's other setters and build method:
+//!
+//! * `immutable` setters can be used on any builder, but need the builder to be `Clone`.
+//! * `mutable` setters can be used on immutable builders. They are rejected on owned builders,
+//!   because the returned `&mut Self` can't be passed on to the owned setters or build method.
+//! * On a mutable or immutable builder, `owned` keeps the regular setter of the builder's pattern
+//!   and adds an owned `with_` setter, e.g. to start a chain from `FooBuilder::default()`.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! struct Lorem {
+//!     #[builder(pattern = "owned", setter(into))]
+//!     ipsum: String,
+//!     dolor: u32,
+//! }
+//!
+//! fn main() {
+//!     let x = LoremBuilder::default()
+//!         .with_ipsum("lorem")
+//!         .dolor(42)
+//!         .build()
+//!         .unwrap();
+//!
+//!     assert_eq!(x, Lorem { ipsum: "lorem".into(), dolor: 42 });
+//! }
+//! ```
+//!
+//! ## Unused Setter Calls
+//!
+//! Setters which take and return the builder by value, i.e. those of the `owned` and
+//! `immutable` patterns and `with_` setters, are marked `#[must_use]`, as dropping their return
+//! value drops the builder with the value which was just set. `#[builder(must_use)]` marks all
+//! setters and the builder struct itself, and `#[builder(must_use = false)]` marks none.
+//!
 //! ## (*) Performance Considerations
 //!
 //! Luckily Rust is clever enough to optimize these clone-calls away in release builds
@@ -148,6 +210,29 @@
 //! Switching to consuming signatures (=`self`) is unlikely to give you any performance
 //! gain, but very likely to restrict your API for non-chained use cases.
 //!
+//! Types which rely on this can check it with `#[builder(assert_no_alloc_build)]`, which
+//! generates a unit test asserting that building from an empty builder doesn't allocate. All
+//! fields need defaults, and the test binary must count allocations with the
+//! `CountingAllocator` of the `std` feature:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! use derive_builder::CountingAllocator;
+//!
+//! #[global_allocator]
+//! static ALLOCATOR: CountingAllocator = CountingAllocator;
+//!
+//! #[derive(Builder, Clone, Copy)]
+//! #[builder(assert_no_alloc_build)]
+//! struct Point {
+//!     #[builder(default)]
+//!     x: i32,
+//!     #[builder(default)]
+//!     y: i32,
+//! }
+//! # fn main() {}
+//! ```
+//!
 //! # More Features
 //!
 //! ## Hidden Fields
@@ -204,6 +289,34 @@
 //! - `#[builder(setter(custom = true))]`
 //! - `#[builder(setter(custom = false))]`
 //!
+//! ## Mutators
+//!
+//! Methods which set several fields at once can be written in `#[builder(mutators(...))]`,
+//! next to the struct. They are added to the `impl` block of the builder as they are written,
+//! so they see its fields as `Option<T>`:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder)]
+//! #[builder(mutators(
+//!     pub fn preset_fast(&mut self) -> &mut Self {
+//!         self.timeout_ms = Some(100);
+//!         self.retries = Some(0);
+//!         self
+//!     }
+//! ))]
+//! struct Client {
+//!     timeout_ms: u32,
+//!     retries: u8,
+//! }
+//!
+//! fn main() {
+//!     let client = ClientBuilder::default().preset_fast().build().unwrap();
+//!     assert_eq!(client.retries, 0);
+//! }
+//! ```
+//!
 //! ## Setter Visibility
 //!
 //! Setters are public by default. You can precede your struct (or field) with `#[builder(public)]`
@@ -212,6 +325,41 @@
 //! Otherwise precede your struct (or field) with `#[builder(private)]` to opt into private
 //! setters.
 //!
+//! On the struct level, this also sets the visibility of the builder. To give the setters another
+//! visibility than the builder, use `#[builder(setter(public))]`, `setter(private)` or
+//! `setter(vis = "...")`. Fields can still override it, e.g. to expose a few setters of a
+//! builder which is otherwise only set up by your crate:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #[derive(Builder)]
+//! #[builder(public, setter(vis = "pub(crate)"))]
+//! pub struct Lorem {
+//!     ipsum: u32,
+//!     #[builder(public)]
+//!     dolor: u32,
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! The build method has the visibility of the builder, unless it is set with
+//! `#[builder(build_fn(public))]`, `build_fn(private)` or `build_fn(vis = "...")`. This is
+//! independent of the visibility of the builder and its setters, e.g. for a public builder which
+//! only your crate can build:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #[derive(Builder)]
+//! #[builder(public, build_fn(vis = "pub(crate)"))]
+//! pub struct Lorem {
+//!     ipsum: u32,
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! The other methods which build the struct, e.g. `build_with_fallback`, have the same
+//! visibility as the build method.
+//!
 //! ## Generated builder struct name
 //!
 //! By default, the builder struct for `struct Foo` is `FooBuilder`.
@@ -238,9 +386,60 @@
 //! - Alternatively you can set a prefix via `#[builder(setter(prefix = "xyz"))`, which will change
 //!   the method name to `xyz_foo` if the field is named `foo`. Note that an underscore is
 //!   inserted, since Rust favors snake case here.
+//! - A suffix set via `#[builder(setter(suffix = "mut"))` is appended the same way, turning the
+//!   method name into `foo_mut`. It can be combined with a prefix, e.g. into `set_foo_value`.
+//!
+//! Prefixes and suffixes can also be defined on the struct level, but renames only work on
+//! fields. Renames take precedence over prefix and suffix definitions.
+//!
+//! For bindings where snake case method names are unidiomatic, a case convention can be applied
+//! to all setter names on the struct level with `#[builder(setter(rename_all = "camelCase"))]`.
+//! The supported conventions are `lowercase`, `UPPERCASE`, `PascalCase`, `camelCase`,
+//! `snake_case` and `SCREAMING_SNAKE_CASE`. The convention is applied after the prefix, so
+//! `prefix = "with"` turns the setter of `foo_bar` into `withFooBar`. Names set with
+//! `setter(name = "...")` are left as they are, and `try_` setters keep their prefix.
+//!
+//! Field names which aren't in `snake_case`, e.g. in structs generated from C headers, are split
+//! into words at their capitals first, so `rename_all = "snake_case"` names the setter of
+//! `tempoRate` `tempo_rate`, and that of `HTTPPort` `http_port`. `lowercase` and `UPPERCASE` only
+//! change the case.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! #[builder(setter(rename_all = "camelCase"))]
+//! struct Lorem {
+//!     ipsum_dolor: u32,
+//! }
+//!
+//! fn main() {
+//!     let x = LoremBuilder::default().ipsumDolor(42).build().unwrap();
+//!
+//!     assert_eq!(x, Lorem { ipsum_dolor: 42 });
+//! }
+//! ```
+//!
+//! To rename a setter without breaking its callers, keep its old name with
+//! `#[builder(setter(alias = "old_name"))]`, which can be repeated. Each alias is an additional
+//! setter doing the same as the renamed one, marked `#[deprecated]` with a note pointing to it:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! struct Lorem {
+//!     #[builder(setter(alias = "ipsum"))]
+//!     dolor: u32,
+//! }
+//!
+//! #[allow(deprecated)]
+//! fn main() {
+//!     let x = LoremBuilder::default().ipsum(42).build().unwrap();
 //!
-//! Prefixes can also be defined on the struct level, but renames only work on fields. Renames
-//! take precedence over prefix definitions.
+//!     assert_eq!(x, Lorem { dolor: 42 });
+//! }
+//! ```
 //!
 //! ## Generic Setters
 //!
@@ -266,6 +465,53 @@
 //! }
 //! ```
 //!
+//! These setters have a type parameter, which callers can name in a turbofish. It is named
+//! `VALUE`, or `VALUE_` if the struct has a generic parameter named `VALUE`, unless you name it
+//! with `#[builder(setter(generic = "V"))]`. `#[builder(setter(into_impl))]` makes the setters
+//! take a `value: impl Into<T>` instead, and implies `into`.
+//!
+//! ## Converting at Build
+//!
+//! Setters convert values when they are called, so the builder only ever holds the field's
+//! type. With `#[builder(setter(convert = "at_build", from = "..."))]`, the setter instead stores
+//! the value as the `from` type, and `build` converts it with `TryFrom`. This defers expensive
+//! conversions until the builder is complete, lets validators see the value as it was passed, and
+//! turns conversion failures into errors of `build` rather than of the setter.
+//! `convert = "at_set"` is the default.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! # use std::convert::TryFrom;
+//! #
+//! #[derive(Debug, PartialEq)]
+//! struct Port(u16);
+//!
+//! impl TryFrom<String> for Port {
+//!     type Error = std::num::ParseIntError;
+//!
+//!     fn try_from(s: String) -> Result<Self, Self::Error> {
+//!         s.parse().map(Port)
+//!     }
+//! }
+//!
+//! #[derive(Builder, Debug, PartialEq)]
+//! struct Server {
+//!     #[builder(setter(into, convert = "at_build", from = "String"))]
+//!     port: Port,
+//! }
+//!
+//! # fn main() {
+//! let server = ServerBuilder::default().port("8080").build().unwrap();
+//! assert_eq!(server.port, Port(8080));
+//!
+//! let err = ServerBuilder::default().port("http").build().unwrap_err();
+//! assert_eq!(&err.to_string(), "invalid digit found in string");
+//! # }
+//! ```
+//!
+//! Conversion errors become validation errors with `ToString`, so a custom error type must
+//! implement `From<String>`. Defaults are of the field's type and aren't converted.
+//!
 //! ## Setters for Option
 //!
 //! You can avoid wrapping values in `Some(...)` for fields of type `Option<T>`. It's as simple as adding
@@ -294,218 +540,2004 @@
 //! ```
 //! If you want to set the value to None when unset, then enable `default` on this field (or do not use `strip_option`).
 //!
-//! Limitation: only the `Option` type name is supported, not type alias nor `std::option::Option`.
-//!
-//! ## Fallible Setters
-//!
-//! Alongside the normal setter methods, you can expose fallible setters which are generic over
-//! the `TryInto` trait. TryInto is similar to `Into` with the key distinction that the conversion
-//! can fail, and therefore produces a `Result`.
-//!
-//! You can only declare the `try_setter` attribute today if you're targeting nightly, and you have
-//! to add `#![feature(try_from)]` to your crate to use it.
+//! To pass through an `Option` you already have, add `maybe` next to `strip_option`. This
+//! generates an additional `maybe_foo` setter which takes an `Option<T>` (or `Option<VALUE>`
+//! with `into`) and stores it as is:
 //!
 //! ```rust
 //! # use derive_builder::Builder;
+//! #
 //! #[derive(Builder, Debug, PartialEq)]
-//! #[builder(try_setter, setter(into))]
 //! struct Lorem {
-//!     pub name: String,
-//!     pub ipsum: u8,
+//!     #[builder(setter(into, strip_option, maybe), default)]
+//!     pub ipsum: Option<String>,
+//! }
+//!
+//! fn main() {
+//!     let from_config: Option<&str> = None;
+//!     let x = LoremBuilder::default().maybe_ipsum(from_config).build().unwrap();
+//!
+//!     assert_eq!(x, Lorem { ipsum: None });
 //! }
+//! ```
+//!
+//! Limitation: only the `Option` type name is supported, not type alias nor `std::option::Option`.
 //!
+//! Without `strip_option`, `maybe` generates a `maybe_foo` setter which sets the field if it is
+//! given `Some` value, and leaves the builder as it is for `None`:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
 //! #[derive(Builder, Debug, PartialEq)]
-//! struct Ipsum {
-//!     #[builder(try_setter, setter(into, name = "foo"))]
-//!     pub dolor: u8,
+//! struct Server {
+//!     #[builder(setter(into, maybe), default = "8080")]
+//!     port: u16,
 //! }
 //!
 //! fn main() {
-//!    LoremBuilder::default()
-//!        .try_ipsum(1u16).unwrap()
-//!        .name("hello")
-//!        .build()
-//!        .expect("1 fits into a u8");
+//!     let from_env: Option<u16> = None;
+//!     let x = ServerBuilder::default().maybe_port(from_env).build().unwrap();
 //!
-//!    IpsumBuilder::default()
-//!        .try_foo(1u16).unwrap()
-//!        .build()
-//!        .expect("1 fits into a u8");
+//!     assert_eq!(x, Server { port: 8080 });
 //! }
 //! ```
 //!
-//! ## Default Values
+//! ## Conditional Setters
 //!
-//! You can define default values for each field via annotation by `#[builder(default = "...")]`,
-//! where `...` stands for any Rust expression and must be string-escaped, e.g.
+//! With `#[builder(setter(conditional))]` on the struct or a field, an additional `foo_if`
+//! setter takes a `bool` before the value and only sets the field if it is `true`, which keeps
+//! a chain of setter calls readable:
 //!
-//! * `#[builder(default = "42")]`
-//! * `#[builder(default)]` delegates to the [`Default`] trait of the base type.
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! #[builder(setter(conditional))]
+//! struct Request {
+//!     #[builder(default = "1")]
+//!     retries: u8,
+//!     #[builder(setter(into), default)]
+//!     proxy: String,
+//! }
 //!
-//! The expression will be evaluated with each call to `build`.
+//! fn main() {
+//!     let offline = true;
+//!     let x = RequestBuilder::default()
+//!         .retries_if(offline, 5)
+//!         .proxy_if(!offline, "http://proxy")
+//!         .build()
+//!         .unwrap();
+//!
+//!     assert_eq!(x, Request { retries: 5, proxy: String::new() });
+//! }
+//! ```
+//!
+//! ## Flag Setters
+//!
+//! With `#[builder(setter(strip_bool))]`, the setter of a `bool` field takes no parameter and
+//! sets the field to `true`. Unless it has another default, the field is `false` if the setter
+//! isn't called. Applied to the whole struct, this only affects fields of type `bool`:
 //!
 //! ```rust
 //! # use derive_builder::Builder;
 //! #
 //! #[derive(Builder, Debug, PartialEq)]
-//! struct Lorem {
-//!     #[builder(default = "42")]
-//!     pub ipsum: u32,
+//! #[builder(setter(strip_bool))]
+//! struct Options {
+//!     verbose: bool,
+//!     force: bool,
 //! }
 //!
 //! fn main() {
-//!     // If we don't set the field `ipsum`,
-//!     let x = LoremBuilder::default().build().unwrap();
+//!     let x = OptionsBuilder::default().verbose().build().unwrap();
 //!
-//!     // .. the custom default will be used for `ipsum`:
-//!     assert_eq!(x, Lorem {
-//!         ipsum: 42,
-//!     });
+//!     assert_eq!(x, Options { verbose: true, force: false });
 //! }
 //! ```
 //!
-//! ### Tips on Defaults
-//!
-//! * The `#[builder(default)]` annotation can be used on the struct level, too. Overrides are
-//!   still possible.
-//! * Delegate to a private helper method on `FooBuilder` for anything fancy. This way
-//!   you will get _much better error diagnostics_ from the rust compiler and it will be _much
-//!   more readable_ for other human beings. :-)
-//! * Defaults will not work while using `#[builder(build_fn(skip))]`. In this case, you'll
-//!   need to handle default values yourself when converting from the builder, such as by
-//!   using `.unwrap_or()` and `.unwrap_or_else()`.
+//! ## Boxed Setters
 //!
-//! [`Default`]: https://doc.rust-lang.org/std/default/trait.Default.html
+//! Recursive types need a `Box` to have a finite size. With `#[builder(setter(boxed))]`, the
+//! setter takes the contents of the `Box` and boxes them. Combined with `strip_option`, a field
+//! of type `Option<Box<T>>` can be set from a plain `T`:
 //!
 //! ```rust
 //! # use derive_builder::Builder;
 //! #
-//! # #[derive(Builder, PartialEq, Debug)]
-//! struct Lorem {
-//!     ipsum: String,
-//!     // Custom defaults can delegate to helper methods
-//!     // and pass errors to the enclosing `build()` method via `?`.
-//!     #[builder(default = "self.default_dolor()?")]
-//!     dolor: String,
+//! #[derive(Builder, Clone, Debug, PartialEq)]
+//! struct Node {
+//!     value: u32,
+//!     #[builder(setter(into, strip_option, boxed), default)]
+//!     next: Option<Box<Node>>,
 //! }
 //!
-//! impl LoremBuilder {
-//!     // Private helper method with access to the builder struct.
-//!     fn default_dolor(&self) -> Result<String, String> {
-//!         match self.ipsum {
-//!             Some(ref x) if x.chars().count() > 3 => Ok(format!("dolor {}", x)),
-//!             _ => Err("ipsum must at least 3 chars to build dolor".to_string()),
-//!         }
-//!     }
-//! }
+//! fn main() {
+//!     let tail = NodeBuilder::default().value(2).build().unwrap();
+//!     let head = NodeBuilder::default().value(1).next(tail).build().unwrap();
 //!
-//! # fn main() {
-//! #     let x = LoremBuilder::default()
-//! #         .ipsum("ipsum".to_string())
-//! #         .build()
-//! #         .unwrap();
-//! #
-//! #     assert_eq!(x, Lorem {
-//! #         ipsum: "ipsum".to_string(),
-//! #         dolor: "dolor ipsum".to_string(),
-//! #     });
-//! # }
+//!     assert_eq!(head.next.unwrap().value, 2);
+//! }
 //! ```
 //!
-//! You can even reference other fields, but you have to remember that the builder struct
-//! will wrap every type in an Option ([as illustrated earlier](#what-you-get)).
+//! Like `strip_option`, `boxed` can be applied to the whole struct, and only the `Box` type
+//! name is recognized.
 //!
-//! ## Generic Structs
+//! ## Borrowing Setters
+//!
+//! Zero-copy structs often hold `Cow<'a, str>` or `Cow<'a, [u8]>` fields. With
+//! `#[builder(setter(borrowed))]`, the setter of a `Cow<'a, T>` field takes a `&'a T` and
+//! stores `Cow::Borrowed`, so setting the field never allocates:
 //!
 //! ```rust
 //! # use derive_builder::Builder;
+//! # use std::borrow::Cow;
 //! #
-//! #[derive(Builder, Debug, PartialEq, Default, Clone)]
-//! struct GenLorem<T: Clone> {
-//!     ipsum: &'static str,
-//!     dolor: T,
+//! #[derive(Builder, Debug)]
+//! #[builder(setter(borrowed), pattern = "owned")]
+//! struct Record<'a> {
+//!     name: Cow<'a, str>,
+//!     #[builder(setter(strip_option), default)]
+//!     payload: Option<Cow<'a, [u8]>>,
 //! }
 //!
 //! fn main() {
-//!     let x = GenLoremBuilder::default().ipsum("sit").dolor(42).build().unwrap();
-//!     assert_eq!(x, GenLorem { ipsum: "sit".into(), dolor: 42 });
+//!     let line = String::from("lorem,ipsum");
+//!     let record = RecordBuilder::default()
+//!         .name(&line[..5])
+//!         .payload(line[6..].as_bytes())
+//!         .build()
+//!         .unwrap();
+//!
+//!     assert!(matches!(record.name, Cow::Borrowed("lorem")));
 //! }
 //! ```
 //!
-//! ## Build Method Customization
+//! With `#[builder(setter(borrowed, cow_owned))]`, each borrowing setter also comes with an
+//! `_owned` setter named after it, which takes the owned type of the `Cow`, e.g. a `String` for a
+//! `Cow<'a, str>`, and stores `Cow::Owned` without copying it:
 //!
-//! You can rename or suppress the auto-generated build method, leaving you free to implement
-//! your own version. Suppression is done using `#[builder(build_fn(skip))]` at the struct level,
-//! and renaming is done with `#[builder(build_fn(name = "YOUR_NAME"))]`.
+//! ```rust
+//! # use derive_builder::Builder;
+//! # use std::borrow::Cow;
+//! #
+//! #[derive(Builder, Debug)]
+//! #[builder(setter(borrowed, cow_owned))]
+//! struct Label<'a> {
+//!     name: Cow<'a, str>,
+//! }
 //!
-//! ## Pre-Build Validation
+//! fn main() {
+//!     let label = LabelBuilder::default()
+//!         .name_owned(format!("{}-{}", "lorem", 1))
+//!         .build()
+//!         .unwrap();
 //!
-//! If you're using the provided `build` method, you can declare
-//! `#[builder(build_fn(validate = "path::to::fn"))]` to specify a validator function which gets
-//! access to the builder before construction. The path does not need to be fully-qualified, and
-//! will consider `use` statements made at module level. It must be accessible from the scope
-//! where the target struct is declared.
+//!     assert!(matches!(label.name, Cow::Owned(_)));
+//! }
+//! ```
 //!
-//! The provided function must have the signature `(&FooBuilder) -> Result<_, String>`;
-//! the `Ok` variant is not used by the `build` method.
+//! `borrowed` can be applied to the whole struct, in which case fields of other types keep
+//! their regular setters. Only the `Cow` type name is recognized. With the owned pattern, `build`
+//! moves the values out of the builder; the other patterns clone them, which doesn't allocate
+//! for `Cow::Borrowed` either. Borrowing setters are skipped by `from_str`, `from_query` and
+//! `dyn_set`, which can't produce borrowed values.
+//!
+//! ## Closure Setters
+//!
+//! Callbacks are usually stored as `Box<dyn Fn(...)>`. With `#[builder(setter(closure))]`, the
+//! setter is generic over the traits of the trait object and boxes the value, so closures can
+//! be passed directly. A `'static` bound is added unless the trait object has a lifetime:
 //!
 //! ```rust
 //! # use derive_builder::Builder;
 //! #
-//! #[derive(Builder, Debug, PartialEq)]
-//! #[builder(build_fn(validate = "Self::validate"))]
-//! struct Lorem {
-//!     pub ipsum: u8,
+//! #[derive(Builder)]
+//! #[builder(pattern = "owned")]
+//! struct Button {
+//!     #[builder(setter(closure))]
+//!     on_click: Box<dyn Fn(u32) -> String + Send>,
 //! }
 //!
-//! impl LoremBuilder {
+//! fn main() {
+//!     let button = ButtonBuilder::default()
+//!         .on_click(|clicks| format!("clicked {} times", clicks))
+//!         .build()
+//!         .unwrap();
+//!
+//!     assert_eq!((button.on_click)(2), "clicked 2 times");
+//! }
+//! ```
+//!
+//! The option can't be called `fn`, since keywords can't be used as option names. It can be
+//! combined with `strip_option` for fields of type `Option<Box<dyn Trait>>`. Since trait
+//! objects usually don't implement `Clone`, such builders need `pattern = "owned"`.
+//!
+//! ## Wrapping Setters
+//!
+//! `#[builder(setter(wrap = "Arc"))]` does the same for other wrappers: the setter of a field of
+//! type `Arc<T>` takes a `T`, or a value implementing the traits of a `dyn Trait`, and wraps it
+//! with `Arc::new`. The wrapper is given by a path to its type, e.g. `Box`, `std::rc::Rc` or
+//! your own handle type with a `new` function, and compared with the field type by its name:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! # use std::sync::Arc;
+//! #
+//! pub trait Handler: Send + Sync {
+//!     fn handle(&self, request: &str) -> String;
+//! }
+//!
+//! struct Echo;
+//!
+//! impl Handler for Echo {
+//!     fn handle(&self, request: &str) -> String {
+//!         request.to_string()
+//!     }
+//! }
+//!
+//! #[derive(Builder)]
+//! struct Server {
+//!     #[builder(setter(wrap = "Arc"))]
+//!     handler: Arc<dyn Handler>,
+//! }
+//!
+//! fn main() {
+//!     let server = ServerBuilder::default().handler(Echo).build().unwrap();
+//!
+//!     assert_eq!(server.handler.handle("ipsum"), "ipsum");
+//! }
+//! ```
+//!
+//! `wrap` can be combined with `strip_option`, and with `into` or `into_impl` to take any
+//! `impl Into<T>` for an `Arc<T>` field. The builder stores the wrapper, so building from clones
+//! of the builder shares the wrapped value instead of cloning it.
+//!
+//! ## Array Setters
+//!
+//! For a field of type `[T; N]`, `#[builder(setter(array))]` adds two setters next to the
+//! regular one:
+//!
+//! * `try_{field}_from_iter` takes an iterator, and fails with an [`ArrayLengthError`] if the
+//!   number of items does not match the length of the array.
+//! * `set_nth_{field}` sets the item at an index, and panics if it is out of bounds. If the
+//!   field has not been set yet, the other items are filled with `T::default()`, so `T` must
+//!   implement `Default`.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! struct Rgb {
+//!     #[builder(setter(array))]
+//!     channels: [u8; 3],
+//! }
+//!
+//! fn main() {
+//!     let mut builder = RgbBuilder::default();
+//!     assert!(builder.try_channels_from_iter(vec![1, 2]).is_err());
+//!
+//!     let rgb = builder
+//!         .try_channels_from_iter(vec![1, 2, 3])
+//!         .unwrap()
+//!         .set_nth_channels(0, 255)
+//!         .build()
+//!         .unwrap();
+//!     assert_eq!(rgb.channels, [255, 2, 3]);
+//! }
+//! ```
+//!
+//! The length may be a const generic parameter. Collecting the iterator requires the `alloc`
+//! feature.
+//!
+//! ## Tuple Setters
+//!
+//! With `#[builder(setter(untuple(start, end)))]`, the setter of a tuple field takes each
+//! element as a parameter of the given name and packs them into the tuple. Without names, the
+//! parameters are called `value_0`, `value_1` and so on:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! struct Selection {
+//!     #[builder(setter(untuple(start, end)))]
+//!     range: (u32, u32),
+//!     #[builder(setter(untuple))]
+//!     cursor: (u32, u32),
+//! }
+//!
+//! fn main() {
+//!     let x = SelectionBuilder::default().range(1, 5).cursor(0, 3).build().unwrap();
+//!
+//!     assert_eq!(x, Selection { range: (1, 5), cursor: (0, 3) });
+//! }
+//! ```
+//!
+//! `untuple` can be combined with `strip_option`, but not with `into`, which is ignored if it is
+//! set on the struct level.
+//!
+//! ## Transforming Setters
+//!
+//! With `#[builder(setter(transform = |w: u32, h: u32| Size { w, h }))]`, the setter takes the
+//! parameters of the closure, which all need a type, and stores the value it returns. The
+//! closure can also be given as a string:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! # use std::time::Duration;
+//! #
+//! #[derive(Debug, PartialEq, Clone)]
+//! struct Size {
+//!     w: u32,
+//!     h: u32,
+//! }
+//!
+//! #[derive(Builder, Debug, PartialEq)]
+//! struct Window {
+//!     #[builder(setter(transform = |w: u32, h: u32| Size { w, h }))]
+//!     size: Size,
+//!     #[builder(setter(transform = "|secs: u64| Duration::from_secs(secs)"))]
+//!     timeout: Duration,
+//! }
+//!
+//! fn main() {
+//!     let x = WindowBuilder::default().size(800, 600).timeout(30).build().unwrap();
+//!
+//!     assert_eq!(x.size, Size { w: 800, h: 600 });
+//! }
+//! ```
+//!
+//! Like `untuple`, `transform` can be combined with `strip_option`, but not with `into`, which is
+//! ignored if it is set on the struct level. Transforming setters are never `const`.
+//!
+//! ## Lazy Setters
+//!
+//! With `#[builder(setter(lazy))]`, an additional `foo_with` setter takes a closure returning the
+//! value. The builder stores the closure in a [`LazyValue`] and only calls it in `build()`, after
+//! validation, if the field hasn't been set otherwise:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug)]
+//! struct Session {
+//!     user: String,
+//!     #[builder(setter(lazy))]
+//!     key: String,
+//! }
+//!
+//! fn main() {
+//!     let x = SessionBuilder::default()
+//!         .user("alice".into())
+//!         .key_with(|| "generated".into())
+//!         .build()
+//!         .unwrap();
+//!
+//!     assert_eq!(x.key, "generated");
+//! }
+//! ```
+//!
+//! The closure must be `Fn() -> T + Send + Sync + 'static`, because the builder can be cloned and
+//! built several times. The last setter called wins, `foo` or `foo_with`. Lazy setters need the
+//! `alloc` or `std` feature, and can't be combined with `const` builders or `convert = "at_build"`.
+//!
+//! ## Nullable Setters
+//!
+//! Patch-style types often use `Option<Option<T>>` to tell "leave unchanged" (`None`) apart from
+//! "set to null" (`Some(None)`) and "set to a value" (`Some(Some(value))`). With
+//! `#[builder(setter(nullable))]`, the setter takes a plain `T`, an additional `set_null_*`
+//! method sets the field to `Some(None)`, and the field is left as `None` if neither is called:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! struct UserPatch {
+//!     #[builder(setter(nullable, into))]
+//!     nickname: Option<Option<String>>,
+//!     #[builder(setter(nullable))]
+//!     age: Option<Option<u8>>,
+//! }
+//!
+//! fn main() {
+//!     let patch = UserPatchBuilder::default()
+//!         .nickname("lorem")
+//!         .build()
+//!         .unwrap();
+//!     assert_eq!(patch, UserPatch { nickname: Some(Some("lorem".into())), age: None });
+//!
+//!     let patch = UserPatchBuilder::default().set_null_age().build().unwrap();
+//!     assert_eq!(patch, UserPatch { nickname: None, age: Some(None) });
+//! }
+//! ```
+//!
+//! An explicit `default` or a struct-level `default` takes precedence over leaving the field
+//! as `None`.
+//!
+//! ## Overridable Setters
+//!
+//! With `#[builder(overridable)]`, the setters are declared as default methods of a trait named
+//! after the builder, e.g. `LoremBuilderSetters`, which the builder implements. Other crates can
+//! then wrap the builder and override individual setters, e.g. to add logging or clamping,
+//! while keeping all other setters:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq, Clone)]
+//! #[builder(overridable)]
+//! struct Lorem {
+//!     ipsum: u8,
+//!     dolor: bool,
+//! }
+//!
+//! #[derive(Default)]
+//! struct Clamping(LoremBuilder);
+//!
+//! impl LoremBuilderSetters for Clamping {
+//!     fn lorem_builder_mut(&mut self) -> &mut LoremBuilder {
+//!         &mut self.0
+//!     }
+//!
+//!     fn ipsum(&mut self, value: u8) -> &mut Self {
+//!         self.0.ipsum(value.min(10));
+//!         self
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let mut builder = Clamping::default();
+//!     builder.ipsum(42).dolor(true);
+//!
+//!     assert_eq!(builder.0.build().unwrap(), Lorem { ipsum: 10, dolor: true });
+//! }
+//! ```
+//!
+//! The required method of the trait is named after the builder in snake case, with a `_mut`
+//! suffix. Since the setters are trait methods, the trait must be in scope wherever they are
+//! called, also on the builder itself.
+//!
+//! ## Flattened Builders
+//!
+//! A field whose type derives `Builder` with `#[builder(overridable)]` can be marked with
+//! `#[builder(flatten)]`. The builder then stores the nested builder in that field and
+//! implements its setters trait, so the setters of the nested struct can be called on the outer
+//! builder directly. The build method builds the nested struct with its own builder:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq, Clone)]
+//! #[builder(overridable, setter(into))]
+//! struct Network {
+//!     host: String,
+//!     #[builder(default = "8080")]
+//!     port: u16,
+//! }
+//!
+//! #[derive(Builder, Debug, PartialEq)]
+//! struct Config {
+//!     name: &'static str,
+//!     #[builder(flatten)]
+//!     network: Network,
+//! }
+//!
+//! fn main() {
+//!     let config = ConfigBuilder::default()
+//!         .name("lorem")
+//!         .host("localhost")
+//!         .build()
+//!         .unwrap();
+//!
+//!     assert_eq!(config.network, Network { host: "localhost".into(), port: 8080 });
+//! }
+//! ```
+//!
+//! Notes:
+//! * The nested builder and its setters trait are found by naming convention, e.g.
+//!   `NetworkBuilder` and `NetworkBuilderSetters` in the module of `Network`, so the nested
+//!   builder must not be renamed. Its build method must be named `build`.
+//! * The setters keep their names, since trait methods can't be renamed or prefixed, and the
+//!   setters trait must be in scope where they are called.
+//! * Errors of the nested builder are converted into the validation error of the outer
+//!   builder, using their `Display` output.
+//!
+//! ## Delegated Builders
+//!
+//! A field whose type derives `Builder` can be marked with `#[builder(delegate)]` instead, which
+//! doesn't need a setters trait. The builder then stores the nested builder in that field and
+//! gives mutable access to it with a method named after the field, in place of its setter.
+//! The build method builds the nested struct first, and its errors name uninitialized fields
+//! by their path:
+//!
+//! ```rust
+//! # use derive_builder::{Builder, BuilderError};
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! struct Database {
+//!     url: &'static str,
+//!     #[builder(default = "10")]
+//!     pool_size: u32,
+//! }
+//!
+//! #[derive(Builder, Debug, PartialEq)]
+//! struct Config {
+//!     name: &'static str,
+//!     #[builder(delegate)]
+//!     database: Database,
+//! }
+//!
+//! fn main() {
+//!     let mut builder = ConfigBuilder::default();
+//!     builder.name("lorem");
+//!     builder.database().pool_size(5);
+//!
+//!     let error = builder.build().unwrap_err();
+//!     assert_eq!(error.uninitialized_field(), Some("database.url"));
+//!     assert_eq!(error.to_string(), "`database.url` must be initialized");
+//!
+//!     builder.database().url("postgres://localhost");
+//!     assert_eq!(builder.build().unwrap().database.pool_size, 5);
+//! }
+//! ```
+//!
+//! Notes:
+//! * The nested builder is found by naming convention, e.g. `DatabaseBuilder` in the module of
+//!   `Database`, so it must not be renamed. Its build method must be named `build`.
+//! * Errors of the nested builder are wrapped in a [`NestedFieldError`], which needs the `alloc`
+//!   or `std` feature. A custom error type of the outer builder must implement
+//!   `From<NestedFieldError>`, and a custom error type of the nested builder must implement
+//!   [`BuilderError`].
+//!
+//! ## Collection Setters
+//!
+//! `#[builder(setter(each(name = "...")))]` adds a setter which extends the collection of a field
+//! with one item. The setter of a `HashMap` or `BTreeMap` takes a `(key, value)` tuple, or with
+//! `key_value` the key and the value as two parameters, which `into` both converts with `Into`:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! # use std::collections::HashMap;
+//! #
+//! #[derive(Builder)]
+//! struct Request {
+//!     #[builder(setter(each(name = "header", into, key_value)))]
+//!     headers: HashMap<String, String>,
+//! }
+//!
+//! fn main() {
+//!     let request = RequestBuilder::default()
+//!         .header("Accept", "text/html")
+//!         .header("Host", "example.com")
+//!         .build()
+//!         .unwrap();
+//!     assert_eq!(request.headers["Host"], "example.com");
+//! }
+//! ```
+//!
+//! In `each(bound = "...")`, `VALUE` is the type of the item. With `key_value` and `into`, the
+//! setter has a `KEY` and a `VALUE` type parameter for the key and the value instead.
+//!
+//! `#[builder(setter(extend))]` adds a `_extend` setter, which appends all items of an
+//! `IntoIterator` without collecting them first, e.g. `.headers_extend(other.into_iter())`.
+//!
+//! ## Builders of Collection Items
+//!
+//! For a collection of structs which derive `Builder`, `#[builder(setter(each_builder = "..."))]`
+//! adds a setter which takes a closure configuring the builder of a new item. The item is built
+//! right away and added to the collection, and if it fails to build, its error is kept and
+//! returned by the build method, like with [deferred errors](#deferred-errors):
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq, Clone)]
+//! #[builder(setter(into))]
+//! struct Server {
+//!     host: String,
+//!     #[builder(default = "80")]
+//!     port: u16,
+//! }
+//!
+//! #[derive(Builder, Debug, PartialEq)]
+//! struct Cluster {
+//!     #[builder(setter(each_builder = "server"))]
+//!     servers: Vec<Server>,
+//! }
+//!
+//! fn main() {
+//!     let cluster = ClusterBuilder::default()
+//!         .server(|s| {
+//!             s.host("a.example.com");
+//!         })
+//!         .server(|s| {
+//!             s.host("b.example.com").port(8080u16);
+//!         })
+//!         .build()
+//!         .unwrap();
+//!     assert_eq!(cluster.servers[1], Server { host: "b.example.com".into(), port: 8080 });
+//!
+//!     let err = ClusterBuilder::default()
+//!         .server(|s| {
+//!             s.port(8080u16);
+//!         })
+//!         .build()
+//!         .unwrap_err();
+//!     assert_eq!(err.to_string(), "`host` must be initialized");
+//! }
+//! ```
+//!
+//! The builder of the items is named after the first type argument of the collection, e.g.
+//! `ServerBuilder` for `Vec<Server>`, and must be in scope with a mutable builder pattern and a
+//! build method named `build`. Name it with
+//! `each_builder(name = "server", builder = "net::ServerBuilder")`, e.g. for maps.
+//!
+//! ## Fallible Setters
+//!
+//! Alongside the normal setter methods, you can expose fallible setters which are generic over
+//! the `TryInto` trait. TryInto is similar to `Into` with the key distinction that the conversion
+//! can fail, and therefore produces a `Result`.
+//!
+//! You can only declare the `try_setter` attribute today if you're targeting nightly, and you have
+//! to add `#![feature(try_from)]` to your crate to use it.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #[derive(Builder, Debug, PartialEq)]
+//! #[builder(try_setter, setter(into))]
+//! struct Lorem {
+//!     pub name: String,
+//!     pub ipsum: u8,
+//! }
+//!
+//! #[derive(Builder, Debug, PartialEq)]
+//! struct Ipsum {
+//!     #[builder(try_setter, setter(into, name = "foo"))]
+//!     pub dolor: u8,
+//! }
+//!
+//! fn main() {
+//!    LoremBuilder::default()
+//!        .try_ipsum(1u16).unwrap()
+//!        .name("hello")
+//!        .build()
+//!        .expect("1 fits into a u8");
+//!
+//!    IpsumBuilder::default()
+//!        .try_foo(1u16).unwrap()
+//!        .build()
+//!        .expect("1 fits into a u8");
+//! }
+//! ```
+//!
+//! ## Deferred Errors
+//!
+//! With `#[builder(setter(defer_err))]` the setter takes a `Result` of the value, so results can
+//! be passed along without breaking the chain of setters. An `Ok` sets the field, and the first
+//! `Err` is converted into the build error with `Into` and kept in the builder, which returns it
+//! from the build method before any validation runs. `reset` clears it again.
+//!
+//! ```rust
+//! # use derive_builder::{Builder, UninitializedFieldError};
+//! # use std::num::ParseIntError;
+//! #[derive(Debug, Clone, PartialEq)]
+//! enum LoremError {
+//!     Missing(&'static str),
+//!     Parse(ParseIntError),
+//! }
+//!
+//! impl From<UninitializedFieldError> for LoremError {
+//!     fn from(e: UninitializedFieldError) -> Self {
+//!         Self::Missing(e.field_name())
+//!     }
+//! }
+//!
+//! impl From<ParseIntError> for LoremError {
+//!     fn from(e: ParseIntError) -> Self {
+//!         Self::Parse(e)
+//!     }
+//! }
+//!
+//! #[derive(Builder, Debug, PartialEq)]
+//! #[builder(build_fn(error = "LoremError"))]
+//! struct Lorem {
+//!     #[builder(setter(defer_err))]
+//!     port: u16,
+//!     #[builder(setter(defer_err))]
+//!     workers: u8,
+//! }
+//!
+//! # fn main() {
+//! let lorem = LoremBuilder::default()
+//!     .port("8080".parse())
+//!     .workers("4".parse())
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(lorem, Lorem { port: 8080, workers: 4 });
+//!
+//! let err = LoremBuilder::default()
+//!     .port("80x".parse())
+//!     .workers("4".parse())
+//!     .build()
+//!     .unwrap_err();
+//! assert_eq!(err, LoremError::Parse("80x".parse::<u16>().unwrap_err()));
+//! # }
+//! ```
+//!
+//! The setter accepts any error type which implements `Into` of the build error. The build
+//! error is stored in the builder, so it has to be `Clone`; the generated error derives `Clone`
+//! for this. Other errors can be mapped first, e.g. with `.map_err(|e| e.to_string())` into the
+//! `validation_error` of the generated error.
+//!
+//! ## Setter Hooks
+//!
+//! `#[builder(on_set = "path::to::fn")]` makes every setter call `fn(field_name: &'static str)`
+//! after setting its field, e.g. to record which fields are used. This includes the
+//! additional setters, like `each` setters or those of `try_setter`.
+//! With `#[builder(on_set(path = "path::to::fn", value))]`, the function also takes the builder
+//! field, which must implement `Debug`, as a `&dyn Debug`:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! # use std::fmt::Debug;
+//! #
+//! fn record(field: &'static str, value: &dyn Debug) {
+//!     println!("set {} to {:?}", field, value);
+//! }
+//!
+//! #[derive(Builder)]
+//! #[builder(on_set(path = "record", value))]
+//! struct Lorem {
+//!     port: u16,
+//! }
+//!
+//! # fn main() {
+//! // Prints "set port to Some(8080)".
+//! LoremBuilder::default().port(8080).build().unwrap();
+//! # }
+//! ```
+//!
+//! The hook can't be used with `const_fn`, because it isn't a `const fn`.
+//!
+//! ## Public API Snapshot
+//!
+//! `#[builder(public_api_snapshot)]` emits a hidden module next to the builder, with a function
+//! which calls every `pub` setter with a value of the type it takes, e.g.
+//! `b.ipsum::<String>(value::<String>())` for a `setter(into)`. The function is never called,
+//! but it is type-checked with the crate, so a change of a setter's signature, e.g. by toggling
+//! `into` or `strip_option`, breaks the crate's own build before it breaks downstream users.
+//! Setters of `setter(closure)` and of wrapped trait objects are left out. The module imports its parent with
+//! `use super::*`, so the struct must be declared in a module, not in a function body.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder)]
+//! #[builder(public_api_snapshot)]
+//! pub struct Lorem {
+//!     #[builder(setter(into))]
+//!     ipsum: String,
+//!     #[builder(setter(strip_option), default)]
+//!     dolor: Option<u32>,
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! ## Default Values
+//!
+//! You can define default values for each field via annotation by `#[builder(default = "...")]`,
+//! where `...` stands for any Rust expression and must be string-escaped, e.g.
+//!
+//! * `#[builder(default = "42")]`
+//! * `#[builder(default)]` delegates to the [`Default`] trait of the base type.
+//!
+//! The expression will be evaluated with each call to `build`.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! struct Lorem {
+//!     #[builder(default = "42")]
+//!     pub ipsum: u32,
+//! }
+//!
+//! fn main() {
+//!     // If we don't set the field `ipsum`,
+//!     let x = LoremBuilder::default().build().unwrap();
+//!
+//!     // .. the custom default will be used for `ipsum`:
+//!     assert_eq!(x, Lorem {
+//!         ipsum: 42,
+//!     });
+//! }
+//! ```
+//!
+//! ### Tips on Defaults
+//!
+//! * The `#[builder(default)]` annotation can be used on the struct level, too. Overrides are
+//!   still possible.
+//! * Delegate to a private helper method on `FooBuilder` for anything fancy. This way
+//!   you will get _much better error diagnostics_ from the rust compiler and it will be _much
+//!   more readable_ for other human beings. :-)
+//! * Defaults will not work while using `#[builder(build_fn(skip))]`. In this case, you'll
+//!   need to handle default values yourself when converting from the builder, such as by
+//!   using `.unwrap_or()` and `.unwrap_or_else()`.
+//!
+//! [`Default`]: https://doc.rust-lang.org/std/default/trait.Default.html
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! # #[derive(Builder, PartialEq, Debug)]
+//! struct Lorem {
+//!     ipsum: String,
+//!     // Custom defaults can delegate to helper methods
+//!     // and pass errors to the enclosing `build()` method via `?`.
+//!     #[builder(default = "self.default_dolor()?")]
+//!     dolor: String,
+//! }
+//!
+//! impl LoremBuilder {
+//!     // Private helper method with access to the builder struct.
+//!     fn default_dolor(&self) -> Result<String, String> {
+//!         match self.ipsum {
+//!             Some(ref x) if x.chars().count() > 3 => Ok(format!("dolor {}", x)),
+//!             _ => Err("ipsum must at least 3 chars to build dolor".to_string()),
+//!         }
+//!     }
+//! }
+//!
+//! # fn main() {
+//! #     let x = LoremBuilder::default()
+//! #         .ipsum("ipsum".to_string())
+//! #         .build()
+//! #         .unwrap();
+//! #
+//! #     assert_eq!(x, Lorem {
+//! #         ipsum: "ipsum".to_string(),
+//! #         dolor: "dolor ipsum".to_string(),
+//! #     });
+//! # }
+//! ```
+//!
+//! You can even reference other fields, but you have to remember that the builder struct
+//! will wrap every type in an Option ([as illustrated earlier](#what-you-get)).
+//!
+//! ### Defaults Without `Option`
+//!
+//! For small `Copy` fields, the `Option` of the builder field can double its size. With
+//! `#[builder(field(bare))]`, the builder field has the type of the field itself and starts out
+//! with the default value, which the setter overwrites.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! struct Limits {
+//!     #[builder(default = "16", field(bare))]
+//!     max_depth: u8,
+//!     #[builder(default, field(bare))]
+//!     strict: bool,
+//! }
+//!
+//! # fn main() {
+//! assert_eq!(std::mem::size_of::<LimitsBuilder>(), std::mem::size_of::<Limits>());
+//!
+//! let limits = LimitsBuilder::default().strict(true).build().unwrap();
+//! assert_eq!(limits, Limits { max_depth: 16, strict: true });
+//! # }
+//! ```
+//!
+//! Compared to a regular default, there are some differences:
+//!
+//! * The default expression is evaluated when the builder is created, so it can't reference
+//!   `self` or return errors.
+//! * The build method clones the field, unless the builder uses the owned pattern.
+//! * Whether the field has been set can't be told anymore, so it can't take part in
+//!   [field dependencies](#field-dependencies).
+//! * `reset` sets the field back to its default value.
+//!
+//! ### Defaults Reading Other Fields
+//!
+//! With `#[builder(after = "...")]` or `#[builder(after(a, b))]`, a field is initialized after
+//! the named fields, and its default or `field(build = "...")` expression can read their
+//! resolved values - set or defaulted - through variables named like the fields.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! struct Server {
+//!     #[builder(default = "format!(\"{}:{}\", host, port)", after(host, port))]
+//!     url: String,
+//!     #[builder(setter(into), default = "\"localhost\".into()")]
+//!     host: String,
+//!     #[builder(default = "if tls { 443 } else { 80 }", after = "tls")]
+//!     port: u16,
+//!     #[builder(default)]
+//!     tls: bool,
+//! }
+//!
+//! # fn main() {
+//! let server = ServerBuilder::default().tls(true).build().unwrap();
+//! assert_eq!(server.url, "localhost:443");
+//! # }
+//! ```
+//!
+//! Fields without `after` are initialized in declaration order. The expressions should borrow
+//! or clone the values they read, as the values are moved into the struct afterwards. Fields
+//! with `after` need a default or a `field(build)` expression, and cycles are rejected.
+//!
+//! ### Imports for Defaults
+//!
+//! Default values and `field(build = "...")` expressions are resolved in the module of the
+//! struct. To use short paths in them without importing the paths into the module, list them
+//! in `#[builder(uses = "...")]`, separated by commas as in a `use` declaration:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! mod units {
+//!     #[derive(Clone, Debug, PartialEq)]
+//!     pub struct Seconds(pub u64);
+//! }
+//!
+//! #[derive(Builder, Debug)]
+//! #[builder(uses = "crate::units::Seconds")]
+//! struct Timeout {
+//!     #[builder(default = "Seconds(30)")]
+//!     read: units::Seconds,
+//! }
+//!
+//! # fn main() {
+//! assert_eq!(TimeoutBuilder::default().build().unwrap().read, units::Seconds(30));
+//! # }
+//! ```
+//!
+//! The paths are imported in an anonymous `const` around the `impl` of the builder, so they
+//! apply to its methods but not to the types of the fields.
+//!
+//! ### Time Defaults
+//!
+//! Timestamp fields can default to the time of the build. `#[builder(default(now))]` works
+//! for `SystemTime`, `Instant` and other types implementing [`Now`], and
+//! `#[builder(default(since_epoch = "..."))]` takes the time since the Unix epoch as `"secs"`,
+//! `"millis"`, `"micros"` or `"nanos"`, cast to the integer type of the field, or as a
+//! `"duration"`. This needs the `std` feature:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! use std::time::{SystemTime, UNIX_EPOCH};
+//!
+//! #[derive(Builder)]
+//! struct Event {
+//!     #[builder(default(now))]
+//!     created_at: SystemTime,
+//!     #[builder(default(since_epoch = "millis"))]
+//!     created_millis: u64,
+//! }
+//!
+//! # fn main() {
+//! let event = EventBuilder::default().build().unwrap();
+//! assert!(event.created_at > UNIX_EPOCH);
+//! assert!(event.created_millis > 0);
+//! # }
+//! ```
+//!
+//! The defaults are evaluated each time the struct is built, so they can't be used with
+//! `field(bare)`, whose default is stored when the builder is created.
+//!
+//! ## Generic Structs
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq, Default, Clone)]
+//! struct GenLorem<T: Clone> {
+//!     ipsum: &'static str,
+//!     dolor: T,
+//! }
+//!
+//! fn main() {
+//!     let x = GenLoremBuilder::default().ipsum("sit").dolor(42).build().unwrap();
+//!     assert_eq!(x, GenLorem { ipsum: "sit".into(), dolor: 42 });
+//! }
+//! ```
+//!
+//! ## Build Method Customization
+//!
+//! You can rename or suppress the auto-generated build method, leaving you free to implement
+//! your own version. Suppression is done using `#[builder(build_fn(skip))]` at the struct level,
+//! and renaming is done with `#[builder(build_fn(name = "YOUR_NAME"))]`.
+//!
+//! `#[builder(build_fn(fallback))]` adds a `build_with_fallback` method (named after the build
+//! method), which takes the values of unset fields from an existing instance. Its values take
+//! precedence over declared defaults, and skipped fields are copied from it too, so the target
+//! struct's fields must implement `Clone`:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! #[builder(build_fn(fallback))]
+//! struct Config {
+//!     #[builder(default = "8080")]
+//!     port: u16,
+//!     host: String,
+//! }
+//!
+//! fn main() {
+//!     let base = Config { port: 80, host: "localhost".into() };
+//!     let config = ConfigBuilder::default()
+//!         .host("example.com".into())
+//!         .build_with_fallback(&base)
+//!         .unwrap();
+//!
+//!     assert_eq!(config, Config { port: 80, host: "example.com".into() });
+//! }
+//! ```
+//!
+//! Fields of `#[repr(packed)]` structs can't be borrowed, so they are copied from the fallback
+//! instead, and must implement `Copy`. The other generated code never borrows fields of the
+//! target struct, so `#[repr(C)]` and `#[repr(packed)]` structs can derive `Builder` as usual.
+//!
+//! If the builder is the public API of a type which lives elsewhere,
+//! `#[builder(build_fn(into = "PublicConfig"))]` makes the build method convert the built struct
+//! with `Into`, and return the given type instead. The other methods which return the built
+//! struct, e.g. `build_with_fallback` and `build_or_panic`, convert it too. `post_build` still
+//! receives the struct before it is converted, and the `const fn` build method returns it
+//! unconverted:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder)]
+//! #[builder(build_fn(into = "PublicConfig"))]
+//! struct Config {
+//!     host: String,
+//!     #[builder(default = "8080")]
+//!     port: u16,
+//! }
+//!
+//! #[derive(Debug, PartialEq)]
+//! pub struct PublicConfig {
+//!     pub address: String,
+//! }
+//!
+//! impl From<Config> for PublicConfig {
+//!     fn from(config: Config) -> Self {
+//!         PublicConfig { address: format!("{}:{}", config.host, config.port) }
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let config = ConfigBuilder::default().host("localhost".into()).build().unwrap();
+//!     assert_eq!(config, PublicConfig { address: "localhost:8080".into() });
+//! }
+//! ```
+//!
+//! For hot paths, `#[builder(build_fn(reuse))]` adds a `build_into` method (named after the
+//! build method), which assigns every field of an existing instance instead of returning a new
+//! one. Set values are assigned with `Clone::clone_from`, which keeps the allocations of the
+//! instance, e.g. the buffer of a `String`, and unset fields get their defaults. All checks run
+//! first, so the instance is unchanged if the build fails. It can't be combined with
+//! `post_build`:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! #[builder(build_fn(reuse))]
+//! struct Request {
+//!     path: String,
+//!     #[builder(default = "30")]
+//!     timeout: u32,
+//! }
+//!
+//! fn main() {
+//!     let mut request = Request { path: String::with_capacity(64), timeout: 5 };
+//!     RequestBuilder::default()
+//!         .path("/index.html".into())
+//!         .build_into(&mut request)
+//!         .unwrap();
+//!
+//!     assert_eq!(request, Request { path: "/index.html".into(), timeout: 30 });
+//!     assert!(request.path.capacity() >= 64);
+//! }
+//! ```
+//!
+//! By default, `build()` fails at the first required field which isn't set. With
+//! `#[builder(build_fn(collect_errors))]`, it checks all fields first and reports every missing
+//! one in an `UninitializedFields` variant of the generated error, which holds an
+//! `UninitializedFieldsError`. A custom error type must implement
+//! `From<UninitializedFieldsError>` instead of `From<UninitializedFieldError>`. This needs the
+//! `alloc` or `std` feature.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug)]
+//! #[builder(build_fn(collect_errors))]
+//! struct Config {
+//!     port: u16,
+//!     host: String,
+//! }
+//!
+//! # fn main() {
+//! let err = ConfigBuilder::default().build().unwrap_err();
+//! assert_eq!(err.to_string(), "Fields not initialized: port, host");
+//! # }
+//! ```
+//!
+//! To report validation errors together as well, e.g. in a form,
+//! `#[builder(build_fn(all_errors))]` adds a `build_all_errors` method (named after the build
+//! method), which returns a `Vec` of errors. It runs every field validation, field dependency
+//! check, `validate` function and required field check, even after one of them fails, and only
+//! builds the struct if all pass. This needs the `alloc` or `std` feature.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug)]
+//! #[builder(build_fn(all_errors))]
+//! struct Config {
+//!     #[builder(validate = "validate_port")]
+//!     port: u16,
+//!     host: String,
+//! }
+//!
+//! fn validate_port(port: &u16) -> Result<(), String> {
+//!     match *port {
+//!         0 => Err("port must not be 0".into()),
+//!         _ => Ok(()),
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let errors = ConfigBuilder::default().port(0).build_all_errors().unwrap_err();
+//! assert_eq!(errors.len(), 2);
+//! assert_eq!(errors[0].to_string(), "port must not be 0");
+//! assert_eq!(errors[1].to_string(), "`host` must be initialized");
+//! # }
+//! ```
+//!
+//! For tests and examples, where a failing build is a bug, `#[builder(build_fn(panic_variant))]`
+//! adds a `build_or_panic` method (named after the build method), which returns the struct or
+//! panics. The message lists all required fields which haven't been set, and other errors are
+//! shown with their `Debug` representation:
+//!
+//! ```rust,should_panic
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug)]
+//! #[builder(build_fn(panic_variant))]
+//! struct Config {
+//!     port: u16,
+//!     host: String,
+//! }
+//!
+//! # fn main() {
+//! // Panics with "could not build `Config`: missing fields `port`, `host`".
+//! ConfigBuilder::default().build_or_panic();
+//! # }
+//! ```
+//!
+//! For structs which are stored behind a smart pointer, `#[builder(build_fn(wrappers(box, arc,
+//! rc)))]` adds `build_boxed`, `build_arc` and `build_rc` methods (named after the build
+//! method), which return the built struct in a `Box`, `Arc` or `Rc`. The struct is built
+//! first and then moved into the pointer, so the compiler may still copy it. This needs the
+//! `alloc` or `std` feature.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! # use std::sync::Arc;
+//! #
+//! #[derive(Builder)]
+//! #[builder(build_fn(wrappers(arc)))]
+//! struct Config {
+//!     port: u16,
+//! }
+//!
+//! # fn main() {
+//! let config: Arc<Config> = ConfigBuilder::default().port(8080).build_arc().unwrap();
+//! # assert_eq!(config.port, 8080);
+//! # }
+//! ```
+//!
+//! For generic code which expects `TryFrom` or `TryInto`, `#[builder(build_fn(try_from))]`
+//! implements `TryFrom<ConfigBuilder>` for the built struct, or for the type of
+//! `build_fn(into = "...")`. It calls the build method, so its `Error` is the error of the build
+//! method, including the builder with `return_builder`. The impl is public even if the build
+//! method isn't.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! # use std::convert::TryInto;
+//! #
+//! #[derive(Builder)]
+//! #[builder(build_fn(try_from))]
+//! struct Config {
+//!     port: u16,
+//! }
+//!
+//! # fn main() {
+//! let mut builder = ConfigBuilder::default();
+//! builder.port(8080);
+//! let config: Config = builder.try_into().unwrap();
+//! # assert_eq!(config.port, 8080);
+//! # }
+//! ```
+//!
+//! ## Build Subsets
+//!
+//! `#[builder(build_fn(subset(name = "...", requires(...))))]` adds another build method, which
+//! only fails if one of the listed fields isn't set. Other unset fields take their declared
+//! default, or `Default::default()`, so their types must implement `Default`. The option can be
+//! repeated for several build methods, and each runs the same validations as `build()`:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! #[builder(build_fn(subset(name = "build_minimal", requires(host))))]
+//! struct Config {
+//!     host: String,
+//!     port: u16,
+//!     #[builder(default = "30")]
+//!     timeout: u32,
+//! }
+//!
+//! # fn main() {
+//! let config = ConfigBuilder::default()
+//!     .host("localhost".into())
+//!     .build_minimal()
+//!     .unwrap();
+//! assert_eq!(config, Config { host: "localhost".into(), port: 0, timeout: 30 });
+//!
+//! // `build()` still requires every field without a default.
+//! assert!(ConfigBuilder::default().host("localhost".into()).build().is_err());
+//! # }
+//! ```
+//!
+//! ## Pre-Build Validation
+//!
+//! If you're using the provided `build` method, you can declare
+//! `#[builder(build_fn(validate = "path::to::fn"))]` to specify a validator function which gets
+//! access to the builder before construction. The path does not need to be fully-qualified, and
+//! will consider `use` statements made at module level. It must be accessible from the scope
+//! where the target struct is declared.
+//!
+//! The provided function must have the signature `(&FooBuilder) -> Result<_, E>`, where the error
+//! type of `build` implements `From<E>`; the `Ok` variant is not used by the `build` method. The
+//! generated error implements `From<String>`.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! #[builder(build_fn(validate = "Self::validate"))]
+//! struct Lorem {
+//!     pub ipsum: u8,
+//! }
+//!
+//! impl LoremBuilder {
 //!     /// Check that `Lorem` is putting in the right amount of effort.
 //!     fn validate(&self) -> Result<(), String> {
-//!         if let Some(ref ipsum) = self.ipsum {
-//!             match *ipsum {
-//!                 i if i < 20 => Err("Try harder".to_string()),
-//!                 i if i > 100 => Err("You'll tire yourself out".to_string()),
-//!                 _ => Ok(())
-//!             }
-//!         } else {
-//!             Ok(())
+//!         if let Some(ref ipsum) = self.ipsum {
+//!             match *ipsum {
+//!                 i if i < 20 => Err("Try harder".to_string()),
+//!                 i if i > 100 => Err("You'll tire yourself out".to_string()),
+//!                 _ => Ok(())
+//!             }
+//!         } else {
+//!             Ok(())
+//!         }
+//!     }
+//! }
+//!
+//! fn main() {
+//!     // If we're trying too hard...
+//!     let x = LoremBuilder::default().ipsum(120).build().unwrap_err();
+//!
+//!     // .. the build will fail:
+//!     assert_eq!(&x.to_string(), "You'll tire yourself out");
+//! }
+//! ```
+//!
+//! Note:
+//! * Default values are applied _after_ validation, and will therefore not be validated!
+//! * Several independent validators can be composed by repeating `validate`; they run in
+//!   declaration order, and the first error is returned.
+//!
+//! To keep the errors of validators typed instead of converting them into strings, return them
+//! from `build` with a [custom error type](#error-return-type-from-autogenerated-build-function):
+//!
+//! ```rust
+//! # use derive_builder::{Builder, UninitializedFieldError};
+//! #
+//! #[derive(Debug, PartialEq)]
+//! enum EffortError {
+//!     Missing(&'static str),
+//!     TooLow(u8),
+//! }
+//!
+//! impl From<UninitializedFieldError> for EffortError {
+//!     fn from(e: UninitializedFieldError) -> Self {
+//!         Self::Missing(e.field_name())
+//!     }
+//! }
+//!
+//! #[derive(Builder, Debug)]
+//! #[builder(build_fn(validate = "Self::validate", error = "EffortError"))]
+//! struct Lorem {
+//!     pub ipsum: u8,
+//! }
+//!
+//! impl LoremBuilder {
+//!     fn validate(&self) -> Result<(), EffortError> {
+//!         match self.ipsum {
+//!             Some(ipsum) if ipsum < 20 => Err(EffortError::TooLow(ipsum)),
+//!             _ => Ok(()),
+//!         }
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let err = LoremBuilder::default().ipsum(10).build().unwrap_err();
+//! assert_eq!(err, EffortError::TooLow(10));
+//! # }
+//! ```
+//!
+//! With the generated error, implement `From<E>` for it to convert the errors of the validator.
+//!
+//! ## Field Dependencies
+//!
+//! Use `#[builder(requires = "other")]` on a field to fail the build if it was set without
+//! `other`, or `#[builder(conflicts_with = "other")]` to fail if both were set. Several fields
+//! can be listed as `requires(a, b)`. The checks run before the validator function, and the
+//! error names both fields:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug)]
+//! struct Login {
+//!     #[builder(setter(into, strip_option), default, requires = "password")]
+//!     username: Option<String>,
+//!     #[builder(setter(into, strip_option), default)]
+//!     password: Option<String>,
+//!     #[builder(setter(into, strip_option), default, conflicts_with(username, password))]
+//!     token: Option<String>,
+//! }
+//!
+//! fn main() {
+//!     let err = LoginBuilder::default().username("lorem").build().unwrap_err();
+//!     assert_eq!(&err.to_string(), "`username` requires `password` to be set");
+//!
+//!     let err = LoginBuilder::default().password("ipsum").token("dolor").build().unwrap_err();
+//!     assert_eq!(&err.to_string(), "`token` conflicts with `password`");
+//! }
+//! ```
+//!
+//! Notes:
+//! * A field counts as set if its setter was called, regardless of the value passed to it.
+//!   Default values don't count.
+//! * Both fields need a regular builder field, so `setter(skip)` and `field(ty = "...")` can't
+//!   be used with dependencies.
+//! * A custom error type must implement `From<FieldDependencyError>`.
+//!
+//! ## Validation Order
+//!
+//! Invariants can be checked at several stages of the build method, which always run in this
+//! order, stopping at the first error:
+//!
+//! 1. `#[builder(validate = "path::to::fn")]` on a field calls the function with a reference
+//!    to the value of the field, if it was set. Fields are validated in declaration order.
+//! 2. The checks of [field dependencies](#field-dependencies).
+//! 3. `#[builder(build_fn(validate = "path::to::fn"))]` calls the function with the builder.
+//!    `validate` can be repeated, e.g. `build_fn(validate = "a", validate = "b")`, and the
+//!    functions are called in declaration order.
+//! 4. Default values are applied and the struct is built.
+//! 5. `#[builder(build_fn(post_build = "path::to::fn"))]` calls the function with a reference
+//!    to the built struct, before it is returned. To modify the struct, use
+//!    `post_build(path = "path::to::fn", arg = "mut")` for a function taking `&mut Foo`, or
+//!    `arg = "owned"` for a function taking `Foo` and returning `Result<Foo, E>`.
+//!
+//! All of these functions return `Result<_, E>`, where the build error type must implement
+//! `From<E>`, e.g. `E = String` for the generated error type.
+//!
+//! Before any of these stages, `#[builder(build_fn(normalize = "path::to::fn"))]` calls the
+//! function with a `&mut` copy of the builder, so it can canonicalize the values, e.g. trim
+//! strings or sort vectors. It returns nothing, and the builder on which `build` is called
+//! keeps its values. The copy is a clone, unless the builder uses the owned pattern.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug)]
+//! #[builder(build_fn(normalize = "Self::normalize"))]
+//! struct User {
+//!     #[builder(setter(into))]
+//!     name: String,
+//! }
+//!
+//! impl UserBuilder {
+//!     fn normalize(&mut self) {
+//!         if let Some(ref mut name) = self.name {
+//!             *name = name.trim().to_lowercase();
+//!         }
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let user = UserBuilder::default().name(" Lorem ").build().unwrap();
+//! assert_eq!(user.name, "lorem");
+//! # }
+//! ```
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! #[builder(build_fn(validate = "Self::validate", post_build = "Range::check"))]
+//! struct Range {
+//!     #[builder(validate = "check_finite")]
+//!     start: f64,
+//!     #[builder(validate = "check_finite", default = "f64::MAX")]
+//!     end: f64,
+//! }
+//!
+//! fn check_finite(value: &f64) -> Result<(), String> {
+//!     if value.is_finite() {
+//!         Ok(())
+//!     } else {
+//!         Err(format!("{} is not finite", value))
+//!     }
+//! }
+//!
+//! impl RangeBuilder {
+//!     fn validate(&self) -> Result<(), String> {
+//!         match (self.start, self.end) {
+//!             (Some(start), Some(end)) if start > end => Err("start is after end".into()),
+//!             _ => Ok(()),
+//!         }
+//!     }
+//! }
+//!
+//! impl Range {
+//!     // Runs with the default of `end` applied.
+//!     fn check(&self) -> Result<(), String> {
+//!         if self.end - self.start < 1.0 {
+//!             return Err("range is too small".into());
+//!         }
+//!         Ok(())
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let err = RangeBuilder::default().start(f64::NAN).build().unwrap_err();
+//!     assert_eq!(&err.to_string(), "NaN is not finite");
+//!
+//!     let err = RangeBuilder::default().start(2.0).end(1.0).build().unwrap_err();
+//!     assert_eq!(&err.to_string(), "start is after end");
+//!
+//!     let err = RangeBuilder::default().start(1.0).end(1.5).build().unwrap_err();
+//!     assert_eq!(&err.to_string(), "range is too small");
+//! }
+//! ```
+//!
+//! A `post_build` function which takes the struct by value can replace it, e.g. to fill in
+//! fields which are derived from the others:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug)]
+//! #[builder(build_fn(post_build(path = "Stats::finish", arg = "owned")))]
+//! struct Stats {
+//!     total: u32,
+//!     count: u32,
+//!     #[builder(setter(skip))]
+//!     average: u32,
+//! }
+//!
+//! impl Stats {
+//!     fn finish(self) -> Result<Self, String> {
+//!         match self.total.checked_div(self.count) {
+//!             Some(average) => Ok(Self { average, ..self }),
+//!             None => Err("count must not be 0".into()),
 //!         }
 //!     }
 //! }
 //!
+//! # fn main() {
+//! let stats = StatsBuilder::default().total(12).count(4).build().unwrap();
+//! assert_eq!(stats.average, 3);
+//! # }
+//! ```
+//!
+//! ## Reusing Builders
+//!
+//! `#[builder(reset)]` adds a `reset` method, which unsets all fields so a pooled builder can
+//! be reused. Fields of type `Vec`, `VecDeque`, `String`, `HashMap`, `HashSet` and `BinaryHeap`
+//! which have been set are cleared instead, which keeps their allocations. Note that this leaves
+//! them set to an empty collection, rather than unset:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! #[builder(reset)]
+//! struct Batch {
+//!     id: u32,
+//!     #[builder(setter(each(name = "item")))]
+//!     items: Vec<u32>,
+//! }
+//!
+//! fn main() {
+//!     let mut builder = BatchBuilder::default();
+//!     let first = builder.id(1).item(1).item(2).build().unwrap();
+//!     assert_eq!(first.items, vec![1, 2]);
+//!
+//!     let second = builder.reset().id(2).item(3).build().unwrap();
+//!     assert_eq!(second, Batch { id: 2, items: vec![3] });
+//! }
+//! ```
+//!
+//! ## Converting Between Builders
+//!
+//! When a struct evolves, `#[builder(convert_from = "...")]` implements `From` for its builder,
+//! converting from the builder of the previous version. Each field is taken from the
+//! identically named field of the other builder and converted with `Into`. Fields which the
+//! other builder doesn't have must be listed in `skip(...)`, and are left unset:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder)]
+//! struct ConfigV1 {
+//!     port: u16,
+//! }
+//!
+//! #[derive(Builder, Debug, PartialEq)]
+//! #[builder(convert_from(ty = "ConfigV1Builder", skip(tls)))]
+//! struct ConfigV2 {
+//!     port: u32,
+//!     #[builder(default)]
+//!     tls: bool,
+//! }
+//!
+//! fn main() {
+//!     let mut v1 = ConfigV1Builder::default();
+//!     v1.port(8080);
+//!
+//!     let v2 = ConfigV2Builder::from(v1).build().unwrap();
+//!     assert_eq!(v2, ConfigV2 { port: 8080, tls: false });
+//! }
+//! ```
+//!
+//! Fields of the other builder which this builder doesn't have are dropped. Builder fields are
+//! private, so both builders must be defined in the same module, unless the fields of the
+//! other builder are made visible with `#[builder(field(vis = "..."))]`.
+//!
+//! To change some fields of an existing value, `#[builder(from_target)]` implements
+//! `From<Config>` for the builder, which sets every builder field to the value of the
+//! identically named field, and adds a `to_builder(&self)` method to the struct, which does the
+//! same with a clone. The struct must implement `Clone` for `to_builder`. Fields with
+//! `setter(skip)` are dropped, and the nested builders of `flatten` and `delegate` fields are
+//! converted with `From`, so the nested struct needs `from_target` as well. Fields which are
+//! converted by the build method, with `field(build = "...")` or `convert = "at_build"`, can't
+//! be set:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Clone, Debug, PartialEq)]
+//! #[builder(from_target)]
+//! struct Config {
+//!     host: String,
+//!     port: u16,
+//! }
+//!
+//! fn main() {
+//!     let config = Config { host: "localhost".into(), port: 8080 };
+//!     let changed = config.to_builder().port(9090).build().unwrap();
+//!     assert_eq!(changed, Config { host: "localhost".into(), port: 9090 });
+//! }
+//! ```
+//!
+//! ## Const Builders
+//!
+//! For configuration which is known at compile time, `#[builder(const_fn)]` emits the setters
+//! as `const fn`, along with a `const fn` counterpart of the build method named with a `_const`
+//! suffix. The `create_empty` constructor becomes a public `const fn`, so you will usually want
+//! to give it a nicer name:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug)]
+//! #[builder(const_fn, pattern = "owned", create_empty = "new")]
+//! struct Config {
+//!     name: &'static str,
+//!     #[builder(default = "8080")]
+//!     port: u16,
+//! }
+//!
+//! static CONFIG: Config = ConfigBuilder::new().name("lorem").build_const();
+//!
+//! fn main() {
+//!     assert_eq!(CONFIG.port, 8080);
+//! }
+//! ```
+//!
+//! Instead of returning an error, `build_const` panics if a required field is missing, which
+//! is a compile error when it is evaluated in a constant. The regular build method is still
+//! generated.
+//!
+//! Restrictions:
+//! * The builder must use the owned pattern.
+//! * `setter(into)`, `setter(boxed)`, `setter(closure)` and `build_fn(validate)` are not
+//!   supported, since they call functions which aren't `const`. Fallible setters and `each`
+//!   setters are emitted as regular functions.
+//! * Field types must not need dropping, e.g. `&'static str` works but `String` does not.
+//! * Default values must be constant expressions, so neither a plain `#[builder(default)]` nor a
+//!   struct-level `default` is supported.
+//!
+//! ## Query Strings
+//!
+//! With the `urlencoded` feature, `#[builder(from_query)]` generates a `from_query` function,
+//! which creates the builder from the `key=value` pairs of a URL query string, so HTTP
+//! handlers can feed request parameters straight into a validated build:
+//!
+//! ```rust,ignore
+//! use derive_builder::Builder;
+//!
+//! #[derive(Builder)]
+//! #[builder(from_query)]
+//! struct Search {
+//!     #[builder(setter(into))]
+//!     q: String,
+//!     #[builder(parse(rename = "page-size"), default = "20")]
+//!     page_size: u8,
+//!     #[builder(parse(skip), default)]
+//!     tags: Vec<String>,
+//! }
+//!
+//! fn main() {
+//!     let search = SearchBuilder::from_query("q=lorem+ipsum&page-size=50")
+//!         .unwrap()
+//!         .build()
+//!         .unwrap();
+//!     assert_eq!((search.q.as_str(), search.page_size), ("lorem ipsum", 50));
+//! }
+//! ```
+//!
+//! Values are percent-decoded, parsed with `FromStr` and passed to the setters, so they go
+//! through `strip_option` and `into` like any other value. Keys which don't match a field are
+//! ignored, and a value which can't be decoded or parsed returns a `QueryError`. Fields are
+//! keyed by their name, unless they declare `#[builder(parse(rename = "..."))]`, and
+//! `#[builder(parse(skip))]` leaves out fields whose type can't be parsed.
+//!
+//! To keep old keys working when a field is renamed, declare them with
+//! `#[builder(alias = "...")]`, which can be repeated. Aliases are accepted in addition to the
+//! key of the field. Builders which derive serde's `Deserialize` can accept the same keys with
+//! `#[builder_field_attr(serde(alias = "..."))]`.
+//!
+//! ## Key-Value Lists
+//!
+//! `#[builder(from_str)]` implements `FromStr` for the builder, which parses a comma-separated
+//! list of `key=value` pairs, e.g. for dense command line flags like
+//! `--connection host=db,port=5432,tls=true`:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder)]
+//! #[builder(from_str)]
+//! struct Connection {
+//!     host: String,
+//!     #[builder(default = "5432")]
+//!     port: u16,
+//!     #[builder(default)]
+//!     tls: bool,
+//! }
+//!
+//! fn main() {
+//!     let builder: ConnectionBuilder = "host=db, tls=true".parse().unwrap();
+//!     let connection = builder.build().unwrap();
+//!     assert_eq!((connection.port, connection.tls), (5432, true));
+//!
+//!     let err = "host=db,user=lorem".parse::<ConnectionBuilder>().err().unwrap();
+//!     assert_eq!(err.to_string(), "`user`: unknown key, expected one of `host`, `port`, `tls`");
+//! }
+//! ```
+//!
+//! Like with `from_query`, values are parsed with `FromStr` and passed to the setters, and
+//! `#[builder(parse(...))]` and `#[builder(alias = "...")]` rename or skip fields, or accept
+//! additional keys. Unlike `from_query`, unknown keys are errors, and values are not
+//! percent-decoded, so they can't contain commas.
+//!
+//! ## JSON Maps
+//!
+//! With the `json` feature, `#[builder(to_map)]` generates a `to_map` method, which serializes
+//! the fields set in the builder into a `serde_json::Map`, e.g. to hand a partial configuration
+//! to a templating engine or an audit log:
+//!
+//! ```rust,ignore
+//! use derive_builder::Builder;
+//! use serde_json::json;
+//!
+//! #[derive(Builder)]
+//! #[builder(to_map)]
+//! struct Connection {
+//!     #[builder(setter(into))]
+//!     host: String,
+//!     #[builder(default = "5432")]
+//!     port: u16,
+//! }
+//!
+//! fn main() {
+//!     let map = ConnectionBuilder::default().host("db").to_map().unwrap();
+//!     assert_eq!(serde_json::Value::Object(map), json!({ "host": "db" }));
+//! }
+//! ```
+//!
+//! Fields are keyed by their names, following `field_name_case`. Unset fields are left out,
+//! while fields with a custom `field(ty = "...")` are always included. The values must
+//! implement `Serialize`, and `to_map` returns the error of the first value which fails to
+//! serialize.
+//!
+//! ## Fuzzing
+//!
+//! With the `fuzzing` feature, every builder gets a `fuzz_build(data: &[u8])` function while
+//! compiling with `cfg(fuzzing)`, as `cargo fuzz` does. It fills the builder fields from the
+//! bytes with [`arbitrary`](https://docs.rs/arbitrary) and calls the build method, so the
+//! validators and default expressions of a struct are fuzzed by a single line:
+//!
+//! ```rust,ignore
+//! #![no_main]
+//!
+//! libfuzzer_sys::fuzz_target!(|data: &[u8]| my_crate::ConnectionBuilder::fuzz_build(data));
+//! ```
+//!
+//! Build errors are ignored, so only panics are reported. The types of the fields, or the
+//! custom `field(ty = "...")` types, must implement `Arbitrary` for any lifetime. Builders with
+//! `build_fn(skip)` don't get the function.
+//!
+//! ## Field Names
+//!
+//! Field names appear in errors, in the output of `#[builder(derive(Display))]` and as the keys
+//! of `from_query` and `from_str`. To match the conventions of config files, while the Rust
+//! fields stay `snake_case`, set `#[builder(field_name_case = "...")]` to one of `lowercase`,
+//! `UPPERCASE`, `PascalCase`, `camelCase`, `snake_case`, `SCREAMING_SNAKE_CASE`, `kebab-case`
+//! or `SCREAMING-KEBAB-CASE`:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder)]
+//! #[builder(from_str, field_name_case = "kebab-case")]
+//! struct Pool {
+//!     max_connections: u32,
+//!     #[builder(default)]
+//!     idle_timeout_secs: u64,
+//! }
+//!
+//! fn main() {
+//!     let builder: PoolBuilder = "idle-timeout-secs=30".parse().unwrap();
+//!     let err = builder.build().err().unwrap();
+//!     assert_eq!(err.to_string(), "`max-connections` must be initialized");
+//! }
+//! ```
+//!
+//! Keys set with `#[builder(parse(rename = "..."))]` or `#[builder(alias = "...")]` are used as
+//! they are. Builders which
+//! derive serde traits can follow the same convention with
+//! `#[builder_struct_attr(serde(rename_all = "kebab-case"))]`.
+//!
+//! ## Setting Fields at Runtime
+//!
+//! Plugin systems may need to configure structs which they only know by name and `TypeId`.
+//! `#[builder(dyn_set)]` implements the object-safe [`DynSet`] trait for the builder, which
+//! sets a field by its name with a `Box<dyn Any>` holding the value the setter takes:
+//!
+//! ```rust
+//! # use derive_builder::{Builder, DynSet};
+//! # use std::any::TypeId;
+//! #
+//! #[derive(Builder)]
+//! #[builder(dyn_set)]
+//! struct Plugin {
+//!     name: String,
+//!     #[builder(default)]
+//!     priority: i32,
+//! }
+//!
+//! fn configure(builder: &mut dyn DynSet) {
+//!     if builder.field_type("priority") == Some(TypeId::of::<i32>()) {
+//!         builder.set("priority", Box::new(10i32)).unwrap();
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let mut builder = PluginBuilder::default();
+//!     builder.name("lorem".into());
+//!     configure(&mut builder);
+//!     assert_eq!(builder.build().unwrap().priority, 10);
+//!
+//!     // A `&str` is not the `String` the setter takes.
+//!     let err = builder.set("name", Box::new("ipsum")).unwrap_err();
+//!     assert_eq!(err.field_name(), "name");
+//! }
+//! ```
+//!
+//! Values go through the setters, so they must have the type the setter takes after
+//! `strip_option`, and fields without a setter are unknown. Field names follow
+//! `field_name_case`. The types of the fields must be `'static`, and `dyn_set` requires the
+//! `alloc` or `std` feature.
+//!
+//! ## Additional Trait Derivations
+//!
+//! You can derive additional traits on the builder, including traits defined by other crates:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Clone)]
+//! #[builder(derive(Debug, PartialEq, Eq))]
+//! pub struct Lorem {
+//!     foo: u8,
+//!     bar: String,
+//! }
+//!
+//! fn main() {
+//!    assert_eq!(LoremBuilder::default(), LoremBuilder::default());
+//! }
+//! ```
+//!
+//! Attributes declared for those traits are _not_ forwarded to the fields on the builder.
+//!
+//! Builders whose fields are all `Copy`, e.g. of numeric configurations, can derive `Copy` to
+//! be passed around and reused without calling `clone`. If a field isn't `Copy`, the compile
+//! error points at that field:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder)]
+//! #[builder(derive(Copy))]
+//! pub struct Limits {
+//!     min: u32,
+//!     max: u32,
+//! }
+//!
+//! fn main() {
+//!     let mut base = LimitsBuilder::default();
+//!     base.min(1);
+//!     let narrow = base.max(2).build().unwrap();
+//!     let wide = base.max(100).build().unwrap();
+//!     assert_eq!((narrow.max, wide.max), (2, 100));
+//! }
+//! ```
+//!
+//! `Display` is the exception: it can't be derived by the compiler, so `derive(Display)`
+//! generates a one-line summary of the builder instead, e.g. for `--dry-run` output or logs.
+//! Fields which have been set are shown with their `Debug` representation, and fields marked
+//! with `#[builder(redact)]` are never shown:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder)]
+//! #[builder(derive(Display))]
+//! pub struct Login {
+//!     user: String,
+//!     port: u16,
+//!     #[builder(redact)]
+//!     password: String,
+//! }
+//!
+//! fn main() {
+//!     let mut builder = LoginBuilder::default();
+//!     builder.user("lorem".into()).password("ipsum".into());
+//!
+//!     assert_eq!(
+//!         builder.to_string(),
+//!         r#"LoginBuilder { user: "lorem", port: <unset>, password: <redacted> }"#
+//!     );
+//! }
+//! ```
+//!
+//! `derive(ConfigKey)` is generated as well. It implements `PartialEq`, `Eq` and `Hash` over the
+//! fields which have been set, and the [`ConfigKey`] trait, whose `config_key` method hashes
+//! them with a [`StableHasher`]. Builders can then be used as keys to memoize expensive
+//! resources built from them, like connection pools. Unlike the hashers of `std`, the key is the
+//! same in every process, so it can also name a cache on disk:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! use derive_builder::ConfigKey;
+//! use std::collections::HashMap;
+//!
+//! #[derive(Builder)]
+//! #[builder(derive(ConfigKey), setter(into))]
+//! pub struct Pool {
+//!     url: String,
+//!     #[builder(default = "4")]
+//!     size: u16,
+//! }
+//!
 //! fn main() {
-//!     // If we're trying too hard...
-//!     let x = LoremBuilder::default().ipsum(120).build().unwrap_err();
-//!
-//!     // .. the build will fail:
-//!     assert_eq!(&x.to_string(), "You'll tire yourself out");
+//!     let mut pools: HashMap<PoolBuilder, Pool> = HashMap::new();
+//!     let mut builder = PoolBuilder::default();
+//!     builder.url("localhost");
+//!     let key = builder.config_key();
+//!     pools.insert(builder, PoolBuilder::default().url("localhost").build().unwrap());
+//!
+//!     let mut builder = PoolBuilder::default();
+//!     builder.url("localhost");
+//!     assert_eq!(builder.config_key(), key);
+//!     assert!(pools.contains_key(&builder));
+//!
+//!     // Setting a field to its default still changes the configuration.
+//!     builder.size(4u16);
+//!     assert!(!pools.contains_key(&builder));
 //! }
 //! ```
 //!
-//! Note:
-//! * Default values are applied _after_ validation, and will therefore not be validated!
+//! The types of the fields must implement `Eq` and `Hash`, and `PartialEq`, `Eq` and `Hash` can't
+//! be in the derived traits as well.
 //!
-//! ## Additional Trait Derivations
+//! ## Generated Items
 //!
-//! You can derive additional traits on the builder, including traits defined by other crates:
+//! The generated items and their names only depend on the struct and its options, and are
+//! emitted in a fixed order:
+//!
+//! 1. The builder struct.
+//! 2. Its inherent methods: the setters in the declaration order of the fields, each followed by
+//!    its additional setters, e.g. of `each`, then the build methods, e.g. `build`,
+//!    `build_with_fallback` and `build_or_panic`, then other methods like `reset`, and finally
+//!    `create_empty`.
+//! 3. The generated error type, and the other types and impls enabled by options.
+//!
+//! Helpers which aren't meant to be called are `#[doc(hidden)]`, and their names start with
+//! `__`. A change to the order or names of the other items is a breaking change.
+//!
+//! `#[builder(abi_stable_names)]` lists the names of the items in a hidden `GENERATED_ITEMS`
+//! constant on the builder, so semver tooling, FFI layers and tests can check that the generated
+//! surface doesn't change. Methods are prefixed with their type, and hidden items, trait impls
+//! and items which depend on a `cfg`, like `fuzz_build`, are left out:
 //!
 //! ```rust
 //! # use derive_builder::Builder;
 //! #
-//! #[derive(Builder, Clone)]
-//! #[builder(derive(Debug, PartialEq, Eq))]
-//! pub struct Lorem {
-//!     foo: u8,
-//!     bar: String,
+//! #[derive(Builder)]
+//! #[builder(abi_stable_names, reset)]
+//! struct Lorem {
+//!     #[builder(setter(each(name = "item")))]
+//!     items: Vec<u32>,
+//!     ipsum: u32,
 //! }
 //!
-//! fn main() {
-//!    assert_eq!(LoremBuilder::default(), LoremBuilder::default());
-//! }
+//! # fn main() {
+//! assert_eq!(
+//!     LoremBuilder::GENERATED_ITEMS,
+//!     &[
+//!         "LoremBuilder",
+//!         "LoremBuilder::items",
+//!         "LoremBuilder::item",
+//!         "LoremBuilder::ipsum",
+//!         "LoremBuilder::build",
+//!         "LoremBuilder::reset",
+//!         "LoremBuilder::create_empty",
+//!         "LoremBuilderError",
+//!     ]
+//! );
+//! # }
 //! ```
 //!
-//! Attributes declared for those traits are _not_ forwarded to the fields on the builder.
-//!
 //! ## Documentation Comments and Attributes
 //!
 //! `#[derive(Builder)]` copies doc comments and attributes (`#[...]`) from your fields
@@ -519,6 +2551,13 @@
 //! The whitelisting minimizes interference with other custom attributes like
 //! those used by Serde, Diesel, or others.
 //!
+//! With the `doc_cfg` feature of `derive_builder` enabled, each `#[cfg(...)]` on a field also
+//! adds `#[cfg_attr(docsrs, doc(cfg(...)))]` to its setter, so that docs.rs shows which features
+//! the setter requires. Crates using this need `#![cfg_attr(docsrs, feature(doc_cfg))]`.
+//!
+//! The documentation of the builder struct itself includes a usage example, which calls the
+//! setter of every field without a default value, followed by the build method.
+//!
 //! ```rust
 //! # use derive_builder::Builder;
 //! #
@@ -564,6 +2603,263 @@
 //! # }
 //! ```
 //!
+//! With `#[builder(split_impls)]`, the setters, the setters of `try_setter` and the build
+//! methods (with variants like `build_or_panic`) are generated in separate `impl` blocks, next
+//! to the one with the other methods. Each block gets the attributes of its own
+//! `builder_*_impl_attr`, so a whole group can be `cfg`-gated or hidden at once:
+//!
+//! - `builder_setters_impl_attr` adds attributes on the `impl` block of the setters.
+//! - `builder_try_setters_impl_attr` adds attributes on the `impl` block of the try setters.
+//! - `builder_build_impl_attr` adds attributes on the `impl` block of the build methods.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder)]
+//! #[builder(split_impls, try_setter)]
+//! #[builder_try_setters_impl_attr(doc(hidden))]
+//! struct Lorem {
+//!     ipsum: u32,
+//! }
+//!
+//! # fn main() {
+//! let lorem = LoremBuilder::default().ipsum(42).build().unwrap();
+//! # assert_eq!(lorem.ipsum, 42);
+//! # }
+//! ```
+//!
+//! The visibility of the groups is set as usual, with `setter(vis = "...")` and
+//! `build_fn(vis = "...")`. The setters of `overridable` builders are declared in a trait, so
+//! they can't be split from it.
+//!
+//! If the builder field should simply get the same attributes of another macro as the field
+//! of the struct, list those attributes in `#[builder(forward_attrs(...))]` instead of
+//! repeating them:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! # use serde::Serialize;
+//! #
+//! #[derive(Builder, Serialize)]
+//! #[builder(derive(Serialize))]
+//! struct Lorem {
+//!     #[serde(rename = "dolor")]
+//!     #[builder(forward_attrs(serde))]
+//!     ipsum: String,
+//! }
+//!
+//! # fn main() {
+//! let mut show = LoremBuilder::default();
+//! show.ipsum("sit".into());
+//! assert_eq!(serde_json::to_string(&show).unwrap(), r#"{"dolor":"sit"}"#);
+//! # }
+//! ```
+//!
+//! The attributes must still be accepted on the struct, e.g. because it derives the
+//! corresponding trait too.
+//!
+//! Together, these let the builder serve as the input type of another framework, whose
+//! handler calls `build()` to validate the input, instead of a parallel input struct with a
+//! manual conversion. For example, a GraphQL mutation can take the builder as an
+//! [async-graphql](https://docs.rs/async-graphql) input object:
+//!
+//! ```rust,ignore
+//! #[derive(Builder)]
+//! #[builder(derive(async_graphql::InputObject), build_fn(validate = "Self::validate"))]
+//! #[builder_struct_attr(graphql(name = "CreateUserInput"))]
+//! struct CreateUser {
+//!     user_name: String,
+//!     #[builder(setter(skip))]
+//!     #[builder_field_attr(graphql(skip))]
+//!     created: bool,
+//! }
+//!
+//! #[Object]
+//! impl Mutation {
+//!     async fn create_user(&self, input: CreateUserBuilder) -> Result<User> {
+//!         let user = input.build()?;
+//!         // ...
+//!     }
+//! }
+//! ```
+//!
+//! Each builder field is an `Option` of the field type, so all fields of the input type are
+//! optional, and missing required fields are reported by `build()`. Fields whose setters are
+//! skipped are still declared on the builder as `PhantomData`, so they need the framework's
+//! equivalent of `#[builder_field_attr(graphql(skip))]`.
+//!
+//! ### Unknown Keys in Deserialized Builders
+//!
+//! Builders which derive serde's `Deserialize`, e.g. to load configuration files, ignore keys
+//! which don't belong to any field, so a typo silently leaves a field unset. With
+//! `#[builder(deserialize(deny_unknown_fields))]`, such keys are an error instead. With
+//! `#[builder(deserialize(extra = "..."))]`, they are collected into a map with values of the
+//! given type, which the builder's `extra()` method returns for inspection:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug)]
+//! #[builder(derive(serde::Deserialize), deserialize(extra = "serde_json::Value"))]
+//! struct Server {
+//!     host: String,
+//!     #[builder(default = "8080")]
+//!     port: u16,
+//! }
+//!
+//! # fn main() {
+//! let builder: ServerBuilder =
+//!     serde_json::from_str(r#"{"host": "localhost", "prot": 80}"#).unwrap();
+//! assert_eq!(builder.extra()["prot"], 80);
+//! assert_eq!(builder.build().unwrap().port, 8080);
+//! # }
+//! ```
+//!
+//! `extra` requires the `std` feature of `derive_builder`. The map is a builder field with
+//! `#[serde(flatten)]`, so the two options can't be combined, and the struct must not have a
+//! field named `extra`.
+//!
+//! ### Lints
+//!
+//! Trait impls of the builder are marked `#[automatically_derived]`. Lints which fire inside
+//! generated code can be allowed on all generated items with `#[builder(allow(...))]`, e.g. for
+//! crates with `#![deny(missing_docs)]` or strict clippy settings:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder)]
+//! #[builder(allow(missing_docs, clippy::needless_update))]
+//! pub struct Lorem {
+//!     /// Field without separate documentation for its setter.
+//!     pub ipsum: u32,
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! ### Deprecated Builders
+//!
+//! `#[builder(deprecated(note = "..."))]` marks the builder struct, its setters and its build
+//! method as `#[deprecated]`, so users of the builder get warnings, while the struct itself
+//! stays usable. The arguments are passed on to `#[deprecated]`, and the generated items allow
+//! the `deprecated` lint, so they don't warn about their own use of the builder.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder)]
+//! #[builder(deprecated(note = "use `ServerConfigBuilder`"))]
+//! pub struct Config {
+//!     pub port: u16,
+//! }
+//!
+//! # #[allow(deprecated)]
+//! # fn main() {
+//! # let config = ConfigBuilder::default().port(80).build().unwrap();
+//! # assert_eq!(config.port, 80);
+//! # }
+//! ```
+//!
+//! ### Feature-Gated Builders
+//!
+//! Libraries can offer the builder as an optional feature with `#[builder(cfg(...))]`, which
+//! puts the builder struct, its impls, its error type and its other generated items behind
+//! that `cfg` predicate. The struct itself is unaffected:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder)]
+//! #[builder(cfg(feature = "std"))]
+//! pub struct Lorem {
+//!     ipsum: u32,
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! Single setters can be gated on a cargo feature with `#[builder(setter(feature = "..."))]`,
+//! which is short for `#[builder_setter_attr(cfg(feature = "..."))]`. The builder field and the
+//! build method are unaffected, so the field needs a default value, which is used while the
+//! feature is disabled. The field can't be set by `from_str`, `from_query` and `dyn_set`
+//! either:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder)]
+//! pub struct Client {
+//!     host: String,
+//!     #[builder(default, setter(feature = "tls"))]
+//!     tls: bool,
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! # Attribute Macro
+//!
+//! A derive cannot change the struct it is applied to. If you would like an associated
+//! `builder()` function on the struct itself, use the `#[builder_attr]` attribute macro
+//! instead of `#[derive(Builder)]`. It accepts the same options as a struct-level
+//! `#[builder(...)]` attribute, and strips all `derive_builder` helper attributes from the
+//! struct before emitting it.
+//!
+//! ```rust
+//! use derive_builder::builder_attr;
+//!
+//! #[builder_attr(setter(into))]
+//! #[derive(Debug, PartialEq)]
+//! struct Lorem {
+//!     ipsum: String,
+//!     #[builder(default)]
+//!     dolor: u32,
+//! }
+//!
+//! # fn main() {
+//! let x = Lorem::builder().ipsum("sit").build().unwrap();
+//! assert_eq!(x, Lorem { ipsum: "sit".into(), dolor: 0 });
+//! # }
+//! ```
+//!
+//! Place `#[builder_attr]` above any `#[derive(...)]` on the struct, so that it is expanded first.
+//!
+//! The struct's documentation gets a link to its builder, complementing the link from the
+//! builder's documentation back to the struct.
+//!
+//! # Constructor Macro
+//!
+//! With `#[builder(constructor_macro)]`, a declarative macro is generated alongside the
+//! builder, which constructs the struct from `field: value` pairs. Each pair calls the
+//! corresponding setter, so defaults and conversions behave exactly like with the builder,
+//! and the result of the build method is unwrapped. Leaving out a field without a default
+//! is a compile error.
+//!
+//! ```rust
+//! # #[macro_use]
+//! # extern crate derive_builder;
+//! #
+//! #[derive(Builder, Debug, PartialEq)]
+//! #[builder(constructor_macro, setter(into))]
+//! struct Lorem {
+//!     ipsum: u32,
+//!     #[builder(default)]
+//!     dolor: String,
+//! }
+//!
+//! # fn main() {
+//! let x = lorem! { ipsum: 42u8, dolor: "x" };
+//! assert_eq!(x, Lorem { ipsum: 42, dolor: "x".into() });
+//! # }
+//! ```
+//!
+//! The macro is named after the struct in snake case. Use `constructor_macro = "..."` to
+//! choose another name. Like any `macro_rules!` macro, it can only be used below the struct
+//! in the same module and its child modules. The builder must be in scope wherever the macro
+//! is used.
+//!
+//! The macro calls `Default::default` and the build method, so `custom_constructor` and
+//! `build_fn(skip)` are not supported, and the build error type must implement `Debug`.
+//! Validation errors cause a panic.
+//!
 //! # Error return type from autogenerated `build` function
 //!
 //! By default, `build` returns an autogenerated error type:
@@ -592,6 +2888,77 @@
 //! impl std::error::Error for LoremBuilderError {}
 //! ```
 //!
+//! Failures can be told apart by matching on the variants instead of the message:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug)]
+//! struct Lorem {
+//!     ipsum: u32,
+//! }
+//!
+//! fn main() {
+//!     match LoremBuilder::default().build() {
+//!         Err(LoremBuilderError::UninitializedField(field)) => assert_eq!(field, "ipsum"),
+//!         Err(LoremBuilderError::ValidationError(msg)) => panic!("invalid: {}", msg),
+//!         Ok(lorem) => panic!("built {:?}", lorem),
+//!     }
+//! }
+//! ```
+//!
+//! To change the message of uninitialized fields, e.g. to localize it, declare
+//! `#[builder(build_fn(message_fn = "path::to::fn"))]`. The function receives the struct name,
+//! the field name and a [`BuilderErrorKind`], and may return anything which implements
+//! `Display`:
+//!
+//! ```rust
+//! # use derive_builder::{Builder, BuilderErrorKind};
+//! #
+//! #[derive(Builder, Debug)]
+//! #[builder(build_fn(message_fn = "builder_msg"))]
+//! struct Lorem {
+//!     pub ipsum: u32,
+//! }
+//!
+//! fn builder_msg(struct_name: &str, field_name: &str, _kind: BuilderErrorKind) -> String {
+//!     format!("{}: veuillez renseigner `{}`", struct_name, field_name)
+//! }
+//!
+//! # fn main() {
+//! let err = LoremBuilder::default().build().unwrap_err();
+//! assert_eq!(&err.to_string(), "Lorem: veuillez renseigner `ipsum`");
+//! # }
+//! ```
+//!
+//! Validation errors keep the message returned by the validator.
+//!
+//! To tell which field an error is about without comparing names, e.g. to highlight it in a
+//! form, declare `#[builder(field_enum)]`. This generates an enum with a variant for each
+//! field, named after the struct with a `Field` suffix unless named with
+//! `field_enum = "..."`, and a `field()` method on the generated error which returns it:
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder, Debug)]
+//! #[builder(field_enum)]
+//! struct Lorem {
+//!     ipsum: u32,
+//!     dolor_sit: u32,
+//! }
+//!
+//! # fn main() {
+//! let err = LoremBuilder::default().ipsum(1).build().unwrap_err();
+//! assert_eq!(err.field(), Some(LoremField::DolorSit));
+//! assert_eq!(LoremField::DolorSit.name(), "dolor_sit");
+//! assert_eq!(LoremField::from_name("ipsum"), Some(LoremField::Ipsum));
+//! # }
+//! ```
+//!
+//! Errors about several fields return the first of them, and validation errors return `None`.
+//! With a custom error type, `from_name` converts the field names it receives.
+//!
 //! Alternatively, you can specify your own error type:
 //! ```rust
 //! # use derive_builder::{Builder, UninitializedFieldError};
@@ -614,6 +2981,14 @@
 //! # }
 //! ```
 //!
+//! The error type must implement `From<UninitializedFieldError>`. Errors of validators are
+//! converted with `?`, so they may return any type which the error type implements `From` for,
+//! e.g. `String` or the error type itself.
+//!
+//! `UninitializedFieldError` implements `std::error::Error` and tells the name of the missing
+//! field with `field_name()`, so `build_fn(error = "Box<dyn std::error::Error>")` works too, and
+//! the boxed error can be downcast to find the field.
+//!
 //! # Completely custom fields in the builder
 //!
 //! Instead of having an `Option`, you can have whatever type you like:
@@ -662,17 +3037,61 @@
 //!
 //! # Troubleshooting
 //!
+//! ## Debugging Info
+//!
+//! Add `#[builder(dump)]` to a struct to print the code generated for it to stderr
+//! while compiling. Enable the `pretty` feature of `derive_builder` to have the dump
+//! formatted much like `rustfmt` would, instead of as a single line of tokens.
+//!
+//! ```rust
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder)]
+//! #[builder(dump)]
+//! struct Lorem {
+//!     ipsum: u32,
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! ## Strict Mode
+//!
+//! Some combinations of options are tolerated, although one of them has no effect. With
+//! `#[builder(strict)]`, they are compile errors instead:
+//!
+//! * Other setter options next to `setter(skip)`, e.g. `setter(skip, into)`.
+//! * `default` without a value on a field without a setter, whose default is implied.
+//! * `setter(name = "...")` together with a `prefix` on the field or the struct, which the
+//!   name overrides.
+//!
+//! ```rust,compile_fail
+//! # use derive_builder::Builder;
+//! #
+//! #[derive(Builder)]
+//! #[builder(strict, setter(prefix = "with"))]
+//! struct Lorem {
+//!     #[builder(setter(name = "ipsum"))]
+//!     ipsum: u32,
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! Options which only exist on fields, e.g. `setter(name = "...")`, are rejected at the
+//! struct level in any case.
+//!
 //! ## Gotchas
 //!
 //! - Tuple structs and unit structs are not supported as they have no field
 //!   names.
-//! - Generic setters introduce a type parameter `VALUE: Into<_>`. Therefore you can't use
-//!   `VALUE` as a type parameter on a generic struct in combination with generic setters.
+//! - Generic setters introduce a type parameter `VALUE: Into<_>`, which is renamed to `VALUE_`
+//!   if the struct has a generic parameter named `VALUE`, including in the `bound` of `each`
+//!   setters. Name it yourself with `#[builder(setter(generic = "V"))]` on the struct or a field.
 //! - The `try_setter` attribute and `owned` builder pattern are not compatible in practice;
 //!   an error during building will consume the builder, making it impossible to continue
 //!   construction.
-//! - When re-exporting the underlying struct under a different name, the
-//!   auto-generated documentation will not match.
+//! - `macro_rules!` cannot substitute metavariables such as `$crate` inside string literals.
+//!   When deriving `Builder` on a struct declared by a macro, pass expressions without quotes,
+//!   e.g. `#[builder(default = $crate::default_port())]`.
 //! - If derive_builder depends on your crate, and vice versa, then a cyclic
 //!   dependency would occur. To break it you could try to depend on the
 //!   [`derive_builder_core`] crate instead.
@@ -695,21 +3114,117 @@ extern crate alloc;
 
 extern crate derive_builder_macro;
 
+mod config_key;
+#[cfg(feature = "std")]
+mod counting_alloc;
+#[cfg(any(feature = "alloc", feature = "std"))]
+mod dyn_set;
 mod error;
+#[cfg(any(feature = "alloc", feature = "std"))]
+mod key_value;
+#[cfg(any(feature = "alloc", feature = "std"))]
+mod lazy;
+#[cfg(feature = "urlencoded")]
+mod query;
+#[cfg(feature = "std")]
+mod time;
 
-pub use derive_builder_macro::Builder;
+pub use derive_builder_macro::{builder_attr, Builder};
 
 #[doc(inline)]
-pub use error::UninitializedFieldError;
+pub use config_key::{ConfigKey, StableHasher};
+#[cfg(feature = "std")]
+#[doc(inline)]
+pub use counting_alloc::{count_allocations, CountingAllocator};
+#[cfg(any(feature = "alloc", feature = "std"))]
+#[doc(inline)]
+pub use dyn_set::{DynSet, DynSetError, DynSetErrorKind};
+#[doc(inline)]
+pub use error::{
+    ArrayLengthError, BuilderError, BuilderErrorKind, FieldDependencyError, FieldDependencyKind,
+    UninitializedFieldError,
+};
+#[cfg(any(feature = "alloc", feature = "std"))]
+#[doc(inline)]
+pub use error::{NestedFieldError, UninitializedFieldsError};
+#[cfg(any(feature = "alloc", feature = "std"))]
+#[doc(inline)]
+pub use key_value::KeyValueError;
+#[cfg(any(feature = "alloc", feature = "std"))]
+#[doc(inline)]
+pub use lazy::LazyValue;
+#[cfg(feature = "urlencoded")]
+#[doc(inline)]
+pub use query::QueryError;
+#[cfg(feature = "std")]
+#[doc(inline)]
+pub use time::Now;
+
+/// Keep the items only with `cfg(fuzzing)`, which is checked here rather than in the crate
+/// deriving the builder.
+#[cfg(all(feature = "fuzzing", fuzzing))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __fuzzing {
+    ($($item:tt)*) => { $($item)* };
+}
+
+#[cfg(all(feature = "fuzzing", not(fuzzing)))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __fuzzing {
+    ($($item:tt)*) => {};
+}
 
 #[doc(hidden)]
 pub mod export {
     pub mod core {
+        #[cfg(feature = "alloc")]
+        pub use ::alloc::borrow;
+        #[cfg(feature = "alloc")]
+        pub use ::alloc::boxed;
+        #[cfg(feature = "alloc")]
+        pub use ::alloc::rc;
         #[cfg(feature = "alloc")]
         pub use ::alloc::string;
+        #[cfg(feature = "alloc")]
+        pub use ::alloc::sync;
+        #[cfg(feature = "alloc")]
+        pub use ::alloc::vec;
         #[cfg(not(feature = "std"))]
         pub use core::*;
         #[cfg(feature = "std")]
         pub use std::*;
     }
+
+    #[cfg(any(feature = "alloc", feature = "std"))]
+    pub mod key_value {
+        pub use crate::key_value::{pairs, parse, unknown_key};
+    }
+
+    #[cfg(feature = "urlencoded")]
+    pub mod query {
+        pub use crate::query::{pairs, parse};
+    }
+
+    #[cfg(feature = "fuzzing")]
+    pub mod arbitrary {
+        pub use crate::__fuzzing as fuzzing;
+        pub use ::arbitrary::{Arbitrary, Unstructured};
+    }
+
+    pub mod panic {
+        pub use crate::error::MissingFields;
+    }
+
+    #[cfg(feature = "std")]
+    pub mod time {
+        pub use crate::time::{since_epoch, Now};
+    }
+
+    #[cfg(feature = "json")]
+    pub mod json {
+        pub use serde::Serialize;
+        pub use serde_json::{to_value, Error, Map, Value};
+    }
 }
@@ -0,0 +1,135 @@
+//! Decoding of `application/x-www-form-urlencoded` query strings for the `from_query`
+//! functions of builders.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Runtime error when a `from_query` function is given a query string whose value for a field
+/// can't be decoded or parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryError {
+    key: String,
+    message: String,
+}
+
+impl QueryError {
+    /// Create a new `QueryError` for the query parameter `key`.
+    pub fn new(key: String, message: String) -> Self {
+        QueryError { key, message }
+    }
+
+    /// Get the key of the query parameter
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Get the description of why the value couldn't be decoded or parsed
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid query parameter `{}`: {}",
+            self.key, self.message
+        )
+    }
+}
+
+impl Error for QueryError {}
+
+/// Split a query string, with or without a leading `?`, into its decoded key-value pairs.
+pub fn pairs(query: &str) -> impl Iterator<Item = Result<(String, String), QueryError>> + '_ {
+    let query = query.strip_prefix('?').unwrap_or(query);
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let key = decode(key).map_err(|message| QueryError::new(key.to_string(), message))?;
+            let value = decode(value).map_err(|message| QueryError::new(key.clone(), message))?;
+            Ok((key, value))
+        })
+}
+
+/// Parse the decoded value of the query parameter `key`.
+pub fn parse<T>(key: &str, value: &str) -> Result<T, QueryError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    value
+        .parse()
+        .map_err(|e: T::Err| QueryError::new(key.to_string(), e.to_string()))
+}
+
+/// Decode `+` and percent-encoded bytes.
+fn decode(encoded: &str) -> Result<String, String> {
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut iter = encoded.bytes();
+    while let Some(byte) = iter.next() {
+        match byte {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let high = iter.next().and_then(hex_digit);
+                let low = iter.next().and_then(hex_digit);
+                match (high, low) {
+                    (Some(high), Some(low)) => bytes.push(high << 4 | low),
+                    _ => return Err("invalid percent-encoding".to_string()),
+                }
+            }
+            _ => bytes.push(byte),
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| "invalid UTF-8".to_string())
+}
+
+fn hex_digit(byte: u8) -> Option<u8> {
+    (byte as char).to_digit(16).map(|digit| digit as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decoded(query: &str) -> Result<Vec<(String, String)>, QueryError> {
+        pairs(query).collect()
+    }
+
+    #[test]
+    fn decodes_pairs() {
+        assert_eq!(
+            decoded("?a=1&b=lorem+ipsum&&c&d=%C3%A4%3D").unwrap(),
+            vec![
+                ("a".to_string(), "1".to_string()),
+                ("b".to_string(), "lorem ipsum".to_string()),
+                ("c".to_string(), String::new()),
+                ("d".to_string(), "ä=".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_encoding() {
+        let err = decoded("a=%4").unwrap_err();
+        assert_eq!(err.key(), "a");
+        assert_eq!(
+            err.to_string(),
+            "invalid query parameter `a`: invalid percent-encoding"
+        );
+        assert_eq!(decoded("a=%FF").unwrap_err().message(), "invalid UTF-8");
+    }
+
+    #[test]
+    fn parse_error() {
+        let err = parse::<u8>("a", "256").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid query parameter `a`: number too large to fit in target type"
+        );
+    }
+}
@@ -0,0 +1,32 @@
+//! Time defaults which are evaluated when the struct is built, for `#[builder(default(now))]`
+//! and `#[builder(default(since_epoch = "..."))]`.
+
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// A point in time which `#[builder(default(now))]` sets to the time of the build.
+///
+/// It is implemented for `SystemTime` and `Instant`, and can be implemented for other
+/// timestamp types, e.g. of a date and time crate.
+pub trait Now {
+    /// Get the current time.
+    fn now() -> Self;
+}
+
+impl Now for SystemTime {
+    fn now() -> Self {
+        SystemTime::now()
+    }
+}
+
+impl Now for Instant {
+    fn now() -> Self {
+        Instant::now()
+    }
+}
+
+/// Get the time since the Unix epoch, or zero if the system clock is set before it.
+pub fn since_epoch() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
@@ -0,0 +1,59 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, Clone, Builder)]
+#[builder(abi_stable_names, reset, build_fn(fallback, panic_variant))]
+pub struct Lorem {
+    #[builder(setter(into, strip_option), default)]
+    ipsum: Option<String>,
+    #[builder(setter(each(name = "dolor_item")))]
+    dolor: Vec<u32>,
+    sit: u32,
+}
+
+#[derive(Debug, Builder)]
+#[allow(dead_code)]
+#[builder(abi_stable_names, pattern = "owned", field_enum)]
+pub struct Amet<T> {
+    consectetur: T,
+}
+
+#[test]
+fn setters_in_field_order() {
+    assert_eq!(
+        LoremBuilder::GENERATED_ITEMS,
+        &[
+            "LoremBuilder",
+            "LoremBuilder::ipsum",
+            "LoremBuilder::dolor",
+            "LoremBuilder::dolor_item",
+            "LoremBuilder::sit",
+            "LoremBuilder::build",
+            "LoremBuilder::build_with_fallback",
+            "LoremBuilder::build_or_panic",
+            "LoremBuilder::reset",
+            "LoremBuilder::create_empty",
+            "LoremBuilderError",
+        ]
+    );
+}
+
+#[test]
+fn generic_builder() {
+    assert_eq!(
+        AmetBuilder::<u32>::GENERATED_ITEMS,
+        &[
+            "AmetBuilder",
+            "AmetBuilder::consectetur",
+            "AmetBuilder::build",
+            "AmetBuilder::create_empty",
+            "AmetBuilderError",
+            "AmetBuilderError::field",
+            "AmetField",
+            "AmetField::name",
+            "AmetField::from_name",
+        ]
+    );
+}
@@ -0,0 +1,43 @@
+#![deny(missing_docs)]
+//! Crate-level lints must not fail because of code generated by `derive_builder`.
+
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+/// Struct whose fields and setters have no documentation.
+#[derive(Debug, PartialEq, Builder)]
+#[builder(
+    allow(missing_docs, clippy::needless_update),
+    derive(Display),
+    build_fn(validate = "Self::validate")
+)]
+#[allow(missing_docs)]
+pub struct Lorem {
+    pub ipsum: u32,
+    pub dolor: Option<String>,
+}
+
+impl LoremBuilder {
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+#[test]
+fn builds_with_allowed_lints() {
+    let lorem = LoremBuilder::default()
+        .ipsum(42)
+        .dolor(None)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        lorem,
+        Lorem {
+            ipsum: 42,
+            dolor: None,
+        }
+    );
+}
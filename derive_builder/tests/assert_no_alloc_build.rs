@@ -0,0 +1,62 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use derive_builder::{count_allocations, CountingAllocator};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[derive(Debug, Clone, Copy, PartialEq, Builder)]
+#[builder(assert_no_alloc_build)]
+struct Point {
+    #[builder(default)]
+    x: i32,
+    #[builder(default = "1")]
+    y: i32,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(assert_no_alloc_build, pattern = "owned", build_fn(name = "finish"))]
+struct Limits<'a> {
+    #[builder(default = "\"none\"")]
+    name: &'a str,
+    #[builder(default, field(bare))]
+    max: u64,
+}
+
+#[test]
+fn counts_allocations() {
+    let (v, allocations) = count_allocations(|| vec![1u8, 2, 3]);
+    assert_eq!(v.len(), 3);
+    assert_eq!(allocations, 1);
+
+    let (_, allocations) = count_allocations(|| 1 + 1);
+    assert_eq!(allocations, 0);
+}
+
+#[test]
+fn nested_counts_add_up() {
+    let (inner, outer) = count_allocations(|| {
+        let _ = Box::new(1);
+        count_allocations(|| Box::new(2)).1
+    });
+    assert_eq!(inner, 1);
+    assert_eq!(outer, 2);
+}
+
+#[test]
+fn set_values_are_built() {
+    let point = PointBuilder::default().x(3).build().unwrap();
+    assert_eq!(point, Point { x: 3, y: 1 });
+
+    let limits = LimitsBuilder::default().max(5).finish().unwrap();
+    assert_eq!(
+        limits,
+        Limits {
+            name: "none",
+            max: 5
+        }
+    );
+}
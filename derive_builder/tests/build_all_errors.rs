@@ -0,0 +1,112 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(all_errors, validate = "Self::validate"))]
+struct Server {
+    #[builder(setter(into))]
+    host: String,
+    #[builder(validate = "validate_port")]
+    port: u16,
+    #[builder(default)]
+    workers: u32,
+    #[builder(default, requires = "host")]
+    tls: bool,
+}
+
+fn validate_port(port: &u16) -> Result<(), String> {
+    match *port {
+        0 => Err("port must not be 0".to_string()),
+        _ => Ok(()),
+    }
+}
+
+impl ServerBuilder {
+    fn validate(&self) -> Result<(), String> {
+        match self.workers {
+            Some(0) => Err("workers must not be 0".to_string()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned", derive(Debug), build_fn(all_errors, return_builder))]
+struct Owned {
+    name: String,
+    size: u32,
+}
+
+#[test]
+fn builds_like_build() {
+    let server = ServerBuilder::default()
+        .host("localhost")
+        .port(8080)
+        .build_all_errors()
+        .unwrap();
+
+    assert_eq!(
+        server,
+        Server {
+            host: "localhost".to_string(),
+            port: 8080,
+            workers: 0,
+            tls: false,
+        }
+    );
+}
+
+#[test]
+fn reports_all_errors() {
+    let errors = ServerBuilder::default()
+        .port(0)
+        .workers(0)
+        .tls(true)
+        .build_all_errors()
+        .unwrap_err();
+
+    let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        messages,
+        vec![
+            "port must not be 0",
+            "`tls` requires `host` to be set",
+            "workers must not be 0",
+            "`host` must be initialized",
+        ]
+    );
+}
+
+#[test]
+fn build_still_fails_fast() {
+    let error = ServerBuilder::default()
+        .port(0)
+        .workers(0)
+        .build()
+        .unwrap_err();
+
+    assert_eq!(error.to_string(), "port must not be 0");
+}
+
+#[test]
+fn returns_owned_builder_with_errors() {
+    let (builder, errors) = OwnedBuilder::default().build_all_errors().unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].to_string(), "`name` must be initialized");
+    assert_eq!(errors[1].to_string(), "`size` must be initialized");
+
+    let owned = builder
+        .name("lorem".into())
+        .size(2)
+        .build_all_errors()
+        .unwrap();
+    assert_eq!(
+        owned,
+        Owned {
+            name: "lorem".to_string(),
+            size: 2,
+        }
+    );
+}
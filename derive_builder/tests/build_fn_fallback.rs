@@ -0,0 +1,128 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(build_fn(fallback, validate = "Self::validate"))]
+struct Lorem {
+    ipsum: u32,
+    #[builder(default = "42")]
+    dolor: u32,
+    #[builder(setter(into))]
+    sit: String,
+    #[builder(setter(skip))]
+    amet: bool,
+}
+
+impl LoremBuilder {
+    fn validate(&self) -> Result<(), String> {
+        match self.ipsum {
+            Some(0) => Err("ipsum must not be zero".into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(pattern = "owned", build_fn(fallback, name = "finish"))]
+struct Consectetur {
+    adipiscing: Vec<u8>,
+    elit: Option<String>,
+}
+
+fn fallback() -> Lorem {
+    Lorem {
+        ipsum: 1,
+        dolor: 2,
+        sit: "sit".into(),
+        amet: true,
+    }
+}
+
+#[test]
+fn unset_fields_from_fallback() {
+    let lorem = LoremBuilder::default()
+        .ipsum(7)
+        .build_with_fallback(&fallback())
+        .unwrap();
+
+    assert_eq!(
+        lorem,
+        Lorem {
+            ipsum: 7,
+            dolor: 2,
+            sit: "sit".into(),
+            amet: true,
+        }
+    );
+}
+
+#[test]
+fn set_fields_take_precedence() {
+    let lorem = LoremBuilder::default()
+        .ipsum(7)
+        .dolor(8)
+        .sit("amet")
+        .build_with_fallback(&fallback())
+        .unwrap();
+
+    assert_eq!(
+        lorem,
+        Lorem {
+            ipsum: 7,
+            dolor: 8,
+            sit: "amet".into(),
+            amet: true,
+        }
+    );
+}
+
+#[test]
+fn build_is_unaffected() {
+    assert_eq!(
+        LoremBuilder::default()
+            .ipsum(7)
+            .sit("amet")
+            .build()
+            .unwrap(),
+        Lorem {
+            ipsum: 7,
+            dolor: 42,
+            sit: "amet".into(),
+            amet: false,
+        }
+    );
+    assert!(LoremBuilder::default().build().is_err());
+}
+
+#[test]
+fn validation_still_applies() {
+    let err = LoremBuilder::default()
+        .ipsum(0)
+        .build_with_fallback(&fallback())
+        .unwrap_err();
+
+    assert_eq!(err.to_string(), "ipsum must not be zero");
+}
+
+#[test]
+fn owned_with_custom_name() {
+    let fallback = Consectetur {
+        adipiscing: vec![1, 2],
+        elit: Some("elit".into()),
+    };
+
+    let consectetur = ConsecteturBuilder::default()
+        .elit(None)
+        .finish_with_fallback(&fallback)
+        .unwrap();
+
+    assert_eq!(
+        consectetur,
+        Consectetur {
+            adipiscing: vec![1, 2],
+            elit: None,
+        }
+    );
+}
@@ -0,0 +1,91 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use std::sync::Arc;
+
+mod public {
+    #[derive(Debug, PartialEq)]
+    pub struct PublicConfig {
+        pub address: String,
+    }
+}
+
+use public::PublicConfig;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(
+    build_fn(
+        into = "PublicConfig",
+        fallback,
+        panic_variant,
+        wrappers(arc),
+        post_build = "check_port"
+    ),
+    derive(Debug)
+)]
+struct Config {
+    #[builder(setter(into))]
+    host: String,
+    #[builder(default = "8080")]
+    port: u16,
+}
+
+fn check_port(config: &Config) -> Result<(), String> {
+    match config.port {
+        0 => Err("port must not be 0".to_string()),
+        _ => Ok(()),
+    }
+}
+
+impl From<Config> for PublicConfig {
+    fn from(config: Config) -> Self {
+        PublicConfig {
+            address: format!("{}:{}", config.host, config.port),
+        }
+    }
+}
+
+#[test]
+fn build_converts() {
+    let config: PublicConfig = ConfigBuilder::default().host("localhost").build().unwrap();
+
+    assert_eq!(
+        config,
+        PublicConfig {
+            address: "localhost:8080".to_string()
+        }
+    );
+}
+
+#[test]
+fn build_checks_before_converting() {
+    let error = ConfigBuilder::default()
+        .host("localhost")
+        .port(0)
+        .build()
+        .unwrap_err();
+
+    assert_eq!(error.to_string(), "port must not be 0");
+}
+
+#[test]
+fn other_build_methods_convert() {
+    let base = Config {
+        host: "example.com".to_string(),
+        port: 80,
+    };
+    let config: PublicConfig = ConfigBuilder::default().build_with_fallback(&base).unwrap();
+    assert_eq!(config.address, "example.com:80");
+
+    let config: PublicConfig = ConfigBuilder::default().host("localhost").build_or_panic();
+    assert_eq!(config.address, "localhost:8080");
+
+    let config: Arc<PublicConfig> = ConfigBuilder::default()
+        .host("localhost")
+        .port(3000)
+        .build_arc()
+        .unwrap();
+    assert_eq!(config.address, "localhost:3000");
+}
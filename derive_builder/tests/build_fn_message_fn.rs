@@ -0,0 +1,71 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use derive_builder::BuilderErrorKind;
+
+mod i18n {
+    use derive_builder::BuilderErrorKind;
+
+    pub fn builder_msg(
+        struct_name: &'static str,
+        field_name: &'static str,
+        kind: BuilderErrorKind,
+    ) -> String {
+        match kind {
+            BuilderErrorKind::UninitializedField => {
+                format!("{}: veuillez renseigner `{}`", struct_name, field_name)
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+fn static_msg(_: &'static str, _: &'static str, _: BuilderErrorKind) -> &'static str {
+    "incomplete"
+}
+
+#[derive(Debug, Builder)]
+#[builder(build_fn(message_fn = "i18n::builder_msg", validate = "Self::validate"))]
+struct Lorem {
+    #[allow(dead_code)]
+    ipsum: u8,
+}
+
+impl LoremBuilder {
+    fn validate(&self) -> Result<(), String> {
+        match self.ipsum {
+            Some(0) => Err("ipsum must not be zero".into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Builder)]
+#[builder(build_fn(message_fn = "static_msg"))]
+struct Dolor {
+    #[allow(dead_code)]
+    sit: u8,
+}
+
+#[test]
+fn uninitialized_field_message() {
+    let err = LoremBuilder::default().build().unwrap_err();
+
+    assert_eq!(err.to_string(), "Lorem: veuillez renseigner `ipsum`");
+}
+
+#[test]
+fn validation_message_unchanged() {
+    let err = LoremBuilder::default().ipsum(0).build().unwrap_err();
+
+    assert_eq!(err.to_string(), "ipsum must not be zero");
+}
+
+#[test]
+fn message_fn_returning_str() {
+    let err = DolorBuilder::default().build().unwrap_err();
+
+    assert_eq!(err.to_string(), "incomplete");
+}
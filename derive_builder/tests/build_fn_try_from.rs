@@ -0,0 +1,61 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use std::convert::{TryFrom, TryInto};
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(try_from))]
+struct Lorem {
+    ipsum: u32,
+    #[builder(default)]
+    dolor: String,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(
+    pattern = "owned",
+    derive(Debug),
+    build_fn(try_from, return_builder, name = "finish")
+)]
+struct Generic<T: Clone> {
+    value: T,
+}
+
+fn build_with<B, T>(builder: B) -> Result<T, B::Error>
+where
+    B: TryInto<T>,
+{
+    builder.try_into()
+}
+
+#[test]
+fn converts_like_build() {
+    let mut builder = LoremBuilder::default();
+    builder.ipsum(42);
+
+    assert_eq!(
+        Lorem::try_from(builder).unwrap(),
+        Lorem {
+            ipsum: 42,
+            dolor: String::new(),
+        }
+    );
+}
+
+#[test]
+fn error_is_build_error() {
+    let error: LoremBuilderError = build_with::<_, Lorem>(LoremBuilder::default()).unwrap_err();
+
+    assert_eq!(error.to_string(), "`ipsum` must be initialized");
+}
+
+#[test]
+fn generic_returns_builder() {
+    let (builder, error) = Generic::<u8>::try_from(GenericBuilder::default()).unwrap_err();
+    assert_eq!(error.to_string(), "`value` must be initialized");
+
+    let generic: Generic<u8> = builder.value(7).try_into().unwrap();
+    assert_eq!(generic, Generic { value: 7 });
+}
@@ -0,0 +1,53 @@
+#[macro_use]
+extern crate derive_builder;
+
+mod server {
+    /// A public builder with public setters, which only this crate can build.
+    #[derive(Debug, PartialEq, Builder)]
+    #[builder(public, build_fn(vis = "pub(crate)", fallback))]
+    pub struct Lorem {
+        pub ipsum: u32,
+        #[builder(private)]
+        pub dolor: u32,
+    }
+
+    impl LoremBuilder {
+        pub fn with_dolor(dolor: u32) -> Self {
+            let mut builder = Self::default();
+            builder.dolor(dolor);
+            builder
+        }
+    }
+}
+
+use server::{Lorem, LoremBuilder};
+
+#[test]
+fn crate_visible_build_fn() {
+    let lorem = LoremBuilder::with_dolor(7).ipsum(42).build().unwrap();
+
+    assert_eq!(
+        lorem,
+        Lorem {
+            ipsum: 42,
+            dolor: 7
+        }
+    );
+}
+
+#[test]
+fn crate_visible_fallback_build_fn() {
+    let fallback = Lorem { ipsum: 1, dolor: 2 };
+    let lorem = LoremBuilder::default()
+        .ipsum(42)
+        .build_with_fallback(&fallback)
+        .unwrap();
+
+    assert_eq!(
+        lorem,
+        Lorem {
+            ipsum: 42,
+            dolor: 2
+        }
+    );
+}
@@ -0,0 +1,125 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, Clone, PartialEq, Default, Builder)]
+#[builder(build_fn(reuse, validate = "Self::validate"))]
+struct Request {
+    method: &'static str,
+    #[builder(setter(into))]
+    path: String,
+    #[builder(default)]
+    headers: Vec<(String, String)>,
+    #[builder(default = "30")]
+    timeout: u32,
+}
+
+impl RequestBuilder {
+    fn validate(&self) -> Result<(), String> {
+        match self.timeout {
+            Some(0) => Err("timeout must not be 0".to_string()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned", build_fn(reuse))]
+struct Owned {
+    name: String,
+    #[builder(default)]
+    tags: Vec<String>,
+}
+
+#[test]
+fn assigns_set_and_default_fields() {
+    let mut request = Request {
+        method: "GET",
+        path: "/old".to_string(),
+        headers: vec![("accept".to_string(), "*/*".to_string())],
+        timeout: 5,
+    };
+
+    RequestBuilder::default()
+        .method("POST")
+        .path("/new")
+        .build_into(&mut request)
+        .unwrap();
+
+    assert_eq!(
+        request,
+        Request {
+            method: "POST",
+            path: "/new".to_string(),
+            headers: vec![],
+            timeout: 30,
+        }
+    );
+}
+
+#[test]
+fn keeps_allocation_of_set_values() {
+    let mut request = Request {
+        path: String::with_capacity(64),
+        ..Default::default()
+    };
+    let buffer = request.path.as_ptr();
+
+    RequestBuilder::default()
+        .method("GET")
+        .path("/index.html")
+        .build_into(&mut request)
+        .unwrap();
+
+    assert_eq!(request.path, "/index.html");
+    assert_eq!(request.path.as_ptr(), buffer);
+}
+
+#[test]
+fn leaves_target_unchanged_on_error() {
+    let original = Request {
+        method: "GET",
+        path: "/old".to_string(),
+        headers: vec![],
+        timeout: 5,
+    };
+    let mut request = original.clone();
+
+    let error = RequestBuilder::default()
+        .method("POST")
+        .build_into(&mut request)
+        .unwrap_err();
+    assert_eq!(error.to_string(), "`path` must be initialized");
+    assert_eq!(request, original);
+
+    let error = RequestBuilder::default()
+        .method("POST")
+        .path("/new")
+        .timeout(0)
+        .build_into(&mut request)
+        .unwrap_err();
+    assert_eq!(error.to_string(), "timeout must not be 0");
+    assert_eq!(request, original);
+}
+
+#[test]
+fn owned_builder() {
+    let mut owned = Owned {
+        name: "old".to_string(),
+        tags: vec!["a".to_string()],
+    };
+
+    OwnedBuilder::default()
+        .name("new".to_string())
+        .build_into(&mut owned)
+        .unwrap();
+
+    assert_eq!(
+        owned,
+        Owned {
+            name: "new".to_string(),
+            tags: vec![],
+        }
+    );
+}
@@ -0,0 +1,72 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(panic_variant, validate = "Self::validate"))]
+struct Lorem {
+    ipsum: u32,
+    dolor: String,
+    #[builder(default)]
+    sit: bool,
+}
+
+impl LoremBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if self.ipsum == Some(0) {
+            return Err("ipsum must not be 0".into());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned", build_fn(name = "finish", panic_variant))]
+struct Amet<T> {
+    value: T,
+}
+
+#[test]
+fn builds() {
+    let lorem = LoremBuilder::default()
+        .ipsum(1)
+        .dolor("dolor".into())
+        .build_or_panic();
+
+    assert_eq!(
+        lorem,
+        Lorem {
+            ipsum: 1,
+            dolor: "dolor".into(),
+            sit: false,
+        }
+    );
+}
+
+#[test]
+#[should_panic(expected = "could not build `Lorem`: missing field `dolor`")]
+fn panics_with_missing_field() {
+    LoremBuilder::default().ipsum(1).build_or_panic();
+}
+
+#[test]
+#[should_panic(expected = "could not build `Lorem`: missing fields `ipsum`, `dolor`")]
+fn panics_with_missing_fields() {
+    LoremBuilder::default().sit(true).build_or_panic();
+}
+
+#[test]
+#[should_panic(expected = "could not build `Lorem`: ValidationError(\"ipsum must not be 0\")")]
+fn panics_with_error() {
+    LoremBuilder::default()
+        .ipsum(0)
+        .dolor("dolor".into())
+        .build_or_panic();
+}
+
+#[test]
+fn renamed_owned() {
+    let amet = AmetBuilder::default().value(1).finish_or_panic();
+    assert_eq!(amet, Amet { value: 1 });
+}
@@ -0,0 +1,72 @@
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(
+    subset(name = "build_minimal", requires(host)),
+    subset(name = "build_unchecked")
+))]
+struct Connection {
+    host: String,
+    port: u16,
+    #[builder(default = "30")]
+    timeout: u32,
+}
+
+#[test]
+fn subset_defaults_other_fields() {
+    let connection = ConnectionBuilder::default()
+        .host("localhost".to_string())
+        .build_minimal()
+        .unwrap();
+
+    assert_eq!(
+        connection,
+        Connection {
+            host: "localhost".to_string(),
+            port: 0,
+            timeout: 30,
+        }
+    );
+}
+
+#[test]
+fn subset_uses_set_fields() {
+    let connection = ConnectionBuilder::default()
+        .host("localhost".to_string())
+        .port(8080)
+        .timeout(5)
+        .build_minimal()
+        .unwrap();
+
+    assert_eq!(connection.port, 8080);
+    assert_eq!(connection.timeout, 5);
+}
+
+#[test]
+fn subset_requires_fields() {
+    let error = ConnectionBuilder::default()
+        .port(8080)
+        .build_minimal()
+        .unwrap_err();
+
+    assert_eq!(&error.to_string(), "`host` must be initialized");
+}
+
+#[test]
+fn subset_without_required_fields() {
+    let connection = ConnectionBuilder::default().build_unchecked().unwrap();
+
+    assert_eq!(connection.host, "");
+    assert_eq!(connection.timeout, 30);
+}
+
+#[test]
+fn build_still_requires_all_fields() {
+    let error = ConnectionBuilder::default()
+        .host("localhost".to_string())
+        .build()
+        .unwrap_err();
+
+    assert_eq!(&error.to_string(), "`port` must be initialized");
+}
@@ -0,0 +1,59 @@
+#[macro_use]
+extern crate derive_builder;
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(wrappers(box, arc, rc)))]
+struct Lorem {
+    ipsum: u32,
+    #[builder(default = "[0; 64]")]
+    dolor: [u8; 64],
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(
+    pattern = "owned",
+    derive(Debug),
+    build_fn(name = "finish", return_builder, wrappers(arc))
+)]
+struct Ipsum<T: Clone> {
+    dolor: T,
+}
+
+#[test]
+fn build_boxed() {
+    let lorem: Box<Lorem> = LoremBuilder::default().ipsum(42).build_boxed().unwrap();
+
+    assert_eq!(lorem.ipsum, 42);
+    assert_eq!(lorem.dolor, [0; 64]);
+}
+
+#[test]
+fn build_arc_and_rc() {
+    let mut builder = LoremBuilder::default();
+    builder.ipsum(42);
+
+    let arc: Arc<Lorem> = builder.build_arc().unwrap();
+    let rc: Rc<Lorem> = builder.build_rc().unwrap();
+
+    assert_eq!(*arc, *rc);
+}
+
+#[test]
+fn wrapped_build_fails() {
+    let error = LoremBuilder::default().build_arc().unwrap_err();
+
+    assert_eq!(&error.to_string(), "`ipsum` must be initialized");
+}
+
+#[test]
+fn generic_with_return_builder() {
+    let ipsum: Arc<Ipsum<&str>> = IpsumBuilder::default().dolor("sit").finish_arc().unwrap();
+    assert_eq!(ipsum.dolor, "sit");
+
+    let (builder, error) = IpsumBuilder::<u8>::default().finish_arc().unwrap_err();
+    assert_eq!(&error.to_string(), "`dolor` must be initialized");
+    assert_eq!(*builder.dolor(7).finish_arc().unwrap(), Ipsum { dolor: 7 });
+}
@@ -0,0 +1,62 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[builder_attr(setter(into))]
+#[derive(Debug, PartialEq)]
+struct Lorem {
+    ipsum: String,
+    #[builder(default = "42")]
+    dolor: u32,
+}
+
+#[builder_attr]
+#[builder(pattern = "owned")]
+#[derive(Debug, PartialEq)]
+struct Generic<T> {
+    #[builder_field_attr(allow(unused))]
+    value: T,
+}
+
+#[test]
+fn builder_fn_on_target() {
+    let x = Lorem::builder().ipsum("ipsum").build().unwrap();
+
+    assert_eq!(
+        x,
+        Lorem {
+            ipsum: "ipsum".into(),
+            dolor: 42,
+        }
+    );
+}
+
+#[test]
+fn builder_fn_equals_default() {
+    let x = LoremBuilder::default().ipsum("ipsum").dolor(1u8).build();
+    let y = Lorem::builder().ipsum("ipsum").dolor(1u8).build();
+
+    assert_eq!(x.unwrap(), y.unwrap());
+}
+
+#[test]
+fn struct_level_builder_attr_alongside_args() {
+    let x = Generic::builder().value(1u8).build().unwrap();
+
+    assert_eq!(x, Generic { value: 1u8 });
+}
+
+/// Already documented.
+#[builder_attr]
+#[derive(Debug, PartialEq)]
+pub struct Documented {
+    ipsum: u32,
+}
+
+#[test]
+fn documented_target() {
+    let x = Documented::builder().ipsum(1).build().unwrap();
+
+    assert_eq!(x, Documented { ipsum: 1 });
+}
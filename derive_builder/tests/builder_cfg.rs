@@ -0,0 +1,86 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+// `all()` is always true and `any()` is always false.
+#[derive(Debug, PartialEq, Builder)]
+#[builder(cfg(all()), derive(Display), constructor_macro)]
+struct Lorem {
+    ipsum: u32,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(cfg(any()), setter(into))]
+#[allow(dead_code)]
+struct Dolor {
+    sit: String,
+}
+
+/// This would conflict with the generated builder if the `cfg` were not applied.
+#[allow(dead_code)]
+struct DolorBuilder;
+
+/// This would conflict with the generated error type if the `cfg` were not applied.
+#[allow(dead_code)]
+struct DolorBuilderError;
+
+#[builder_attr(cfg(any()))]
+#[derive(Debug, PartialEq)]
+struct Amet {
+    consectetur: u32,
+}
+
+#[allow(dead_code)]
+struct AmetBuilder;
+
+// The imports of `uses` live in a `const _` item, which refers to the builder.
+#[derive(Debug, PartialEq, Builder)]
+#[builder(cfg(any()), uses = "std::time::Duration")]
+#[allow(dead_code)]
+struct Elit {
+    #[builder(default = "Duration::from_secs(1)")]
+    timeout: std::time::Duration,
+}
+
+// The snapshot module calls the setters of the builder.
+#[derive(Debug, PartialEq, Builder)]
+#[builder(cfg(any()), public_api_snapshot)]
+#[allow(dead_code)]
+pub struct Sed {
+    pub eiusmod: u32,
+}
+
+// The generated unit test builds the struct with the builder.
+#[derive(Debug, PartialEq, Builder)]
+#[builder(cfg(any()), assert_no_alloc_build)]
+#[allow(dead_code)]
+struct Tempor {
+    #[builder(default)]
+    incididunt: u32,
+}
+
+#[test]
+fn enabled_cfg() {
+    assert_eq!(
+        LoremBuilder::default().ipsum(1).build().unwrap(),
+        Lorem { ipsum: 1 }
+    );
+    assert_eq!(lorem! { ipsum: 2 }, Lorem { ipsum: 2 });
+    assert_eq!(
+        LoremBuilder::default().to_string(),
+        "LoremBuilder { ipsum: <unset> }"
+    );
+}
+
+#[test]
+fn disabled_cfg() {
+    let _ = DolorBuilder;
+    let _ = DolorBuilderError;
+    let _ = Sed { eiusmod: 4 };
+    let _ = Tempor { incididunt: 5 };
+    let _ = Elit {
+        timeout: std::time::Duration::from_secs(2),
+    };
+    assert_eq!(Amet { consectetur: 3 }.consectetur, 3);
+}
@@ -0,0 +1,69 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[allow(dead_code)]
+#[derive(Debug, Builder)]
+#[builder(derive(Debug, Display), setter(into))]
+struct Lorem {
+    ipsum: u32,
+    dolor: String,
+    #[builder(redact)]
+    password: String,
+    #[builder(setter(skip))]
+    skipped: bool,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Builder)]
+#[builder(derive(std::fmt::Display))]
+struct Generic<T: Clone> {
+    value: T,
+    #[builder(field(ty = "Vec<T>", build = "self.extra.clone()"))]
+    extra: Vec<T>,
+}
+
+#[derive(Debug, Builder)]
+#[builder(derive(Display))]
+struct Empty {}
+
+#[test]
+fn unset_fields() {
+    assert_eq!(
+        LoremBuilder::default().to_string(),
+        "LoremBuilder { ipsum: <unset>, dolor: <unset>, password: <unset> }"
+    );
+}
+
+#[test]
+fn set_fields() {
+    let builder = LoremBuilder::default()
+        .ipsum(42u32)
+        .dolor("sit")
+        .password("hunter2")
+        .clone();
+
+    assert_eq!(
+        builder.to_string(),
+        r#"LoremBuilder { ipsum: 42, dolor: "sit", password: <redacted> }"#
+    );
+    assert!(!format!("{:?}", builder).is_empty());
+}
+
+#[test]
+fn generic_and_custom_field_type() {
+    let mut builder = GenericBuilder::default();
+    builder.value(1u8);
+    builder.extra = vec![2, 3];
+
+    assert_eq!(
+        builder.to_string(),
+        "GenericBuilder { value: 1, extra: [2, 3] }"
+    );
+}
+
+#[test]
+fn no_fields() {
+    assert_eq!(EmptyBuilder::default().to_string(), "EmptyBuilder {}");
+}
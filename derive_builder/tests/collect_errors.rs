@@ -0,0 +1,78 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use derive_builder::UninitializedFieldsError;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(collect_errors))]
+struct Lorem {
+    ipsum: u32,
+    #[builder(default)]
+    dolor: u32,
+    #[builder(setter(into))]
+    sit: String,
+    amet: bool,
+}
+
+#[test]
+fn all_missing_fields_are_reported() {
+    let err = LoremBuilder::default().sit("sit").build().unwrap_err();
+
+    match err {
+        LoremBuilderError::UninitializedFields(ref error) => {
+            assert_eq!(error.field_names(), &["ipsum", "amet"]);
+        }
+        _ => panic!("unexpected error: {}", err),
+    }
+    assert_eq!(&err.to_string(), "Fields not initialized: ipsum, amet");
+}
+
+#[test]
+fn complete_builder_builds() {
+    let lorem = LoremBuilder::default()
+        .ipsum(1)
+        .sit("sit")
+        .amet(true)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        lorem,
+        Lorem {
+            ipsum: 1,
+            dolor: 0,
+            sit: "sit".into(),
+            amet: true,
+        }
+    );
+}
+
+#[derive(Debug, PartialEq)]
+enum AppError {
+    Missing(Vec<&'static str>),
+}
+
+impl From<UninitializedFieldsError> for AppError {
+    fn from(e: UninitializedFieldsError) -> Self {
+        AppError::Missing(e.field_names().to_vec())
+    }
+}
+
+#[derive(Debug, Builder)]
+#[builder(build_fn(collect_errors, error = "AppError"))]
+struct Ipsum {
+    #[builder(setter(into))]
+    dolor: String,
+    sit: u8,
+}
+
+#[test]
+fn custom_error_type() {
+    let err = IpsumBuilder::default().build().unwrap_err();
+
+    assert_eq!(err, AppError::Missing(vec!["dolor", "sit"]));
+    let ipsum = IpsumBuilder::default().dolor("a").sit(1).build().unwrap();
+    assert_eq!((ipsum.dolor.as_str(), ipsum.sit), ("a", 1));
+}
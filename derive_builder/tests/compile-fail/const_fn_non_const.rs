@@ -0,0 +1,20 @@
+#[macro_use]
+extern crate derive_builder;
+
+// `Box::new` and `Default::default()` can't be called in the `const fn` build method.
+#[derive(Builder)]
+#[builder(const_fn, pattern = "owned")]
+struct Lorem {
+    #[builder(setter(boxed))]
+    ipsum: Box<u32>,
+    #[builder(default)]
+    dolor: u32,
+}
+
+#[derive(Builder, Default)]
+#[builder(const_fn, pattern = "owned", default)]
+struct Sit {
+    amet: u32,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+#![deny(unused_must_use)]
+
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Builder)]
+#[builder(pattern = "owned")]
+pub struct Lorem {
+    ipsum: usize,
+}
+
+fn main() {
+    LoremBuilder::default().ipsum(42);
+}
@@ -0,0 +1,84 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use derive_builder::{ConfigKey, StableHasher};
+
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(overridable, derive(Debug, ConfigKey), setter(into))]
+struct Pool {
+    url: String,
+    #[builder(default = "4")]
+    size: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(derive(Debug, derive_builder::ConfigKey))]
+struct Pipeline<T: Clone> {
+    stages: Vec<T>,
+    #[builder(flatten)]
+    pool: Pool,
+}
+
+#[test]
+fn equal_if_same_fields_are_set() {
+    let mut a = PoolBuilder::default();
+    a.url("localhost");
+    let mut b = PoolBuilder::default();
+    b.url("localhost");
+
+    assert_eq!(a, b);
+
+    b.size(4u16);
+    // `size` is set to its default, but the builders are configured differently.
+    assert_ne!(a, b);
+    assert_ne!(a.config_key(), b.config_key());
+}
+
+#[test]
+fn key_is_stable() {
+    assert_eq!(
+        PoolBuilder::default().config_key(),
+        StableHasher::new().finish()
+    );
+
+    let mut expected = StableHasher::new();
+    "size".hash(&mut expected);
+    7u16.hash(&mut expected);
+
+    assert_eq!(
+        PoolBuilder::default().size(7u16).config_key(),
+        expected.finish()
+    );
+}
+
+#[test]
+fn memoize_by_builder() {
+    let mut pools: HashMap<PoolBuilder, Pool> = HashMap::new();
+    let mut builder = PoolBuilder::default();
+    builder.url("localhost");
+
+    let pool = pools
+        .entry(builder.clone())
+        .or_insert_with_key(|builder| builder.build().unwrap());
+    assert_eq!(pool.size, 4);
+
+    assert!(pools.contains_key(PoolBuilder::default().url("localhost")));
+    assert!(!pools.contains_key(PoolBuilder::default().url("remote")));
+}
+
+#[test]
+fn generic_and_flattened() {
+    let mut a = PipelineBuilder::default();
+    a.stages(vec![1, 2]).url("localhost");
+    let mut b = a.clone();
+
+    assert_eq!(a.config_key(), b.config_key());
+
+    b.size(8u16);
+    assert_ne!(a, b);
+}
@@ -0,0 +1,62 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(const_fn, pattern = "owned", create_empty = "new")]
+struct Config {
+    name: &'static str,
+    #[builder(setter(strip_option), default = "None")]
+    port: Option<u16>,
+    #[builder(default = "4")]
+    workers: usize,
+    #[builder(setter(skip), default = "false")]
+    verbose: bool,
+}
+
+static CONFIG: Config = ConfigBuilder::new().name("lorem").port(8080).build_const();
+
+const DEFAULTS: Config = ConfigBuilder::new().name("ipsum").build_const();
+
+#[test]
+fn static_config() {
+    assert_eq!(
+        CONFIG,
+        Config {
+            name: "lorem",
+            port: Some(8080),
+            workers: 4,
+            verbose: false,
+        }
+    );
+}
+
+#[test]
+fn const_defaults() {
+    assert_eq!(
+        DEFAULTS,
+        Config {
+            name: "ipsum",
+            port: None,
+            workers: 4,
+            verbose: false,
+        }
+    );
+}
+
+#[test]
+fn fallible_build_still_available() {
+    let config = ConfigBuilder::default().workers(1).build();
+
+    assert_eq!(
+        &config.unwrap_err().to_string(),
+        "`name` must be initialized"
+    );
+}
+
+#[test]
+#[should_panic(expected = "`name` must be initialized")]
+fn uninitialized_field_panics() {
+    ConfigBuilder::new().build_const();
+}
@@ -0,0 +1,92 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(constructor_macro, setter(into))]
+struct Lorem {
+    ipsum: u32,
+    dolor: String,
+    #[builder(default = "7")]
+    sit: u8,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(
+    constructor_macro = "make_amet",
+    pattern = "owned",
+    build_fn(name = "finish")
+)]
+struct Amet<T> {
+    #[builder(setter(prefix = "with"))]
+    consectetur: T,
+    #[builder(setter(strip_option), default)]
+    adipiscing: Option<bool>,
+    #[builder(setter(skip))]
+    elit: u8,
+}
+
+#[derive(Debug, Default, PartialEq, Builder)]
+#[builder(constructor_macro(name = "empty_struct"), default)]
+struct Empty {
+    field: u32,
+}
+
+#[test]
+fn required_fields() {
+    assert_eq!(
+        lorem! { ipsum: 1u8, dolor: "dolor" },
+        Lorem {
+            ipsum: 1,
+            dolor: "dolor".into(),
+            sit: 7,
+        }
+    );
+}
+
+#[test]
+fn optional_fields_in_any_order() {
+    assert_eq!(
+        lorem! { sit: 3, dolor: "dolor", ipsum: 2u8, },
+        Lorem {
+            ipsum: 2,
+            dolor: "dolor".into(),
+            sit: 3,
+        }
+    );
+}
+
+#[test]
+fn custom_names_and_owned_pattern() {
+    assert_eq!(
+        make_amet! { consectetur: "x", adipiscing: true },
+        Amet {
+            consectetur: "x",
+            adipiscing: Some(true),
+            elit: 0,
+        }
+    );
+    assert_eq!(
+        make_amet! { consectetur: 5 },
+        Amet {
+            consectetur: 5,
+            adipiscing: None,
+            elit: 0,
+        }
+    );
+}
+
+#[test]
+fn no_required_fields() {
+    assert_eq!(empty_struct! {}, Empty { field: 0 });
+}
+
+mod nested {
+    use super::EmptyBuilder;
+
+    #[test]
+    fn usable_in_child_module() {
+        assert_eq!(empty_struct! { field: 1 }, super::Empty { field: 1 });
+    }
+}
@@ -0,0 +1,120 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use std::convert::TryFrom;
+
+#[derive(Debug, PartialEq)]
+struct Port(u16);
+
+impl TryFrom<String> for Port {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+            .map(Port)
+            .map_err(|_| format!("invalid port `{}`", s))
+    }
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+struct Server {
+    #[builder(setter(into, convert = "at_build", from = "String"))]
+    port: Port,
+    #[builder(default = "8", setter(convert = "at_build", from = "u64"))]
+    workers: u8,
+    #[builder(setter(into, convert = "at_set"))]
+    name: String,
+}
+
+impl ServerBuilder {
+    fn validate(&self) -> Result<(), String> {
+        // The validator sees the value as it was passed to the setter.
+        match self.port {
+            Some(ref port) if port.starts_with('+') => Err("port must not have a sign".into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[test]
+fn converts_at_build() {
+    let server = ServerBuilder::default()
+        .port("8080")
+        .workers(4)
+        .name("web")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        server,
+        Server {
+            port: Port(8080),
+            workers: 4,
+            name: "web".into(),
+        }
+    );
+}
+
+#[test]
+fn default_is_not_converted() {
+    let server = ServerBuilder::default()
+        .port("80")
+        .name("web")
+        .build()
+        .unwrap();
+
+    assert_eq!(server.workers, 8);
+}
+
+#[test]
+fn conversion_error() {
+    let err = ServerBuilder::default()
+        .port("http")
+        .name("web")
+        .build()
+        .unwrap_err();
+    assert_eq!(&err.to_string(), "invalid port `http`");
+
+    let err = ServerBuilder::default()
+        .port("80")
+        .workers(1000)
+        .name("web")
+        .build()
+        .unwrap_err();
+    assert!(matches!(err, ServerBuilderError::ValidationError(_)));
+}
+
+#[test]
+fn validator_sees_raw_value() {
+    let err = ServerBuilder::default()
+        .port("+80")
+        .name("web")
+        .build()
+        .unwrap_err();
+
+    assert_eq!(&err.to_string(), "port must not have a sign");
+}
+
+#[test]
+fn uninitialized_field() {
+    let err = ServerBuilder::default().name("web").build().unwrap_err();
+
+    assert_eq!(&err.to_string(), "`port` must be initialized");
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned")]
+struct Job {
+    #[builder(setter(convert = "at_build", from = "String"))]
+    port: Port,
+}
+
+#[test]
+fn owned_pattern() {
+    let job = JobBuilder::default().port("1".into()).build().unwrap();
+
+    assert_eq!(job, Job { port: Port(1) });
+}
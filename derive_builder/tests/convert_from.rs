@@ -0,0 +1,92 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+struct OldConfig {
+    #[builder(setter(into))]
+    host: String,
+    port: u16,
+    legacy: bool,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(convert_from(ty = "OldConfigBuilder", skip(tls)))]
+struct Config {
+    #[builder(setter(into))]
+    host: String,
+    port: u32,
+    #[builder(default)]
+    tls: bool,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned", convert_from = "ConfigBuilder")]
+struct Renamed {
+    host: String,
+    #[builder(field(ty = "Option<u32>", build = "self.port.unwrap_or(80)"))]
+    port: u32,
+    tls: bool,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+struct Wrapper<T> {
+    value: T,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(convert_from = "WrapperBuilder<T>")]
+struct NewWrapper<T: Clone> {
+    value: T,
+}
+
+#[test]
+fn converts_identically_named_fields() {
+    let mut old = OldConfigBuilder::default();
+    old.host("localhost").port(8080).legacy(true);
+
+    let config = ConfigBuilder::from(old).build().unwrap();
+    assert_eq!(
+        config,
+        Config {
+            host: "localhost".into(),
+            port: 8080,
+            tls: false,
+        }
+    );
+}
+
+#[test]
+fn leaves_unset_fields_unset() {
+    let mut old = OldConfigBuilder::default();
+    old.port(8080);
+
+    let err = ConfigBuilder::from(old).build().unwrap_err();
+    assert_eq!(err.to_string(), "`host` must be initialized");
+}
+
+#[test]
+fn converts_custom_field_type() {
+    let mut config = ConfigBuilder::default();
+    config.host("localhost").tls(true);
+
+    let renamed = RenamedBuilder::from(config).build().unwrap();
+    assert_eq!(
+        renamed,
+        Renamed {
+            host: "localhost".into(),
+            port: 80,
+            tls: true,
+        }
+    );
+}
+
+#[test]
+fn generic() {
+    let mut old = WrapperBuilder::default();
+    old.value(1u8);
+
+    let wrapper: NewWrapper<u8> = NewWrapperBuilder::from(old).build().unwrap();
+    assert_eq!(wrapper, NewWrapper { value: 1 });
+}
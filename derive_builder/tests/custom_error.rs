@@ -0,0 +1,131 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use derive_builder::UninitializedFieldError;
+
+/// Crate-wide error, which the builder returns directly.
+#[derive(Debug, PartialEq)]
+enum AppError {
+    Missing(&'static str),
+    Invalid(String),
+    PortInUse(u16),
+}
+
+impl From<UninitializedFieldError> for AppError {
+    fn from(e: UninitializedFieldError) -> Self {
+        Self::Missing(e.field_name())
+    }
+}
+
+impl From<String> for AppError {
+    fn from(e: String) -> Self {
+        Self::Invalid(e)
+    }
+}
+
+fn validate_port(port: &u16) -> Result<(), AppError> {
+    if *port == 80 {
+        return Err(AppError::PortInUse(80));
+    }
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(validate = "Self::validate", error = "AppError"))]
+struct Server {
+    host: String,
+    #[builder(validate = "validate_port")]
+    port: u16,
+}
+
+impl ServerBuilder {
+    fn validate(&self) -> Result<(), String> {
+        match self.host {
+            Some(ref host) if host.is_empty() => Err("host must not be empty".into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[test]
+fn missing_field() {
+    let err: AppError = ServerBuilder::default().port(8080).build().unwrap_err();
+    assert_eq!(err, AppError::Missing("host"));
+}
+
+#[test]
+fn validation_errors_are_converted() {
+    let err = ServerBuilder::default()
+        .host(String::new())
+        .port(8080)
+        .build()
+        .unwrap_err();
+    assert_eq!(err, AppError::Invalid("host must not be empty".into()));
+
+    let err = ServerBuilder::default()
+        .host("localhost".into())
+        .port(80)
+        .build()
+        .unwrap_err();
+    assert_eq!(err, AppError::PortInUse(80));
+}
+
+#[test]
+fn success() {
+    let server = ServerBuilder::default()
+        .host("localhost".into())
+        .port(8080)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        server,
+        Server {
+            host: "localhost".into(),
+            port: 8080,
+        }
+    );
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(validate = "Self::validate", error = "AppError"))]
+struct Listener {
+    port: u16,
+}
+
+impl ListenerBuilder {
+    /// Returns the builder's error type, so nothing is converted into a string.
+    fn validate(&self) -> Result<(), AppError> {
+        match self.port {
+            Some(port) if port < 1024 => Err(AppError::PortInUse(port)),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[test]
+fn typed_validation_error() {
+    let err = ListenerBuilder::default().port(22).build().unwrap_err();
+    assert_eq!(err, AppError::PortInUse(22));
+
+    let listener = ListenerBuilder::default().port(8080).build().unwrap();
+    assert_eq!(listener, Listener { port: 8080 });
+}
+
+#[derive(Debug, Builder)]
+#[builder(build_fn(error = "Box<dyn std::error::Error>"))]
+struct Client {
+    #[allow(dead_code)]
+    url: String,
+}
+
+#[test]
+fn boxed_error_downcasts_to_uninitialized_field() {
+    let err = ClientBuilder::default().build().unwrap_err();
+
+    let uninitialized = err.downcast_ref::<UninitializedFieldError>().unwrap();
+    assert_eq!(uninitialized.field_name(), "url");
+    assert_eq!(&err.to_string(), "Field not initialized: url");
+}
@@ -0,0 +1,134 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use derive_builder::UninitializedFieldError;
+use std::num::ParseIntError;
+
+#[derive(Debug, Clone, PartialEq)]
+enum LoremError {
+    UninitializedField(&'static str),
+    Parse(ParseIntError),
+    Validation(String),
+}
+
+impl From<UninitializedFieldError> for LoremError {
+    fn from(e: UninitializedFieldError) -> Self {
+        Self::UninitializedField(e.field_name())
+    }
+}
+
+impl From<ParseIntError> for LoremError {
+    fn from(e: ParseIntError) -> Self {
+        Self::Parse(e)
+    }
+}
+
+impl From<String> for LoremError {
+    fn from(s: String) -> Self {
+        Self::Validation(s)
+    }
+}
+
+fn parse_error(s: &str) -> LoremError {
+    LoremError::Parse(s.parse::<u16>().unwrap_err())
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(validate = "Self::validate", error = "LoremError"))]
+struct Lorem {
+    #[builder(setter(defer_err))]
+    ipsum: u16,
+    #[builder(setter(into, defer_err), default)]
+    dolor: String,
+    #[builder(setter(defer_err, strip_option), default)]
+    sit: Option<u8>,
+}
+
+impl LoremBuilder {
+    fn validate(&self) -> Result<(), String> {
+        match self.ipsum {
+            Some(0) => Err("ipsum must not be 0".into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[test]
+fn stores_values() {
+    let lorem = LoremBuilder::default()
+        .ipsum("8080".parse())
+        .dolor(Ok::<_, ParseIntError>("amet"))
+        .sit("7".parse())
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        lorem,
+        Lorem {
+            ipsum: 8080,
+            dolor: "amet".into(),
+            sit: Some(7),
+        }
+    );
+}
+
+#[test]
+fn build_returns_first_error() {
+    let err = LoremBuilder::default()
+        .ipsum("80x".parse())
+        .sit("256".parse())
+        .build()
+        .unwrap_err();
+
+    assert_eq!(err, parse_error("80x"));
+}
+
+#[test]
+fn deferred_error_comes_before_validation() {
+    let err = LoremBuilder::default()
+        .ipsum(Ok::<_, ParseIntError>(0))
+        .sit("x".parse())
+        .build()
+        .unwrap_err();
+
+    assert_eq!(err, parse_error("x"));
+}
+
+#[test]
+fn later_value_keeps_error() {
+    let err = LoremBuilder::default()
+        .ipsum("x".parse())
+        .ipsum("1".parse())
+        .build()
+        .unwrap_err();
+
+    assert_eq!(err, parse_error("x"));
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned", reset)]
+struct Consectetur {
+    #[builder(setter(defer_err))]
+    adipiscing: u32,
+}
+
+#[test]
+fn owned_pattern_and_reset() {
+    let builder = ConsecteturBuilder::default().adipiscing(Err("-1 is negative".to_string()));
+    let builder = builder.reset();
+    let consectetur = builder.adipiscing(Ok::<_, String>(42)).build().unwrap();
+
+    assert_eq!(consectetur, Consectetur { adipiscing: 42 });
+}
+
+#[test]
+fn unconverted_error() {
+    let err = ConsecteturBuilder::default()
+        .adipiscing("x".parse::<u32>().map_err(|e| e.to_string()))
+        .build()
+        .unwrap_err();
+
+    assert!(matches!(err, ConsecteturBuilderError::ValidationError(_)));
+}
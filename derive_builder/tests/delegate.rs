@@ -0,0 +1,90 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use derive_builder::BuilderError;
+
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(derive(Debug))]
+struct Pool {
+    size: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(derive(Debug))]
+struct Database {
+    url: &'static str,
+    #[builder(default = "5")]
+    pool_size: u32,
+    #[builder(delegate)]
+    pool: Pool,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(derive(Debug))]
+struct Config {
+    name: &'static str,
+    #[builder(delegate)]
+    database: Database,
+}
+
+#[test]
+fn accessor_of_nested_builder() {
+    let mut builder = ConfigBuilder::default();
+    builder.name("lorem");
+    builder.database().url("postgres://localhost").pool_size(10);
+    builder.database().pool().size(2);
+
+    assert_eq!(
+        builder.build().unwrap(),
+        Config {
+            name: "lorem",
+            database: Database {
+                url: "postgres://localhost",
+                pool_size: 10,
+                pool: Pool { size: 2 },
+            },
+        }
+    );
+}
+
+#[test]
+fn error_names_nested_path() {
+    let mut builder = ConfigBuilder::default();
+    builder.name("lorem");
+    builder.database().pool().size(2);
+
+    let error = builder.build().unwrap_err();
+    assert_eq!(error.uninitialized_field(), Some("database.url"));
+    assert_eq!(error.to_string(), "`database.url` must be initialized");
+    match error {
+        ConfigBuilderError::NestedField(ref error) => {
+            assert_eq!(error.field_name(), "database");
+            assert_eq!(error.message(), "`url` must be initialized");
+        }
+        _ => panic!("expected a nested error: {:?}", error),
+    }
+}
+
+#[test]
+fn error_names_deeply_nested_path() {
+    let mut builder = ConfigBuilder::default();
+    builder.name("lorem");
+    builder.database().url("postgres://localhost");
+
+    let error = builder.build().unwrap_err();
+    assert_eq!(error.uninitialized_field(), Some("database.pool.size"));
+    assert_eq!(
+        error.to_string(),
+        "`database.pool.size` must be initialized"
+    );
+}
+
+#[test]
+fn outer_fields_are_checked_first() {
+    let error = ConfigBuilder::default().build().unwrap_err();
+
+    assert_eq!(error.uninitialized_field(), Some("name"));
+    assert_eq!(error.to_string(), "`name` must be initialized");
+}
@@ -0,0 +1,63 @@
+#![deny(deprecated)]
+//! Code generated for a deprecated builder must not warn about its own use of the builder.
+
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(
+    deprecated(note = "use IpsumBuilder"),
+    derive(Debug, Display),
+    from_str,
+    dyn_set,
+    build_fn(fallback)
+)]
+struct Lorem {
+    ipsum: u32,
+    #[builder(default, setter(strip_option))]
+    dolor: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(deprecated, pattern = "owned", const_fn)]
+struct Sit {
+    amet: u32,
+}
+
+#[test]
+#[allow(deprecated)]
+fn deprecated_builder_builds() {
+    let lorem = LoremBuilder::default()
+        .ipsum(1)
+        .dolor("dolor".into())
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        lorem,
+        Lorem {
+            ipsum: 1,
+            dolor: Some("dolor".into()),
+        }
+    );
+    assert_eq!(
+        "ipsum=2".parse::<LoremBuilder>().unwrap().build().unwrap(),
+        Lorem {
+            ipsum: 2,
+            dolor: None,
+        }
+    );
+    assert_eq!(SitBuilder::default().amet(3).build().unwrap().amet, 3);
+}
+
+#[test]
+fn struct_is_not_deprecated() {
+    let lorem = Lorem {
+        ipsum: 1,
+        dolor: None,
+    };
+
+    assert_eq!(lorem.ipsum, 1);
+}
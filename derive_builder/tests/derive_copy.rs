@@ -0,0 +1,51 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use std::marker::PhantomData;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(derive(Copy, Debug, PartialEq))]
+struct Lorem {
+    ipsum: u32,
+    #[builder(default)]
+    dolor: f64,
+    #[builder(setter(skip))]
+    sit: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(derive(Copy), pattern = "owned")]
+struct Amet<'a, T: Copy> {
+    consectetur: &'a str,
+    adipiscing: T,
+    #[builder(setter(skip))]
+    elit: PhantomData<String>,
+}
+
+fn build_twice(builder: LoremBuilder) -> (Lorem, Lorem) {
+    (builder.build().unwrap(), builder.build().unwrap())
+}
+
+#[test]
+fn copies_builder() {
+    let mut builder = LoremBuilder::default();
+    builder.ipsum(1);
+    let copy = builder;
+    builder.ipsum(2);
+
+    assert_eq!(copy.build().unwrap().ipsum, 1);
+    assert_eq!(builder.build().unwrap().ipsum, 2);
+    assert_eq!(build_twice(copy).0, build_twice(copy).1);
+}
+
+#[test]
+fn generic_owned_builder() {
+    let base = AmetBuilder::default().consectetur("lorem");
+    let first = base.adipiscing(1u8).build().unwrap();
+    let second = base.adipiscing(2u8).build().unwrap();
+
+    assert_eq!(first.consectetur, second.consectetur);
+    assert_eq!((first.adipiscing, second.adipiscing), (1, 2));
+}
@@ -0,0 +1,73 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+extern crate serde;
+extern crate serde_json;
+
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(derive(Debug, Deserialize), deserialize(deny_unknown_fields))]
+struct Strict {
+    host: String,
+    #[builder(default = "5432")]
+    port: u16,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(
+    derive(Debug, Deserialize),
+    deserialize(extra = "serde_json::Value"),
+    setter(into)
+)]
+struct Lenient {
+    host: String,
+    #[builder(default = "5432")]
+    port: u16,
+}
+
+#[test]
+fn deny_unknown_fields() {
+    let builder: StrictBuilder = serde_json::from_str(r#"{"host":"localhost"}"#).unwrap();
+    assert_eq!(
+        builder.build().unwrap(),
+        Strict {
+            host: "localhost".into(),
+            port: 5432,
+        }
+    );
+
+    let err = serde_json::from_str::<StrictBuilder>(r#"{"host":"localhost","prot":1}"#)
+        .unwrap_err()
+        .to_string();
+    assert!(err.starts_with("unknown field `prot`"), "{}", err);
+}
+
+#[test]
+fn collect_unknown_fields() {
+    let builder: LenientBuilder =
+        serde_json::from_str(r#"{"host":"localhost","prot":1,"tls":{"verify":true}}"#).unwrap();
+
+    let mut unknown: Vec<&str> = builder.extra().keys().map(String::as_str).collect();
+    unknown.sort_unstable();
+    assert_eq!(unknown, vec!["prot", "tls"]);
+    assert_eq!(builder.extra()["prot"], serde_json::json!(1));
+
+    assert_eq!(
+        builder.build().unwrap(),
+        Lenient {
+            host: "localhost".into(),
+            port: 5432,
+        }
+    );
+}
+
+#[test]
+fn extra_is_empty_by_default() {
+    let mut builder = LenientBuilder::default();
+    builder.host("localhost");
+
+    assert!(builder.extra().is_empty());
+    assert!(builder.build().is_ok());
+}
@@ -0,0 +1,90 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use std::any::TypeId;
+
+use derive_builder::{DynSet, DynSetErrorKind};
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(dyn_set)]
+struct Plugin {
+    #[builder(setter(into))]
+    name: String,
+    #[builder(default)]
+    priority: i32,
+    #[builder(setter(strip_option), default)]
+    limit: Option<u32>,
+    #[builder(setter(skip))]
+    loaded: bool,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(dyn_set, pattern = "owned", field_name_case = "kebab-case")]
+struct Owned<T> {
+    max_value: T,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(dyn_set, pattern = "immutable")]
+struct Immutable {
+    value: u8,
+}
+
+fn configure(builder: &mut dyn DynSet) {
+    builder
+        .set("name", Box::new(String::from("lorem")))
+        .unwrap();
+    builder.set("limit", Box::new(3u32)).unwrap();
+}
+
+#[test]
+fn sets_fields_by_name() {
+    let mut builder = PluginBuilder::default();
+    configure(&mut builder);
+
+    assert_eq!(
+        builder.build().unwrap(),
+        Plugin {
+            name: "lorem".into(),
+            priority: 0,
+            limit: Some(3),
+            loaded: false,
+        }
+    );
+}
+
+#[test]
+fn field_types() {
+    let builder = PluginBuilder::default();
+
+    assert_eq!(builder.field_type("name"), Some(TypeId::of::<String>()));
+    assert_eq!(builder.field_type("limit"), Some(TypeId::of::<u32>()));
+    assert_eq!(builder.field_type("loaded"), None);
+}
+
+#[test]
+fn errors() {
+    let mut builder = PluginBuilder::default();
+
+    let err = builder.set("priority", Box::new(1u8)).unwrap_err();
+    assert_eq!(err.field_name(), "priority");
+    assert_eq!(err.kind(), DynSetErrorKind::TypeMismatch("i32"));
+    assert_eq!(err.to_string(), "`priority` takes a value of type `i32`");
+
+    let err = builder.set("loaded", Box::new(true)).unwrap_err();
+    assert_eq!(err.kind(), DynSetErrorKind::UnknownField);
+    assert_eq!(err.to_string(), "unknown field `loaded`");
+}
+
+#[test]
+fn owned_and_immutable_builders() {
+    let mut owned = OwnedBuilder::default();
+    owned.set("max-value", Box::new(7u64)).unwrap();
+    assert_eq!(owned.build().unwrap(), Owned { max_value: 7u64 });
+
+    let mut immutable = ImmutableBuilder::default();
+    immutable.set("value", Box::new(2u8)).unwrap();
+    assert_eq!(immutable.build().unwrap(), Immutable { value: 2 });
+}
@@ -0,0 +1,100 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+mod net {
+    #[derive(Debug, Clone, PartialEq, Builder)]
+    pub struct Server {
+        #[builder(setter(into))]
+        pub host: String,
+        #[builder(default = "80")]
+        pub port: u16,
+    }
+}
+
+use net::{Server, ServerBuilder};
+
+#[derive(Debug, PartialEq, Builder)]
+struct Lorem {
+    #[builder(setter(each_builder = "server"))]
+    servers: Vec<net::Server>,
+    #[builder(setter(strip_option, each_builder = "backup"), default)]
+    backups: Option<Vec<Server>>,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned")]
+struct Ipsum {
+    #[builder(
+        setter(each_builder(name = "primary", builder = "net::ServerBuilder")),
+        default
+    )]
+    dolor: Vec<Server>,
+}
+
+#[test]
+fn builds_items() {
+    let lorem = LoremBuilder::default()
+        .server(|s| {
+            s.host("a.example.com");
+        })
+        .server(|s| {
+            s.host("b.example.com").port(8080);
+        })
+        .backup(|s| {
+            s.host("c.example.com");
+        })
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        lorem,
+        Lorem {
+            servers: vec![
+                Server {
+                    host: "a.example.com".into(),
+                    port: 80,
+                },
+                Server {
+                    host: "b.example.com".into(),
+                    port: 8080,
+                },
+            ],
+            backups: Some(vec![Server {
+                host: "c.example.com".into(),
+                port: 80,
+            }]),
+        }
+    );
+}
+
+#[test]
+fn item_errors_are_returned_by_build() {
+    let err = LoremBuilder::default()
+        .server(|s| {
+            s.port(8080);
+        })
+        .build()
+        .unwrap_err();
+
+    assert_eq!(&err.to_string(), "`host` must be initialized");
+}
+
+#[test]
+fn custom_builder_path() {
+    let ipsum = IpsumBuilder::default()
+        .primary(|s| {
+            s.host("a.example.com");
+        })
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        ipsum.dolor,
+        vec![Server {
+            host: "a.example.com".into(),
+            port: 80,
+        }]
+    );
+}
@@ -0,0 +1,70 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(setter(into), build_fn(fallback))]
+struct Server {
+    #[builder(default = "format!(\"{}:{}\", host, port)", after(host, port))]
+    url: String,
+    #[builder(default = "\"localhost\".into()")]
+    host: String,
+    #[builder(default = "if tls { 443 } else { 80 }", after = "tls")]
+    port: u16,
+    #[builder(default)]
+    tls: bool,
+    #[builder(
+        field(ty = "Option<usize>", build = "self.workers.unwrap_or(url.len())"),
+        after = "url"
+    )]
+    workers: usize,
+}
+
+#[test]
+fn defaults_read_resolved_values() {
+    let server = ServerBuilder::default().tls(true).build().unwrap();
+
+    assert_eq!(
+        server,
+        Server {
+            url: "localhost:443".into(),
+            host: "localhost".into(),
+            port: 443,
+            tls: true,
+            workers: 13,
+        }
+    );
+}
+
+#[test]
+fn set_values_are_read() {
+    let server = ServerBuilder::default()
+        .host("example.com")
+        .port(8080u16)
+        .workers(Some(4))
+        .build()
+        .unwrap();
+
+    assert_eq!(server.url, "example.com:8080");
+    assert_eq!(server.workers, 4);
+}
+
+#[test]
+fn fallback_values_are_read() {
+    let fallback = Server {
+        url: "unused".into(),
+        host: "fallback".into(),
+        port: 1,
+        tls: false,
+        workers: 1,
+    };
+    let server = ServerBuilder::default()
+        .port(2u16)
+        .build_with_fallback(&fallback)
+        .unwrap();
+
+    assert_eq!(server.host, "fallback");
+    assert_eq!(server.url, "unused");
+    assert_eq!(server.port, 2);
+}
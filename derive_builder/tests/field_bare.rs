@@ -0,0 +1,129 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use std::mem::size_of;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(reset)]
+struct Server {
+    host: String,
+    #[builder(default = "8080", field(bare))]
+    port: u16,
+    #[builder(default, field(bare))]
+    verbose: bool,
+    #[builder(default = "Some(30)", field(bare), setter(strip_option))]
+    timeout: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned")]
+struct Limits {
+    #[builder(default = "u64::MAX", field(bare))]
+    max_bytes: u64,
+    #[builder(default = "16", field(bare))]
+    max_depth: u8,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+struct Release {
+    #[builder(default = "\"v1\".into()", field(bare))]
+    channel: String,
+}
+
+#[derive(Builder)]
+struct Optional {
+    #[builder(default = "8080")]
+    #[allow(dead_code)]
+    port: u16,
+}
+
+#[test]
+fn defaults() {
+    let server = ServerBuilder::default()
+        .host("localhost".into())
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        server,
+        Server {
+            host: "localhost".into(),
+            port: 8080,
+            verbose: false,
+            timeout: Some(30),
+        }
+    );
+}
+
+#[test]
+fn set_values() {
+    let server = ServerBuilder::default()
+        .host("localhost".into())
+        .port(443)
+        .verbose(true)
+        .timeout(5)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        server,
+        Server {
+            host: "localhost".into(),
+            port: 443,
+            verbose: true,
+            timeout: Some(5),
+        }
+    );
+}
+
+#[test]
+fn builder_field_is_bare() {
+    let mut builder = ServerBuilder::default();
+    assert_eq!(builder.port, 8080);
+
+    builder.port(443);
+    assert_eq!(builder.port, 443);
+}
+
+#[test]
+fn reset_to_default() {
+    let mut builder = ServerBuilder::default();
+    builder.host("localhost".into()).port(443).verbose(true);
+    builder.reset();
+    builder.host("localhost".into());
+
+    let server = builder.build().unwrap();
+    assert_eq!(server.port, 8080);
+    assert!(!server.verbose);
+}
+
+#[test]
+fn owned_pattern() {
+    let limits = LimitsBuilder::default().max_depth(4).build().unwrap();
+
+    assert_eq!(
+        limits,
+        Limits {
+            max_bytes: u64::MAX,
+            max_depth: 4,
+        }
+    );
+}
+
+#[test]
+fn no_option_overhead() {
+    assert_eq!(size_of::<LimitsBuilder>(), size_of::<Limits>());
+    assert!(size_of::<OptionalBuilder>() > size_of::<Optional>());
+}
+
+#[test]
+fn mutable_pattern_clones() {
+    let mut builder = ReleaseBuilder::default();
+    assert_eq!(builder.build().unwrap().channel, "v1");
+
+    builder.channel("beta".into());
+    assert_eq!(builder.build().unwrap().channel, "beta");
+    assert_eq!(builder.channel, "beta");
+}
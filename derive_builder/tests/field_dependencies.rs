@@ -0,0 +1,119 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use derive_builder::{FieldDependencyError, FieldDependencyKind, UninitializedFieldError};
+
+#[derive(Debug, PartialEq, Default, Builder, Clone)]
+struct Lorem {
+    #[builder(default, requires = "password")]
+    username: Option<String>,
+    #[builder(default)]
+    password: Option<String>,
+    #[builder(default, conflicts_with(username, password))]
+    token: Option<String>,
+}
+
+#[derive(Debug)]
+enum MyError {
+    Dependency(FieldDependencyError),
+    Uninitialized(String),
+}
+
+impl From<FieldDependencyError> for MyError {
+    fn from(e: FieldDependencyError) -> Self {
+        MyError::Dependency(e)
+    }
+}
+
+impl From<UninitializedFieldError> for MyError {
+    fn from(e: UninitializedFieldError) -> Self {
+        MyError::Uninitialized(e.field_name().to_string())
+    }
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned", build_fn(error = "MyError"))]
+struct Ipsum {
+    #[builder(conflicts_with = "sit")]
+    dolor: u8,
+    #[builder(default)]
+    sit: u8,
+}
+
+#[test]
+fn requires_error_names_both_fields() {
+    let error = LoremBuilder::default()
+        .username(Some("lorem".into()))
+        .build()
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "`username` requires `password` to be set"
+    );
+}
+
+#[test]
+fn conflicts_with_error_names_both_fields() {
+    let error = LoremBuilder::default()
+        .password(Some("ipsum".into()))
+        .token(Some("dolor".into()))
+        .build()
+        .unwrap_err();
+
+    assert_eq!(error.to_string(), "`token` conflicts with `password`");
+}
+
+#[test]
+fn satisfied_dependencies() {
+    let lorem = LoremBuilder::default()
+        .username(Some("lorem".into()))
+        .password(Some("ipsum".into()))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        lorem,
+        Lorem {
+            username: Some("lorem".into()),
+            password: Some("ipsum".into()),
+            token: None,
+        }
+    );
+
+    let lorem = LoremBuilder::default()
+        .token(Some("dolor".into()))
+        .build()
+        .unwrap();
+
+    assert_eq!(lorem.token, Some("dolor".into()));
+}
+
+#[test]
+fn unset_fields_have_no_dependencies() {
+    assert_eq!(LoremBuilder::default().build().unwrap(), Lorem::default());
+}
+
+#[test]
+fn custom_error() {
+    match IpsumBuilder::default().dolor(1).sit(2).build() {
+        Err(MyError::Dependency(e)) => {
+            assert_eq!(e.field_name(), "dolor");
+            assert_eq!(e.other_field_name(), "sit");
+            assert_eq!(e.kind(), FieldDependencyKind::ConflictsWith);
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    match IpsumBuilder::default().build() {
+        Err(MyError::Uninitialized(field)) => assert_eq!(field, "dolor"),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    assert_eq!(
+        IpsumBuilder::default().dolor(1).build().unwrap(),
+        Ipsum { dolor: 1, sit: 0 }
+    );
+}
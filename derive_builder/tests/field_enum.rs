@@ -0,0 +1,73 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, Builder)]
+#[allow(dead_code)]
+#[builder(field_enum)]
+struct Lorem {
+    ipsum: u32,
+    #[builder(default)]
+    dolor: u32,
+    #[builder(setter(into), requires = "ipsum")]
+    sit_amet: String,
+}
+
+#[test]
+fn uninitialized_field() {
+    let err = LoremBuilder::default().ipsum(1).build().unwrap_err();
+    assert_eq!(err.field(), Some(LoremField::SitAmet));
+}
+
+#[test]
+fn field_dependency() {
+    let err = LoremBuilder::default().sit_amet("a").build().unwrap_err();
+    assert_eq!(err.field(), Some(LoremField::SitAmet));
+}
+
+#[test]
+fn names() {
+    assert_eq!(LoremField::Ipsum.name(), "ipsum");
+    assert_eq!(LoremField::SitAmet.to_string(), "sit_amet");
+    assert_eq!(LoremField::from_name("dolor"), Some(LoremField::Dolor));
+    assert_eq!(LoremField::from_name("consectetur"), None);
+}
+
+#[derive(Debug, Builder)]
+#[allow(dead_code)]
+#[builder(
+    field_enum = "Input",
+    field_name_case = "camelCase",
+    build_fn(collect_errors, validate = "Self::validate")
+)]
+struct Form {
+    user_name: String,
+    age: u8,
+}
+
+impl FormBuilder {
+    fn validate(&self) -> Result<(), String> {
+        match self.age {
+            Some(age) if age < 18 => Err("too young".into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[test]
+fn collected_errors() {
+    let err = FormBuilder::default().build().unwrap_err();
+    assert_eq!(err.field(), Some(Input::UserName));
+    assert_eq!(Input::UserName.name(), "userName");
+}
+
+#[test]
+fn validation_error() {
+    let err = FormBuilder::default()
+        .user_name("a".into())
+        .age(3)
+        .build()
+        .unwrap_err();
+    assert_eq!(err.field(), None);
+}
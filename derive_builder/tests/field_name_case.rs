@@ -0,0 +1,89 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(
+    from_str,
+    field_name_case = "kebab-case",
+    derive(Debug, Display),
+    setter(rename_all = "camelCase")
+)]
+struct Pool {
+    max_connections: u32,
+    #[builder(default, requires = "max_connections")]
+    idle_timeout_secs: u64,
+    #[builder(default, parse(rename = "user"))]
+    user_name: String,
+    #[builder(default, setter(array), parse(skip))]
+    weights: [u8; 2],
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(field_name_case = "SCREAMING-KEBAB-CASE")]
+struct Screaming {
+    r#type: u8,
+    max_size: u8,
+}
+
+#[test]
+fn uninitialized_error() {
+    let err = PoolBuilder::default().build().unwrap_err();
+    assert_eq!(err.to_string(), "`max-connections` must be initialized");
+
+    let err = ScreamingBuilder::default().r#type(1).build().unwrap_err();
+    assert_eq!(err.to_string(), "`MAX-SIZE` must be initialized");
+}
+
+#[test]
+fn dependency_error() {
+    let err = PoolBuilder::default()
+        .idleTimeoutSecs(30)
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`idle-timeout-secs` requires `max-connections` to be set"
+    );
+}
+
+#[test]
+fn array_length_error() {
+    let err = PoolBuilder::default()
+        .tryWeightsFromIter(vec![1])
+        .unwrap_err();
+    assert_eq!(err.to_string(), "`weights` takes 2 items, but 1 were given");
+}
+
+#[test]
+fn display() {
+    let mut builder = PoolBuilder::default();
+    builder.maxConnections(8);
+    assert_eq!(
+        builder.to_string(),
+        "PoolBuilder { max-connections: 8, idle-timeout-secs: <unset>, user-name: <unset>, weights: <unset> }"
+    );
+}
+
+#[test]
+fn parsed_keys() {
+    let builder: PoolBuilder = "max-connections=8, idle-timeout-secs=30, user=lorem"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        builder.build().unwrap(),
+        Pool {
+            max_connections: 8,
+            idle_timeout_secs: 30,
+            user_name: "lorem".into(),
+            weights: [0, 0],
+        }
+    );
+
+    let err = "max_connections=8".parse::<PoolBuilder>().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`max_connections`: unknown key, expected one of `max-connections`, `idle-timeout-secs`, `user`"
+    );
+}
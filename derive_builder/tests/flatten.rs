@@ -0,0 +1,78 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+mod network {
+    #[derive(Debug, Clone, PartialEq, Builder)]
+    #[builder(overridable, setter(into), derive(Debug))]
+    pub struct Network {
+        pub host: String,
+        #[builder(default = "8080")]
+        pub port: u16,
+    }
+}
+
+use network::NetworkBuilderSetters;
+
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(overridable, derive(Debug))]
+struct Storage<T: Clone> {
+    capacity: T,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(derive(Debug))]
+struct Config<T: Clone> {
+    name: &'static str,
+    #[builder(flatten)]
+    network: network::Network,
+    #[builder(flatten)]
+    storage: Storage<T>,
+}
+
+#[test]
+fn setters_of_nested_builders() {
+    let config = ConfigBuilder::default()
+        .name("lorem")
+        .host("localhost")
+        .capacity(42u32)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        config,
+        Config {
+            name: "lorem",
+            network: network::Network {
+                host: "localhost".into(),
+                port: 8080,
+            },
+            storage: Storage { capacity: 42 },
+        }
+    );
+}
+
+#[test]
+fn nested_builder_field() {
+    let mut builder = ConfigBuilder::default();
+    builder
+        .name("lorem")
+        .capacity(1u8)
+        .port(80u16)
+        .host("ipsum");
+
+    assert_eq!(builder.network.build().unwrap().port, 80);
+    assert_eq!(builder.build().unwrap().network.port, 80);
+}
+
+#[test]
+fn nested_errors() {
+    let err = ConfigBuilder::default()
+        .name("lorem")
+        .capacity(1u8)
+        .build()
+        .unwrap_err();
+
+    assert_eq!(err.to_string(), "`host` must be initialized");
+}
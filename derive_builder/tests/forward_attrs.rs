@@ -0,0 +1,44 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+#[macro_use]
+extern crate serde;
+extern crate serde_json;
+
+#[derive(Debug, PartialEq, Builder, Serialize, Deserialize)]
+#[builder(derive(Deserialize))]
+struct Lorem {
+    #[serde(rename = "Ipsum")]
+    #[builder(forward_attrs(serde))]
+    ipsum: String,
+    #[serde(rename = "Dolor")]
+    #[builder(default)]
+    dolor: u32,
+}
+
+#[test]
+fn forwarded_attr_applies_to_builder() {
+    let builder: LoremBuilder = serde_json::from_str(r#"{"Ipsum": "sit", "dolor": 7}"#).unwrap();
+
+    assert_eq!(
+        builder.build().unwrap(),
+        Lorem {
+            ipsum: "sit".into(),
+            dolor: 7,
+        }
+    );
+}
+
+#[test]
+fn unforwarded_attr_is_ignored_by_builder() {
+    let builder: LoremBuilder = serde_json::from_str(r#"{"Ipsum": "sit", "Dolor": 7}"#).unwrap();
+
+    assert_eq!(
+        builder.build().unwrap(),
+        Lorem {
+            ipsum: "sit".into(),
+            dolor: 0,
+        }
+    );
+}
@@ -0,0 +1,64 @@
+//! Builders which derive an input type of another framework, so the framework deserializes
+//! the builder and `build()` validates it. serde stands in for frameworks like async-graphql,
+//! whose `InputObject` derive is configured the same way.
+
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+extern crate serde;
+extern crate serde_json;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(
+    derive(Debug, serde::Deserialize),
+    build_fn(validate = "Self::validate")
+)]
+#[builder_struct_attr(serde(rename_all = "camelCase", deny_unknown_fields))]
+struct CreateUser {
+    user_name: String,
+    #[builder(default)]
+    display_name: Option<String>,
+    #[builder(setter(skip))]
+    #[builder_field_attr(serde(skip))]
+    created: bool,
+}
+
+impl CreateUserBuilder {
+    fn validate(&self) -> Result<(), String> {
+        match self.user_name {
+            Some(ref name) if name.is_empty() => Err("user name must not be empty".into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn resolve(input: &str) -> Result<CreateUser, String> {
+    let builder: CreateUserBuilder = serde_json::from_str(input).map_err(|e| e.to_string())?;
+    builder.build().map_err(|e| e.to_string())
+}
+
+#[test]
+fn builder_as_input_type() {
+    assert_eq!(
+        resolve(r#"{"userName":"lorem","displayName":"Lorem"}"#).unwrap(),
+        CreateUser {
+            user_name: "lorem".into(),
+            display_name: Some("Lorem".into()),
+            created: false,
+        }
+    );
+}
+
+#[test]
+fn build_validates_input() {
+    assert_eq!(
+        resolve(r#"{}"#).unwrap_err(),
+        "`user_name` must be initialized"
+    );
+    assert_eq!(
+        resolve(r#"{"userName":""}"#).unwrap_err(),
+        "user name must not be empty"
+    );
+    assert!(resolve(r#"{"userName":"lorem","created":true}"#).is_err());
+}
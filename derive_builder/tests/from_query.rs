@@ -0,0 +1,109 @@
+#![cfg(feature = "urlencoded")]
+
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use std::net::IpAddr;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(from_query, build_fn(validate = "Self::validate"), derive(Debug))]
+struct Search {
+    #[builder(setter(into), alias = "query")]
+    q: String,
+    #[builder(default = "1")]
+    page: u32,
+    #[builder(parse(rename = "page-size"), default = "20")]
+    page_size: u8,
+    #[builder(setter(strip_option), default)]
+    client: Option<IpAddr>,
+    #[builder(parse(skip), default)]
+    tags: Vec<String>,
+    #[builder(setter(skip))]
+    cached: bool,
+}
+
+impl SearchBuilder {
+    fn validate(&self) -> Result<(), String> {
+        match self.page {
+            Some(0) => Err("pages start at 1".into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(from_query, pattern = "owned")]
+struct Filter<T: Clone> {
+    r#type: T,
+}
+
+#[test]
+fn sets_fields_from_query() {
+    let search = SearchBuilder::from_query("?q=lorem+ipsum%21&page-size=50&client=127.0.0.1&utm=x")
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        search,
+        Search {
+            q: "lorem ipsum!".into(),
+            page: 1,
+            page_size: 50,
+            client: Some([127, 0, 0, 1].into()),
+            tags: vec![],
+            cached: false,
+        }
+    );
+}
+
+#[test]
+fn builder_can_be_extended() {
+    let mut builder = SearchBuilder::from_query("page=3").unwrap();
+    let search = builder.q("dolor").build().unwrap();
+
+    assert_eq!((search.q.as_str(), search.page), ("dolor", 3));
+}
+
+#[test]
+fn aliases() {
+    let search = SearchBuilder::from_query("query=sit")
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(search.q, "sit");
+}
+
+#[test]
+fn parse_errors() {
+    let err = SearchBuilder::from_query("q=lorem&page-size=300").unwrap_err();
+
+    assert_eq!(err.key(), "page-size");
+    assert_eq!(
+        err.to_string(),
+        "invalid query parameter `page-size`: number too large to fit in target type"
+    );
+}
+
+#[test]
+fn validation_applies() {
+    let err = SearchBuilder::from_query("q=lorem&page=0")
+        .unwrap()
+        .build()
+        .unwrap_err();
+
+    assert_eq!(err.to_string(), "pages start at 1");
+}
+
+#[test]
+fn generic_owned_builder() {
+    let filter = FilterBuilder::<u16>::from_query("type=7")
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(filter, Filter { r#type: 7 });
+}
@@ -0,0 +1,89 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use std::time::Duration;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(from_str, derive(Debug))]
+struct Connection {
+    #[builder(setter(into), alias = "hostname", alias = "host_name")]
+    host: String,
+    #[builder(default = "5432")]
+    port: u16,
+    #[builder(default)]
+    tls: bool,
+    #[builder(setter(strip_option), default, parse(rename = "user"))]
+    user_name: Option<String>,
+    #[builder(parse(skip), default = "Duration::from_secs(30)")]
+    timeout: Duration,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(from_str, pattern = "owned")]
+struct Pair<T> {
+    r#type: T,
+}
+
+#[test]
+fn parses_key_value_list() {
+    let builder: ConnectionBuilder = "host=db, tls = true,user=lorem".parse().unwrap();
+
+    assert_eq!(
+        builder.build().unwrap(),
+        Connection {
+            host: "db".into(),
+            port: 5432,
+            tls: true,
+            user_name: Some("lorem".into()),
+            timeout: Duration::from_secs(30),
+        }
+    );
+}
+
+#[test]
+fn aliases() {
+    let builder: ConnectionBuilder = "hostname=db".parse().unwrap();
+    assert_eq!(builder.build().unwrap().host, "db");
+
+    let builder: ConnectionBuilder = "host=db,host_name=replica".parse().unwrap();
+    assert_eq!(builder.build().unwrap().host, "replica");
+}
+
+#[test]
+fn unknown_key() {
+    let err = "host=db,timeout=10"
+        .parse::<ConnectionBuilder>()
+        .unwrap_err();
+
+    assert_eq!(err.key(), "timeout");
+    assert_eq!(
+        err.to_string(),
+        "`timeout`: unknown key, expected one of `host`, `port`, `tls`, `user`"
+    );
+}
+
+#[test]
+fn invalid_value() {
+    let err = "port=lorem".parse::<ConnectionBuilder>().unwrap_err();
+
+    assert_eq!(err.to_string(), "`port`: invalid digit found in string");
+}
+
+#[test]
+fn missing_required_field() {
+    let builder: ConnectionBuilder = "port=1".parse().unwrap();
+
+    assert_eq!(
+        builder.build().unwrap_err().to_string(),
+        "`host` must be initialized"
+    );
+}
+
+#[test]
+fn generic_owned_builder() {
+    let pair: PairBuilder<u8> = "type=3".parse().unwrap();
+
+    assert_eq!(pair.build().unwrap(), Pair { r#type: 3 });
+}
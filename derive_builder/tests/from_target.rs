@@ -0,0 +1,81 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(from_target)]
+struct Pool {
+    size: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(from_target)]
+struct Server {
+    #[builder(setter(into))]
+    host: String,
+    #[builder(setter(strip_option), default)]
+    port: Option<u16>,
+    #[builder(field(bare), default)]
+    retries: u8,
+    #[builder(delegate)]
+    pool: Pool,
+    #[builder(setter(skip))]
+    connections: u32,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "immutable", from_target)]
+struct Generic<T> {
+    value: T,
+}
+
+fn server() -> Server {
+    Server {
+        host: "localhost".to_string(),
+        port: Some(8080),
+        retries: 3,
+        pool: Pool { size: 4 },
+        connections: 2,
+    }
+}
+
+#[test]
+fn from_target_sets_every_field() {
+    let built = ServerBuilder::from(server()).build().unwrap();
+
+    assert_eq!(
+        built,
+        Server {
+            connections: 0,
+            ..server()
+        }
+    );
+}
+
+#[test]
+fn to_builder_changes_one_field() {
+    let server = server();
+    let mut builder = server.to_builder();
+    builder.port(9090);
+    builder.pool().size(8);
+
+    assert_eq!(
+        builder.build().unwrap(),
+        Server {
+            host: "localhost".to_string(),
+            port: Some(9090),
+            retries: 3,
+            pool: Pool { size: 8 },
+            connections: 0,
+        }
+    );
+    assert_eq!(server.port, Some(8080));
+}
+
+#[test]
+fn generic_from_target() {
+    let builder = GenericBuilder::from(Generic { value: "lorem" });
+
+    assert_eq!(builder.build().unwrap(), Generic { value: "lorem" });
+}
@@ -0,0 +1,39 @@
+#![cfg(all(feature = "fuzzing", fuzzing))]
+
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, Builder)]
+#[allow(dead_code)]
+#[builder(build_fn(validate = "Self::validate"))]
+struct Range {
+    lo: u8,
+    hi: u8,
+    #[builder(setter(custom), field(ty = "Vec<u8>", build = "self.steps.clone()"))]
+    steps: Vec<u8>,
+}
+
+impl RangeBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let (Some(lo), Some(hi)) = (self.lo, self.hi) {
+            assert!(lo <= hi, "validator reached with lo > hi");
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn empty_data() {
+    RangeBuilder::fuzz_build(&[]);
+}
+
+#[test]
+fn ordered_range() {
+    RangeBuilder::fuzz_build(&[1, 3, 1, 5]);
+}
+
+#[test]
+#[should_panic(expected = "validator reached with lo > hi")]
+fn reaches_validator() {
+    RangeBuilder::fuzz_build(&[1, 5, 1, 3]);
+}
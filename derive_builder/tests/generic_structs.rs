@@ -40,7 +40,7 @@ fn generic_builder() {
     assert_eq!(
         x,
         Generic {
-            ipsum: "Generic".into(),
+            ipsum: "Generic",
             dolor: true,
         }
     );
@@ -57,3 +57,32 @@ fn generic_reference_builder() {
 
     assert_eq!(x, GenericReference { bar: Some(&BAR) });
 }
+
+#[derive(Debug, PartialEq, Default, Builder, Clone)]
+#[builder(setter(into))]
+struct GenericValue<VALUE: Clone> {
+    ipsum: VALUE,
+    #[builder(setter(each(name = "item", into)))]
+    items: Vec<String>,
+    #[builder(setter(generic = "V"))]
+    dolor: String,
+}
+
+#[test]
+fn generic_named_value() {
+    let x = GenericValueBuilder::default()
+        .ipsum(1u8)
+        .item("sit")
+        .dolor("amet")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        x,
+        GenericValue {
+            ipsum: 1u8,
+            items: vec!["sit".to_string()],
+            dolor: "amet".to_string(),
+        }
+    );
+}
@@ -0,0 +1,87 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+pub fn default_port() -> u16 {
+    8080
+}
+
+macro_rules! config_struct {
+    ($name:ident, $builder:ident { $($field:ident : $ty:ty),* $(,)? }) => {
+        #[derive(Debug, PartialEq, Builder)]
+        #[builder(name = $builder, build_fn(validate = Self::validate))]
+        pub struct $name {
+            $(pub $field: $ty,)*
+            #[builder(default = $crate::default_port())]
+            pub port: u16,
+            #[builder(default = self.default_name())]
+            pub name: String,
+        }
+
+        impl $builder {
+            fn validate(&self) -> Result<(), String> {
+                Ok(())
+            }
+
+            fn default_name(&self) -> String {
+                stringify!($name).to_string()
+            }
+        }
+    };
+}
+
+config_struct!(Server, ServerBuilder { host: String });
+
+#[test]
+fn defaults_from_macro() {
+    let x = ServerBuilder::default()
+        .host("localhost".into())
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        x,
+        Server {
+            host: "localhost".into(),
+            port: 8080,
+            name: "Server".into(),
+        }
+    );
+}
+
+macro_rules! with_default {
+    ($name:ident, $default:expr, $build:expr) => {
+        #[derive(Debug, PartialEq, Builder)]
+        pub struct $name {
+            #[builder(default = $default)]
+            pub ipsum: u32,
+            #[builder(field(ty = "String", build = $build))]
+            pub dolor: u32,
+        }
+    };
+}
+
+with_default!(Quoted, "40 + 2", "self.dolor.parse().unwrap_or(7)");
+with_default!(Unquoted, 40 + 2, self.dolor.parse().unwrap_or(7));
+
+#[test]
+fn expr_fragments() {
+    assert_eq!(
+        QuotedBuilder::default().build().unwrap(),
+        Quoted {
+            ipsum: 42,
+            dolor: 7
+        }
+    );
+    assert_eq!(
+        UnquotedBuilder::default()
+            .dolor("3".into())
+            .build()
+            .unwrap(),
+        Unquoted {
+            ipsum: 42,
+            dolor: 3
+        }
+    );
+}
@@ -0,0 +1,48 @@
+#![deny(unused_must_use)]
+
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+struct Lorem {
+    ipsum: u32,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned", must_use = false)]
+struct Dolor {
+    sit: u32,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned", must_use)]
+struct Amet {
+    consectetur: u32,
+}
+
+#[test]
+fn mutable_setters_are_not_must_use() {
+    let mut builder = LoremBuilder::default();
+    builder.ipsum(1);
+
+    assert_eq!(builder.build().unwrap(), Lorem { ipsum: 1 });
+}
+
+#[test]
+fn must_use_can_be_disabled() {
+    DolorBuilder::default().sit(2);
+
+    assert_eq!(
+        DolorBuilder::default().sit(1).build().unwrap(),
+        Dolor { sit: 1 }
+    );
+}
+
+#[test]
+fn must_use_setters() {
+    let x = AmetBuilder::default().consectetur(1).build().unwrap();
+
+    assert_eq!(x, Amet { consectetur: 1 });
+}
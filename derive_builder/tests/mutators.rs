@@ -0,0 +1,78 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(mutators(
+    /// Use short timeouts and no retries.
+    pub fn preset_fast(&mut self) -> &mut Self {
+        self.timeout_ms = Some(100);
+        self.retries = Some(0);
+        self
+    }
+
+    pub fn endpoint(&mut self, host: &str, port: u16) -> &mut Self {
+        self.host = Some(host.into());
+        self.port = Some(port);
+        self
+    }
+))]
+struct Client {
+    host: String,
+    port: u16,
+    timeout_ms: u32,
+    retries: u8,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(
+    pattern = "owned",
+    mutators(
+        fn doubled(self) -> Self {
+            let count = self.count.unwrap_or_default();
+            self.count(count * 2)
+        }
+    )
+)]
+struct Counter<T> {
+    count: u32,
+    #[builder(default)]
+    tag: Option<T>,
+}
+
+#[test]
+fn mutators_set_several_fields() {
+    let client = ClientBuilder::default()
+        .endpoint("localhost", 8080)
+        .preset_fast()
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        client,
+        Client {
+            host: "localhost".into(),
+            port: 8080,
+            timeout_ms: 100,
+            retries: 0,
+        }
+    );
+}
+
+#[test]
+fn mutators_of_owned_generic_builder() {
+    let counter = CounterBuilder::<()>::default()
+        .count(21)
+        .doubled()
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        counter,
+        Counter {
+            count: 42,
+            tag: None,
+        }
+    );
+}
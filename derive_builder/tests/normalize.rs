@@ -0,0 +1,114 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(normalize = "Self::normalize", validate = "Self::validate", fallback))]
+struct Tags {
+    #[builder(setter(into))]
+    name: String,
+    #[builder(default)]
+    tags: Vec<String>,
+}
+
+impl TagsBuilder {
+    fn normalize(&mut self) {
+        if let Some(ref mut name) = self.name {
+            *name = name.trim().to_string();
+        }
+        if let Some(ref mut tags) = self.tags {
+            tags.sort();
+            tags.dedup();
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        match self.name {
+            Some(ref name) if name.is_empty() => Err("name is empty".into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[test]
+fn normalizes_before_build() {
+    let tags = TagsBuilder::default()
+        .name("  lorem ")
+        .tags(vec!["b".into(), "a".into(), "b".into()])
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        tags,
+        Tags {
+            name: "lorem".into(),
+            tags: vec!["a".into(), "b".into()],
+        }
+    );
+}
+
+#[test]
+fn validator_sees_normalized_values() {
+    let err = TagsBuilder::default().name("   ").build().unwrap_err();
+
+    assert_eq!(err.to_string(), "name is empty");
+}
+
+#[test]
+fn builder_is_unchanged() {
+    let mut builder = TagsBuilder::default();
+    builder.name(" lorem ");
+    builder.build().unwrap();
+
+    assert_eq!(
+        builder.build_with_fallback(&fallback()).unwrap().name,
+        "lorem"
+    );
+    assert_eq!(builder.name.as_deref(), Some(" lorem "));
+}
+
+#[test]
+fn fallback_is_normalized() {
+    let tags = TagsBuilder::default()
+        .tags(vec!["b".into(), "a".into()])
+        .build_with_fallback(&fallback())
+        .unwrap();
+
+    assert_eq!(
+        tags,
+        Tags {
+            name: "ipsum".into(),
+            tags: vec!["a".into(), "b".into()],
+        }
+    );
+}
+
+fn fallback() -> Tags {
+    Tags {
+        name: "ipsum".into(),
+        tags: vec![],
+    }
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned", build_fn(normalize = "normalize_owned"))]
+struct Owned {
+    values: Vec<u8>,
+}
+
+fn normalize_owned(builder: &mut OwnedBuilder) {
+    if let Some(ref mut values) = builder.values {
+        values.sort_unstable();
+    }
+}
+
+#[test]
+fn owned_pattern() {
+    let owned = OwnedBuilder::default()
+        .values(vec![3, 1, 2])
+        .build()
+        .unwrap();
+
+    assert_eq!(owned.values, vec![1, 2, 3]);
+}
@@ -0,0 +1,69 @@
+// `const` initializers of thread locals need a newer compiler than the MSRV.
+#![allow(clippy::missing_const_for_thread_local)]
+
+#[macro_use]
+extern crate derive_builder;
+
+use std::cell::RefCell;
+use std::fmt::Debug;
+
+thread_local! {
+    static USED: RefCell<Vec<String>> = RefCell::new(Vec::new());
+}
+
+fn record(field: &'static str) {
+    USED.with(|used| used.borrow_mut().push(field.to_string()));
+}
+
+fn record_value(field: &'static str, value: &dyn Debug) {
+    USED.with(|used| used.borrow_mut().push(format!("{}={:?}", field, value)));
+}
+
+fn take_used() -> Vec<String> {
+    USED.with(|used| used.borrow_mut().drain(..).collect())
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(on_set = "record")]
+struct Lorem {
+    ipsum: u32,
+    #[builder(setter(into, strip_option), default)]
+    dolor: Option<String>,
+    #[builder(setter(each = "amet"), default)]
+    sit: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned", on_set(path = "record_value", value))]
+struct Ipsum {
+    dolor: u32,
+}
+
+#[test]
+fn setters_call_on_set() {
+    let lorem = LoremBuilder::default()
+        .ipsum(1)
+        .dolor("dolor")
+        .amet(2)
+        .amet(3)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        lorem,
+        Lorem {
+            ipsum: 1,
+            dolor: Some("dolor".to_string()),
+            sit: vec![2, 3],
+        }
+    );
+    assert_eq!(take_used(), vec!["ipsum", "dolor", "sit", "sit"]);
+}
+
+#[test]
+fn on_set_with_value() {
+    let ipsum = IpsumBuilder::default().dolor(42).build().unwrap();
+
+    assert_eq!(ipsum, Ipsum { dolor: 42 });
+    assert_eq!(take_used(), vec!["dolor=Some(42)"]);
+}
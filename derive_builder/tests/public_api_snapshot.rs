@@ -0,0 +1,75 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use std::borrow::Cow;
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(public_api_snapshot)]
+struct Lorem<'a, T: Display + Clone> {
+    #[builder(setter(into))]
+    ipsum: String,
+    #[builder(setter(strip_option), default)]
+    dolor: Option<u32>,
+    #[builder(setter(into, defer_err), default)]
+    sit: u16,
+    #[builder(setter(untuple(start, end)), default)]
+    range: (u32, u32),
+    #[builder(setter(borrowed), default)]
+    label: Cow<'a, str>,
+    #[builder(pattern = "owned", default)]
+    amet: Vec<T>,
+    #[builder(private, default)]
+    hidden: bool,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned", public_api_snapshot, setter(into))]
+struct Consectetur {
+    adipiscing: String,
+    #[builder(setter(skip))]
+    elit: u32,
+    #[builder(setter(transform = |w: u32, h: u32| (w, h)), default)]
+    size: (u32, u32),
+    #[builder(setter(strip_bool))]
+    verbose: bool,
+}
+
+#[test]
+fn setters_are_unchanged() {
+    let lorem = LoremBuilder::<u8>::default()
+        .ipsum("lorem")
+        .dolor(1)
+        .sit(Ok::<_, String>(2u16))
+        .range(3, 4)
+        .label("label")
+        .hidden(true)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        lorem,
+        Lorem {
+            ipsum: "lorem".into(),
+            dolor: Some(1),
+            sit: 2,
+            range: (3, 4),
+            label: Cow::Borrowed("label"),
+            amet: vec![],
+            hidden: true,
+        }
+    );
+
+    let consectetur = ConsecteturBuilder::default()
+        .adipiscing("adipiscing")
+        .size(1, 2)
+        .verbose()
+        .build()
+        .unwrap();
+    assert_eq!(consectetur.adipiscing, "adipiscing");
+    assert_eq!(consectetur.elit, 0);
+    assert_eq!(consectetur.size, (1, 2));
+    assert!(consectetur.verbose);
+}
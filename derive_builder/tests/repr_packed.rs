@@ -0,0 +1,108 @@
+//! The generated code must not take references to fields of packed structs.
+
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, Clone, Copy, PartialEq, Builder)]
+#[repr(C, packed)]
+#[builder(build_fn(fallback))]
+struct Header {
+    tag: u8,
+    #[builder(default = "4")]
+    len: u32,
+    #[builder(setter(skip))]
+    checksum: u16,
+}
+
+#[test]
+fn build_packed() {
+    let header = HeaderBuilder::default().tag(1).build().unwrap();
+
+    assert_eq!(
+        header,
+        Header {
+            tag: 1,
+            len: 4,
+            checksum: 0,
+        }
+    );
+}
+
+#[test]
+fn fallback_is_copied() {
+    let fallback = Header {
+        tag: 1,
+        len: 8,
+        checksum: 0xabcd,
+    };
+    let header = HeaderBuilder::default()
+        .tag(2)
+        .build_with_fallback(&fallback)
+        .unwrap();
+
+    assert_eq!(
+        header,
+        Header {
+            tag: 2,
+            len: 8,
+            checksum: 0xabcd,
+        }
+    );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Builder)]
+#[repr(C, packed(2))]
+#[builder(default, build_fn(fallback))]
+struct Sample {
+    channel: u8,
+    value: u64,
+}
+
+#[test]
+fn packed_with_default_struct() {
+    let sample = SampleBuilder::default().value(3).build().unwrap();
+    assert_eq!(
+        sample,
+        Sample {
+            channel: 0,
+            value: 3,
+        }
+    );
+
+    let fallback = Sample {
+        channel: 7,
+        value: 0,
+    };
+    let sample = SampleBuilder::default()
+        .value(5)
+        .build_with_fallback(&fallback)
+        .unwrap();
+    assert_eq!(
+        sample,
+        Sample {
+            channel: 7,
+            value: 5,
+        }
+    );
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[repr(C)]
+#[builder(build_fn(fallback))]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+#[test]
+fn repr_c() {
+    let fallback = Point { x: 1.0, y: 2.0 };
+    let point = PointBuilder::default()
+        .x(3.0)
+        .build_with_fallback(&fallback)
+        .unwrap();
+
+    assert_eq!(point, Point { x: 3.0, y: 2.0 });
+}
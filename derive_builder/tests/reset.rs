@@ -0,0 +1,82 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(reset)]
+struct Lorem {
+    ipsum: u32,
+    #[builder(setter(each(name = "item")))]
+    items: Vec<u32>,
+    #[builder(default)]
+    name: String,
+    #[builder(default)]
+    headers: HashMap<String, String>,
+    #[builder(field(ty = "Option<u8>", build = "self.custom.unwrap_or(1)"))]
+    custom: u8,
+    #[builder(setter(skip))]
+    skipped: bool,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(reset, pattern = "owned")]
+struct Dolor {
+    sit: u32,
+    amet: Vec<u8>,
+}
+
+#[test]
+fn reset_unsets_fields() {
+    let mut builder = LoremBuilder::default();
+    builder.ipsum(1).name("lorem".into()).custom = Some(5);
+    builder.reset();
+
+    assert_eq!(
+        builder.build().unwrap_err().to_string(),
+        "`ipsum` must be initialized"
+    );
+    assert!(builder.custom.is_none());
+}
+
+#[test]
+fn reset_keeps_collection_allocations() {
+    let mut builder = LoremBuilder::default();
+    builder.ipsum(1);
+    for i in 0..100 {
+        builder.item(i);
+    }
+    let capacity = builder.items.as_ref().unwrap().capacity();
+
+    builder.reset();
+    assert_eq!(builder.items.as_ref().unwrap().capacity(), capacity);
+
+    let lorem = builder.ipsum(2).item(3).build().unwrap();
+    assert_eq!(
+        lorem,
+        Lorem {
+            ipsum: 2,
+            items: vec![3],
+            name: String::new(),
+            headers: HashMap::new(),
+            custom: 1,
+            skipped: false,
+        }
+    );
+}
+
+#[test]
+fn reset_owned() {
+    let builder = DolorBuilder::default().sit(1).amet(vec![1, 2, 3]);
+    let builder = builder.reset();
+
+    assert_eq!(
+        builder.sit(2).build().unwrap(),
+        Dolor {
+            sit: 2,
+            amet: vec![],
+        }
+    );
+}
@@ -0,0 +1,67 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(
+    pattern = "owned",
+    derive(Debug),
+    setter(into),
+    build_fn(return_builder, validate = "Self::validate", panic_variant)
+)]
+struct Lorem {
+    ipsum: String,
+    #[builder(default)]
+    dolor: Vec<u32>,
+}
+
+impl LoremBuilder {
+    fn validate(&self) -> Result<(), String> {
+        match self.ipsum {
+            Some(ref ipsum) if ipsum.is_empty() => Err("ipsum must not be empty".into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[test]
+fn returns_built_struct() {
+    let lorem = LoremBuilder::default()
+        .ipsum("sit")
+        .dolor(vec![1, 2])
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        lorem,
+        Lorem {
+            ipsum: "sit".into(),
+            dolor: vec![1, 2],
+        }
+    );
+}
+
+#[test]
+fn returns_builder_on_missing_field() {
+    let (builder, err) = LoremBuilder::default()
+        .dolor(vec![1, 2])
+        .build()
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        LoremBuilderError::UninitializedField("ipsum")
+    ));
+
+    let lorem = builder.ipsum("sit").build().unwrap();
+    assert_eq!(lorem.dolor, vec![1, 2]);
+}
+
+#[test]
+fn returns_builder_on_validation_error() {
+    let (builder, err) = LoremBuilder::default().ipsum("").build().unwrap_err();
+
+    assert_eq!(&err.to_string(), "ipsum must not be empty");
+    assert_eq!(builder.ipsum("amet").build_or_panic().ipsum, "amet");
+}
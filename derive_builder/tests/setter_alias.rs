@@ -0,0 +1,66 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+struct Lorem {
+    #[builder(setter(into, alias = "hostname"))]
+    host: String,
+    #[builder(setter(strip_option, alias = "portnumber", alias = "port_no"), default)]
+    port: Option<u16>,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned", setter(prefix = "with"))]
+struct Sit {
+    #[builder(setter(alias = "amet"))]
+    consectetur: u32,
+}
+
+#[test]
+fn renamed_setter() {
+    let x = LoremBuilder::default()
+        .host("localhost")
+        .port(80)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        x,
+        Lorem {
+            host: "localhost".into(),
+            port: Some(80),
+        }
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn alias_sets_field() {
+    let x = LoremBuilder::default()
+        .hostname("localhost")
+        .portnumber(80)
+        .port_no(8080)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        x,
+        Lorem {
+            host: "localhost".into(),
+            port: Some(8080),
+        }
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn alias_keeps_old_name() {
+    let x = SitBuilder::default().amet(1).build().unwrap();
+
+    assert_eq!(
+        x,
+        SitBuilder::default().with_consectetur(1).build().unwrap()
+    );
+}
@@ -0,0 +1,81 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use derive_builder::ArrayLengthError;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(derive(Debug))]
+struct Lorem {
+    #[builder(setter(array))]
+    ipsum: [u8; 4],
+    #[builder(setter(array), default)]
+    dolor: [String; 2],
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned")]
+struct Matrix<const N: usize> {
+    #[builder(setter(array))]
+    diagonal: [i32; N],
+}
+
+#[test]
+fn from_iter() {
+    let lorem = LoremBuilder::default()
+        .try_ipsum_from_iter(1..=4)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        lorem,
+        Lorem {
+            ipsum: [1, 2, 3, 4],
+            dolor: Default::default(),
+        }
+    );
+}
+
+#[test]
+fn from_iter_wrong_length() {
+    let mut builder = LoremBuilder::default();
+    let err = builder.try_ipsum_from_iter(vec![1, 2]).unwrap_err();
+
+    assert_eq!(err, ArrayLengthError::new("ipsum", 4, 2));
+    assert_eq!(err.to_string(), "`ipsum` takes 4 items, but 2 were given");
+    assert!(builder.build().is_err());
+}
+
+#[test]
+fn set_nth() {
+    let lorem = LoremBuilder::default()
+        .set_nth_ipsum(2, 7)
+        .ipsum([1, 1, 1, 1])
+        .set_nth_ipsum(0, 5)
+        .set_nth_dolor(1, "sit".into())
+        .build()
+        .unwrap();
+
+    assert_eq!(lorem.ipsum, [5, 1, 1, 1]);
+    assert_eq!(lorem.dolor, [String::new(), "sit".into()]);
+}
+
+#[test]
+#[should_panic]
+fn set_nth_out_of_bounds() {
+    LoremBuilder::default().set_nth_ipsum(4, 1);
+}
+
+#[test]
+fn const_generic_length() {
+    let matrix = MatrixBuilder::<3>::default()
+        .try_diagonal_from_iter(vec![1, 2, 3])
+        .unwrap()
+        .set_nth_diagonal(1, 0)
+        .build()
+        .unwrap();
+
+    assert_eq!(matrix.diagonal, [1, 0, 3]);
+}
@@ -0,0 +1,103 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use std::borrow::Cow;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(setter(borrowed, cow_owned), pattern = "owned", from_str)]
+struct Record<'a> {
+    name: Cow<'a, str>,
+    #[builder(setter(strip_option), default)]
+    payload: Option<Cow<'a, [u8]>>,
+    #[builder(try_setter)]
+    tag: std::borrow::Cow<'a, str>,
+    id: u32,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+struct Label<'a> {
+    #[builder(setter(borrowed, cow_owned, name = "text"))]
+    name: Cow<'a, str>,
+}
+
+#[test]
+fn setters_borrow() {
+    let input = String::from("lorem ipsum");
+    let bytes = [1u8, 2, 3];
+    let record = RecordBuilder::default()
+        .name(&input[..5])
+        .payload(&bytes[..])
+        .tag("dolor")
+        .id(1)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        record,
+        Record {
+            name: Cow::Borrowed("lorem"),
+            payload: Some(Cow::Borrowed(&[1, 2, 3][..])),
+            tag: Cow::Borrowed("dolor"),
+            id: 1,
+        }
+    );
+    assert!(matches!(record.name, Cow::Borrowed(_)));
+    assert!(matches!(record.payload, Some(Cow::Borrowed(_))));
+}
+
+#[test]
+fn try_setter_borrows() {
+    let record = RecordBuilder::default()
+        .name("lorem")
+        .try_tag("dolor")
+        .unwrap()
+        .id(1)
+        .build()
+        .unwrap();
+
+    assert!(matches!(record.tag, Cow::Borrowed("dolor")));
+}
+
+#[test]
+fn borrowed_fields_are_not_parsed() {
+    let builder: RecordBuilder = "id=1".parse().unwrap();
+    let record = builder.name("lorem").tag("dolor").build().unwrap();
+    assert_eq!(record.id, 1);
+
+    assert!("name=lorem".parse::<RecordBuilder>().is_err());
+}
+
+#[test]
+fn mutable_pattern_keeps_borrow() {
+    let input = String::from("lorem");
+    let record = LabelBuilder::default().text(&input).build().unwrap();
+
+    assert!(matches!(record.name, Cow::Borrowed("lorem")));
+}
+
+#[test]
+fn owned_setter_takes_owned_value() {
+    let record = RecordBuilder::default()
+        .name_owned(String::from("lorem"))
+        .payload_owned(vec![1, 2, 3])
+        .tag("dolor")
+        .id(1)
+        .build()
+        .unwrap();
+
+    assert!(matches!(record.name, Cow::Owned(ref name) if name == "lorem"));
+    assert!(matches!(record.payload, Some(Cow::Owned(ref payload)) if payload == &[1, 2, 3]));
+    assert!(matches!(record.tag, Cow::Borrowed("dolor")));
+}
+
+#[test]
+fn mutable_pattern_owned_setter() {
+    let record = LabelBuilder::default()
+        .text_owned(String::from("lorem"))
+        .build()
+        .unwrap();
+
+    assert!(matches!(record.name, Cow::Owned(ref name) if name == "lorem"));
+}
@@ -0,0 +1,58 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+struct Node {
+    value: u32,
+    #[builder(setter(into, strip_option, boxed), default)]
+    next: Option<Box<Node>>,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(setter(boxed), try_setter)]
+struct Expr {
+    #[builder(setter(into))]
+    lhs: Box<u64>,
+    rhs: std::boxed::Box<u8>,
+    op: char,
+}
+
+#[test]
+fn recursive_type() {
+    let tail = NodeBuilder::default().value(2).build().unwrap();
+    let head = NodeBuilder::default()
+        .value(1)
+        .next(tail.clone())
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        head,
+        Node {
+            value: 1,
+            next: Some(Box::new(tail)),
+        }
+    );
+}
+
+#[test]
+fn struct_level_boxed() {
+    let expr = ExprBuilder::default()
+        .lhs(1u32)
+        .try_rhs(2u64)
+        .unwrap()
+        .op('+')
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        expr,
+        Expr {
+            lhs: Box::new(1),
+            rhs: Box::new(2),
+            op: '+',
+        }
+    );
+}
@@ -0,0 +1,67 @@
+#[macro_use]
+extern crate derive_builder;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Event {
+    Open,
+    Close,
+}
+
+#[derive(Builder)]
+#[builder(pattern = "owned")]
+#[allow(clippy::type_complexity)]
+struct Handlers {
+    #[builder(setter(closure))]
+    on_event: Box<dyn Fn(Event) + Send>,
+    #[builder(setter(closure, strip_option), default)]
+    on_error: Option<Box<dyn FnMut(&str) -> bool>>,
+}
+
+#[derive(Builder)]
+#[builder(pattern = "owned")]
+struct Borrowing<'a> {
+    #[builder(setter(closure))]
+    filter: Box<dyn Fn(u32) -> bool + 'a>,
+}
+
+#[test]
+fn boxes_closures() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&count);
+    let mut handlers = HandlersBuilder::default()
+        .on_event(move |event| {
+            if event == Event::Open {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+        .on_error(|message: &str| message.is_empty())
+        .build()
+        .unwrap();
+
+    (handlers.on_event)(Event::Open);
+    (handlers.on_event)(Event::Close);
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+    assert!((handlers.on_error.as_mut().unwrap())(""));
+}
+
+#[test]
+fn optional_closure() {
+    let handlers = HandlersBuilder::default().on_event(|_| {}).build().unwrap();
+
+    assert!(handlers.on_error.is_none());
+}
+
+#[test]
+fn borrowing_closure() {
+    let limit = 10;
+    let borrowing = BorrowingBuilder::default()
+        .filter(|value| value < limit)
+        .build()
+        .unwrap();
+
+    assert!((borrowing.filter)(3));
+    assert!(!(borrowing.filter)(30));
+}
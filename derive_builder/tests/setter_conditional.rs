@@ -0,0 +1,70 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+struct Lorem {
+    #[builder(setter(into, conditional), default)]
+    ipsum: String,
+    #[builder(setter(strip_option, conditional), default)]
+    dolor: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(setter(conditional), pattern = "owned")]
+struct Sit {
+    amet: u32,
+}
+
+#[test]
+fn sets_field_if_condition_holds() {
+    let x = LoremBuilder::default()
+        .ipsum_if(true, "lorem")
+        .dolor_if(true, 1)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        x,
+        Lorem {
+            ipsum: "lorem".into(),
+            dolor: Some(1),
+        }
+    );
+}
+
+#[test]
+fn keeps_field_otherwise() {
+    let x = LoremBuilder::default()
+        .ipsum("lorem")
+        .ipsum_if(false, "ipsum")
+        .dolor_if(false, 1)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        x,
+        Lorem {
+            ipsum: "lorem".into(),
+            dolor: None,
+        }
+    );
+}
+
+#[test]
+fn struct_level() {
+    let verbose = false;
+    let x = SitBuilder::default()
+        .amet(1)
+        .amet_if(verbose, 2)
+        .build()
+        .unwrap();
+    assert_eq!(x, Sit { amet: 1 });
+
+    let err = SitBuilder::default()
+        .amet_if(verbose, 2)
+        .build()
+        .unwrap_err();
+    assert_eq!(&err.to_string(), "`amet` must be initialized");
+}
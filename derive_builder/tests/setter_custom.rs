@@ -93,3 +93,40 @@ fn setter_custom_setters_called() {
         }
     );
 }
+
+#[derive(Debug, PartialEq, Builder)]
+struct Endpoint {
+    #[builder(setter(custom))]
+    port: u16,
+    host: String,
+}
+
+impl EndpointBuilder {
+    fn port<P: std::convert::TryInto<u16>>(&mut self, port: P) -> &mut Self {
+        self.port = port.try_into().ok();
+        self
+    }
+}
+
+#[test]
+fn setter_custom_required_field() {
+    let x = EndpointBuilder::default()
+        .port(8080u32)
+        .host("localhost".into())
+        .build()
+        .unwrap();
+    assert_eq!(
+        x,
+        Endpoint {
+            port: 8080,
+            host: "localhost".into(),
+        }
+    );
+
+    let err = EndpointBuilder::default()
+        .port(-1)
+        .host("localhost".into())
+        .build()
+        .unwrap_err();
+    assert_eq!(err.to_string(), "`port` must be initialized");
+}
@@ -158,3 +158,129 @@ fn extend_field_enabled() {
     let x = SitBuilder::default().foo(1).foo(2).build().unwrap();
     assert_eq!(x, Sit { foos: vec![1, 2] });
 }
+
+/// Marker for the items which may be added to a `Bag`.
+trait Item {}
+
+impl Item for u8 {}
+
+#[derive(Debug, PartialEq, Default, Builder, Clone)]
+struct Bag<C: Default + Clone> {
+    #[builder(setter(each(name = "item", bound = "VALUE: Item, C: Clone")))]
+    items: C,
+}
+
+#[test]
+fn extend_field_generic_collection_with_bound() {
+    let x = BagBuilder::<Vec<u8>>::default()
+        .item(1)
+        .item(2)
+        .build()
+        .unwrap();
+    assert_eq!(x, Bag { items: vec![1, 2] });
+
+    let x = BagBuilder::<std::collections::BTreeSet<u8>>::default()
+        .item(2)
+        .item(1)
+        .item(2)
+        .build()
+        .unwrap();
+    assert_eq!(x.items.into_iter().collect::<Vec<_>>(), vec![1, 2]);
+}
+
+#[derive(Debug, PartialEq, Default, Builder, Clone)]
+struct Batch {
+    #[builder(setter(each(name = "item")), field(capacity = 16))]
+    items: Vec<u32>,
+    #[builder(setter(each(name = "label")), field(capacity = "2 * 8"))]
+    labels: HashMap<u32, String>,
+    #[builder(setter(strip_option, each = "tag"), field(capacity = 4))]
+    tags: Option<Vec<String>>,
+}
+
+#[test]
+fn extend_field_with_capacity() {
+    let mut builder = BatchBuilder::default();
+    builder.item(1).label((1, "one".into())).tag("tag".into());
+
+    assert!(builder.items.as_ref().unwrap().capacity() >= 16);
+    assert!(builder.labels.as_ref().unwrap().capacity() >= 16);
+    assert!(builder.tags.as_ref().unwrap().as_ref().unwrap().capacity() >= 4);
+
+    let batch = builder.item(2).build().unwrap();
+    assert_eq!(batch.items, vec![1, 2]);
+    assert_eq!(batch.labels[&1], "one");
+    assert_eq!(batch.tags, Some(vec!["tag".to_string()]));
+}
+
+#[derive(Debug, PartialEq, Builder)]
+struct Command {
+    #[builder(setter(each(name = "arg", into)))]
+    args: Vec<String>,
+}
+
+#[test]
+fn extend_field_after_whole_collection() {
+    let mut builder = CommandBuilder::default();
+    builder.args(vec!["-v".into()]).arg("--color").arg("auto");
+    assert_eq!(
+        builder.build().unwrap(),
+        Command {
+            args: vec!["-v".into(), "--color".into(), "auto".into()],
+        }
+    );
+
+    builder.args(vec!["-q".into()]);
+    assert_eq!(
+        builder.build().unwrap(),
+        Command {
+            args: vec!["-q".into()],
+        }
+    );
+}
+
+#[derive(Debug, PartialEq, Builder)]
+struct Request {
+    #[builder(setter(each(name = "header", into, key_value)))]
+    headers: HashMap<String, String>,
+    #[builder(setter(each(name = "param", key_value)))]
+    params: std::collections::BTreeMap<String, u32>,
+}
+
+#[test]
+fn extend_map_field_with_key_and_value() {
+    let request = RequestBuilder::default()
+        .header("Accept", "text/html")
+        .header("Host", String::from("example.com"))
+        .param("page".into(), 2)
+        .build()
+        .unwrap();
+
+    assert_eq!(request.headers.len(), 2);
+    assert_eq!(request.headers["Accept"], "text/html");
+    assert_eq!(request.headers["Host"], "example.com");
+    assert_eq!(request.params["page"], 2);
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned")]
+struct Pipeline {
+    #[builder(setter(extend, each = "stage"))]
+    stages: Vec<String>,
+    #[builder(setter(strip_option, extend))]
+    env: Option<HashMap<String, String>>,
+}
+
+#[test]
+fn extend_field_from_iterator() {
+    let pipeline = PipelineBuilder::default()
+        .stage("fetch".into())
+        .stages_extend(["build", "test"].iter().map(|s| s.to_string()))
+        .stages_extend(vec!["deploy".to_string()])
+        .env_extend(Some(("CI".to_string(), "true".to_string())))
+        .build()
+        .unwrap();
+
+    assert_eq!(pipeline.stages, vec!["fetch", "build", "test", "deploy"]);
+    assert_eq!(pipeline.env.unwrap()["CI"], "true");
+}
@@ -0,0 +1,64 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use derive_builder::DynSet;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(from_str, dyn_set)]
+struct Lorem {
+    ipsum: u32,
+    #[builder(default, setter(feature = "std"))]
+    dolor: u32,
+    #[builder(default = "7", setter(feature = "pretty"))]
+    sit: u32,
+}
+
+#[test]
+fn enabled_feature_has_setter() {
+    let lorem = LoremBuilder::default().ipsum(1).dolor(2).build().unwrap();
+
+    assert_eq!(
+        lorem,
+        Lorem {
+            ipsum: 1,
+            dolor: 2,
+            sit: 7,
+        }
+    );
+}
+
+#[test]
+fn enabled_feature_is_parsed() {
+    let lorem: Lorem = "ipsum=1,dolor=2"
+        .parse::<LoremBuilder>()
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(lorem.dolor, 2);
+
+    let mut builder = LoremBuilder::default();
+    builder.set("dolor", Box::new(3u32)).unwrap();
+    assert_eq!(builder.dolor, Some(3));
+}
+
+#[cfg(not(feature = "pretty"))]
+#[test]
+fn disabled_feature_uses_default() {
+    let lorem = LoremBuilder::default().ipsum(1).build().unwrap();
+    assert_eq!(lorem.sit, 7);
+
+    assert!("ipsum=1,sit=2".parse::<LoremBuilder>().is_err());
+
+    let mut builder = LoremBuilder::default();
+    assert!(builder.set("sit", Box::new(2u32)).is_err());
+    assert_eq!(builder.field_type("sit"), None);
+}
+
+#[cfg(feature = "pretty")]
+#[test]
+fn disabled_feature_uses_default() {
+    let lorem = LoremBuilder::default().ipsum(1).sit(2).build().unwrap();
+    assert_eq!(lorem.sit, 2);
+}
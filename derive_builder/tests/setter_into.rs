@@ -33,3 +33,51 @@ fn generic_struct() {
 
     assert_eq!(x, Ipsum { foo: 42u32 });
 }
+
+#[derive(Debug, PartialEq, Default, Builder, Clone)]
+struct Dolor<VALUE> {
+    #[builder(setter(into_impl))]
+    foo: String,
+    #[builder(setter(into_impl, strip_option, maybe), default)]
+    bar: Option<u32>,
+    baz: VALUE,
+}
+
+#[derive(Debug, PartialEq, Default, Builder, Clone)]
+#[builder(setter(into_impl), public_api_snapshot)]
+pub struct Sit {
+    foo: u32,
+    #[builder(setter(defer_err))]
+    bar: u16,
+}
+
+#[test]
+fn into_impl_field() {
+    let x = DolorBuilder::default()
+        .foo("foo")
+        .bar(1u8)
+        .maybe_bar(Some(2u16))
+        .baz(true)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        x,
+        Dolor {
+            foo: "foo".to_string(),
+            bar: Some(2),
+            baz: true,
+        }
+    );
+}
+
+#[test]
+fn into_impl_struct() {
+    let x = SitBuilder::default()
+        .foo(42u8)
+        .bar(Ok::<_, String>(7u8))
+        .build()
+        .unwrap();
+
+    assert_eq!(x, Sit { foo: 42, bar: 7 });
+}
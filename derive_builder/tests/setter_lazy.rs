@@ -0,0 +1,100 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+fn expensive_key() -> String {
+    CALLS.fetch_add(1, Ordering::SeqCst);
+    "generated".into()
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(validate = "Self::validate"), reset, derive(Debug))]
+struct Session {
+    user: String,
+    #[builder(setter(into, lazy))]
+    key: String,
+    #[builder(setter(lazy), default = "30")]
+    timeout: u32,
+}
+
+impl SessionBuilder {
+    fn validate(&self) -> Result<(), String> {
+        match self.user {
+            Some(ref user) if user.is_empty() => Err("empty user".into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[test]
+fn closure_is_called_at_build() {
+    let calls = CALLS.load(Ordering::SeqCst);
+    let mut builder = SessionBuilder::default();
+    builder.user("alice".into()).key_with(expensive_key);
+    assert_eq!(CALLS.load(Ordering::SeqCst), calls);
+
+    assert_eq!(
+        builder.build().unwrap(),
+        Session {
+            user: "alice".into(),
+            key: "generated".into(),
+            timeout: 30,
+        }
+    );
+    assert!(CALLS.load(Ordering::SeqCst) > calls);
+}
+
+#[test]
+fn closure_is_not_called_if_validation_fails() {
+    let calls = CALLS.load(Ordering::SeqCst);
+    let err = SessionBuilder::default()
+        .user("".into())
+        .key_with(|| -> String { panic!("must not be called") })
+        .build()
+        .unwrap_err();
+
+    assert_eq!(err.to_string(), "empty user");
+    assert_eq!(CALLS.load(Ordering::SeqCst), calls);
+}
+
+#[test]
+fn last_setter_wins() {
+    let session = SessionBuilder::default()
+        .user("bob".into())
+        .key_with(|| -> String { panic!("must not be called") })
+        .key("explicit")
+        .timeout_with(|| 60)
+        .build()
+        .unwrap();
+    assert_eq!(session.key, "explicit");
+    assert_eq!(session.timeout, 60);
+
+    let session = SessionBuilder::default()
+        .user("bob".into())
+        .timeout(10)
+        .timeout_with(|| 20)
+        .key("explicit")
+        .build()
+        .unwrap();
+    assert_eq!(session.timeout, 20);
+}
+
+#[test]
+fn unset_and_reset() {
+    let mut builder = SessionBuilder::default();
+    builder.user("carol".into());
+    let err = builder.build().unwrap_err();
+    assert_eq!(err.to_string(), "`key` must be initialized");
+
+    builder
+        .key_with(|| "lazy".to_string())
+        .reset()
+        .user("carol".into());
+    let err = builder.build().unwrap_err();
+    assert_eq!(err.to_string(), "`key` must be initialized");
+}
@@ -0,0 +1,122 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+struct Lorem {
+    #[builder(setter(into, strip_option, maybe), default)]
+    ipsum: Option<String>,
+    #[builder(setter(strip_option, maybe, boxed))]
+    dolor: Option<Box<u32>>,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(setter(strip_option, maybe), pattern = "owned")]
+struct Sit {
+    amet: Option<u32>,
+    #[builder(setter(strip_option = false))]
+    plain: Option<u32>,
+}
+
+#[test]
+fn maybe_setter_passes_option_through() {
+    let upstream: Option<&str> = Some("lorem");
+    let x = LoremBuilder::default()
+        .maybe_ipsum(upstream)
+        .maybe_dolor(Some(1))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        x,
+        Lorem {
+            ipsum: Some("lorem".into()),
+            dolor: Some(Box::new(1)),
+        }
+    );
+}
+
+#[test]
+fn maybe_setter_sets_none() {
+    let x = LoremBuilder::default()
+        .ipsum("lorem")
+        .maybe_ipsum(None::<String>)
+        .maybe_dolor(None)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        x,
+        Lorem {
+            ipsum: None,
+            dolor: None,
+        }
+    );
+}
+
+#[test]
+fn struct_level() {
+    let x = SitBuilder::default()
+        .maybe_amet(Some(1))
+        .plain(None)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        x,
+        Sit {
+            amet: Some(1),
+            plain: None,
+        }
+    );
+}
+
+#[derive(Debug, PartialEq, Builder)]
+struct Amet {
+    #[builder(setter(into, maybe))]
+    host: String,
+    #[builder(setter(maybe), default = "8080")]
+    port: u16,
+}
+
+#[test]
+fn maybe_setter_without_strip_option_sets_some() {
+    let x = AmetBuilder::default()
+        .maybe_host(Some("localhost"))
+        .maybe_port(Some(80))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        x,
+        Amet {
+            host: "localhost".into(),
+            port: 80,
+        }
+    );
+}
+
+#[test]
+fn maybe_setter_without_strip_option_ignores_none() {
+    let x = AmetBuilder::default()
+        .host("localhost")
+        .maybe_host(None::<&str>)
+        .maybe_port(None)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        x,
+        Amet {
+            host: "localhost".into(),
+            port: 8080,
+        }
+    );
+
+    let err = AmetBuilder::default()
+        .maybe_host(None::<String>)
+        .build()
+        .unwrap_err();
+    assert_eq!(&err.to_string(), "`host` must be initialized");
+}
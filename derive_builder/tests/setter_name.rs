@@ -27,3 +27,32 @@ fn renamed_setter() {
         }
     );
 }
+
+#[derive(Debug, PartialEq, Default, Builder, Clone)]
+#[builder(setter(suffix = "mut"))]
+struct Ipsum {
+    dolor: &'static str,
+    #[builder(setter(prefix = "set", suffix = "value"))]
+    sit: &'static str,
+    #[builder(setter(name = "amet"))]
+    consectetur: &'static str,
+}
+
+#[test]
+fn suffixed_setter() {
+    let x = IpsumBuilder::default()
+        .dolor_mut("dolor")
+        .set_sit_value("sit")
+        .amet("amet")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        x,
+        Ipsum {
+            dolor: "dolor",
+            sit: "sit",
+            consectetur: "amet",
+        }
+    );
+}
@@ -0,0 +1,109 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Default, Builder, Clone)]
+struct Patch {
+    #[builder(setter(nullable, into))]
+    name: Option<Option<String>>,
+    #[builder(setter(nullable), default = "Some(Some(7))")]
+    age: Option<Option<u8>>,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned", setter(prefix = "with"))]
+struct Update {
+    #[builder(setter(nullable))]
+    nickname: Option<::std::option::Option<&'static str>>,
+}
+
+#[test]
+fn unset_is_none() {
+    let patch = PatchBuilder::default().build().unwrap();
+
+    assert_eq!(
+        patch,
+        Patch {
+            name: None,
+            age: Some(Some(7)),
+        }
+    );
+}
+
+#[test]
+fn set_to_value() {
+    let patch = PatchBuilder::default()
+        .name("lorem")
+        .age(3)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        patch,
+        Patch {
+            name: Some(Some("lorem".into())),
+            age: Some(Some(3)),
+        }
+    );
+}
+
+#[test]
+fn set_to_null() {
+    let patch = PatchBuilder::default()
+        .set_null_name()
+        .set_null_age()
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        patch,
+        Patch {
+            name: Some(None),
+            age: Some(None),
+        }
+    );
+}
+
+#[test]
+fn last_call_wins() {
+    let patch = PatchBuilder::default()
+        .name("lorem")
+        .set_null_name()
+        .build()
+        .unwrap();
+    assert_eq!(patch.name, Some(None));
+
+    let patch = PatchBuilder::default()
+        .set_null_name()
+        .name("ipsum")
+        .build()
+        .unwrap();
+    assert_eq!(patch.name, Some(Some("ipsum".into())));
+}
+
+#[test]
+fn owned_pattern_with_prefix() {
+    assert_eq!(
+        UpdateBuilder::default().build().unwrap(),
+        Update { nickname: None }
+    );
+    assert_eq!(
+        UpdateBuilder::default()
+            .with_nickname("dolor")
+            .build()
+            .unwrap(),
+        Update {
+            nickname: Some(Some("dolor")),
+        }
+    );
+    assert_eq!(
+        UpdateBuilder::default()
+            .set_null_nickname()
+            .build()
+            .unwrap(),
+        Update {
+            nickname: Some(None),
+        }
+    );
+}
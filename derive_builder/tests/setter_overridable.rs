@@ -0,0 +1,88 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+mod upstream {
+    #[derive(Debug, PartialEq, Clone, Builder)]
+    #[builder(overridable, setter(into))]
+    pub struct Lorem {
+        pub ipsum: String,
+        #[builder(default, setter(each = "dolor_item"))]
+        pub dolor: Vec<u8>,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Builder)]
+    #[builder(overridable, pattern = "immutable")]
+    pub struct Generic<T: Clone> {
+        pub value: T,
+    }
+}
+
+use upstream::{GenericBuilder, GenericBuilderSetters, Lorem, LoremBuilder, LoremBuilderSetters};
+
+/// Counts the `ipsum` calls before forwarding them to the builder.
+#[derive(Default)]
+struct Counting {
+    builder: LoremBuilder,
+    ipsum_calls: usize,
+}
+
+impl LoremBuilderSetters for Counting {
+    fn lorem_builder_mut(&mut self) -> &mut LoremBuilder {
+        &mut self.builder
+    }
+
+    fn ipsum<VALUE: Into<String>>(&mut self, value: VALUE) -> &mut Self {
+        self.ipsum_calls += 1;
+        self.builder.ipsum(value);
+        self
+    }
+
+    fn dolor_item<VALUE>(&mut self, item: VALUE) -> &mut Self
+    where
+        Vec<u8>: Default + Extend<VALUE>,
+    {
+        self.builder.dolor_item(item);
+        self
+    }
+}
+
+#[test]
+fn builder_implements_setters() {
+    let x = LoremBuilder::default()
+        .ipsum("ipsum")
+        .dolor_item(1)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        x,
+        Lorem {
+            ipsum: "ipsum".into(),
+            dolor: vec![1],
+        }
+    );
+}
+
+#[test]
+fn wrapper_overrides_setter() {
+    let mut wrapper = Counting::default();
+    wrapper.ipsum("a").ipsum("b").dolor_item(2);
+
+    assert_eq!(wrapper.ipsum_calls, 2);
+    assert_eq!(
+        wrapper.builder.build().unwrap(),
+        Lorem {
+            ipsum: "b".into(),
+            dolor: vec![2],
+        }
+    );
+}
+
+#[test]
+fn immutable_generic() {
+    let x = GenericBuilder::default().value(1u8).build().unwrap();
+
+    assert_eq!(x, upstream::Generic { value: 1 });
+}
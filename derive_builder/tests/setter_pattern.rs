@@ -19,6 +19,14 @@ struct Ipsum {
     owned_override: u32,
 }
 
+#[derive(Debug, PartialEq, Default, Builder, Clone)]
+#[builder(pattern = "mutable")]
+struct Amet {
+    mutable: u32,
+    #[builder(pattern = "owned", setter(into))]
+    owned_override: String,
+}
+
 #[derive(Debug, PartialEq, Default, Builder, Clone)]
 #[builder(pattern = "owned")]
 struct Dolor {
@@ -98,10 +106,29 @@ fn owned() {
 
 #[test]
 fn owned_override() {
-    // the setter must have the correct signature
-    let owned_setter: OwnedSetter<IpsumBuilder, u32> = IpsumBuilder::owned_override;
+    // the setters must have the correct signatures
+    let mutable_setter: MutableSetter<IpsumBuilder, u32> = IpsumBuilder::owned_override;
+    let owned_setter: OwnedSetter<IpsumBuilder, u32> = IpsumBuilder::with_owned_override;
 
-    let old = IpsumBuilder::default();
-    let new = owned_setter(old, 42);
-    assert_eq!(new.owned_override, Some(42));
+    let mut old = IpsumBuilder::default();
+    mutable_setter(&mut old, 42);
+    let new = owned_setter(old, 43);
+    assert_eq!(new.owned_override, Some(43));
+}
+
+#[test]
+fn owned_override_chains_with_mutable_setters() {
+    let amet = AmetBuilder::default()
+        .with_owned_override("lorem")
+        .mutable(42)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        amet,
+        Amet {
+            mutable: 42,
+            owned_override: "lorem".into(),
+        }
+    );
 }
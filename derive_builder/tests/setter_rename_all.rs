@@ -0,0 +1,93 @@
+#![deny(non_snake_case)]
+
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(setter(rename_all = "camelCase"))]
+struct Lorem {
+    ipsum_dolor: u32,
+    #[builder(setter(prefix = "with"))]
+    sit_amet: u32,
+    #[builder(setter(name = "custom_name"))]
+    consectetur: u32,
+    r#type: u32,
+    #[builder(setter(nullable), default)]
+    adipiscing_elit: Option<Option<u32>>,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(setter(rename_all = "SCREAMING_SNAKE_CASE"), pattern = "owned")]
+struct Dolor {
+    sit_amet: u32,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(setter(rename_all = "PascalCase", prefix = "set"))]
+struct Sed {
+    do_eiusmod: u32,
+}
+
+#[test]
+fn camel_case() {
+    let lorem = LoremBuilder::default()
+        .ipsumDolor(1)
+        .withSitAmet(2)
+        .custom_name(3)
+        .r#type(4)
+        .setNullAdipiscingElit()
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        lorem,
+        Lorem {
+            ipsum_dolor: 1,
+            sit_amet: 2,
+            consectetur: 3,
+            r#type: 4,
+            adipiscing_elit: Some(None),
+        }
+    );
+}
+
+#[test]
+fn screaming_snake_case() {
+    assert_eq!(
+        DolorBuilder::default().SIT_AMET(1).build().unwrap(),
+        Dolor { sit_amet: 1 }
+    );
+}
+
+#[test]
+fn pascal_case_with_struct_prefix() {
+    assert_eq!(
+        SedBuilder::default().SetDoEiusmod(1).build().unwrap(),
+        Sed { do_eiusmod: 1 }
+    );
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, PartialEq, Builder)]
+#[builder(setter(rename_all = "snake_case"))]
+struct Ffi {
+    tempoRate: u32,
+    HTTPPort: u16,
+}
+
+#[test]
+fn snake_case_from_camel_case_fields() {
+    assert_eq!(
+        FfiBuilder::default()
+            .tempo_rate(1)
+            .http_port(80)
+            .build()
+            .unwrap(),
+        Ffi {
+            tempoRate: 1,
+            HTTPPort: 80,
+        }
+    );
+}
@@ -0,0 +1,73 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+struct Lorem {
+    #[builder(setter(strip_bool))]
+    verbose: bool,
+    #[builder(setter(strip_bool), default = "true")]
+    color: bool,
+    quiet: bool,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned", setter(strip_bool))]
+struct Ipsum {
+    name: String,
+    force: bool,
+    #[builder(setter(strip_bool = false))]
+    dry_run: bool,
+}
+
+#[test]
+fn field_level() {
+    let x = LoremBuilder::default().quiet(false).build().unwrap();
+    assert_eq!(
+        x,
+        Lorem {
+            verbose: false,
+            color: true,
+            quiet: false,
+        }
+    );
+
+    let x = LoremBuilder::default()
+        .verbose()
+        .quiet(true)
+        .build()
+        .unwrap();
+    assert_eq!(
+        x,
+        Lorem {
+            verbose: true,
+            color: true,
+            quiet: true,
+        }
+    );
+}
+
+#[test]
+fn struct_level() {
+    let x = IpsumBuilder::default()
+        .name("foo".into())
+        .force()
+        .dry_run(true)
+        .build()
+        .unwrap();
+    assert_eq!(
+        x,
+        Ipsum {
+            name: "foo".into(),
+            force: true,
+            dry_run: true,
+        }
+    );
+
+    let err = IpsumBuilder::default()
+        .name("foo".into())
+        .build()
+        .unwrap_err();
+    assert_eq!(err.to_string(), "`dry_run` must be initialized");
+}
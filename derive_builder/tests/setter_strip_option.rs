@@ -62,3 +62,28 @@ fn generic_struct() {
         }
     );
 }
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(setter(strip_option))]
+struct Dolor {
+    name: Option<String>,
+    #[builder(setter(strip_option = false))]
+    alias: Option<String>,
+}
+
+#[test]
+fn field_overrides_struct_level() {
+    let x = DolorBuilder::default()
+        .name("foo".to_string())
+        .alias(None)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        x,
+        Dolor {
+            name: Some("foo".to_string()),
+            alias: None,
+        }
+    );
+}
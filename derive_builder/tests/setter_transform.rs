@@ -0,0 +1,66 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use std::time::Duration;
+
+#[derive(Debug, PartialEq, Clone)]
+struct Size {
+    w: u32,
+    h: u32,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+struct Window {
+    #[builder(setter(transform = |w: u32, h: u32| Size { w, h }))]
+    size: Size,
+    #[builder(setter(transform = "|secs: u64| Duration::from_secs(secs)"))]
+    timeout: Duration,
+    #[builder(setter(strip_option, transform = |title: &str| title.to_uppercase()), default)]
+    title: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned", setter(into))]
+struct Screen {
+    #[builder(setter(transform = |(w, h): (u32, u32)| Size { w, h }))]
+    resolution: Size,
+    name: String,
+}
+
+#[test]
+fn multi_argument_setters() {
+    let window = WindowBuilder::default()
+        .size(800, 600)
+        .timeout(30)
+        .title("main")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        window,
+        Window {
+            size: Size { w: 800, h: 600 },
+            timeout: Duration::from_secs(30),
+            title: Some("MAIN".into()),
+        }
+    );
+}
+
+#[test]
+fn pattern_parameter_ignores_struct_level_into() {
+    let screen = ScreenBuilder::default()
+        .resolution((1920, 1080))
+        .name("primary")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        screen,
+        Screen {
+            resolution: Size { w: 1920, h: 1080 },
+            name: "primary".into(),
+        }
+    );
+}
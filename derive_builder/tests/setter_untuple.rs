@@ -0,0 +1,69 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+struct Lorem {
+    #[builder(setter(untuple(start, end)))]
+    range: (u32, u32),
+    #[builder(setter(untuple))]
+    origin: (f32, f32, f32),
+    #[builder(setter(untuple(label), strip_option), default)]
+    single: Option<(String,)>,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(pattern = "owned", setter(into))]
+struct Dolor {
+    #[builder(setter(untuple(x, y)))]
+    position: (i64, i64),
+    name: String,
+}
+
+#[test]
+fn multi_argument_setters() {
+    let lorem = LoremBuilder::default()
+        .range(1, 10)
+        .origin(0.5, 1.0, 1.5)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        lorem,
+        Lorem {
+            range: (1, 10),
+            origin: (0.5, 1.0, 1.5),
+            single: None,
+        }
+    );
+}
+
+#[test]
+fn one_tuple_with_strip_option() {
+    let lorem = LoremBuilder::default()
+        .range(0, 0)
+        .origin(0.0, 0.0, 0.0)
+        .single("ipsum".to_string())
+        .build()
+        .unwrap();
+
+    assert_eq!(lorem.single, Some(("ipsum".to_string(),)));
+}
+
+#[test]
+fn struct_level_into_is_overridden() {
+    let dolor = DolorBuilder::default()
+        .position(-1, 2)
+        .name("sit")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        dolor,
+        Dolor {
+            position: (-1, 2),
+            name: "sit".into(),
+        }
+    );
+}
@@ -61,3 +61,32 @@ fn public_setters_foreign_module() {
 
     assert_eq!(&error.to_string(), "`private` must be initialized");
 }
+
+pub mod bar {
+    #[derive(Debug, PartialEq, Default, Builder, Clone)]
+    #[builder(public, setter(private))]
+    pub struct Dolor {
+        pub private: u32,
+        #[builder(public)]
+        pub public: u32,
+    }
+
+    pub fn builder() -> DolorBuilder {
+        let mut builder = DolorBuilder::default();
+        builder.private(1);
+        builder
+    }
+}
+
+#[test]
+fn public_builder_with_private_setters() {
+    let x = bar::builder().public(2).build().unwrap();
+
+    assert_eq!(
+        x,
+        bar::Dolor {
+            private: 1,
+            public: 2,
+        }
+    );
+}
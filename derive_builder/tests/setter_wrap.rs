@@ -0,0 +1,127 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use std::fmt::Debug;
+use std::rc::Rc;
+use std::sync::Arc;
+
+pub trait Handler: Debug {
+    fn handle(&self, request: &str) -> String;
+}
+
+#[derive(Debug)]
+struct Echo;
+
+impl Handler for Echo {
+    fn handle(&self, request: &str) -> String {
+        request.to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Handle<T>(T);
+
+impl<T> Handle<T> {
+    pub fn new(value: T) -> Self {
+        Handle(value)
+    }
+}
+
+#[derive(Debug, Builder)]
+#[builder(pattern = "owned")]
+struct Server {
+    #[builder(setter(wrap = "Box"))]
+    handler: Box<dyn Handler>,
+    #[builder(setter(wrap = "Arc", strip_option), default)]
+    fallback: Option<Arc<dyn Handler + Send + Sync>>,
+    #[builder(setter(wrap = "std::rc::Rc", into))]
+    name: Rc<String>,
+    #[builder(setter(wrap = "Handle"))]
+    port: Handle<u16>,
+}
+
+#[derive(Debug)]
+struct Upper;
+
+impl Handler for Upper {
+    fn handle(&self, request: &str) -> String {
+        request.to_uppercase()
+    }
+}
+
+#[test]
+fn wraps_trait_objects_and_values() {
+    let server = ServerBuilder::default()
+        .handler(Echo)
+        .fallback(Upper)
+        .name("lorem")
+        .port(8080)
+        .build()
+        .unwrap();
+
+    assert_eq!(server.handler.handle("ipsum"), "ipsum");
+    assert_eq!(server.fallback.unwrap().handle("ipsum"), "IPSUM");
+    assert_eq!(*server.name, "lorem");
+    assert_eq!(server.port, Handle::new(8080));
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Config {
+    retries: u8,
+}
+
+impl From<u8> for Config {
+    fn from(retries: u8) -> Self {
+        Config { retries }
+    }
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(public_api_snapshot)]
+pub struct Client {
+    #[builder(setter(wrap = "Arc", into_impl))]
+    config: Arc<Config>,
+    #[builder(setter(wrap = "Rc", into))]
+    user_agent: Rc<String>,
+    #[builder(setter(wrap = "Arc", strip_option), default)]
+    proxy: Option<Arc<String>>,
+}
+
+#[test]
+fn wraps_converted_values() {
+    let shared = Arc::new(Config { retries: 3 });
+    let client = ClientBuilder::default()
+        .config(3)
+        .user_agent("lorem")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        client,
+        Client {
+            config: shared,
+            user_agent: Rc::new("lorem".to_string()),
+            proxy: None,
+        }
+    );
+}
+
+#[test]
+fn builder_clones_share_the_wrapped_value() {
+    let mut builder = ClientBuilder::default();
+    builder
+        .config(Config::default())
+        .user_agent("lorem")
+        .proxy("http://proxy".to_string());
+
+    let a = builder.clone().build().unwrap();
+    let b = builder.build().unwrap();
+
+    assert!(Arc::ptr_eq(&a.config, &b.config));
+    assert!(Arc::ptr_eq(
+        a.proxy.as_ref().unwrap(),
+        b.proxy.as_ref().unwrap()
+    ));
+}
@@ -0,0 +1,44 @@
+#[macro_use]
+extern crate derive_builder;
+
+use std::convert::TryInto;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(split_impls, try_setter, build_fn(panic_variant))]
+#[builder_setters_impl_attr(allow(clippy::needless_lifetimes))]
+// Removes the generated `try_ipsum`, so it can be implemented by hand below.
+#[builder_try_setters_impl_attr(cfg(any()))]
+#[builder_build_impl_attr(doc(hidden))]
+struct Lorem {
+    ipsum: u32,
+    #[builder(default)]
+    dolor: Option<String>,
+}
+
+impl LoremBuilder {
+    fn try_ipsum(&mut self, value: i64) -> Result<&mut Self, String> {
+        let value: u32 = value.try_into().map_err(|_| "out of range".to_string())?;
+        Ok(self.ipsum(value))
+    }
+}
+
+#[test]
+fn split_impls() {
+    let lorem = LoremBuilder::default()
+        .try_ipsum(42)
+        .unwrap()
+        .dolor(Some("sit".to_string()))
+        .build_or_panic();
+
+    assert_eq!(
+        lorem,
+        Lorem {
+            ipsum: 42,
+            dolor: Some("sit".to_string()),
+        }
+    );
+    assert_eq!(
+        LoremBuilder::default().try_ipsum(-1).err(),
+        Some("out of range".to_string())
+    );
+}
@@ -0,0 +1,28 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(strict, setter(prefix = "with"))]
+struct Lorem {
+    ipsum: u32,
+    #[builder(setter(skip))]
+    dolor: u32,
+    #[builder(setter(skip), default = "42")]
+    sit: u32,
+}
+
+#[test]
+fn accepts_consistent_options() {
+    let lorem = LoremBuilder::default().with_ipsum(1).build().unwrap();
+
+    assert_eq!(
+        lorem,
+        Lorem {
+            ipsum: 1,
+            dolor: 0,
+            sit: 42,
+        }
+    );
+}
@@ -0,0 +1,54 @@
+#[macro_use]
+extern crate derive_builder;
+
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, Builder)]
+struct Event {
+    name: &'static str,
+    #[builder(default(now))]
+    created_at: SystemTime,
+    #[builder(default(now))]
+    started: Instant,
+    #[builder(default(since_epoch = "secs"))]
+    created_secs: u64,
+    #[builder(default(since_epoch = "millis"))]
+    created_millis: i64,
+    #[builder(default(since_epoch = "duration"))]
+    created_duration: Duration,
+}
+
+#[test]
+fn defaults_are_the_time_of_the_build() {
+    let builder = EventBuilder::default().name("lorem").clone();
+    let before = SystemTime::now();
+    let started = Instant::now();
+    let event = builder.build().unwrap();
+    let after = SystemTime::now();
+
+    assert_eq!(event.name, "lorem");
+    assert!(before <= event.created_at && event.created_at <= after);
+    assert!(started <= event.started);
+
+    let before = before.duration_since(UNIX_EPOCH).unwrap();
+    let after = after.duration_since(UNIX_EPOCH).unwrap();
+    assert!(before.as_secs() <= event.created_secs && event.created_secs <= after.as_secs());
+    assert!(
+        before.as_millis() as i64 <= event.created_millis
+            && event.created_millis <= after.as_millis() as i64
+    );
+    assert!(before <= event.created_duration && event.created_duration <= after);
+}
+
+#[test]
+fn set_values_take_precedence() {
+    let event = EventBuilder::default()
+        .name("lorem")
+        .created_at(UNIX_EPOCH)
+        .created_secs(42u64)
+        .build()
+        .unwrap();
+
+    assert_eq!(event.created_at, UNIX_EPOCH);
+    assert_eq!(event.created_secs, 42);
+}
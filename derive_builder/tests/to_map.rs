@@ -0,0 +1,88 @@
+#![cfg(feature = "json")]
+
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use serde_json::json;
+
+#[derive(Debug, Builder)]
+#[allow(dead_code)]
+#[builder(to_map, field_name_case = "kebab-case")]
+struct Server {
+    #[builder(setter(into))]
+    host_name: String,
+    #[builder(default = "8080")]
+    port: u16,
+    #[builder(default, setter(strip_option))]
+    tls: Option<bool>,
+    #[builder(default)]
+    tags: Vec<String>,
+}
+
+#[test]
+fn empty_builder() {
+    let map = ServerBuilder::default().to_map().unwrap();
+    assert!(map.is_empty());
+}
+
+#[test]
+fn only_set_fields() {
+    let map = ServerBuilder::default()
+        .host_name("localhost")
+        .tls(true)
+        .to_map()
+        .unwrap();
+
+    assert_eq!(
+        serde_json::Value::Object(map),
+        json!({
+            "host-name": "localhost",
+            "tls": true,
+        })
+    );
+}
+
+#[test]
+fn all_fields() {
+    let map = ServerBuilder::default()
+        .host_name("localhost")
+        .port(80)
+        .tls(false)
+        .tags(vec!["web".into()])
+        .to_map()
+        .unwrap();
+
+    assert_eq!(
+        serde_json::Value::Object(map),
+        json!({
+            "host-name": "localhost",
+            "port": 80,
+            "tls": false,
+            "tags": ["web"],
+        })
+    );
+}
+
+#[derive(Debug, Builder)]
+#[allow(dead_code)]
+#[builder(to_map, pattern = "owned")]
+struct Job<T> {
+    payload: T,
+    #[builder(field(ty = "u8", build = "self.retries"))]
+    retries: u8,
+}
+
+#[test]
+fn generic_struct_and_custom_field() {
+    let map = JobBuilder::default().payload("data").to_map().unwrap();
+
+    assert_eq!(
+        serde_json::Value::Object(map),
+        json!({
+            "payload": "data",
+            "retries": 0,
+        })
+    );
+}
@@ -0,0 +1,72 @@
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+mod units {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Seconds(pub u64);
+
+    pub fn minutes(n: u64) -> Seconds {
+        Seconds(n * 60)
+    }
+}
+
+mod net {
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Host(pub String);
+
+    impl Host {
+        pub fn localhost() -> Self {
+            Host("localhost".into())
+        }
+    }
+}
+
+mod config {
+    /// Unrelated type of the same name, which the imports of the builder must not clash with.
+    #[allow(dead_code)]
+    pub struct Host;
+
+    #[derive(Debug, PartialEq, Builder)]
+    #[builder(uses = "crate::units::{minutes, Seconds}, crate::net::Host as NetHost")]
+    pub struct Server {
+        #[builder(default = "Seconds(30)")]
+        pub timeout: crate::units::Seconds,
+        #[builder(default = "minutes(5)")]
+        pub idle: crate::units::Seconds,
+        #[builder(field(
+            ty = "Option<String>",
+            build = "self.host.clone().map_or_else(NetHost::localhost, NetHost)"
+        ))]
+        pub host: crate::net::Host,
+    }
+}
+
+use config::{Server, ServerBuilder};
+
+#[test]
+fn defaults_use_imports() {
+    let server = ServerBuilder::default().build().unwrap();
+
+    assert_eq!(
+        server,
+        Server {
+            timeout: units::Seconds(30),
+            idle: units::Seconds(300),
+            host: net::Host::localhost(),
+        }
+    );
+}
+
+#[test]
+fn field_build_uses_imports() {
+    let server = ServerBuilder::default()
+        .host(Some("example.com".into()))
+        .timeout(units::Seconds(1))
+        .build()
+        .unwrap();
+
+    assert_eq!(server.host, net::Host("example.com".into()));
+    assert_eq!(server.timeout, units::Seconds(1));
+}
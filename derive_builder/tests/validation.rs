@@ -77,3 +77,100 @@ fn validation_pass() {
         }
     );
 }
+
+#[test]
+fn error_variants() {
+    match LoremBuilder::default().my_effort(120).build() {
+        Err(LoremBuilderError::ValidationError(msg)) => {
+            assert_eq!(msg, "Don't wear yourself out")
+        }
+        other => panic!("expected a validation error, got {:?}", other),
+    }
+
+    match LoremBuilder::default().my_effort(90).build() {
+        Err(LoremBuilderError::UninitializedField(field)) => assert_eq!(field, "rivals_effort"),
+        other => panic!("expected an uninitialized field, got {:?}", other),
+    }
+}
+
+#[test]
+fn error_from_string() {
+    let err = LoremBuilderError::from("The game has changed".to_string());
+    assert!(matches!(err, LoremBuilderError::ValidationError(_)));
+
+    let err: Box<dyn std::error::Error> = Box::new(err);
+    assert_eq!(err.to_string(), "The game has changed");
+}
+
+/// Domain error of a validator, which the generated error is converted from.
+#[derive(Debug)]
+struct OutOfRange(u8);
+
+#[derive(Debug, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+struct Ipsum {
+    #[allow(dead_code)]
+    percent: u8,
+}
+
+impl IpsumBuilder {
+    fn validate(&self) -> Result<(), OutOfRange> {
+        match self.percent {
+            Some(percent) if percent > 100 => Err(OutOfRange(percent)),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl From<OutOfRange> for IpsumBuilderError {
+    fn from(e: OutOfRange) -> Self {
+        Self::ValidationError(format!("{} is not a percentage", e.0))
+    }
+}
+
+#[test]
+fn validator_error_converted_into_generated_error() {
+    let err = IpsumBuilder::default().percent(120).build().unwrap_err();
+
+    assert_eq!(err.to_string(), "120 is not a percentage");
+}
+
+#[derive(Debug, Builder)]
+#[builder(build_fn(validate = "check_not_empty", validate = "check_ascii"))]
+struct Dolor {
+    #[allow(dead_code)]
+    name: String,
+}
+
+fn check_not_empty(builder: &DolorBuilder) -> Result<(), String> {
+    match builder.name {
+        Some(ref name) if name.is_empty() => Err("name is empty".into()),
+        _ => Ok(()),
+    }
+}
+
+fn check_ascii(builder: &DolorBuilder) -> Result<(), String> {
+    match builder.name {
+        Some(ref name) if !name.is_ascii() => Err("name is not ASCII".into()),
+        // Only reached if `check_not_empty` passed.
+        Some(ref name) if name.is_empty() => unreachable!(),
+        _ => Ok(()),
+    }
+}
+
+#[test]
+fn multiple_validators_run_in_order() {
+    let err = DolorBuilder::default()
+        .name(String::new())
+        .build()
+        .unwrap_err();
+    assert_eq!(err.to_string(), "name is empty");
+
+    let err = DolorBuilder::default()
+        .name("dolör".into())
+        .build()
+        .unwrap_err();
+    assert_eq!(err.to_string(), "name is not ASCII");
+
+    assert!(DolorBuilder::default().name("dolor".into()).build().is_ok());
+}
@@ -0,0 +1,243 @@
+// `const` initializers of thread locals need a newer compiler than the MSRV.
+#![allow(clippy::missing_const_for_thread_local)]
+
+#[macro_use]
+extern crate pretty_assertions;
+#[macro_use]
+extern crate derive_builder;
+
+use std::cell::RefCell;
+
+thread_local! {
+    static STAGES: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+}
+
+fn record(stage: &'static str) {
+    STAGES.with(|stages| stages.borrow_mut().push(stage));
+}
+
+fn take_stages() -> Vec<&'static str> {
+    STAGES.with(|stages| stages.borrow_mut().drain(..).collect())
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(
+    validate = "Self::validate",
+    post_build = "Lorem::post_build",
+    fallback
+))]
+struct Lorem {
+    #[builder(validate = "validate_ipsum")]
+    ipsum: u32,
+    #[builder(validate = "validate_dolor", default, requires = "ipsum")]
+    dolor: u32,
+    #[builder(
+        validate = "validate_sit",
+        field(ty = "Option<u32>", build = "self.sit.unwrap_or(3)")
+    )]
+    sit: u32,
+}
+
+fn validate_ipsum(value: &u32) -> Result<(), String> {
+    record("ipsum");
+    if *value == 0 {
+        return Err("ipsum must not be 0".into());
+    }
+    Ok(())
+}
+
+fn validate_dolor(_: &u32) -> Result<(), String> {
+    record("dolor");
+    Ok(())
+}
+
+fn validate_sit(value: &Option<u32>) -> Result<(), String> {
+    record("sit");
+    if *value == Some(0) {
+        return Err("sit must not be 0".into());
+    }
+    Ok(())
+}
+
+impl LoremBuilder {
+    fn validate(&self) -> Result<(), String> {
+        record("validate");
+        Ok(())
+    }
+}
+
+impl Lorem {
+    fn post_build(&self) -> Result<(), String> {
+        record("post_build");
+        if self.ipsum + self.dolor > 100 {
+            return Err("ipsum and dolor must not exceed 100".into());
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn stages_run_in_order() {
+    take_stages();
+    let lorem = LoremBuilder::default().ipsum(1).dolor(2).build().unwrap();
+
+    assert_eq!(
+        lorem,
+        Lorem {
+            ipsum: 1,
+            dolor: 2,
+            sit: 3
+        }
+    );
+    assert_eq!(
+        take_stages(),
+        vec!["ipsum", "dolor", "sit", "validate", "post_build"]
+    );
+}
+
+#[test]
+fn unset_fields_are_not_validated() {
+    take_stages();
+    LoremBuilder::default().ipsum(1).build().unwrap();
+
+    assert_eq!(
+        take_stages(),
+        vec!["ipsum", "sit", "validate", "post_build"]
+    );
+}
+
+#[test]
+fn field_validation_fails_first() {
+    take_stages();
+    let err = LoremBuilder::default()
+        .ipsum(0)
+        .dolor(2)
+        .build()
+        .unwrap_err();
+
+    assert_eq!(&err.to_string(), "ipsum must not be 0");
+    assert_eq!(take_stages(), vec!["ipsum"]);
+}
+
+#[test]
+fn field_validation_before_dependencies() {
+    take_stages();
+    let err = LoremBuilder::default()
+        .dolor(2)
+        .sit(Some(0))
+        .build()
+        .unwrap_err();
+
+    assert_eq!(&err.to_string(), "sit must not be 0");
+
+    take_stages();
+    let err = LoremBuilder::default().dolor(2).build().unwrap_err();
+
+    assert_eq!(&err.to_string(), "`dolor` requires `ipsum` to be set");
+    assert_eq!(take_stages(), vec!["dolor", "sit"]);
+}
+
+#[test]
+fn post_build_fails() {
+    take_stages();
+    let err = LoremBuilder::default()
+        .ipsum(60)
+        .dolor(60)
+        .build()
+        .unwrap_err();
+
+    assert_eq!(&err.to_string(), "ipsum and dolor must not exceed 100");
+    assert_eq!(
+        take_stages(),
+        vec!["ipsum", "dolor", "sit", "validate", "post_build"]
+    );
+}
+
+#[test]
+fn fallback_runs_stages() {
+    let base = Lorem {
+        ipsum: 90,
+        dolor: 0,
+        sit: 0,
+    };
+
+    take_stages();
+    let err = LoremBuilder::default()
+        .dolor(20)
+        .build_with_fallback(&base)
+        .unwrap_err();
+
+    assert_eq!(&err.to_string(), "`dolor` requires `ipsum` to be set");
+    assert_eq!(take_stages(), vec!["dolor", "sit"]);
+
+    let err = LoremBuilder::default()
+        .ipsum(90)
+        .dolor(20)
+        .build_with_fallback(&base)
+        .unwrap_err();
+
+    assert_eq!(&err.to_string(), "ipsum and dolor must not exceed 100");
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(post_build(path = "Amet::sort", arg = "mut")))]
+struct Amet {
+    values: Vec<u32>,
+}
+
+impl Amet {
+    fn sort(&mut self) -> Result<(), String> {
+        if self.values.is_empty() {
+            return Err("values must not be empty".into());
+        }
+        self.values.sort_unstable();
+        Ok(())
+    }
+}
+
+#[test]
+fn post_build_mut() {
+    let amet = AmetBuilder::default()
+        .values(vec![3, 1, 2])
+        .build()
+        .unwrap();
+    assert_eq!(amet.values, vec![1, 2, 3]);
+
+    let err = AmetBuilder::default().values(vec![]).build().unwrap_err();
+    assert_eq!(&err.to_string(), "values must not be empty");
+}
+
+#[derive(Debug, PartialEq, Builder)]
+#[builder(build_fn(post_build(path = "Consectetur::finish", arg = "owned")))]
+struct Consectetur {
+    total: u32,
+    #[builder(setter(skip))]
+    average: u32,
+    count: u32,
+}
+
+impl Consectetur {
+    fn finish(self) -> Result<Self, String> {
+        match self.total.checked_div(self.count) {
+            Some(average) => Ok(Self { average, ..self }),
+            None => Err("count must not be 0".into()),
+        }
+    }
+}
+
+#[test]
+fn post_build_owned() {
+    let consectetur = ConsecteturBuilder::default()
+        .total(12)
+        .count(4)
+        .build()
+        .unwrap();
+    assert_eq!(consectetur.average, 3);
+
+    let err = ConsecteturBuilder::default()
+        .total(12)
+        .count(0)
+        .build()
+        .unwrap_err();
+    assert_eq!(&err.to_string(), "count must not be 0");
+}
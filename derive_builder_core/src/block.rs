@@ -1,6 +1,6 @@
 use std::convert::TryFrom;
 
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
 use quote::ToTokens;
 use syn::{spanned::Spanned, Block, LitStr};
 
@@ -13,10 +13,12 @@ use syn::{spanned::Spanned, Block, LitStr};
 pub struct BlockContents(Block);
 
 impl BlockContents {
+    /// Returns `true` if the block contains no statements.
     pub fn is_empty(&self) -> bool {
         self.0.stmts.is_empty()
     }
 
+    /// The span of the block.
     pub fn span(&self) -> Span {
         self.0.span()
     }
@@ -63,11 +65,44 @@ impl darling::FromMeta for BlockContents {
     }
 
     fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
-        if let syn::Expr::Lit(lit) = expr {
-            Self::from_value(&lit.lit)
-        } else {
-            Ok(Self::from(expr.clone()))
+        match expr {
+            syn::Expr::Lit(lit) => Self::from_value(&lit.lit),
+            // `macro_rules!` wraps `$e:expr` fragments in invisible delimiters
+            syn::Expr::Group(group) => Self::from_expr(&group.expr),
+            _ => Ok(Self::from(expr.clone())),
+        }
+        .map(Self::with_call_site_self)
+    }
+}
+
+impl BlockContents {
+    /// Make `self` in the block refer to the receiver of the generated method.
+    ///
+    /// `self` is hygienic, so a `self` token written outside of a `macro_rules!` macro which
+    /// declares the input struct won't resolve to the `self` parameter of the generated `build`
+    /// method. Resolving it at the call site fixes that, while the original span is kept for
+    /// error reporting.
+    fn with_call_site_self(self) -> Self {
+        fn respan(tokens: TokenStream) -> TokenStream {
+            tokens
+                .into_iter()
+                .map(|token| match token {
+                    TokenTree::Group(group) => {
+                        let mut new_group = Group::new(group.delimiter(), respan(group.stream()));
+                        new_group.set_span(group.span());
+                        TokenTree::Group(new_group)
+                    }
+                    TokenTree::Ident(mut ident) if ident == "self" => {
+                        ident.set_span(ident.span().resolved_at(Span::call_site()));
+                        TokenTree::Ident(ident)
+                    }
+                    other => other,
+                })
+                .collect()
         }
+
+        let tokens = respan(self.0.into_token_stream());
+        Self(syn::parse2(tokens).expect("Changing spans keeps the block valid"))
     }
 }
 
@@ -102,6 +137,20 @@ mod test {
         );
     }
 
+    #[test]
+    fn block_from_grouped_expr() {
+        let expr: syn::Expr = syn::Expr::Group(syn::ExprGroup {
+            attrs: vec![],
+            group_token: Default::default(),
+            expr: Box::new(parse_quote!("self.foo()")),
+        });
+        let block = <BlockContents as darling::FromMeta>::from_expr(&expr).unwrap();
+        assert_eq!(
+            quote!(#block).to_string(),
+            quote!({ self.foo() }).to_string()
+        );
+    }
+
     #[test]
     fn block_single_token_tree() {
         let expr = parse("42").unwrap();
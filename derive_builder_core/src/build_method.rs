@@ -5,7 +5,8 @@ use quote::{ToTokens, TokenStreamExt};
 use syn::spanned::Spanned;
 
 use crate::{
-    doc_comment_from, BuilderPattern, DefaultExpression, Initializer, DEFAULT_STRUCT_NAME,
+    doc_comment_from, BuilderPattern, DefaultExpression, FieldDependency, FieldValidation,
+    Initializer, PostBuildArg, DEFAULT_STRUCT_NAME, DEFERRED_ERROR_FIELD,
 };
 
 /// Initializer for the struct fields in the build method, implementing
@@ -36,6 +37,7 @@ use crate::{
 /// # }
 /// ```
 #[derive(Debug)]
+#[non_exhaustive]
 pub struct BuildMethod<'a> {
     /// Path to the root of the derive_builder crate.
     pub crate_root: &'a syn::Path,
@@ -55,17 +57,69 @@ pub struct BuildMethod<'a> {
     pub target_ty_generics: Option<syn::TypeGenerics<'a>>,
     /// Type of error.
     pub error_ty: syn::Path,
+    /// Type which the built struct is converted into with `Into`, and returned instead.
+    ///
+    /// The `const fn` and `into_target` methods ignore it.
+    pub into_ty: Option<&'a syn::Type>,
     /// Field initializers for the target type.
     pub initializers: Vec<TokenStream>,
     /// Doc-comment of the builder struct.
     pub doc_comment: Option<syn::Attribute>,
+    /// `#[deprecated]` attribute of a deprecated builder.
+    pub deprecated: Option<&'a syn::Attribute>,
     /// Default value for the whole struct.
     ///
     /// This will be in scope for all initializers as `__default`.
     pub default_struct: Option<&'a DefaultExpression>,
-    /// Validation function with signature `&FooBuilder -> Result<(), String>`
-    /// to call before the macro-provided struct buildout.
-    pub validate_fn: Option<&'a syn::Path>,
+    /// Function with signature `&mut FooBuilder -> ()` which normalizes a copy of the builder
+    /// before it is validated and built, e.g. by trimming strings.
+    pub normalize_fn: Option<&'a syn::Path>,
+    /// Validation functions with signature `&FooBuilder -> Result<(), E>` to call in order
+    /// before the macro-provided struct buildout.
+    pub validate_fns: &'a [syn::Path],
+    /// Return the error stored by a `setter(defer_err)`, if any, before all validations.
+    pub deferred_error: bool,
+    /// Validations of single fields, which run first.
+    pub field_validations: Vec<TokenStream>,
+    /// Checks of `requires` and `conflicts_with` dependencies between fields, which run after
+    /// the validations of single fields and before `validate_fns`.
+    pub dependency_checks: Vec<TokenStream>,
+    /// Initializers which bind the values of fields to local variables before the struct is
+    /// built, in the order in which they run, e.g. `let foo = ...;`.
+    pub bindings: Vec<TokenStream>,
+    /// Collect all uninitialized fields before failing the build, instead of failing at the
+    /// first one. The error type must implement `From<UninitializedFieldsError>`.
+    pub collect_errors: bool,
+    /// Checks which record uninitialized fields, if `collect_errors` is set.
+    pub missing_field_checks: Vec<TokenStream>,
+    /// Function to call with the built struct, before it is returned.
+    pub post_build_fn: Option<&'a syn::Path>,
+    /// How `post_build_fn` receives the built struct, i.e. `&Foo -> Result<(), E>`,
+    /// `&mut Foo -> Result<(), E>` or `Foo -> Result<Foo, E>`.
+    pub post_build_arg: PostBuildArg,
+    /// Emit a `const fn` named `#{ident}_const`, which takes the builder by value and returns
+    /// the target type directly. Its initializers must panic instead of returning errors.
+    pub const_fn: bool,
+    /// Emit a method named `#{ident}_with_fallback`, which takes an instance of the target type
+    /// whose values are used for unset fields. Its initializers must use `fallback` instead of
+    /// defaults.
+    pub fallback: bool,
+    /// Emit a method named `#{ident}_into`, which assigns the fields of an existing `target`
+    /// instance of the target type instead of returning a new one. Its initializers are
+    /// assignments, which run after all checks, so `target` is unchanged if the build fails.
+    pub into_target: bool,
+    /// Emit a method named `#{ident}_all_errors`, which runs all checks and returns the errors
+    /// of every failing one in a `Vec`, instead of failing at the first one.
+    pub all_errors: bool,
+    /// Checks which fail the build if a required field is not set, for `into_target` and
+    /// `all_errors`.
+    pub uninitialized_checks: Vec<TokenStream>,
+    /// Whether the target struct is `#[repr(packed)]`, which is passed on to the initializers so
+    /// they don't borrow fields of the struct.
+    pub packed: bool,
+    /// Return the builder together with the error if an owned build method fails. The values
+    /// of the fields are cloned, so that the builder is kept until the build succeeds.
+    pub return_builder: bool,
 }
 
 impl<'a> ToTokens for BuildMethod<'a> {
@@ -74,42 +128,346 @@ impl<'a> ToTokens for BuildMethod<'a> {
         let vis = &self.visibility;
         let target_ty = &self.target_ty;
         let target_ty_generics = &self.target_ty_generics;
+        let error_ty = &self.error_ty;
         let initializers = &self.initializers;
-        let self_param = match self.pattern {
-            BuilderPattern::Owned => quote!(self),
-            BuilderPattern::Mutable | BuilderPattern::Immutable => quote!(&self),
-        };
         let doc_comment = &self.doc_comment;
+        let deprecated = self.deprecated;
         let default_struct = self.default_struct.as_ref().map(|default_expr| {
             let default_expr = default_expr.with_crate_root(self.crate_root);
             let ident = syn::Ident::new(DEFAULT_STRUCT_NAME, Span::call_site());
             quote!(let #ident: #target_ty #target_ty_generics = #default_expr;)
         });
-        let validate_fn = self
-            .validate_fn
-            .as_ref()
+        let validate_fns = self
+            .validate_fns
+            .iter()
             .map(|vfn| quote_spanned!(vfn.span() => #vfn(&self)?;));
-        let error_ty = &self.error_ty;
-
-        if self.enabled {
+        let deferred_error = if self.deferred_error {
             let crate_root = &self.crate_root;
+            let field = syn::Ident::new(DEFERRED_ERROR_FIELD, Span::call_site());
+            Some(quote!(
+                if let #crate_root::export::core::option::Option::Some(ref error) = self.#field {
+                    return #crate_root::export::core::result::Result::Err(
+                        #crate_root::export::core::clone::Clone::clone(error)
+                    );
+                }
+            ))
+        } else {
+            None
+        };
+        let field_validations = &self.field_validations;
+        let uninitialized_checks = &self.uninitialized_checks;
+        let dependency_checks = &self.dependency_checks;
+        let bindings = &self.bindings;
+        let missing_fields = if self.missing_field_checks.is_empty() {
+            None
+        } else {
+            let crate_root = &self.crate_root;
+            let missing_field_checks = &self.missing_field_checks;
+            Some(quote!(
+                let mut __missing = #crate_root::export::core::vec::Vec::new();
+                #(#missing_field_checks)*
+                if !__missing.is_empty() {
+                    return #crate_root::export::core::result::Result::Err(
+                        #crate_root::export::core::convert::Into::into(
+                            #crate_root::UninitializedFieldsError::new(__missing)
+                        )
+                    );
+                }
+            ))
+        };
+        let ok = |built: TokenStream| match self.into_ty {
+            Some(_) => {
+                let crate_root = &self.crate_root;
+                quote!(Ok(#crate_root::export::core::convert::Into::into(#built)))
+            }
+            None => quote!(Ok(#built)),
+        };
+        let build = match self.post_build_fn {
+            Some(pbfn) if self.post_build_arg == PostBuildArg::Owned => {
+                let post_build = ok(quote_spanned!(pbfn.span() => #pbfn(built)?));
+                quote!(
+                    let built = #target_ty {
+                        #(#initializers)*
+                    };
+                    #post_build
+                )
+            }
+            Some(pbfn) => {
+                let (binding, arg) = match self.post_build_arg {
+                    PostBuildArg::Mut => (quote!(let mut built), quote!(&mut built)),
+                    _ => (quote!(let built), quote!(&built)),
+                };
+                let post_build = quote_spanned!(pbfn.span() => #pbfn(#arg)?;);
+                let ok = ok(quote!(built));
+                quote!(
+                    #binding = #target_ty {
+                        #(#initializers)*
+                    };
+                    #post_build
+                    #ok
+                )
+            }
+            None => ok(quote!(#target_ty {
+                #(#initializers)*
+            })),
+        };
+        let ok_ty = match self.into_ty {
+            Some(into_ty) => quote!(#into_ty),
+            None => quote!(#target_ty #target_ty_generics),
+        };
+
+        if self.enabled && self.const_fn {
+            let ident = format_ident!("{}_const", ident);
             tokens.append_all(quote!(
                 #doc_comment
-                #vis fn #ident(#self_param)
-                    -> #crate_root::export::core::result::Result<#target_ty #target_ty_generics, #error_ty>
-                {
-                    #validate_fn
+                #deprecated
+                #vis const fn #ident(self) -> #target_ty #target_ty_generics {
+                    #(#dependency_checks)*
                     #default_struct
-                    Ok(#target_ty {
+                    #(#bindings)*
+                    #target_ty {
                         #(#initializers)*
-                    })
+                    }
                 }
             ))
+        } else if self.enabled && self.fallback {
+            let ident = format_ident!("{}_with_fallback", ident);
+            self.append_build_fn(
+                tokens,
+                &ident,
+                quote!(, fallback: &#target_ty #target_ty_generics),
+                quote!(fallback),
+                ok_ty,
+                quote!(
+                    #deferred_error
+                    #(#field_validations)*
+                    #(#dependency_checks)*
+                    #(#validate_fns)*
+                    #(#bindings)*
+                    #build
+                ),
+            );
+        } else if self.enabled && self.into_target {
+            let ident = format_ident!("{}_into", ident);
+            self.append_build_fn(
+                tokens,
+                &ident,
+                quote!(, target: &mut #target_ty #target_ty_generics),
+                quote!(target),
+                quote!(()),
+                quote!(
+                    #deferred_error
+                    #(#field_validations)*
+                    #(#dependency_checks)*
+                    #(#validate_fns)*
+                    #default_struct
+                    #missing_fields
+                    #(#uninitialized_checks)*
+                    #(#bindings)*
+                    #(#initializers)*
+                    Ok(())
+                ),
+            );
+        } else if self.enabled && self.all_errors {
+            let crate_root = &self.crate_root;
+            let core = quote!(#crate_root::export::core);
+            let ident = format_ident!("{}_all_errors", ident);
+            // Each check runs in a closure of its own, so its `?` and `return` only end the check.
+            let checks = deferred_error
+                .iter()
+                .chain(field_validations)
+                .chain(dependency_checks)
+                .map(|check| quote!(#check))
+                .chain(validate_fns)
+                .chain(missing_fields)
+                .chain(uninitialized_checks.iter().cloned())
+                .map(|check| {
+                    quote!(
+                        let __check = || -> #core::result::Result<(), #error_ty> {
+                            #check
+                            #core::result::Result::Ok(())
+                        };
+                        if let #core::result::Result::Err(error) = __check() {
+                            __errors.push(error);
+                        }
+                    )
+                });
+            self.append_build_fn(
+                tokens,
+                &ident,
+                quote!(),
+                quote!(),
+                ok_ty.clone(),
+                quote!(
+                    let mut __errors = #core::vec::Vec::new();
+                    #(#checks)*
+                    if !__errors.is_empty() {
+                        return #core::result::Result::Err(__errors);
+                    }
+                    let __build = || -> #core::result::Result<#ok_ty, #error_ty> {
+                        #default_struct
+                        #(#bindings)*
+                        #build
+                    };
+                    #core::result::Result::map_err(__build(), |error| #core::vec::Vec::from([error]))
+                ),
+            );
+        } else if self.enabled {
+            self.append_build_fn(
+                tokens,
+                ident,
+                quote!(),
+                quote!(),
+                ok_ty,
+                quote!(
+                    #deferred_error
+                    #(#field_validations)*
+                    #(#dependency_checks)*
+                    #(#validate_fns)*
+                    #default_struct
+                    #missing_fields
+                    #(#bindings)*
+                    #build
+                ),
+            );
         }
     }
 }
 
 impl<'a> BuildMethod<'a> {
+    /// Append a build method with the given parameters after `self`, which are passed on as
+    /// `args`, and `body`, which returns `ok_ty` or the error type, or a `Vec` of errors for
+    /// `all_errors`.
+    ///
+    /// With a `normalize_fn`, the method normalizes a copy of the builder, and builds it with a
+    /// hidden method which has the body instead.
+    fn append_build_fn(
+        &self,
+        tokens: &mut TokenStream,
+        ident: &syn::Ident,
+        params: TokenStream,
+        args: TokenStream,
+        ok_ty: TokenStream,
+        body: TokenStream,
+    ) {
+        let crate_root = &self.crate_root;
+        let error_ty = &self.error_ty;
+        let error_ty = if self.all_errors {
+            quote!(#crate_root::export::core::vec::Vec<#error_ty>)
+        } else {
+            quote!(#error_ty)
+        };
+        let ret = quote!(#crate_root::export::core::result::Result<#ok_ty, #error_ty>);
+        let vis = &self.visibility;
+        let doc_comment = &self.doc_comment;
+        let deprecated = self.deprecated;
+        let borrowed = format_ident!("__{}_borrowed", ident);
+        // An owned build method which returns the builder builds it with a hidden method which
+        // borrows it instead.
+        let (attrs, vis, ident) = if self.returns_builder() {
+            let result = quote!(#crate_root::export::core::result::Result);
+            tokens.append_all(quote!(
+                #doc_comment
+                #deprecated
+                #vis fn #ident(self #params)
+                    -> #result<#ok_ty, (Self, #error_ty)>
+                {
+                    match self.#borrowed(#args) {
+                        #result::Ok(built) => #result::Ok(built),
+                        #result::Err(error) => #result::Err((self, error)),
+                    }
+                }
+            ));
+            (quote!(#[doc(hidden)]), None, &borrowed)
+        } else {
+            (quote!(#doc_comment #deprecated), Some(vis), ident)
+        };
+        let self_param = match self.pattern {
+            BuilderPattern::Owned if !self.returns_builder() => quote!(self),
+            _ => quote!(&self),
+        };
+        let nfn = match self.normalize_fn {
+            Some(nfn) => nfn,
+            None => {
+                tokens.append_all(quote!(
+                    #attrs
+                    #vis fn #ident(#self_param #params) -> #ret {
+                        #body
+                    }
+                ));
+                return;
+            }
+        };
+
+        let unnormalized = format_ident!("__{}_unnormalized", ident);
+        let normalized = match self.pattern {
+            BuilderPattern::Owned if !self.returns_builder() => quote!(self),
+            _ => quote!(#crate_root::export::core::clone::Clone::clone(self)),
+        };
+        let normalize = quote_spanned!(nfn.span() => #nfn(&mut normalized););
+        tokens.append_all(quote!(
+            #attrs
+            #vis fn #ident(#self_param #params) -> #ret {
+                let mut normalized = #normalized;
+                #normalize
+                normalized.#unnormalized(#args)
+            }
+
+            #[doc(hidden)]
+            fn #unnormalized(#self_param #params) -> #ret {
+                #body
+            }
+        ));
+    }
+
+    /// Check if the build method takes the builder by value and returns it with the error.
+    fn returns_builder(&self) -> bool {
+        self.return_builder && self.pattern == BuilderPattern::Owned && !self.const_fn
+    }
+
+    /// Create an enabled build method named `ident`, which returns `target_ty` or `error_ty`.
+    ///
+    /// The method has no initializers yet; they are added with `push_initializer`.
+    pub fn new(
+        crate_root: &'a syn::Path,
+        ident: &'a syn::Ident,
+        target_ty: &'a syn::Ident,
+        error_ty: syn::Path,
+    ) -> Self {
+        BuildMethod {
+            crate_root,
+            enabled: true,
+            ident,
+            visibility: Cow::Owned(parse_quote!(pub)),
+            pattern: BuilderPattern::Mutable,
+            target_ty,
+            target_ty_generics: None,
+            error_ty,
+            into_ty: None,
+            initializers: vec![],
+            doc_comment: None,
+            deprecated: None,
+            default_struct: None,
+            normalize_fn: None,
+            validate_fns: &[],
+            deferred_error: false,
+            field_validations: vec![],
+            dependency_checks: vec![],
+            bindings: vec![],
+            collect_errors: false,
+            missing_field_checks: vec![],
+            post_build_fn: None,
+            post_build_arg: PostBuildArg::Ref,
+            const_fn: false,
+            fallback: false,
+            into_target: false,
+            all_errors: false,
+            uninitialized_checks: vec![],
+            packed: false,
+            return_builder: false,
+        }
+    }
+
     /// Set a doc-comment for this item.
     pub fn doc_comment(&mut self, s: String) -> &mut Self {
         self.doc_comment = Some(doc_comment_from(s));
@@ -121,8 +479,52 @@ impl<'a> BuildMethod<'a> {
     ///
     /// For each struct field this must be called with the appropriate
     /// initializer.
-    pub fn push_initializer(&mut self, init: Initializer) -> &mut Self {
-        self.initializers.push(quote!(#init));
+    pub fn push_initializer(&mut self, mut init: Initializer) -> &mut Self {
+        init.packed |= self.packed;
+        if self.returns_builder() {
+            init.builder_pattern = BuilderPattern::Mutable;
+        }
+        if self.collect_errors {
+            self.missing_field_checks.extend(init.missing_field_check());
+        }
+        if self.all_errors {
+            self.uninitialized_checks.extend(init.uninitialized_check());
+        }
+        if self.into_target {
+            init.reuse_target = true;
+            self.uninitialized_checks.extend(init.uninitialized_check());
+            if !init.reuses_target_value() {
+                let field_ident = init.field_ident;
+                init.bind_local = true;
+                self.bindings.push(quote!(#init));
+                self.initializers
+                    .push(quote!(target.#field_ident = #field_ident;));
+            } else {
+                self.initializers.push(quote!(#init));
+            }
+            return self;
+        }
+        if init.bind_local {
+            let field_ident = init.field_ident;
+            self.bindings.push(quote!(#init));
+            self.initializers.push(quote!(#field_ident,));
+        } else {
+            self.initializers.push(quote!(#init));
+        }
+        self
+    }
+
+    /// Add a validation of a single field, which fails the build if the field's `validate`
+    /// function returns an error.
+    pub fn push_field_validation(&mut self, validation: FieldValidation) -> &mut Self {
+        self.field_validations.push(quote!(#validation));
+        self
+    }
+
+    /// Add a check of a dependency between two fields, which fails the build if the
+    /// dependency is not satisfied.
+    pub fn push_dependency_check(&mut self, dependency: FieldDependency) -> &mut Self {
+        self.dependency_checks.push(quote!(#dependency));
         self
     }
 }
@@ -149,10 +551,28 @@ macro_rules! default_build_method {
             target_ty: &syn::Ident::new("Foo", ::proc_macro2::Span::call_site()),
             target_ty_generics: None,
             error_ty: syn::parse_quote!(FooBuilderError),
+            into_ty: None,
             initializers: vec![quote!(foo: self.foo,)],
             doc_comment: None,
+            deprecated: None,
             default_struct: None,
-            validate_fn: None,
+            normalize_fn: None,
+            validate_fns: &[],
+            deferred_error: false,
+            field_validations: vec![],
+            dependency_checks: vec![],
+            bindings: vec![],
+            collect_errors: false,
+            missing_field_checks: vec![],
+            post_build_fn: None,
+            post_build_arg: PostBuildArg::Ref,
+            const_fn: false,
+            fallback: false,
+            into_target: false,
+            all_errors: false,
+            uninitialized_checks: vec![],
+            packed: false,
+            return_builder: false,
         }
     };
 }
@@ -202,6 +622,76 @@ mod tests {
         );
     }
 
+    #[test]
+    fn deprecated() {
+        let deprecated: syn::Attribute = parse_quote!(#[deprecated(note = "use BarBuilder")]);
+        let mut build_method = default_build_method!();
+        build_method.deprecated = Some(&deprecated);
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                #[deprecated(note = "use BarBuilder")]
+                pub fn build(&self) -> ::db::export::core::result::Result<Foo, FooBuilderError> {
+                    Ok(Foo {
+                        foo: self.foo,
+                    })
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn collect_errors() {
+        let mut build_method = default_build_method!();
+        build_method.missing_field_checks = vec![quote!(check_foo();)];
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub fn build(&self) -> ::db::export::core::result::Result<Foo, FooBuilderError> {
+                    let mut __missing = ::db::export::core::vec::Vec::new();
+                    check_foo();
+                    if !__missing.is_empty() {
+                        return ::db::export::core::result::Result::Err(
+                            ::db::export::core::convert::Into::into(
+                                ::db::UninitializedFieldsError::new(__missing)
+                            )
+                        );
+                    }
+                    Ok(Foo {
+                        foo: self.foo,
+                    })
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn bindings() {
+        let mut build_method = default_build_method!();
+        build_method.bindings = vec![quote!(let foo = self.foo;)];
+        build_method.initializers = vec![quote!(foo,)];
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub fn build(&self) -> ::db::export::core::result::Result<Foo, FooBuilderError> {
+                    let foo = self.foo;
+                    Ok(Foo {
+                        foo,
+                    })
+                }
+            )
+            .to_string()
+        );
+    }
+
     #[test]
     fn skip() {
         let mut build_method = default_build_method!();
@@ -231,12 +721,337 @@ mod tests {
         );
     }
 
+    #[test]
+    fn const_fn() {
+        let mut build_method: BuildMethod = default_build_method!();
+        build_method.const_fn = true;
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub const fn build_const(self) -> Foo {
+                    Foo {
+                        foo: self.foo,
+                    }
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn fallback() {
+        let mut build_method: BuildMethod = default_build_method!();
+        build_method.fallback = true;
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub fn build_with_fallback(&self, fallback: &Foo) -> ::db::export::core::result::Result<Foo, FooBuilderError> {
+                    Ok(Foo {
+                        foo: self.foo,
+                    })
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn into_ty() {
+        let into_ty: syn::Type = parse_quote!(PublicFoo);
+        let mut build_method: BuildMethod = default_build_method!();
+        build_method.into_ty = Some(&into_ty);
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub fn build(&self) -> ::db::export::core::result::Result<PublicFoo, FooBuilderError> {
+                    Ok(::db::export::core::convert::Into::into(Foo {
+                        foo: self.foo,
+                    }))
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn into_target() {
+        let mut build_method: BuildMethod = default_build_method!();
+        build_method.into_target = true;
+        build_method.initializers = vec![quote!(target.foo = foo;)];
+        build_method.uninitialized_checks = vec![quote!(check_foo();)];
+        build_method.bindings = vec![quote!(let foo = self.foo;)];
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub fn build_into(&self, target: &mut Foo) -> ::db::export::core::result::Result<(), FooBuilderError> {
+                    check_foo();
+                    let foo = self.foo;
+                    target.foo = foo;
+                    Ok(())
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn all_errors() {
+        let mut build_method: BuildMethod = default_build_method!();
+        build_method.all_errors = true;
+        build_method.uninitialized_checks = vec![quote!(check_foo();)];
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub fn build_all_errors(&self) -> ::db::export::core::result::Result<Foo, ::db::export::core::vec::Vec<FooBuilderError> > {
+                    let mut __errors = ::db::export::core::vec::Vec::new();
+                    let __check = || -> ::db::export::core::result::Result<(), FooBuilderError> {
+                        check_foo();
+                        ::db::export::core::result::Result::Ok(())
+                    };
+                    if let ::db::export::core::result::Result::Err(error) = __check() {
+                        __errors.push(error);
+                    }
+                    if !__errors.is_empty() {
+                        return ::db::export::core::result::Result::Err(__errors);
+                    }
+                    let __build = || -> ::db::export::core::result::Result<Foo, FooBuilderError> {
+                        Ok(Foo {
+                            foo: self.foo,
+                        })
+                    };
+                    ::db::export::core::result::Result::map_err(__build(), |error| ::db::export::core::vec::Vec::from([error]))
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn dependency_checks() {
+        let validate_paths: [syn::Path; 1] = [parse_quote!(IpsumBuilder::validate)];
+
+        let mut build_method: BuildMethod = default_build_method!();
+        build_method.validate_fns = &validate_paths;
+        build_method.dependency_checks = vec![quote!(check_foo();)];
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub fn build(&self) -> ::db::export::core::result::Result<Foo, FooBuilderError> {
+                    check_foo();
+                    IpsumBuilder::validate(&self)?;
+
+                    Ok(Foo {
+                        foo: self.foo,
+                    })
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn stages() {
+        let validate_paths: [syn::Path; 1] = [parse_quote!(IpsumBuilder::validate)];
+        let post_build_path: syn::Path = parse_quote!(check_foo);
+
+        let mut build_method: BuildMethod = default_build_method!();
+        build_method.validate_fns = &validate_paths;
+        build_method.field_validations = vec![quote!(validate_foo(&self.foo)?;)];
+        build_method.dependency_checks = vec![quote!(check_dependencies();)];
+        build_method.post_build_fn = Some(&post_build_path);
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub fn build(&self) -> ::db::export::core::result::Result<Foo, FooBuilderError> {
+                    validate_foo(&self.foo)?;
+                    check_dependencies();
+                    IpsumBuilder::validate(&self)?;
+
+                    let built = Foo {
+                        foo: self.foo,
+                    };
+                    check_foo(&built)?;
+                    Ok(built)
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn post_build_mut() {
+        let post_build_path: syn::Path = parse_quote!(fix_foo);
+
+        let mut build_method: BuildMethod = default_build_method!();
+        build_method.post_build_fn = Some(&post_build_path);
+        build_method.post_build_arg = PostBuildArg::Mut;
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub fn build(&self) -> ::db::export::core::result::Result<Foo, FooBuilderError> {
+                    let mut built = Foo {
+                        foo: self.foo,
+                    };
+                    fix_foo(&mut built)?;
+                    Ok(built)
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn post_build_owned() {
+        let post_build_path: syn::Path = parse_quote!(finish_foo);
+
+        let mut build_method: BuildMethod = default_build_method!();
+        build_method.post_build_fn = Some(&post_build_path);
+        build_method.post_build_arg = PostBuildArg::Owned;
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub fn build(&self) -> ::db::export::core::result::Result<Foo, FooBuilderError> {
+                    let built = Foo {
+                        foo: self.foo,
+                    };
+                    Ok(finish_foo(built)?)
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn normalize() {
+        let normalize_path: syn::Path = parse_quote!(IpsumBuilder::normalize);
+
+        let mut build_method: BuildMethod = default_build_method!();
+        build_method.normalize_fn = Some(&normalize_path);
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub fn build(&self) -> ::db::export::core::result::Result<Foo, FooBuilderError> {
+                    let mut normalized = ::db::export::core::clone::Clone::clone(self);
+                    IpsumBuilder::normalize(&mut normalized);
+                    normalized.__build_unnormalized()
+                }
+
+                #[doc(hidden)]
+                fn __build_unnormalized(&self) -> ::db::export::core::result::Result<Foo, FooBuilderError> {
+                    Ok(Foo {
+                        foo: self.foo,
+                    })
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn return_builder() {
+        let mut build_method: BuildMethod = default_build_method!();
+        build_method.pattern = BuilderPattern::Owned;
+        build_method.return_builder = true;
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub fn build(self) -> ::db::export::core::result::Result<Foo, (Self, FooBuilderError)> {
+                    match self.__build_borrowed() {
+                        ::db::export::core::result::Result::Ok(built) => ::db::export::core::result::Result::Ok(built),
+                        ::db::export::core::result::Result::Err(error) => ::db::export::core::result::Result::Err((self, error)),
+                    }
+                }
+
+                #[doc(hidden)]
+                fn __build_borrowed(&self) -> ::db::export::core::result::Result<Foo, FooBuilderError> {
+                    Ok(Foo {
+                        foo: self.foo,
+                    })
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn deferred_error() {
+        let mut build_method: BuildMethod = default_build_method!();
+        build_method.deferred_error = true;
+        build_method.field_validations = vec![quote!(validate_foo(&self.foo)?;)];
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub fn build(&self) -> ::db::export::core::result::Result<Foo, FooBuilderError> {
+                    if let ::db::export::core::option::Option::Some(ref error) = self.__deferred_error {
+                        return ::db::export::core::result::Result::Err(
+                            ::db::export::core::clone::Clone::clone(error)
+                        );
+                    }
+                    validate_foo(&self.foo)?;
+
+                    Ok(Foo {
+                        foo: self.foo,
+                    })
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn multiple_validations() {
+        let validate_paths: [syn::Path; 2] = [parse_quote!(check_a), parse_quote!(check_b)];
+
+        let mut build_method: BuildMethod = default_build_method!();
+        build_method.validate_fns = &validate_paths;
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#build_method).to_string(),
+            quote!(
+                pub fn build(&self) -> ::db::export::core::result::Result<Foo, FooBuilderError> {
+                    check_a(&self)?;
+                    check_b(&self)?;
+
+                    Ok(Foo {
+                        foo: self.foo,
+                    })
+                }
+            )
+            .to_string()
+        );
+    }
+
     #[test]
     fn validation() {
-        let validate_path: syn::Path = parse_quote!(IpsumBuilder::validate);
+        let validate_paths: [syn::Path; 1] = [parse_quote!(IpsumBuilder::validate)];
 
         let mut build_method: BuildMethod = default_build_method!();
-        build_method.validate_fn = Some(&validate_path);
+        build_method.validate_fns = &validate_paths;
 
         #[rustfmt::skip]
         assert_eq!(
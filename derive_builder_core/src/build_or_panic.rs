@@ -0,0 +1,255 @@
+use std::borrow::Cow;
+
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+
+use crate::BuilderPattern;
+
+/// Method which calls the build method and panics if it fails, implementing
+/// `quote::ToTokens`.
+///
+/// It is meant for tests and examples, where a failing build is a bug. The panic message
+/// lists the required fields which have not been set.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::{BuildOrPanicMethod, BuilderPattern};
+/// # fn main() {
+/// #    let build_or_panic = default_build_or_panic_method!();
+/// #
+/// #    assert_eq!(quote!(#build_or_panic).to_string(), quote!(
+/// #[doc = "..."]
+/// #[track_caller]
+/// pub fn build_or_panic(&self) -> Foo {
+///     let missing = ::derive_builder::export::panic::MissingFields(&[
+///         (::derive_builder::export::core::option::Option::is_none(&self.foo), "foo"),
+///     ]);
+///     if missing.any() {
+///         ::derive_builder::export::core::panic!("could not build `Foo`: {}", missing);
+///     }
+///     match self.build() {
+///         ::derive_builder::export::core::result::Result::Ok(built) => built,
+///         ::derive_builder::export::core::result::Result::Err(error) => {
+///             ::derive_builder::export::core::panic!("could not build `Foo`: {:?}", error)
+///         }
+///     }
+/// }
+/// #    ).to_string());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct BuildOrPanicMethod<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Enables code generation for this method.
+    pub enabled: bool,
+    /// Name of the build method which is called. The method is named after it, with an
+    /// `_or_panic` suffix.
+    pub build_fn: &'a syn::Ident,
+    /// Visibility of the method, e.g. `syn::Visibility::Public`.
+    pub visibility: Cow<'a, syn::Visibility>,
+    /// How the method takes `self`, which is the same as for the build method.
+    pub pattern: BuilderPattern,
+    /// Whether the build method returns the builder together with the error.
+    pub return_builder: bool,
+    /// Type of the target struct.
+    pub target_ty: &'a syn::Ident,
+    /// Type parameters and lifetimes of the target struct.
+    pub target_ty_generics: Option<syn::TypeGenerics<'a>>,
+    /// Type which the build method converts the target struct into, and returns instead.
+    pub into_ty: Option<&'a syn::Type>,
+    /// Required fields, as pairs of the builder field and its name in errors.
+    pub required_fields: Vec<(&'a syn::Ident, String)>,
+}
+
+impl<'a> ToTokens for BuildOrPanicMethod<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if !self.enabled {
+            return;
+        }
+
+        let crate_root = self.crate_root;
+        let core = quote!(#crate_root::export::core);
+        let vis = &self.visibility;
+        let build_fn = self.build_fn;
+        let ident = format_ident!("{}_or_panic", build_fn);
+        let target_ty = self.target_ty;
+        let target_ty_generics = &self.target_ty_generics;
+        let built_ty = match self.into_ty {
+            Some(into_ty) => quote!(#into_ty),
+            None => quote!(#target_ty #target_ty_generics),
+        };
+        let self_param = match self.pattern {
+            BuilderPattern::Owned => quote!(self),
+            BuilderPattern::Mutable | BuilderPattern::Immutable => quote!(&self),
+        };
+        let error = if self.return_builder {
+            quote!((_, error))
+        } else {
+            quote!(error)
+        };
+        let message = format!("could not build `{}`: {{}}", target_ty);
+        let error_message = format!("could not build `{}`: {{:?}}", target_ty);
+        let doc = format!(
+            "Call `{}` and panic if it fails, e.g. in tests.\n\n\
+             # Panics\n\n\
+             If a required field has not been set, or if the build fails otherwise.",
+            build_fn
+        );
+        // An empty array couldn't infer its type, so the check is left out.
+        let missing_check = if self.required_fields.is_empty() {
+            quote!()
+        } else {
+            let missing = self.required_fields.iter().map(|(field_ident, name)| {
+                quote!((#core::option::Option::is_none(&self.#field_ident), #name),)
+            });
+            quote!(
+                let missing = #crate_root::export::panic::MissingFields(&[#(#missing)*]);
+                if missing.any() {
+                    #core::panic!(#message, missing);
+                }
+            )
+        };
+
+        tokens.append_all(quote!(
+            #[doc = #doc]
+            #[track_caller]
+            #vis fn #ident(#self_param) -> #built_ty {
+                #missing_check
+                match self.#build_fn() {
+                    #core::result::Result::Ok(built) => built,
+                    #core::result::Result::Err(#error) => {
+                        #core::panic!(#error_message, error)
+                    }
+                }
+            }
+        ));
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[cfg(test)] // This contains a Box::leak, so is suitable only for tests
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_build_or_panic_method {
+    () => {
+        BuildOrPanicMethod {
+            // Deliberately don't use the default value here - make sure
+            // that all test cases are passing crate_root through properly.
+            crate_root: &parse_quote!(::db),
+            enabled: true,
+            build_fn: Box::leak(Box::new(syn::Ident::new(
+                "build",
+                ::proc_macro2::Span::call_site(),
+            ))),
+            visibility: ::std::borrow::Cow::Owned(parse_quote!(pub)),
+            pattern: BuilderPattern::Mutable,
+            return_builder: false,
+            target_ty: Box::leak(Box::new(syn::Ident::new(
+                "Foo",
+                ::proc_macro2::Span::call_site(),
+            ))),
+            target_ty_generics: None,
+            into_ty: None,
+            required_fields: vec![(
+                Box::leak(Box::new(syn::Ident::new(
+                    "foo",
+                    ::proc_macro2::Span::call_site(),
+                ))),
+                "foo".to_string(),
+            )],
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    const DOC: &str = "Call `build` and panic if it fails, e.g. in tests.\n\n# Panics\n\nIf a required field has not been set, or if the build fails otherwise.";
+
+    fn expected(self_param: TokenStream, missing_check: TokenStream) -> TokenStream {
+        let core = quote!(::db::export::core);
+        quote!(
+            #[doc = #DOC]
+            #[track_caller]
+            pub fn build_or_panic(#self_param) -> Foo {
+                #missing_check
+                match self.build() {
+                    #core::result::Result::Ok(built) => built,
+                    #core::result::Result::Err(error) => {
+                        #core::panic!("could not build `Foo`: {:?}", error)
+                    }
+                }
+            }
+        )
+    }
+
+    #[test]
+    fn simple() {
+        let build_or_panic = default_build_or_panic_method!();
+
+        assert_eq!(
+            quote!(#build_or_panic).to_string(),
+            expected(
+                quote!(&self),
+                quote!(
+                    let missing = ::db::export::panic::MissingFields(&[
+                        (::db::export::core::option::Option::is_none(&self.foo), "foo"),
+                    ]);
+                    if missing.any() {
+                        ::db::export::core::panic!("could not build `Foo`: {}", missing);
+                    }
+                )
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn owned_without_required_fields() {
+        let mut build_or_panic = default_build_or_panic_method!();
+        build_or_panic.pattern = BuilderPattern::Owned;
+        build_or_panic.required_fields.clear();
+
+        assert_eq!(
+            quote!(#build_or_panic).to_string(),
+            expected(quote!(self), quote!()).to_string()
+        );
+    }
+
+    #[test]
+    fn return_builder() {
+        let mut build_or_panic = default_build_or_panic_method!();
+        build_or_panic.pattern = BuilderPattern::Owned;
+        build_or_panic.return_builder = true;
+        build_or_panic.required_fields.clear();
+
+        let tokens = quote!(#build_or_panic).to_string();
+        let expected = quote!(::db::export::core::result::Result::Err((_, error)) => {
+            ::db::export::core::panic!("could not build `Foo`: {:?}", error)
+        })
+        .to_string();
+
+        assert!(tokens.contains(&expected), "{}", tokens);
+    }
+
+    #[test]
+    fn disabled() {
+        let mut build_or_panic = default_build_or_panic_method!();
+        build_or_panic.enabled = false;
+
+        assert_eq!(quote!(#build_or_panic).to_string(), quote!().to_string());
+    }
+}
@@ -0,0 +1,201 @@
+use std::borrow::Cow;
+
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+
+use crate::{BuildWrapper, BuilderPattern};
+
+/// Method which calls the build method and puts the built struct into a smart pointer,
+/// implementing `quote::ToTokens`.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::{BuildWrappedMethod, BuildWrapper, BuilderPattern};
+/// # fn main() {
+/// #    let build_wrapped = default_build_wrapped_method!();
+/// #
+/// #    assert_eq!(quote!(#build_wrapped).to_string(), quote!(
+/// #[doc = "..."]
+/// pub fn build_arc(&self) -> ::derive_builder::export::core::result::Result<
+///     ::derive_builder::export::core::sync::Arc<Foo>,
+///     FooBuilderError
+/// > {
+///     ::derive_builder::export::core::result::Result::map(
+///         self.build(),
+///         ::derive_builder::export::core::sync::Arc::new
+///     )
+/// }
+/// #    ).to_string());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct BuildWrappedMethod<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Enables code generation for this method.
+    pub enabled: bool,
+    /// Name of the build method which is called. The method is named after it, with the
+    /// suffix of the wrapper.
+    pub build_fn: &'a syn::Ident,
+    /// Smart pointer which the built struct is put into.
+    pub wrapper: BuildWrapper,
+    /// Visibility of the method, e.g. `syn::Visibility::Public`.
+    pub visibility: Cow<'a, syn::Visibility>,
+    /// How the method takes `self`, which is the same as for the build method.
+    pub pattern: BuilderPattern,
+    /// Whether the build method returns the builder together with the error.
+    pub return_builder: bool,
+    /// Type of the target struct.
+    pub target_ty: &'a syn::Ident,
+    /// Type parameters and lifetimes of the target struct.
+    pub target_ty_generics: Option<syn::TypeGenerics<'a>>,
+    /// Type which the build method converts the target struct into, and returns instead.
+    pub into_ty: Option<&'a syn::Type>,
+    /// Type of error.
+    pub error_ty: syn::Path,
+}
+
+impl<'a> ToTokens for BuildWrappedMethod<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if !self.enabled {
+            return;
+        }
+
+        let crate_root = self.crate_root;
+        let core = quote!(#crate_root::export::core);
+        let vis = &self.visibility;
+        let build_fn = self.build_fn;
+        let ident = format_ident!("{}_{}", build_fn, self.wrapper.suffix());
+        let target_ty = self.target_ty;
+        let target_ty_generics = &self.target_ty_generics;
+        let built_ty = match self.into_ty {
+            Some(into_ty) => quote!(#into_ty),
+            None => quote!(#target_ty #target_ty_generics),
+        };
+        let (wrapper, wrapper_name) = match self.wrapper {
+            BuildWrapper::Box => (quote!(#core::boxed::Box), "Box"),
+            BuildWrapper::Arc => (quote!(#core::sync::Arc), "Arc"),
+            BuildWrapper::Rc => (quote!(#core::rc::Rc), "Rc"),
+        };
+        let self_param = match self.pattern {
+            BuilderPattern::Owned => quote!(self),
+            BuilderPattern::Mutable | BuilderPattern::Immutable => quote!(&self),
+        };
+        let error_ty = &self.error_ty;
+        let error_ty = if self.return_builder {
+            quote!((Self, #error_ty))
+        } else {
+            quote!(#error_ty)
+        };
+        let doc = format!(
+            "Call `{}` and put the built struct into an `{}`.\n\n\
+             # Errors\n\n\
+             If `{}` fails.",
+            build_fn, wrapper_name, build_fn
+        );
+
+        tokens.append_all(quote!(
+            #[doc = #doc]
+            #vis fn #ident(#self_param)
+                -> #core::result::Result<#wrapper<#built_ty>, #error_ty>
+            {
+                #core::result::Result::map(self.#build_fn(), #wrapper::new)
+            }
+        ));
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[cfg(test)] // This contains a Box::leak, so is suitable only for tests
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_build_wrapped_method {
+    () => {
+        BuildWrappedMethod {
+            // Deliberately don't use the default value here - make sure
+            // that all test cases are passing crate_root through properly.
+            crate_root: &parse_quote!(::db),
+            enabled: true,
+            build_fn: Box::leak(Box::new(syn::Ident::new(
+                "build",
+                ::proc_macro2::Span::call_site(),
+            ))),
+            wrapper: BuildWrapper::Arc,
+            visibility: ::std::borrow::Cow::Owned(parse_quote!(pub)),
+            pattern: BuilderPattern::Mutable,
+            return_builder: false,
+            target_ty: Box::leak(Box::new(syn::Ident::new(
+                "Foo",
+                ::proc_macro2::Span::call_site(),
+            ))),
+            target_ty_generics: None,
+            into_ty: None,
+            error_ty: parse_quote!(FooBuilderError),
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn arc() {
+        let build_wrapped = default_build_wrapped_method!();
+
+        assert_eq!(
+            quote!(#build_wrapped).to_string(),
+            quote!(
+                #[doc = "Call `build` and put the built struct into an `Arc`.\n\n# Errors\n\nIf `build` fails."]
+                pub fn build_arc(&self) -> ::db::export::core::result::Result<
+                    ::db::export::core::sync::Arc<Foo>,
+                    FooBuilderError
+                > {
+                    ::db::export::core::result::Result::map(
+                        self.build(),
+                        ::db::export::core::sync::Arc::new
+                    )
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn boxed_owned() {
+        let mut build_wrapped = default_build_wrapped_method!();
+        build_wrapped.wrapper = BuildWrapper::Box;
+        build_wrapped.pattern = BuilderPattern::Owned;
+        build_wrapped.return_builder = true;
+
+        let tokens = quote!(#build_wrapped).to_string();
+        let expected = quote!(
+            pub fn build_boxed(self) -> ::db::export::core::result::Result<
+                ::db::export::core::boxed::Box<Foo>,
+                (Self, FooBuilderError)
+            >
+        )
+        .to_string();
+
+        assert!(tokens.contains(&expected), "{}", tokens);
+    }
+
+    #[test]
+    fn disabled() {
+        let mut build_wrapped = default_build_wrapped_method!();
+        build_wrapped.enabled = false;
+
+        assert_eq!(quote!(#build_wrapped).to_string(), quote!().to_string());
+    }
+}
@@ -1,12 +1,14 @@
 use std::borrow::Cow;
 
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, ToTokens, TokenStreamExt};
 use syn::punctuated::Punctuated;
 use syn::{Path, TraitBound, TraitBoundModifier, TypeParamBound};
 
 use crate::{
-    doc_comment_from, BuildMethod, BuilderField, BuilderPattern, DeprecationNotes, Setter,
+    doc_comment_from, BuildMethod, BuildOrPanicMethod, BuildWrappedMethod, BuilderField,
+    BuilderPattern, DelegateAccessor, DeprecationNotes, ExtraFields, FromQueryMethod,
+    FuzzBuildMethod, ResetMethod, Setter, ToMapMethod, DEFERRED_ERROR_FIELD,
 };
 
 const ALLOC_NOT_ENABLED_ERROR: &str = r#"`alloc` is disabled within 'derive_builder', consider one of the following:
@@ -55,9 +57,11 @@ const ALLOC_NOT_ENABLED_ERROR: &str = r#"`alloc` is disabled within 'derive_buil
 ///     ValidationError(::derive_builder::export::core::string::String),
 /// }
 ///
+/// #[automatically_derived]
 /// impl ::derive_builder::export::core::convert::From<... various ...> for FooBuilderError {}
 ///
 /// #[cfg(not(no_std))]
+/// #[automatically_derived]
 /// impl std::error::Error for FooBuilderError {}
 /// #           ));
 /// #           #[cfg(not(feature = "clippy"))]
@@ -72,6 +76,7 @@ const ALLOC_NOT_ENABLED_ERROR: &str = r#"`alloc` is disabled within 'derive_buil
 ///     }
 /// }
 ///
+/// #[automatically_derived]
 /// impl ::derive_builder::export::core::default::Default for FooBuilder {
 ///     fn default() -> Self {
 ///         Self {
@@ -87,6 +92,7 @@ const ALLOC_NOT_ENABLED_ERROR: &str = r#"`alloc` is disabled within 'derive_buil
 /// # }
 /// ```
 #[derive(Debug)]
+#[non_exhaustive]
 pub struct Builder<'a> {
     /// Path to the root of the derive_builder crate.
     pub crate_root: &'a Path,
@@ -102,6 +108,8 @@ pub struct Builder<'a> {
     pub struct_attrs: &'a [syn::Attribute],
     /// Attributes to include on the builder's inherent `impl` block.
     pub impl_attrs: &'a [syn::Attribute],
+    /// Lints to allow on all generated items, e.g. `missing_docs`.
+    pub allow_lints: &'a [Path],
     /// When true, generate `impl Default for #ident` which calls the `create_empty` inherent method.
     ///
     /// Note that the name of `create_empty` can be overridden; see the `create_empty` field for more.
@@ -137,6 +145,25 @@ pub struct Builder<'a> {
     /// This would be `false` when `build_fn.error.as_validation_error() == Some((false, _))`. This
     /// has no effect when `generate_error` is `false`.
     pub generate_validation_error: bool,
+    /// Whether to include `FieldDependency` in the generated enum, for fields declaring
+    /// `requires` or `conflicts_with`.
+    ///
+    /// This has no effect when `generate_error` is `false`.
+    pub generate_dependency_error: bool,
+    /// Whether to include `UninitializedFields` in the generated enum, for build methods which
+    /// collect all uninitialized fields.
+    ///
+    /// This has no effect when `generate_error` is `false`.
+    pub generate_uninitialized_fields_error: bool,
+    /// Whether to include `NestedField` in the generated enum, for `delegate` fields whose
+    /// nested builders fail.
+    ///
+    /// This has no effect when `generate_error` is `false`.
+    pub generate_nested_error: bool,
+    /// Enum of the target struct's fields, which the generated error's `field` method returns.
+    ///
+    /// This has no effect when `generate_error` is `false`.
+    pub field_enum: Option<syn::Ident>,
     /// Indicator of `cfg!(not(any(feature = "alloc", feature = "std")))`, as a field for tests
     pub no_alloc: bool,
     /// Whether this builder must derive `Clone`.
@@ -146,10 +173,103 @@ pub struct Builder<'a> {
     pub must_derive_clone: bool,
     /// Doc-comment of the builder struct.
     pub doc_comment: Option<syn::Attribute>,
+    /// `#[deprecated]` attribute of the builder struct.
+    pub deprecated: Option<&'a syn::Attribute>,
+    /// Mark the builder struct `#[must_use]`.
+    pub must_use: bool,
     /// Emit deprecation notes to the user.
     pub deprecation_notes: DeprecationNotes,
     /// Whether or not a libstd is used.
     pub std: bool,
+    /// Target type on which to emit an associated `builder()` function that returns an empty
+    /// builder.
+    ///
+    /// Only the attribute macro can set this, since a derive cannot add items to its input.
+    pub target_ty: Option<&'a syn::Ident>,
+    /// Emit `create_empty` (and `builder()` on the target type) as a public `const fn`, so
+    /// builders can be used in constant expressions.
+    ///
+    /// This must be set before fields are pushed, since it changes their initializers.
+    pub const_fn: bool,
+    /// Function which produces the message of uninitialized field errors, given the target name,
+    /// field name and `BuilderErrorKind`.
+    pub message_fn: Option<&'a Path>,
+    /// Name of the target struct, which is passed to `message_fn`.
+    pub target_name: String,
+    /// Trait which receives the setters as default methods instead of the inherent `impl`.
+    ///
+    /// This must be set before setters are pushed.
+    pub setters_trait: Option<SettersTrait>,
+    /// Emit `#[serde(deny_unknown_fields)]` on the builder struct, which must derive
+    /// `Deserialize`.
+    pub deny_unknown_fields: bool,
+    /// Paths which are imported for the builder's methods, e.g. `crate::units::Seconds`.
+    ///
+    /// If there are any, the `impl` of the builder is wrapped in an anonymous `const`, which
+    /// keeps the imports out of the user's module.
+    pub uses: &'a [syn::UseTree],
+    /// Add a field of this build error type which holds the first error passed to a
+    /// `setter(defer_err)`, until the build method returns it.
+    pub deferred_error: Option<syn::Path>,
+    /// Separate `impl` blocks which receive the setters and build methods instead of the
+    /// inherent `impl` with the other methods.
+    ///
+    /// This must be set before setters and build methods are pushed.
+    pub split_impls: Option<SplitImpls<'a>>,
+    /// Calls of the public setters, which are emitted into a function of a hidden module, so
+    /// that a changed setter signature breaks the build of the crate which defines the builder.
+    ///
+    /// This must be set before setters are pushed.
+    pub api_snapshot: Option<Vec<TokenStream>>,
+}
+
+/// Trait which declares the setters of a builder as default methods.
+///
+/// The builder implements the trait, and so can wrappers of the builder, which may override
+/// individual setters.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct SettersTrait {
+    /// Name of the trait.
+    pub ident: syn::Ident,
+    /// Name of the required method which returns the builder to set fields on.
+    pub accessor: syn::Ident,
+    /// Whether the trait needs `Clone` as a supertrait, because setters use the immutable pattern.
+    pub requires_clone: bool,
+    /// Setters of the builder, e.g. `fn bar() -> { unimplemented!() }`
+    pub functions: Vec<TokenStream>,
+}
+
+impl SettersTrait {
+    /// Create a trait named `ident` without setters, whose required method `accessor` returns
+    /// the builder.
+    pub fn new(ident: syn::Ident, accessor: syn::Ident) -> Self {
+        SettersTrait {
+            ident,
+            accessor,
+            requires_clone: false,
+            functions: vec![],
+        }
+    }
+}
+
+/// The `impl` blocks of a builder which are split by concern, so each can get its own
+/// attributes, e.g. to `cfg`-gate or hide all try setters at once.
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct SplitImpls<'a> {
+    /// Attributes of the `impl` block of the setters.
+    pub setters_attrs: &'a [syn::Attribute],
+    /// Setters of the builder, except for the try setters.
+    pub setters: Vec<TokenStream>,
+    /// Attributes of the `impl` block of the try setters.
+    pub try_setters_attrs: &'a [syn::Attribute],
+    /// Setters generated by `try_setter`.
+    pub try_setters: Vec<TokenStream>,
+    /// Attributes of the `impl` block of the build methods.
+    pub build_attrs: &'a [syn::Attribute],
+    /// Build methods, including their variants such as `build_or_panic`.
+    pub build_fns: Vec<TokenStream>,
 }
 
 impl<'a> ToTokens for Builder<'a> {
@@ -166,10 +286,34 @@ impl<'a> ToTokens for Builder<'a> {
             let bounded_generics = self.compute_impl_bounds();
             let (impl_generics, impl_ty_generics, impl_where_clause) =
                 bounded_generics.split_for_impl();
-            let builder_fields = &self.fields;
-            let builder_field_initializers = &self.field_initializers;
+            let mut builder_fields = self.fields.clone();
+            let mut builder_field_initializers = self.field_initializers.clone();
+            if let Some(ref error_ty) = self.deferred_error {
+                let crate_root = self.crate_root;
+                let field = syn::Ident::new(DEFERRED_ERROR_FIELD, Span::call_site());
+                // The error isn't part of the builder's data, so it isn't serialized.
+                let serde_skip = if self.derives.iter().any(|path| {
+                    matches!(path.segments.last(), Some(segment)
+                        if segment.ident == "Serialize" || segment.ident == "Deserialize")
+                }) {
+                    quote!(#[serde(skip)])
+                } else {
+                    quote!()
+                };
+                builder_fields.push(quote!(
+                    #serde_skip
+                    #field: #crate_root::export::core::option::Option<#error_ty>,
+                ));
+                builder_field_initializers
+                    .push(quote!(#field: #crate_root::export::core::option::Option::None,));
+            }
             let create_empty = &self.create_empty;
             let functions = &self.functions;
+            let (create_empty_vis, constness) = if self.const_fn {
+                (Some(builder_vis), quote!(const))
+            } else {
+                (None, quote!())
+            };
 
             // Create the comma-separated set of derived traits for the builder
             let derive_attr = {
@@ -190,9 +334,21 @@ impl<'a> ToTokens for Builder<'a> {
 
             let struct_attrs = self.struct_attrs;
             let impl_attrs = self.impl_attrs;
+            let serde_attr = if self.deny_unknown_fields {
+                quote!(#[serde(deny_unknown_fields)])
+            } else {
+                quote!()
+            };
 
             let builder_doc_comment = &self.doc_comment;
+            let deprecated = self.deprecated;
+            let must_use = if self.must_use {
+                quote!(#[must_use])
+            } else {
+                quote!()
+            };
             let deprecation_notes = &self.deprecation_notes.as_item();
+            let allow_lints = allow_lints_attr(self.allow_lints);
 
             #[cfg(not(feature = "clippy"))]
             tokens.append_all(quote!(#[allow(clippy::all)]));
@@ -201,26 +357,33 @@ impl<'a> ToTokens for Builder<'a> {
             // trait will appear before its derivation. As of rustc 1.59.0 this is a compiler
             // warning; see https://github.com/rust-lang/rust/issues/79202
             tokens.append_all(quote!(
+                #allow_lints
                 #derive_attr
                 #(#struct_attrs)*
+                #serde_attr
                 #builder_doc_comment
+                #deprecated
+                #must_use
                 #builder_vis struct #builder_ident #struct_generics #struct_where_clause {
                     #(#builder_fields)*
                 }
             ));
 
+            let mut builder_impl = TokenStream::new();
+
             #[cfg(not(feature = "clippy"))]
-            tokens.append_all(quote!(#[allow(clippy::all)]));
+            builder_impl.append_all(quote!(#[allow(clippy::all)]));
 
-            tokens.append_all(quote!(
+            builder_impl.append_all(quote!(
                 #(#impl_attrs)*
+                #allow_lints
                 #[allow(dead_code)]
                 impl #impl_generics #builder_ident #impl_ty_generics #impl_where_clause {
                     #(#functions)*
                     #deprecation_notes
 
                     /// Create an empty builder, with all fields set to `None` or `PhantomData`.
-                    fn #create_empty() -> Self {
+                    #create_empty_vis #constness fn #create_empty() -> Self {
                         Self {
                             #(#builder_field_initializers)*
                         }
@@ -228,8 +391,124 @@ impl<'a> ToTokens for Builder<'a> {
                 }
             ));
 
+            if let Some(split_impls) = &self.split_impls {
+                let groups = [
+                    (split_impls.setters_attrs, &split_impls.setters),
+                    (split_impls.try_setters_attrs, &split_impls.try_setters),
+                    (split_impls.build_attrs, &split_impls.build_fns),
+                ];
+                for (attrs, functions) in groups {
+                    if functions.is_empty() {
+                        continue;
+                    }
+
+                    #[cfg(not(feature = "clippy"))]
+                    builder_impl.append_all(quote!(#[allow(clippy::all)]));
+
+                    builder_impl.append_all(quote!(
+                        #(#attrs)*
+                        #allow_lints
+                        #[allow(dead_code)]
+                        impl #impl_generics #builder_ident #impl_ty_generics #impl_where_clause {
+                            #(#functions)*
+                        }
+                    ));
+                }
+            }
+
+            if self.uses.is_empty() {
+                tokens.append_all(builder_impl);
+            } else {
+                let uses = self.uses;
+                tokens.append_all(quote!(
+                    const _: () = {
+                        #(use #uses;)*
+                        #builder_impl
+                    };
+                ));
+            }
+
+            if let Some(ref api_assertions) = self.api_snapshot {
+                let module = format_ident!("__{}_api", builder_ident);
+
+                #[cfg(not(feature = "clippy"))]
+                tokens.append_all(quote!(#[allow(clippy::all)]));
+
+                tokens.append_all(quote!(
+                    #[doc(hidden)]
+                    #[allow(non_snake_case, dead_code, deprecated, unused_imports)]
+                    mod #module {
+                        use super::*;
+
+                        fn value<T>() -> T {
+                            #crate_root::export::core::unreachable!()
+                        }
+
+                        fn _assert_api #impl_generics (b: &mut #builder_ident #impl_ty_generics) #impl_where_clause {
+                            #(#api_assertions)*
+                        }
+                    }
+                ));
+            }
+
+            if let Some(setters_trait) = &self.setters_trait {
+                let trait_ident = &setters_trait.ident;
+                let accessor = &setters_trait.accessor;
+                let setter_fns = &setters_trait.functions;
+                let supertraits = if setters_trait.requires_clone {
+                    quote!(#crate_root::export::core::clone::Clone)
+                } else {
+                    quote!(#crate_root::export::core::marker::Sized)
+                };
+                let trait_doc = format!(
+                    "Setters of [`{}`], which can be overridden by implementing this trait for a wrapper of the builder.",
+                    builder_ident
+                );
+                let accessor_doc = format!("Access the [`{}`] to set fields on.", builder_ident);
+
+                #[cfg(not(feature = "clippy"))]
+                tokens.append_all(quote!(#[allow(clippy::all)]));
+
+                tokens.append_all(quote!(
+                    #[doc=#trait_doc]
+                    #allow_lints
+                    #builder_vis trait #trait_ident #impl_generics: #supertraits #impl_where_clause {
+                        #[doc=#accessor_doc]
+                        fn #accessor(&mut self) -> &mut #builder_ident #impl_ty_generics;
+
+                        #(#setter_fns)*
+                    }
+
+                    #[automatically_derived]
+                    #allow_lints
+                    impl #impl_generics #trait_ident #impl_ty_generics for #builder_ident #impl_ty_generics #impl_where_clause {
+                        fn #accessor(&mut self) -> &mut Self {
+                            self
+                        }
+                    }
+                ));
+            }
+
+            if let Some(target_ty) = self.target_ty {
+                let builder_fn_doc = format!(
+                    "Create an empty [`{}`] for constructing a [`{}`].",
+                    builder_ident, target_ty
+                );
+                tokens.append_all(quote!(
+                    #allow_lints
+                    impl #impl_generics #target_ty #impl_ty_generics #impl_where_clause {
+                        #[doc=#builder_fn_doc]
+                        #builder_vis #constness fn builder() -> #builder_ident #impl_ty_generics {
+                            #builder_ident::#create_empty()
+                        }
+                    }
+                ));
+            }
+
             if self.impl_default {
                 tokens.append_all(quote!(
+                    #[automatically_derived]
+                    #allow_lints
                     impl #impl_generics #crate_root::export::core::default::Default for #builder_ident #impl_ty_generics #impl_where_clause {
                         fn default() -> Self {
                             Self::#create_empty()
@@ -255,6 +534,8 @@ impl<'a> ToTokens for Builder<'a> {
                 };
                 let validation_from = if self.generate_validation_error {
                     quote!(
+                        #[automatically_derived]
+                        #allow_lints
                         impl #crate_root::export::core::convert::From<#crate_root::export::core::string::String> for #builder_error_ident {
                             fn from(s: #crate_root::export::core::string::String) -> Self {
                                 Self::ValidationError(s)
@@ -264,6 +545,161 @@ impl<'a> ToTokens for Builder<'a> {
                 } else {
                     TokenStream::new()
                 };
+                let dependency_error = if self.generate_dependency_error {
+                    quote!(
+                        /// Dependency between fields not satisfied
+                        FieldDependency(#crate_root::FieldDependencyError),
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let dependency_from = if self.generate_dependency_error {
+                    quote!(
+                        #[automatically_derived]
+                        #allow_lints
+                        impl #crate_root::export::core::convert::From<#crate_root::FieldDependencyError> for #builder_error_ident {
+                            fn from(e: #crate_root::FieldDependencyError) -> Self {
+                                Self::FieldDependency(e)
+                            }
+                        }
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let dependency_display = if self.generate_dependency_error {
+                    quote!(
+                        Self::FieldDependency(ref error) => write!(f, "{}", error),
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let uninitialized_fields_error = if self.generate_uninitialized_fields_error {
+                    quote!(
+                        /// Uninitialized fields
+                        UninitializedFields(#crate_root::UninitializedFieldsError),
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let uninitialized_fields_from = if self.generate_uninitialized_fields_error {
+                    quote!(
+                        #[automatically_derived]
+                        #allow_lints
+                        impl #crate_root::export::core::convert::From<#crate_root::UninitializedFieldsError> for #builder_error_ident {
+                            fn from(e: #crate_root::UninitializedFieldsError) -> Self {
+                                Self::UninitializedFields(e)
+                            }
+                        }
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let uninitialized_fields_display = if self.generate_uninitialized_fields_error {
+                    quote!(
+                        Self::UninitializedFields(ref error) => write!(f, "{}", error),
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let nested_error = if self.generate_nested_error {
+                    quote!(
+                        /// Error of the nested builder of a field
+                        NestedField(#crate_root::NestedFieldError),
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let nested_from = if self.generate_nested_error {
+                    quote!(
+                        #[automatically_derived]
+                        #allow_lints
+                        impl #crate_root::export::core::convert::From<#crate_root::NestedFieldError> for #builder_error_ident {
+                            fn from(e: #crate_root::NestedFieldError) -> Self {
+                                Self::NestedField(e)
+                            }
+                        }
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let nested_display = if self.generate_nested_error {
+                    quote!(
+                        Self::NestedField(ref error) => write!(f, "{}", error),
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let option = quote!(#crate_root::export::core::option::Option);
+                let uninitialized_fields_field = if self.generate_uninitialized_fields_error {
+                    quote!(
+                        Self::UninitializedFields(ref error) => error.field_names().first().copied(),
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let nested_field = if self.generate_nested_error {
+                    quote!(
+                        Self::NestedField(ref error) => #crate_root::BuilderError::uninitialized_field(error),
+                    )
+                } else {
+                    TokenStream::new()
+                };
+                let builder_error_impl = quote!(
+                    #[automatically_derived]
+                    #allow_lints
+                    impl #crate_root::BuilderError for #builder_error_ident {
+                        #[allow(unreachable_patterns)]
+                        fn uninitialized_field(&self) -> #option<&str> {
+                            match self {
+                                Self::UninitializedField(ref field) => #option::Some(field),
+                                #uninitialized_fields_field
+                                #nested_field
+                                _ => #option::None,
+                            }
+                        }
+                    }
+                );
+                let field_accessor = match self.field_enum {
+                    Some(ref field_enum) => {
+                        let option = quote!(#crate_root::export::core::option::Option);
+                        let dependency_field = if self.generate_dependency_error {
+                            quote!(
+                                Self::FieldDependency(ref error) => #field_enum::from_name(error.field_name()),
+                            )
+                        } else {
+                            TokenStream::new()
+                        };
+                        let uninitialized_fields_field = if self.generate_uninitialized_fields_error
+                        {
+                            quote!(
+                                Self::UninitializedFields(ref error) => error
+                                    .field_names()
+                                    .first()
+                                    .and_then(|field| #field_enum::from_name(field)),
+                            )
+                        } else {
+                            TokenStream::new()
+                        };
+                        quote!(
+                            #allow_lints
+                            impl #builder_error_ident {
+                                /// The field which the error is about, if any.
+                                ///
+                                /// For errors about several fields, this is the first of them.
+                                #[allow(unreachable_patterns)]
+                                pub fn field(&self) -> #option<#field_enum> {
+                                    match self {
+                                        Self::UninitializedField(ref field) => #field_enum::from_name(field),
+                                        #uninitialized_fields_field
+                                        #dependency_field
+                                        _ => #option::None,
+                                    }
+                                }
+                            }
+                        )
+                    }
+                    None => TokenStream::new(),
+                };
                 let validation_display = if self.generate_validation_error {
                     quote!(
                         Self::ValidationError(ref error) => write!(f, "{}", error),
@@ -272,29 +708,77 @@ impl<'a> ToTokens for Builder<'a> {
                     TokenStream::new()
                 };
 
+                let uninitialized_display = match self.message_fn {
+                    Some(message_fn) => {
+                        let target_name = &self.target_name;
+                        quote!(
+                            Self::UninitializedField(ref field) => write!(
+                                f,
+                                "{}",
+                                #message_fn(
+                                    #target_name,
+                                    field,
+                                    #crate_root::BuilderErrorKind::UninitializedField,
+                                )
+                            ),
+                        )
+                    }
+                    None => quote!(
+                        Self::UninitializedField(ref field) => write!(f, "`{}` must be initialized", field),
+                    ),
+                };
+
+                // A deferred error is stored in the builder, so it is cloned along with it.
+                let error_derives = if self.deferred_error.is_some() {
+                    quote!(#[derive(Debug, Clone)])
+                } else {
+                    quote!(#[derive(Debug)])
+                };
+
                 tokens.append_all(quote!(
                     #[doc=#builder_error_doc]
-                    #[derive(Debug)]
+                    #error_derives
                     #[non_exhaustive]
+                    #allow_lints
                     #builder_vis enum #builder_error_ident {
                         /// Uninitialized field
                         UninitializedField(&'static str),
+                        #uninitialized_fields_error
                         #validation_error
+                        #dependency_error
+                        #nested_error
                     }
 
+                    #[automatically_derived]
+                    #allow_lints
                     impl #crate_root::export::core::convert::From<#crate_root::UninitializedFieldError> for #builder_error_ident {
                         fn from(s: #crate_root::UninitializedFieldError) -> Self {
                             Self::UninitializedField(s.field_name())
                         }
                     }
 
+                    #uninitialized_fields_from
+
                     #validation_from
 
+                    #dependency_from
+
+                    #nested_from
+
+                    #field_accessor
+
+                    #builder_error_impl
+
+                    #[automatically_derived]
+                    #allow_lints
                     impl #crate_root::export::core::fmt::Display for #builder_error_ident {
                         fn fmt(&self, f: &mut #crate_root::export::core::fmt::Formatter) -> #crate_root::export::core::fmt::Result {
                             match self {
-                                Self::UninitializedField(ref field) => write!(f, "`{}` must be initialized", field),
+                                #uninitialized_display
+                                #uninitialized_fields_display
                                 #validation_display
+                                #dependency_display
+                                #nested_display
                             }
                         }
                     }
@@ -302,6 +786,8 @@ impl<'a> ToTokens for Builder<'a> {
 
                 if self.std {
                     tokens.append_all(quote!(
+                        #[automatically_derived]
+                        #allow_lints
                         impl std::error::Error for #builder_error_ident {}
                     ));
                 }
@@ -311,6 +797,53 @@ impl<'a> ToTokens for Builder<'a> {
 }
 
 impl<'a> Builder<'a> {
+    /// Create an enabled builder struct named `ident` without fields or functions.
+    ///
+    /// All options are set to the defaults of `#[derive(Builder)]`; fields, setters and the build
+    /// method are added with the `push_*` methods.
+    pub fn new(crate_root: &'a Path, ident: syn::Ident) -> Self {
+        Builder {
+            crate_root,
+            enabled: true,
+            ident,
+            pattern: Default::default(),
+            derives: &[],
+            struct_attrs: &[],
+            impl_attrs: &[],
+            allow_lints: &[],
+            impl_default: true,
+            create_empty: format_ident!("create_empty"),
+            generics: None,
+            visibility: Cow::Owned(parse_quote!(pub)),
+            fields: vec![],
+            field_initializers: vec![],
+            functions: vec![],
+            generate_error: true,
+            generate_validation_error: true,
+            generate_dependency_error: false,
+            generate_uninitialized_fields_error: false,
+            generate_nested_error: false,
+            field_enum: None,
+            no_alloc: false,
+            must_derive_clone: true,
+            doc_comment: None,
+            deprecated: None,
+            must_use: false,
+            deprecation_notes: Default::default(),
+            std: true,
+            target_ty: None,
+            const_fn: false,
+            message_fn: None,
+            target_name: String::new(),
+            setters_trait: None,
+            deny_unknown_fields: false,
+            uses: &[],
+            deferred_error: None,
+            split_impls: None,
+            api_snapshot: None,
+        }
+    }
+
     /// Set a doc-comment for this item.
     pub fn doc_comment(&mut self, s: String) -> &mut Self {
         self.doc_comment = Some(doc_comment_from(s));
@@ -320,18 +853,100 @@ impl<'a> Builder<'a> {
     /// Add a field to the builder
     pub fn push_field(&mut self, f: BuilderField) -> &mut Self {
         self.fields.push(quote!(#f));
-        self.field_initializers.push(f.default_initializer_tokens());
+        self.field_initializers.push(if self.const_fn {
+            f.const_initializer_tokens()
+        } else {
+            f.default_initializer_tokens()
+        });
         self
     }
 
-    /// Add a setter function to the builder
+    /// Add a setter function to the builder, or to its setters trait if there is one
     pub fn push_setter_fn(&mut self, f: Setter) -> &mut Self {
-        self.functions.push(quote!(#f));
+        if let Some(ref mut api_snapshot) = self.api_snapshot {
+            let builder_ident = &self.ident;
+            let ty_generics = self.generics.map(|generics| generics.split_for_impl().1);
+            api_snapshot.extend(f.api_assertion(&quote!(#builder_ident #ty_generics)));
+        }
+        match (&mut self.setters_trait, &mut self.split_impls) {
+            (Some(setters_trait), _) => setters_trait.functions.push(quote!(#f)),
+            (None, Some(split_impls)) => {
+                let (setters, try_setters) = f.split_tokens();
+                split_impls.setters.push(setters);
+                split_impls.try_setters.push(try_setters);
+            }
+            (None, None) => self.functions.push(quote!(#f)),
+        }
+        self
+    }
+
+    /// Add the accessor of the nested builder of a `delegate` field
+    pub(crate) fn push_delegate_accessor(&mut self, f: DelegateAccessor) -> &mut Self {
+        match self.split_impls {
+            Some(ref mut split_impls) => split_impls.setters.push(quote!(#f)),
+            None => self.functions.push(quote!(#f)),
+        }
+        self
+    }
+
+    /// Add a build method, or one of its variants, to the `impl` block of build methods
+    fn push_build_tokens(&mut self, tokens: TokenStream) -> &mut Self {
+        match self.split_impls {
+            Some(ref mut split_impls) => split_impls.build_fns.push(tokens),
+            None => self.functions.push(tokens),
+        }
         self
     }
 
     /// Add final build function to the builder
     pub fn push_build_fn(&mut self, f: BuildMethod) -> &mut Self {
+        self.push_build_tokens(quote!(#f))
+    }
+
+    /// Add a method of `mutators(...)` as it is written
+    pub(crate) fn push_mutator_fn(&mut self, f: &syn::ImplItemFn) -> &mut Self {
+        self.functions.push(quote!(#f));
+        self
+    }
+
+    /// Add the method which resets the builder for reuse
+    pub fn push_reset_fn(&mut self, f: ResetMethod) -> &mut Self {
+        self.functions.push(quote!(#f));
+        self
+    }
+
+    /// Add the map of unknown keys of the deserialized input, and its accessor
+    pub(crate) fn push_extra_fields(&mut self, f: ExtraFields) -> &mut Self {
+        self.fields.push(f.field_tokens());
+        self.field_initializers.push(f.initializer_tokens());
+        self.functions.push(quote!(#f));
+        self
+    }
+
+    /// Add the function which creates the builder from a URL query string
+    pub(crate) fn push_from_query_fn(&mut self, f: FromQueryMethod) -> &mut Self {
+        self.functions.push(quote!(#f));
+        self
+    }
+
+    /// Add the method which panics if the build fails
+    pub(crate) fn push_build_or_panic_fn(&mut self, f: BuildOrPanicMethod) -> &mut Self {
+        self.push_build_tokens(quote!(#f))
+    }
+
+    /// Add a method which puts the built struct into a smart pointer
+    pub(crate) fn push_build_wrapped_fn(&mut self, f: BuildWrappedMethod) -> &mut Self {
+        self.push_build_tokens(quote!(#f))
+    }
+
+    /// Add the method which serializes the set fields into a JSON object
+    pub(crate) fn push_to_map_fn(&mut self, f: ToMapMethod) -> &mut Self {
+        self.functions.push(quote!(#f));
+        self
+    }
+
+    /// Add the function which builds a builder filled from arbitrary bytes
+    pub(crate) fn push_fuzz_build_fn(&mut self, f: FuzzBuildMethod) -> &mut Self {
         self.functions.push(quote!(#f));
         self
     }
@@ -368,6 +983,15 @@ impl<'a> Builder<'a> {
     }
 }
 
+/// Create an `#[allow(...)]` attribute for the given lints, or nothing if there are none.
+pub(crate) fn allow_lints_attr(lints: &[Path]) -> TokenStream {
+    if lints.is_empty() {
+        quote!()
+    } else {
+        quote!(#[allow(#(#lints),*)])
+    }
+}
+
 /// Helper macro for unit tests. This is _only_ public in order to be accessible
 /// from doc-tests too.
 #[doc(hidden)]
@@ -381,9 +1005,10 @@ macro_rules! default_builder {
             enabled: true,
             ident: syn::Ident::new("FooBuilder", ::proc_macro2::Span::call_site()),
             pattern: Default::default(),
-            derives: &vec![],
-            struct_attrs: &vec![],
-            impl_attrs: &vec![],
+            derives: &[],
+            struct_attrs: &[],
+            impl_attrs: &[],
+            allow_lints: &[],
             impl_default: true,
             create_empty: syn::Ident::new("create_empty", ::proc_macro2::Span::call_site()),
             generics: None,
@@ -393,11 +1018,27 @@ macro_rules! default_builder {
             functions: vec![quote!(fn bar() -> { unimplemented!() })],
             generate_error: true,
             generate_validation_error: true,
+            generate_dependency_error: false,
+            generate_uninitialized_fields_error: false,
+            generate_nested_error: false,
+            field_enum: None,
             no_alloc: false,
             must_derive_clone: true,
             doc_comment: None,
+            deprecated: None,
+            must_use: false,
             deprecation_notes: DeprecationNotes::default(),
             std: true,
+            target_ty: None,
+            const_fn: false,
+            message_fn: None,
+            target_name: "Foo".to_string(),
+            setters_trait: None,
+            deny_unknown_fields: false,
+            uses: &[],
+            deferred_error: None,
+            split_impls: None,
+            api_snapshot: None,
         }
     };
 }
@@ -406,6 +1047,7 @@ macro_rules! default_builder {
 mod tests {
     #[allow(unused_imports)]
     use super::*;
+    use crate::BuilderFieldType;
     use syn::Ident;
 
     fn add_simple_foo_builder(result: &mut TokenStream) {
@@ -437,6 +1079,7 @@ mod tests {
                 }
             }
 
+            #[automatically_derived]
             impl ::db::export::core::default::Default for FooBuilder {
                 fn default() -> Self {
                     Self::create_empty()
@@ -457,18 +1100,32 @@ mod tests {
                 ValidationError(::db::export::core::string::String),
             }
 
+            #[automatically_derived]
             impl ::db::export::core::convert::From<::db::UninitializedFieldError> for FooBuilderError {
                 fn from(s: ::db::UninitializedFieldError) -> Self {
                     Self::UninitializedField(s.field_name())
                 }
             }
 
+            #[automatically_derived]
             impl ::db::export::core::convert::From<::db::export::core::string::String> for FooBuilderError {
                 fn from(s: ::db::export::core::string::String) -> Self {
                     Self::ValidationError(s)
                 }
             }
 
+            #[automatically_derived]
+            impl ::db::BuilderError for FooBuilderError {
+                #[allow(unreachable_patterns)]
+                fn uninitialized_field(&self) -> ::db::export::core::option::Option<&str> {
+                    match self {
+                        Self::UninitializedField(ref field) => ::db::export::core::option::Option::Some(field),
+                        _ => ::db::export::core::option::Option::None,
+                    }
+                }
+            }
+
+            #[automatically_derived]
             impl ::db::export::core::fmt::Display for FooBuilderError {
                 fn fmt(&self, f: &mut ::db::export::core::fmt::Formatter) -> ::db::export::core::fmt::Result {
                     match self {
@@ -478,38 +1135,142 @@ mod tests {
                 }
             }
 
+            #[automatically_derived]
             impl std::error::Error for FooBuilderError {}
         ));
     }
 
     #[test]
-    fn simple() {
-        let builder = default_builder!();
+    fn uninitialized_fields_error() {
+        let mut builder = default_builder!();
+        builder.generate_uninitialized_fields_error = true;
+
+        let error = quote!(::db::UninitializedFieldsError);
+        let from = quote!(
+            #[automatically_derived]
+            impl ::db::export::core::convert::From<#error> for FooBuilderError {
+                fn from(e: #error) -> Self {
+                    Self::UninitializedFields(e)
+                }
+            }
+        );
+        let display = quote!(Self::UninitializedFields(ref error) => write!(f, "{}", error),);
 
-        assert_eq!(
-            quote!(#builder).to_string(),
-            {
-                let mut result = quote!();
+        let tokens = quote!(#builder).to_string();
+        assert!(tokens.contains(&quote!(UninitializedFields(#error),).to_string()));
+        assert!(tokens.contains(&from.to_string()));
+        assert!(tokens.contains(&display.to_string()));
+    }
 
-                add_simple_foo_builder(&mut result);
+    #[test]
+    fn nested_error() {
+        let mut builder = default_builder!();
+        builder.generate_nested_error = true;
+
+        let error = quote!(::db::NestedFieldError);
+        let from = quote!(
+            #[automatically_derived]
+            impl ::db::export::core::convert::From<#error> for FooBuilderError {
+                fn from(e: #error) -> Self {
+                    Self::NestedField(e)
+                }
+            }
+        );
+        let display = quote!(Self::NestedField(ref error) => write!(f, "{}", error),);
+        let field = quote!(
+            Self::NestedField(ref error) => ::db::BuilderError::uninitialized_field(error),
+        );
 
-                add_generated_error(&mut result);
+        let tokens = quote!(#builder).to_string();
+        assert!(tokens.contains(&quote!(NestedField(#error),).to_string()));
+        assert!(tokens.contains(&from.to_string()));
+        assert!(tokens.contains(&display.to_string()));
+        assert!(tokens.contains(&field.to_string()));
+    }
 
-                result
+    #[test]
+    fn field_enum_accessor() {
+        let mut builder = default_builder!();
+        builder.field_enum = Some(format_ident!("FooField"));
+
+        let option = quote!(::db::export::core::option::Option);
+        let accessor = quote!(
+            impl FooBuilderError {
+                /// The field which the error is about, if any.
+                ///
+                /// For errors about several fields, this is the first of them.
+                #[allow(unreachable_patterns)]
+                pub fn field(&self) -> #option<FooField> {
+                    match self {
+                        Self::UninitializedField(ref field) => FooField::from_name(field),
+                        _ => #option::None,
+                    }
+                }
             }
-            .to_string()
         );
+
+        let tokens = quote!(#builder).to_string();
+        assert!(tokens.contains(&accessor.to_string()));
     }
 
     #[test]
-    fn rename_create_empty() {
+    fn message_fn() {
+        let message_fn: Path = parse_quote!(i18n::builder_msg);
         let mut builder = default_builder!();
-        builder.create_empty = Ident::new("empty", proc_macro2::Span::call_site());
+        builder.message_fn = Some(&message_fn);
 
-        assert_eq!(
-            quote!(#builder).to_string(),
-            {
-                let mut result = quote!();
+        let fmt = quote!(::db::export::core::fmt);
+        let display = quote!(
+            #[automatically_derived]
+            impl #fmt::Display for FooBuilderError {
+                fn fmt(&self, f: &mut #fmt::Formatter) -> #fmt::Result {
+                    match self {
+                        Self::UninitializedField(ref field) => write!(
+                            f,
+                            "{}",
+                            i18n::builder_msg(
+                                "Foo",
+                                field,
+                                ::db::BuilderErrorKind::UninitializedField,
+                            )
+                        ),
+                        Self::ValidationError(ref error) => write!(f, "{}", error),
+                    }
+                }
+            }
+        );
+
+        assert!(quote!(#builder).to_string().contains(&display.to_string()));
+    }
+
+    #[test]
+    fn simple() {
+        let builder = default_builder!();
+
+        assert_eq!(
+            quote!(#builder).to_string(),
+            {
+                let mut result = quote!();
+
+                add_simple_foo_builder(&mut result);
+
+                add_generated_error(&mut result);
+
+                result
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn rename_create_empty() {
+        let mut builder = default_builder!();
+        builder.create_empty = Ident::new("empty", proc_macro2::Span::call_site());
+
+        assert_eq!(
+            quote!(#builder).to_string(),
+            {
+                let mut result = quote!();
 
                 #[cfg(not(feature = "clippy"))]
                 result.append_all(quote!(#[allow(clippy::all)]));
@@ -539,6 +1300,7 @@ mod tests {
                         }
                     }
 
+                    #[automatically_derived]
                     impl ::db::export::core::default::Default for FooBuilder {
                         fn default() -> Self {
                             Self::empty()
@@ -599,6 +1361,7 @@ mod tests {
                         }
                     }
 
+                    #[automatically_derived]
                     impl<'a, T: Debug + ::db::export::core::clone::Clone> ::db::export::core::default::Default for FooBuilder<'a, T> where T: PartialEq {
                         fn default() -> Self {
                             Self::create_empty()
@@ -662,6 +1425,7 @@ mod tests {
                         }
                     }
 
+                    #[automatically_derived]
                     impl<'a, T: 'a + Default + ::db::export::core::clone::Clone> ::db::export::core::default::Default for FooBuilder<'a, T> where T: PartialEq {
                         fn default() -> Self {
                             Self::create_empty()
@@ -723,6 +1487,7 @@ mod tests {
                         }
                     }
 
+                    #[automatically_derived]
                     impl<T: ::db::export::core::clone::Clone> ::db::export::core::default::Default for FooBuilder<T> {
                         fn default() -> Self {
                             Self::create_empty()
@@ -783,6 +1548,7 @@ mod tests {
                         }
                     }
 
+                    #[automatically_derived]
                     impl<'a, T: Debug> ::db::export::core::default::Default for FooBuilder<'a, T>
                     where T: PartialEq {
                         fn default() -> Self {
@@ -798,6 +1564,215 @@ mod tests {
         );
     }
 
+    #[test]
+    fn target_builder_fn() {
+        let target_ty = Ident::new("Foo", proc_macro2::Span::call_site());
+        let mut builder = default_builder!();
+        builder.target_ty = Some(&target_ty);
+
+        assert_eq!(
+            quote!(#builder).to_string(),
+            {
+                let mut result = quote!();
+
+                #[cfg(not(feature = "clippy"))]
+                result.append_all(quote!(#[allow(clippy::all)]));
+
+                result.append_all(quote!(
+                    #[derive(Clone)]
+                    pub struct FooBuilder {
+                        foo: u32,
+                    }
+                ));
+
+                #[cfg(not(feature = "clippy"))]
+                result.append_all(quote!(#[allow(clippy::all)]));
+
+                result.append_all(quote!(
+                    #[allow(dead_code)]
+                    impl FooBuilder {
+                        fn bar () -> {
+                            unimplemented!()
+                        }
+
+                        /// Create an empty builder, with all fields set to `None` or `PhantomData`.
+                        fn create_empty() -> Self {
+                            Self {
+                                foo: ::db::export::core::default::Default::default(),
+                            }
+                        }
+                    }
+
+                    impl Foo {
+                        #[doc="Create an empty [`FooBuilder`] for constructing a [`Foo`]."]
+                        pub fn builder() -> FooBuilder {
+                            FooBuilder::create_empty()
+                        }
+                    }
+
+                    #[automatically_derived]
+                    impl ::db::export::core::default::Default for FooBuilder {
+                        fn default() -> Self {
+                            Self::create_empty()
+                        }
+                    }
+                ));
+
+                add_generated_error(&mut result);
+
+                result
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn setters_trait() {
+        let mut builder = default_builder!();
+        builder.setters_trait = Some(SettersTrait {
+            ident: Ident::new("FooBuilderSetters", proc_macro2::Span::call_site()),
+            accessor: Ident::new("foo_builder_mut", proc_macro2::Span::call_site()),
+            requires_clone: false,
+            functions: vec![quote!(fn foo() -> { unimplemented!() })],
+        });
+
+        assert_eq!(
+            quote!(#builder).to_string(),
+            {
+                let mut result = quote!();
+
+                #[cfg(not(feature = "clippy"))]
+                result.append_all(quote!(#[allow(clippy::all)]));
+
+                result.append_all(quote!(
+                    #[derive(Clone)]
+                    pub struct FooBuilder {
+                        foo: u32,
+                    }
+                ));
+
+                #[cfg(not(feature = "clippy"))]
+                result.append_all(quote!(#[allow(clippy::all)]));
+
+                result.append_all(quote!(
+                    #[allow(dead_code)]
+                    impl FooBuilder {
+                        fn bar () -> {
+                            unimplemented!()
+                        }
+
+                        /// Create an empty builder, with all fields set to `None` or `PhantomData`.
+                        fn create_empty() -> Self {
+                            Self {
+                                foo: ::db::export::core::default::Default::default(),
+                            }
+                        }
+                    }
+
+                ));
+
+                #[cfg(not(feature = "clippy"))]
+                result.append_all(quote!(#[allow(clippy::all)]));
+
+                result.append_all(quote!(
+                    #[doc="Setters of [`FooBuilder`], which can be overridden by implementing this trait for a wrapper of the builder."]
+                    pub trait FooBuilderSetters: ::db::export::core::marker::Sized {
+                        #[doc="Access the [`FooBuilder`] to set fields on."]
+                        fn foo_builder_mut(&mut self) -> &mut FooBuilder;
+
+                        fn foo() -> { unimplemented!() }
+                    }
+
+                    #[automatically_derived]
+                    impl FooBuilderSetters for FooBuilder {
+                        fn foo_builder_mut(&mut self) -> &mut Self {
+                            self
+                        }
+                    }
+
+                    #[automatically_derived]
+                    impl ::db::export::core::default::Default for FooBuilder {
+                        fn default() -> Self {
+                            Self::create_empty()
+                        }
+                    }
+                ));
+
+                add_generated_error(&mut result);
+
+                result
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn const_fn() {
+        let target_ty = Ident::new("Foo", proc_macro2::Span::call_site());
+        let mut builder = default_builder!();
+        builder.target_ty = Some(&target_ty);
+        builder.const_fn = true;
+        builder.field_initializers.clear();
+        builder.push_field(crate::default_builder_field!());
+
+        assert_eq!(
+            quote!(#builder).to_string(),
+            {
+                let mut result = quote!();
+
+                #[cfg(not(feature = "clippy"))]
+                result.append_all(quote!(#[allow(clippy::all)]));
+
+                result.append_all(quote!(
+                    #[derive(Clone)]
+                    pub struct FooBuilder {
+                        foo: u32,
+                        #[some_attr]
+                        pub foo: ::db::export::core::option::Option<String>,
+                    }
+                ));
+
+                #[cfg(not(feature = "clippy"))]
+                result.append_all(quote!(#[allow(clippy::all)]));
+
+                result.append_all(quote!(
+                    #[allow(dead_code)]
+                    impl FooBuilder {
+                        fn bar () -> {
+                            unimplemented!()
+                        }
+
+                        /// Create an empty builder, with all fields set to `None` or `PhantomData`.
+                        pub const fn create_empty() -> Self {
+                            Self {
+                                foo: ::db::export::core::option::Option::None,
+                            }
+                        }
+                    }
+
+                    impl Foo {
+                        #[doc="Create an empty [`FooBuilder`] for constructing a [`Foo`]."]
+                        pub const fn builder() -> FooBuilder {
+                            FooBuilder::create_empty()
+                        }
+                    }
+
+                    #[automatically_derived]
+                    impl ::db::export::core::default::Default for FooBuilder {
+                        fn default() -> Self {
+                            Self::create_empty()
+                        }
+                    }
+                ));
+
+                add_generated_error(&mut result);
+
+                result
+            }
+            .to_string()
+        );
+    }
+
     #[test]
     fn disabled() {
         let mut builder = default_builder!();
@@ -845,6 +1820,7 @@ mod tests {
                         }
                     }
 
+                    #[automatically_derived]
                     impl ::db::export::core::default::Default for FooBuilder {
                         fn default() -> Self {
                             Self::create_empty()
@@ -860,6 +1836,223 @@ mod tests {
         );
     }
 
+    #[test]
+    fn deprecated() {
+        let deprecated: syn::Attribute = parse_quote!(#[deprecated(note = "use BarBuilder")]);
+        let mut builder = default_builder!();
+        builder.deprecated = Some(&deprecated);
+
+        let builder_struct = quote!(
+            #[derive(Clone)]
+            #[deprecated(note = "use BarBuilder")]
+            pub struct FooBuilder {
+                foo: u32,
+            }
+        );
+
+        assert!(quote!(#builder)
+            .to_string()
+            .contains(&builder_struct.to_string()));
+    }
+
+    #[test]
+    fn must_use() {
+        let mut builder = default_builder!();
+        builder.must_use = true;
+
+        let builder_struct = quote!(
+            #[derive(Clone)]
+            #[must_use]
+            pub struct FooBuilder {
+                foo: u32,
+            }
+        );
+
+        assert!(quote!(#builder)
+            .to_string()
+            .contains(&builder_struct.to_string()));
+    }
+
+    #[test]
+    fn uses() {
+        let uses: Vec<syn::UseTree> = vec![parse_quote!(crate::units::{Meters, Seconds})];
+        let mut builder = default_builder!();
+        builder.uses = &uses;
+        // The default functions aren't valid Rust, so the output couldn't be parsed.
+        builder.functions.clear();
+
+        let file: syn::File = syn::parse2(quote!(#builder)).unwrap();
+        let block = file
+            .items
+            .iter()
+            .find_map(|item| match item {
+                syn::Item::Const(item) => match *item.expr {
+                    syn::Expr::Block(ref block) => Some(&block.block),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .expect("the impl of the builder is wrapped in a const");
+
+        assert_eq!(
+            block.stmts[0],
+            parse_quote!(
+                use crate::units::{Meters, Seconds};
+            )
+        );
+        assert!(matches!(
+            block.stmts[1],
+            syn::Stmt::Item(syn::Item::Impl(ref item)) if item.trait_.is_none()
+        ));
+    }
+
+    #[test]
+    fn split_impls() {
+        let setters_attrs: Vec<syn::Attribute> = vec![parse_quote!(#[doc(hidden)])];
+        let build_attrs: Vec<syn::Attribute> = vec![parse_quote!(#[cfg(feature = "build")])];
+        let mut builder = default_builder!();
+        builder.functions.clear();
+        builder.split_impls = Some(SplitImpls {
+            setters_attrs: &setters_attrs,
+            setters: vec![quote!(
+                fn foo(&mut self) {}
+            )],
+            build_attrs: &build_attrs,
+            build_fns: vec![quote!(
+                fn build(&self) {}
+            )],
+            ..Default::default()
+        });
+
+        let file: syn::File = syn::parse2(quote!(#builder)).unwrap();
+        let impls: Vec<_> = file
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                syn::Item::Impl(item) if item.trait_.is_none() => Some(item),
+                _ => None,
+            })
+            .collect();
+
+        // The empty block of try setters is left out.
+        assert_eq!(impls.len(), 3);
+        assert!(impls[1].attrs.contains(&setters_attrs[0]));
+        assert!(matches!(
+            impls[1].items[0],
+            syn::ImplItem::Fn(ref f) if f.sig.ident == "foo"
+        ));
+        assert!(impls[2].attrs.contains(&build_attrs[0]));
+        assert!(matches!(
+            impls[2].items[0],
+            syn::ImplItem::Fn(ref f) if f.sig.ident == "build"
+        ));
+    }
+
+    #[test]
+    fn api_snapshot() {
+        let mut builder = default_builder!();
+        builder.functions.clear();
+        builder.api_snapshot = Some(vec![quote!(
+            let _: &mut FooBuilder = b.foo(value::<Foo>());
+        )]);
+
+        let file: syn::File = syn::parse2(quote!(#builder)).unwrap();
+        let module = file
+            .items
+            .iter()
+            .find_map(|item| match item {
+                syn::Item::Mod(item) => Some(item),
+                _ => None,
+            })
+            .unwrap();
+
+        assert_eq!(module.ident, "__FooBuilder_api");
+        assert_eq!(quote!(#module).to_string(), {
+            let mut result = quote!();
+
+            #[cfg(not(feature = "clippy"))]
+            result.append_all(quote!(#[allow(clippy::all)]));
+
+            result.append_all(quote!(
+                #[doc(hidden)]
+                #[allow(non_snake_case, dead_code, deprecated, unused_imports)]
+                mod __FooBuilder_api {
+                    use super::*;
+
+                    fn value<T>() -> T {
+                        ::db::export::core::unreachable!()
+                    }
+
+                    fn _assert_api(b: &mut FooBuilder) {
+                        let _: &mut FooBuilder = b.foo(value::<Foo>());
+                    }
+                }
+            ));
+
+            result.to_string()
+        });
+    }
+
+    #[test]
+    fn allow_lints() {
+        let allow_lints = vec![
+            parse_quote!(missing_docs),
+            parse_quote!(clippy::needless_update),
+        ];
+        let mut builder = default_builder!();
+        builder.allow_lints = &allow_lints;
+        builder.generate_error = false;
+
+        assert_eq!(
+            quote!(#builder).to_string(),
+            {
+                let mut result = quote!();
+
+                #[cfg(not(feature = "clippy"))]
+                result.append_all(quote!(#[allow(clippy::all)]));
+
+                result.append_all(quote!(
+                    #[allow(missing_docs, clippy::needless_update)]
+                    #[derive(Clone)]
+                    pub struct FooBuilder {
+                        foo: u32,
+                    }
+                ));
+
+                #[cfg(not(feature = "clippy"))]
+                result.append_all(quote!(#[allow(clippy::all)]));
+
+                result.append_all(quote!(
+                    #[allow(missing_docs, clippy::needless_update)]
+                    #[allow(dead_code)]
+                    impl FooBuilder {
+                        fn bar () -> {
+                            unimplemented!()
+                        }
+
+                        /// Create an empty builder, with all fields set to `None` or `PhantomData`.
+                        fn create_empty() -> Self {
+                            Self {
+                                foo: ::db::export::core::default::Default::default(),
+                            }
+                        }
+                    }
+
+                    #[automatically_derived]
+                    #[allow(missing_docs, clippy::needless_update)]
+                    impl ::db::export::core::default::Default for FooBuilder {
+                        fn default() -> Self {
+                            Self::create_empty()
+                        }
+                    }
+                ));
+
+                result
+            }
+            .to_string()
+        );
+    }
+
     #[test]
     fn no_validation_error() {
         let mut builder = default_builder!();
@@ -881,12 +2074,25 @@ mod tests {
                         UninitializedField(&'static str),
                     }
 
+                    #[automatically_derived]
                     impl ::db::export::core::convert::From<::db::UninitializedFieldError> for FooBuilderError {
                         fn from(s: ::db::UninitializedFieldError) -> Self {
                             Self::UninitializedField(s.field_name())
                         }
                     }
 
+                    #[automatically_derived]
+                    impl ::db::BuilderError for FooBuilderError {
+                        #[allow(unreachable_patterns)]
+                        fn uninitialized_field(&self) -> ::db::export::core::option::Option<&str> {
+                            match self {
+                                Self::UninitializedField(ref field) => ::db::export::core::option::Option::Some(field),
+                                _ => ::db::export::core::option::Option::None,
+                            }
+                        }
+                    }
+
+                    #[automatically_derived]
                     impl ::db::export::core::fmt::Display for FooBuilderError {
                         fn fmt(&self, f: &mut ::db::export::core::fmt::Formatter) -> ::db::export::core::fmt::Result {
                             match self {
@@ -895,6 +2101,7 @@ mod tests {
                         }
                     }
 
+                    #[automatically_derived]
                     impl std::error::Error for FooBuilderError {}
                 ));
 
@@ -0,0 +1,321 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+
+use crate::builder::allow_lints_attr;
+
+/// `Display` impl for the builder, which summarizes its fields on one line,
+/// implementing `quote::ToTokens`.
+///
+/// Fields which have been set are printed with their `Debug` representation, unless they are
+/// redacted.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::{BuilderDisplay, BuilderDisplayField};
+/// # fn main() {
+/// #    let display = default_builder_display!();
+/// #
+/// #    assert_eq!(quote!(#display).to_string(), quote!(
+/// #[automatically_derived]
+/// impl ::derive_builder::export::core::fmt::Display for FooBuilder
+/// where
+///     u32: ::derive_builder::export::core::fmt::Debug,
+/// {
+///     fn fmt(&self, f: &mut ::derive_builder::export::core::fmt::Formatter) -> ::derive_builder::export::core::fmt::Result {
+///         f.write_str("FooBuilder {")?;
+///         f.write_str(" foo: ")?;
+///         match self.foo {
+///             ::derive_builder::export::core::option::Option::Some(ref value) => write!(f, "{:?}", value)?,
+///             ::derive_builder::export::core::option::Option::None => f.write_str("<unset>")?,
+///         }
+///         f.write_str(" }")
+///     }
+/// }
+/// #    ).to_string());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct BuilderDisplay<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Enables code generation for this impl.
+    pub enabled: bool,
+    /// Name of the builder struct.
+    pub builder_ty: syn::Ident,
+    /// Type parameters and lifetimes attached to the builder struct.
+    pub generics: Option<&'a syn::Generics>,
+    /// Lints to allow on the impl, e.g. `missing_docs`.
+    pub allow_lints: &'a [syn::Path],
+    /// Fields of the builder, in declaration order.
+    pub fields: Vec<BuilderDisplayField<'a>>,
+}
+
+/// A field which is summarized by a `BuilderDisplay`.
+#[derive(Debug)]
+pub struct BuilderDisplayField<'a> {
+    /// Name of the builder field.
+    pub field_ident: &'a syn::Ident,
+    /// Name of the field in the output.
+    pub name: String,
+    /// Type of the value which is printed, i.e. the contents of the builder field's `Option`,
+    /// or the builder field itself if `optional` is `false`.
+    pub ty: &'a syn::Type,
+    /// Whether the builder field is an `Option` which is `None` until the setter is called.
+    pub optional: bool,
+    /// Print `<redacted>` instead of the value.
+    pub redacted: bool,
+}
+
+impl<'a> ToTokens for BuilderDisplay<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if !self.enabled {
+            return;
+        }
+
+        let crate_root = self.crate_root;
+        let builder_ty = &self.builder_ty;
+        let allow_lints = allow_lints_attr(self.allow_lints);
+        let generics = self.generics.cloned().unwrap_or_default();
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let mut predicates: Vec<TokenStream> = where_clause
+            .map(|w| w.predicates.iter().map(|p| quote!(#p)).collect())
+            .unwrap_or_default();
+        predicates.extend(self.fields.iter().filter(|f| !f.redacted).map(|f| {
+            let ty = f.ty;
+            quote!(#ty: #crate_root::export::core::fmt::Debug)
+        }));
+
+        let opening = format!("{} {{", builder_ty);
+        let closing = if self.fields.is_empty() { "}" } else { " }" };
+        let field_summaries = self.fields.iter().enumerate().map(|(i, f)| {
+            let field_ident = f.field_ident;
+            let label = format!("{}{}: ", if i == 0 { " " } else { ", " }, f.name);
+            let value = if f.redacted {
+                quote!(f.write_str("<redacted>")?)
+            } else {
+                quote!(write!(f, "{:?}", value)?)
+            };
+            let summary = if f.optional {
+                let value_pattern = if f.redacted {
+                    quote!(_)
+                } else {
+                    quote!(ref value)
+                };
+                quote!(
+                    match self.#field_ident {
+                        #crate_root::export::core::option::Option::Some(#value_pattern) => #value,
+                        #crate_root::export::core::option::Option::None => f.write_str("<unset>")?,
+                    }
+                )
+            } else {
+                quote!(
+                    let value = &self.#field_ident;
+                    #value;
+                )
+            };
+
+            quote!(
+                f.write_str(#label)?;
+                #summary
+            )
+        });
+
+        let where_clause = if predicates.is_empty() {
+            quote!()
+        } else {
+            quote!(where #(#predicates,)*)
+        };
+
+        tokens.append_all(quote!(
+            #[automatically_derived]
+            #allow_lints
+            impl #impl_generics #crate_root::export::core::fmt::Display for #builder_ty #ty_generics #where_clause {
+                fn fmt(&self, f: &mut #crate_root::export::core::fmt::Formatter) -> #crate_root::export::core::fmt::Result {
+                    f.write_str(#opening)?;
+                    #(#field_summaries)*
+                    f.write_str(#closing)
+                }
+            }
+        ));
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[cfg(test)] // This contains a Box::leak, so is suitable only for tests
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_builder_display {
+    () => {{
+        BuilderDisplay {
+            // Deliberately don't use the default value here - make sure
+            // that all test cases are passing crate_root through properly.
+            crate_root: &parse_quote!(::db),
+            enabled: true,
+            builder_ty: syn::Ident::new("FooBuilder", ::proc_macro2::Span::call_site()),
+            generics: None,
+            allow_lints: &[],
+            fields: vec![BuilderDisplayField {
+                field_ident: Box::leak(Box::new(syn::Ident::new(
+                    "foo",
+                    ::proc_macro2::Span::call_site(),
+                ))),
+                name: "foo".to_string(),
+                ty: Box::leak(Box::new(parse_quote!(u32))),
+                optional: true,
+                redacted: false,
+            }],
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn optional_field() {
+        let display = default_builder_display!();
+        let fmt = quote!(::db::export::core::fmt);
+        let option = quote!(::db::export::core::option::Option);
+
+        assert_eq!(
+            quote!(#display).to_string(),
+            quote!(
+                #[automatically_derived]
+                impl #fmt::Display for FooBuilder
+                where
+                    u32: #fmt::Debug,
+                {
+                    fn fmt(&self, f: &mut #fmt::Formatter) -> #fmt::Result {
+                        f.write_str("FooBuilder {")?;
+                        f.write_str(" foo: ")?;
+                        match self.foo {
+                            #option::Some(ref value) => write!(f, "{:?}", value)?,
+                            #option::None => f.write_str("<unset>")?,
+                        }
+                        f.write_str(" }")
+                    }
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn redacted_field() {
+        let mut display = default_builder_display!();
+        display.fields[0].redacted = true;
+        let fmt = quote!(::db::export::core::fmt);
+        let option = quote!(::db::export::core::option::Option);
+
+        assert_eq!(
+            quote!(#display).to_string(),
+            quote!(
+                #[automatically_derived]
+                impl #fmt::Display for FooBuilder {
+                    fn fmt(&self, f: &mut #fmt::Formatter) -> #fmt::Result {
+                        f.write_str("FooBuilder {")?;
+                        f.write_str(" foo: ")?;
+                        match self.foo {
+                            #option::Some(_) => f.write_str("<redacted>")?,
+                            #option::None => f.write_str("<unset>")?,
+                        }
+                        f.write_str(" }")
+                    }
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn custom_field_type() {
+        let mut display = default_builder_display!();
+        display.fields[0].optional = false;
+        let fmt = quote!(::db::export::core::fmt);
+
+        assert_eq!(
+            quote!(#display).to_string(),
+            quote!(
+                #[automatically_derived]
+                impl #fmt::Display for FooBuilder
+                where
+                    u32: #fmt::Debug,
+                {
+                    fn fmt(&self, f: &mut #fmt::Formatter) -> #fmt::Result {
+                        f.write_str("FooBuilder {")?;
+                        f.write_str(" foo: ")?;
+                        let value = &self.foo;
+                        write!(f, "{:?}", value)?;
+                        f.write_str(" }")
+                    }
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn no_fields() {
+        let mut display = default_builder_display!();
+        display.fields.clear();
+        let fmt = quote!(::db::export::core::fmt);
+
+        assert_eq!(
+            quote!(#display).to_string(),
+            quote!(
+                #[automatically_derived]
+                impl #fmt::Display for FooBuilder {
+                    fn fmt(&self, f: &mut #fmt::Formatter) -> #fmt::Result {
+                        f.write_str("FooBuilder {")?;
+                        f.write_str("}")
+                    }
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn allow_lints() {
+        let allow_lints = vec![parse_quote!(missing_docs)];
+        let mut display = default_builder_display!();
+        display.allow_lints = &allow_lints;
+        display.fields.clear();
+        let fmt = quote!(::db::export::core::fmt);
+
+        assert_eq!(
+            quote!(#display).to_string(),
+            quote!(
+                #[automatically_derived]
+                #[allow(missing_docs)]
+                impl #fmt::Display for FooBuilder {
+                    fn fmt(&self, f: &mut #fmt::Formatter) -> #fmt::Result {
+                        f.write_str("FooBuilder {")?;
+                        f.write_str("}")
+                    }
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn disabled() {
+        let mut display = default_builder_display!();
+        display.enabled = false;
+
+        assert_eq!(quote!(#display).to_string(), "");
+    }
+}
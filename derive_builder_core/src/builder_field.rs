@@ -2,6 +2,9 @@ use std::borrow::Cow;
 
 use proc_macro2::TokenStream;
 use quote::{ToTokens, TokenStreamExt};
+use syn::spanned::Spanned;
+
+use crate::{lazy_field_ident, DefaultExpression};
 
 /// Field for the builder struct, implementing `quote::ToTokens`.
 ///
@@ -29,6 +32,7 @@ use quote::{ToTokens, TokenStreamExt};
 /// # }
 /// ```
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct BuilderField<'a> {
     /// Path to the root of the derive_builder crate.
     pub crate_root: &'a syn::Path,
@@ -40,6 +44,11 @@ pub struct BuilderField<'a> {
     pub field_visibility: Cow<'a, syn::Visibility>,
     /// Attributes which will be attached to this builder field.
     pub attrs: &'a [syn::Attribute],
+    /// Initial value of the builder field, instead of `Default::default()`.
+    pub default_value: Option<&'a DefaultExpression>,
+    /// Whether a hidden field holds the closure of the `_with` setter of `setter(lazy)`, which
+    /// computes the value if the field isn't set.
+    pub lazy: bool,
 }
 
 impl<'a> ToTokens for BuilderField<'a> {
@@ -51,15 +60,92 @@ impl<'a> ToTokens for BuilderField<'a> {
         tokens.append_all(quote!(
             #(#attrs)* #vis #ident: #ty,
         ));
+
+        if let (true, BuilderFieldType::Optional(value_ty)) = (self.lazy, &self.field_type) {
+            let crate_root = self.crate_root;
+            let lazy_ident = lazy_field_ident(ident);
+            let cfg_attrs = self.cfg_attrs();
+            tokens.append_all(quote!(
+                #(#cfg_attrs)*
+                #[doc(hidden)]
+                #lazy_ident: #crate_root::export::core::option::Option<#crate_root::LazyValue<#value_ty>>,
+            ));
+        }
     }
 }
 
 impl<'a> BuilderField<'a> {
-    /// Emits a struct field initializer that initializes the field to `Default::default`.
+    /// Create a public builder field for the target field `field_ident`, without attributes.
+    pub fn new(
+        crate_root: &'a syn::Path,
+        field_ident: &'a syn::Ident,
+        field_type: BuilderFieldType<'a>,
+    ) -> Self {
+        BuilderField {
+            crate_root,
+            field_ident,
+            field_type,
+            field_visibility: Cow::Owned(parse_quote!(pub)),
+            attrs: &[],
+            default_value: None,
+            lazy: false,
+        }
+    }
+
+    /// Emits a struct field initializer that initializes the field to `Default::default`, or
+    /// to the `default_value` if there is one.
     pub fn default_initializer_tokens(&self) -> TokenStream {
         let ident = self.field_ident;
         let crate_root = self.crate_root;
-        quote! { #ident : #crate_root::export::core::default::Default::default(), }
+        let mut tokens = match self.default_value {
+            Some(expr) => {
+                let expr = expr.with_crate_root(crate_root);
+                quote! { #ident : #expr, }
+            }
+            None => quote! { #ident : #crate_root::export::core::default::Default::default(), },
+        };
+        tokens.extend(self.lazy_initializer_tokens());
+        tokens
+    }
+
+    /// Emits a struct field initializer which can be evaluated in a `const fn`, i.e. `None` or
+    /// `PhantomData` rather than a call of `Default::default`.
+    ///
+    /// Fields with a custom builder type still use `Default::default`.
+    pub fn const_initializer_tokens(&self) -> TokenStream {
+        let ident = self.field_ident;
+        let crate_root = self.crate_root;
+        match self.field_type {
+            BuilderFieldType::Optional(_) => {
+                let mut tokens =
+                    quote! { #ident : #crate_root::export::core::option::Option::None, };
+                tokens.extend(self.lazy_initializer_tokens());
+                tokens
+            }
+            BuilderFieldType::Phantom(_) => {
+                quote! { #ident : #crate_root::export::core::marker::PhantomData, }
+            }
+            BuilderFieldType::Precise(_) => self.default_initializer_tokens(),
+        }
+    }
+
+    /// Emits the initializer of the hidden field of `setter(lazy)`, if there is one.
+    fn lazy_initializer_tokens(&self) -> Option<TokenStream> {
+        if !self.lazy {
+            return None;
+        }
+
+        let crate_root = self.crate_root;
+        let lazy_ident = lazy_field_ident(self.field_ident);
+        let cfg_attrs = self.cfg_attrs();
+        Some(
+            quote! { #(#cfg_attrs)* #lazy_ident : #crate_root::export::core::option::Option::None, },
+        )
+    }
+
+    /// The `#[cfg(...)]` attributes of the field, which also apply to its hidden field.
+    fn cfg_attrs(&self) -> impl Iterator<Item = &'a syn::Attribute> {
+        self.attrs.iter().filter(|attr| attr.path().is_ident("cfg"))
     }
 }
 
@@ -87,7 +173,7 @@ impl<'a> BuilderFieldType<'a> {
     ///
     /// Return value:
     ///  * `.0`: type of the argument to the setter function
-    ///          (before application of `strip_option`, `into`)
+    ///    (before application of `strip_option`, `into`)
     ///  * `.1`: whether the builder field is `Option<type>` rather than just `type`
     pub fn setter_type_info(&'a self) -> (&'a syn::Type, bool) {
         match self {
@@ -114,11 +200,11 @@ impl<'a> ToTokens for BuilderFieldTypeWithCrateRoot<'a> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let crate_root = self.crate_root;
         match self.field_type {
-            BuilderFieldType::Optional(ty) => tokens.append_all(quote!(
+            BuilderFieldType::Optional(ty) => tokens.append_all(quote_spanned!(ty.span()=>
                 #crate_root::export::core::option::Option<#ty>
             )),
             BuilderFieldType::Precise(ty) => ty.to_tokens(tokens),
-            BuilderFieldType::Phantom(ty) => tokens.append_all(quote!(
+            BuilderFieldType::Phantom(ty) => tokens.append_all(quote_spanned!(ty.span()=>
                 #crate_root::export::core::marker::PhantomData<#ty>
             )),
         }
@@ -140,6 +226,8 @@ macro_rules! default_builder_field {
             field_type: BuilderFieldType::Optional(Box::leak(Box::new(parse_quote!(String)))),
             field_visibility: ::std::borrow::Cow::Owned(parse_quote!(pub)),
             attrs: &[parse_quote!(#[some_attr])],
+            default_value: None,
+            lazy: false,
         }
     }};
 }
@@ -181,6 +269,71 @@ mod tests {
         );
     }
 
+    #[test]
+    fn const_initializer() {
+        let mut field = default_builder_field!();
+
+        assert_eq!(
+            field.const_initializer_tokens().to_string(),
+            quote!(foo: ::db::export::core::option::Option::None,).to_string()
+        );
+
+        field.field_type = match field.field_type {
+            BuilderFieldType::Optional(ty) => BuilderFieldType::Phantom(ty),
+            _ => panic!(),
+        };
+
+        assert_eq!(
+            field.const_initializer_tokens().to_string(),
+            quote!(foo: ::db::export::core::marker::PhantomData,).to_string()
+        );
+    }
+
+    #[test]
+    fn lazy() {
+        let mut field = default_builder_field!();
+        field.lazy = true;
+
+        assert_eq!(
+            quote!(#field).to_string(),
+            quote!(
+                #[some_attr] pub foo: ::db::export::core::option::Option<String>,
+                #[doc(hidden)]
+                __lazy_foo: ::db::export::core::option::Option<::db::LazyValue<String>>,
+            )
+            .to_string()
+        );
+        assert_eq!(
+            field.const_initializer_tokens().to_string(),
+            quote!(
+                foo: ::db::export::core::option::Option::None,
+                __lazy_foo: ::db::export::core::option::Option::None,
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn default_value() {
+        let ty: syn::Type = parse_quote!(u16);
+        let default = DefaultExpression::explicit::<syn::Expr>(parse_quote!(8080));
+        let mut field = default_builder_field!();
+        field.field_type = BuilderFieldType::Precise(&ty);
+        field.default_value = Some(&default);
+
+        assert_eq!(
+            quote!(#field).to_string(),
+            quote!(
+                #[some_attr] pub foo: u16,
+            )
+            .to_string()
+        );
+        assert_eq!(
+            field.default_initializer_tokens().to_string(),
+            quote!(foo: { 8080 },).to_string()
+        );
+    }
+
     #[test]
     fn private_field() {
         let private = Cow::Owned(syn::Visibility::Inherited);
@@ -0,0 +1,297 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+
+use crate::builder::allow_lints_attr;
+
+/// `PartialEq`, `Eq`, `Hash` and `ConfigKey` impls for the builder, which compare and hash the
+/// fields which have been set, implementing `quote::ToTokens`.
+///
+/// Unset fields aren't hashed, so adding a field to the struct doesn't change the keys of
+/// builders which don't set it.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::{ConfigKeyImpl, ConfigKeyField};
+/// # fn main() {
+/// #    let config_key = default_config_key_impl!();
+/// #
+/// #    assert_eq!(quote!(#config_key).to_string(), quote!(
+/// #[automatically_derived]
+/// impl ::derive_builder::export::core::cmp::PartialEq for FooBuilder
+/// where
+///     u32: ::derive_builder::export::core::cmp::PartialEq,
+/// {
+///     fn eq(&self, other: &Self) -> bool {
+///         self.foo == other.foo
+///     }
+/// }
+///
+/// #[automatically_derived]
+/// impl ::derive_builder::export::core::cmp::Eq for FooBuilder
+/// where
+///     u32: ::derive_builder::export::core::cmp::Eq,
+/// {}
+///
+/// #[automatically_derived]
+/// impl ::derive_builder::export::core::hash::Hash for FooBuilder
+/// where
+///     u32: ::derive_builder::export::core::hash::Hash,
+/// {
+///     fn hash<H: ::derive_builder::export::core::hash::Hasher>(&self, state: &mut H) {
+///         if let ::derive_builder::export::core::option::Option::Some(ref value) = self.foo {
+///             ::derive_builder::export::core::hash::Hash::hash("foo", state);
+///             ::derive_builder::export::core::hash::Hash::hash(value, state);
+///         }
+///     }
+/// }
+///
+/// #[automatically_derived]
+/// impl ::derive_builder::ConfigKey for FooBuilder
+/// where
+///     u32: ::derive_builder::export::core::cmp::Eq + ::derive_builder::export::core::hash::Hash,
+/// {}
+/// #    ).to_string());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ConfigKeyImpl<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Enables code generation for these impls.
+    pub enabled: bool,
+    /// Name of the builder struct.
+    pub builder_ty: syn::Ident,
+    /// Type parameters and lifetimes attached to the builder struct.
+    pub generics: Option<&'a syn::Generics>,
+    /// Lints to allow on the impls, e.g. `missing_docs`.
+    pub allow_lints: &'a [syn::Path],
+    /// Fields of the builder, in declaration order.
+    pub fields: Vec<ConfigKeyField<'a>>,
+}
+
+/// A field which is compared and hashed by a `ConfigKeyImpl`.
+#[derive(Debug)]
+pub struct ConfigKeyField<'a> {
+    /// Name of the builder field.
+    pub field_ident: &'a syn::Ident,
+    /// Name of the field, which is hashed before its value.
+    pub name: String,
+    /// Type of the value which is hashed, i.e. the contents of the builder field's `Option`,
+    /// or the builder field itself if `optional` is `false`.
+    pub ty: &'a syn::Type,
+    /// Whether the builder field is an `Option` which is `None` until the setter is called.
+    pub optional: bool,
+}
+
+impl<'a> ToTokens for ConfigKeyImpl<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if !self.enabled {
+            return;
+        }
+
+        let crate_root = self.crate_root;
+        let core = quote!(#crate_root::export::core);
+        let builder_ty = &self.builder_ty;
+        let allow_lints = allow_lints_attr(self.allow_lints);
+        let generics = self.generics.cloned().unwrap_or_default();
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let where_clause_with = |bound: TokenStream| {
+            let mut predicates: Vec<TokenStream> = where_clause
+                .map(|w| w.predicates.iter().map(|p| quote!(#p)).collect())
+                .unwrap_or_default();
+            predicates.extend(self.fields.iter().map(|f| {
+                let ty = f.ty;
+                quote!(#ty: #bound)
+            }));
+            if predicates.is_empty() {
+                quote!()
+            } else {
+                quote!(where #(#predicates,)*)
+            }
+        };
+
+        let eq = if self.fields.is_empty() {
+            quote!(true)
+        } else {
+            let comparisons = self.fields.iter().map(|f| {
+                let field_ident = f.field_ident;
+                quote!(self.#field_ident == other.#field_ident)
+            });
+            quote!(#(#comparisons)&&*)
+        };
+
+        let field_hashes = self.fields.iter().map(|f| {
+            let field_ident = f.field_ident;
+            let name = &f.name;
+            if f.optional {
+                quote!(
+                    if let #core::option::Option::Some(ref value) = self.#field_ident {
+                        #core::hash::Hash::hash(#name, state);
+                        #core::hash::Hash::hash(value, state);
+                    }
+                )
+            } else {
+                quote!(
+                    #core::hash::Hash::hash(#name, state);
+                    #core::hash::Hash::hash(&self.#field_ident, state);
+                )
+            }
+        });
+
+        let partial_eq_where = where_clause_with(quote!(#core::cmp::PartialEq));
+        let eq_where = where_clause_with(quote!(#core::cmp::Eq));
+        let hash_where = where_clause_with(quote!(#core::hash::Hash));
+        let config_key_where = where_clause_with(quote!(#core::cmp::Eq + #core::hash::Hash));
+
+        tokens.append_all(quote!(
+            #[automatically_derived]
+            #allow_lints
+            impl #impl_generics #core::cmp::PartialEq for #builder_ty #ty_generics #partial_eq_where {
+                fn eq(&self, other: &Self) -> bool {
+                    #eq
+                }
+            }
+
+            #[automatically_derived]
+            #allow_lints
+            impl #impl_generics #core::cmp::Eq for #builder_ty #ty_generics #eq_where {}
+
+            #[automatically_derived]
+            #allow_lints
+            impl #impl_generics #core::hash::Hash for #builder_ty #ty_generics #hash_where {
+                fn hash<H: #core::hash::Hasher>(&self, state: &mut H) {
+                    #(#field_hashes)*
+                }
+            }
+
+            #[automatically_derived]
+            #allow_lints
+            impl #impl_generics #crate_root::ConfigKey for #builder_ty #ty_generics #config_key_where {}
+        ));
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[cfg(test)] // This contains a Box::leak, so is suitable only for tests
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_config_key_impl {
+    () => {{
+        ConfigKeyImpl {
+            // Deliberately don't use the default value here - make sure
+            // that all test cases are passing crate_root through properly.
+            crate_root: &parse_quote!(::db),
+            enabled: true,
+            builder_ty: syn::Ident::new("FooBuilder", ::proc_macro2::Span::call_site()),
+            generics: None,
+            allow_lints: &[],
+            fields: vec![ConfigKeyField {
+                field_ident: Box::leak(Box::new(syn::Ident::new(
+                    "foo",
+                    ::proc_macro2::Span::call_site(),
+                ))),
+                name: "foo".to_string(),
+                ty: Box::leak(Box::new(parse_quote!(u32))),
+                optional: true,
+            }],
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn optional_field() {
+        let config_key = default_config_key_impl!();
+        let core = quote!(::db::export::core);
+
+        assert_eq!(
+            quote!(#config_key).to_string(),
+            quote!(
+                #[automatically_derived]
+                impl #core::cmp::PartialEq for FooBuilder
+                where
+                    u32: #core::cmp::PartialEq,
+                {
+                    fn eq(&self, other: &Self) -> bool {
+                        self.foo == other.foo
+                    }
+                }
+
+                #[automatically_derived]
+                impl #core::cmp::Eq for FooBuilder
+                where
+                    u32: #core::cmp::Eq,
+                {}
+
+                #[automatically_derived]
+                impl #core::hash::Hash for FooBuilder
+                where
+                    u32: #core::hash::Hash,
+                {
+                    fn hash<H: #core::hash::Hasher>(&self, state: &mut H) {
+                        if let #core::option::Option::Some(ref value) = self.foo {
+                            #core::hash::Hash::hash("foo", state);
+                            #core::hash::Hash::hash(value, state);
+                        }
+                    }
+                }
+
+                #[automatically_derived]
+                impl ::db::ConfigKey for FooBuilder
+                where
+                    u32: #core::cmp::Eq + #core::hash::Hash,
+                {}
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn required_fields() {
+        let mut config_key = default_config_key_impl!();
+        config_key.fields[0].optional = false;
+        config_key.fields.push(ConfigKeyField {
+            field_ident: Box::leak(Box::new(syn::Ident::new(
+                "bar",
+                ::proc_macro2::Span::call_site(),
+            ))),
+            name: "bar".to_string(),
+            ty: Box::leak(Box::new(parse_quote!(String))),
+            optional: false,
+        });
+        let tokens = quote!(#config_key).to_string();
+
+        assert!(
+            tokens.contains(&quote!(self.foo == other.foo && self.bar == other.bar).to_string())
+        );
+        assert!(tokens.contains(
+            &quote!(
+                ::db::export::core::hash::Hash::hash("bar", state);
+                ::db::export::core::hash::Hash::hash(&self.bar, state);
+            )
+            .to_string()
+        ));
+    }
+
+    #[test]
+    fn disabled() {
+        let mut config_key = default_config_key_impl!();
+        config_key.enabled = false;
+
+        assert_eq!(quote!(#config_key).to_string(), quote!().to_string());
+    }
+}
@@ -0,0 +1,251 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+
+/// Declarative macro which constructs the target struct from `field: value` pairs,
+/// implementing `quote::ToTokens`.
+///
+/// Every pair is turned into a call of the corresponding setter, followed by a call of the build
+/// method. Omitting a required field is a compile error, which is reported by initializing a
+/// local struct that only has the required fields.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::ConstructorMacro;
+/// # fn main() {
+/// #    let constructor_macro = default_constructor_macro!();
+/// #
+/// #    assert_eq!(quote!(#constructor_macro).to_string(), quote!(
+/// #[allow(unused_macros)]
+/// macro_rules! foo {
+///     (@fields [$($set:tt)*] [$($required:tt)*]) => {{
+///         {
+///             #[allow(dead_code)]
+///             struct Foo { foo: (), }
+///             let _ = Foo { $($required)* };
+///         }
+///         FooBuilder::default() $($set)* .build().unwrap()
+///     }};
+///     (@fields [$($set:tt)*] [$($required:tt)*] foo : $value:expr $(, $($rest:tt)*)?) => {
+///         foo!(@fields [$($set)* .foo($value)] [$($required)* foo: (),] $($($rest)*)?)
+///     };
+///     // ...
+/// }
+/// #    ).to_string());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ConstructorMacro<'a> {
+    /// Enables code generation for this macro.
+    pub enabled: bool,
+    /// Name of the macro.
+    pub ident: syn::Ident,
+    /// Name of the target struct.
+    pub target_ty: &'a syn::Ident,
+    /// Name of the builder struct.
+    pub builder_ty: syn::Ident,
+    /// Name of the build method.
+    pub build_fn: &'a syn::Ident,
+    /// Fields which can be passed to the macro.
+    pub fields: Vec<ConstructorMacroField<'a>>,
+}
+
+/// A field which can be passed to a `ConstructorMacro`.
+#[derive(Debug)]
+pub struct ConstructorMacroField<'a> {
+    /// Name of the field, which is used as the key in the macro input.
+    pub field_ident: &'a syn::Ident,
+    /// Name of the setter which is called with the value.
+    pub setter_ident: syn::Ident,
+    /// The build method fails if this field has not been set.
+    pub required: bool,
+}
+
+impl<'a> ToTokens for ConstructorMacro<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if !self.enabled {
+            return;
+        }
+
+        let ident = &self.ident;
+        let target_ty = self.target_ty;
+        let builder_ty = &self.builder_ty;
+        let build_fn = self.build_fn;
+        let required_fields = self
+            .fields
+            .iter()
+            .filter(|f| f.required)
+            .map(|f| f.field_ident);
+        let field_rules = self.fields.iter().map(|f| {
+            let field_ident = f.field_ident;
+            let setter_ident = &f.setter_ident;
+            let required = if f.required {
+                quote!(#field_ident: (),)
+            } else {
+                quote!()
+            };
+
+            quote!(
+                (@fields [$($set:tt)*] [$($required:tt)*] #field_ident : $value:expr $(, $($rest:tt)*)?) => {
+                    #ident!(@fields [$($set)* .#setter_ident($value)] [$($required)* #required] $($($rest)*)?)
+                };
+            )
+        });
+        let unexpected_input = format!(
+            "expected `field: value` for one of the fields of `{}`, found `",
+            target_ty
+        );
+
+        tokens.append_all(quote!(
+            #[allow(unused_macros)]
+            macro_rules! #ident {
+                (@fields [$($set:tt)*] [$($required:tt)*]) => {{
+                    {
+                        #[allow(dead_code)]
+                        struct #target_ty { #(#required_fields: (),)* }
+                        let _ = #target_ty { $($required)* };
+                    }
+                    #builder_ty::default() $($set)* .#build_fn().unwrap()
+                }};
+                #(#field_rules)*
+                (@fields [$($set:tt)*] [$($required:tt)*] $($rest:tt)*) => {
+                    compile_error!(concat!(#unexpected_input, stringify!($($rest)*), "`"))
+                };
+                ($($fields:tt)*) => {
+                    #ident!(@fields [] [] $($fields)*)
+                };
+            }
+        ));
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[cfg(test)] // This contains a Box::leak, so is suitable only for tests
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_constructor_macro {
+    () => {{
+        ConstructorMacro {
+            enabled: true,
+            ident: syn::Ident::new("foo", ::proc_macro2::Span::call_site()),
+            target_ty: Box::leak(Box::new(syn::Ident::new(
+                "Foo",
+                ::proc_macro2::Span::call_site(),
+            ))),
+            builder_ty: syn::Ident::new("FooBuilder", ::proc_macro2::Span::call_site()),
+            build_fn: Box::leak(Box::new(syn::Ident::new(
+                "build",
+                ::proc_macro2::Span::call_site(),
+            ))),
+            fields: vec![ConstructorMacroField {
+                field_ident: Box::leak(Box::new(syn::Ident::new(
+                    "foo",
+                    ::proc_macro2::Span::call_site(),
+                ))),
+                setter_ident: syn::Ident::new("foo", ::proc_macro2::Span::call_site()),
+                required: true,
+            }],
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn required_field() {
+        let constructor_macro = default_constructor_macro!();
+
+        assert_eq!(
+            quote!(#constructor_macro).to_string(),
+            quote!(
+                #[allow(unused_macros)]
+                macro_rules! foo {
+                    (@fields [$($set:tt)*] [$($required:tt)*]) => {{
+                        {
+                            #[allow(dead_code)]
+                            struct Foo { foo: (), }
+                            let _ = Foo { $($required)* };
+                        }
+                        FooBuilder::default() $($set)* .build().unwrap()
+                    }};
+                    (@fields [$($set:tt)*] [$($required:tt)*] foo : $value:expr $(, $($rest:tt)*)?) => {
+                        foo!(@fields [$($set)* .foo($value)] [$($required)* foo: (),] $($($rest)*)?)
+                    };
+                    (@fields [$($set:tt)*] [$($required:tt)*] $($rest:tt)*) => {
+                        compile_error!(concat!(
+                            "expected `field: value` for one of the fields of `Foo`, found `",
+                            stringify!($($rest)*),
+                            "`"
+                        ))
+                    };
+                    ($($fields:tt)*) => {
+                        foo!(@fields [] [] $($fields)*)
+                    };
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn optional_field_with_custom_names() {
+        let build_fn = syn::Ident::new("finish", ::proc_macro2::Span::call_site());
+        let mut constructor_macro = default_constructor_macro!();
+        constructor_macro.ident = syn::Ident::new("make_foo", ::proc_macro2::Span::call_site());
+        constructor_macro.build_fn = &build_fn;
+        constructor_macro.fields[0].setter_ident =
+            syn::Ident::new("with_foo", ::proc_macro2::Span::call_site());
+        constructor_macro.fields[0].required = false;
+
+        assert_eq!(
+            quote!(#constructor_macro).to_string(),
+            quote!(
+                #[allow(unused_macros)]
+                macro_rules! make_foo {
+                    (@fields [$($set:tt)*] [$($required:tt)*]) => {{
+                        {
+                            #[allow(dead_code)]
+                            struct Foo {}
+                            let _ = Foo { $($required)* };
+                        }
+                        FooBuilder::default() $($set)* .finish().unwrap()
+                    }};
+                    (@fields [$($set:tt)*] [$($required:tt)*] foo : $value:expr $(, $($rest:tt)*)?) => {
+                        make_foo!(@fields [$($set)* .with_foo($value)] [$($required)*] $($($rest)*)?)
+                    };
+                    (@fields [$($set:tt)*] [$($required:tt)*] $($rest:tt)*) => {
+                        compile_error!(concat!(
+                            "expected `field: value` for one of the fields of `Foo`, found `",
+                            stringify!($($rest)*),
+                            "`"
+                        ))
+                    };
+                    ($($fields:tt)*) => {
+                        make_foo!(@fields [] [] $($fields)*)
+                    };
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn disabled() {
+        let mut constructor_macro = default_constructor_macro!();
+        constructor_macro.enabled = false;
+
+        assert_eq!(quote!(#constructor_macro).to_string(), "");
+    }
+}
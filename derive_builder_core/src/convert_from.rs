@@ -0,0 +1,199 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+
+use crate::builder::allow_lints_attr;
+
+/// `From` impl which converts another builder into the builder, implementing `quote::ToTokens`.
+///
+/// Each field is taken from the identically named field of the other builder and converted
+/// with `Into`. Fields which the other builder doesn't have are left out, so they stay unset.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::{ConvertFrom, ConvertedField};
+/// # fn main() {
+/// #    let convert_from = default_convert_from!();
+/// #
+/// #    assert_eq!(quote!(#convert_from).to_string(), quote!(
+/// #[automatically_derived]
+/// impl ::derive_builder::export::core::convert::From<OldFooBuilder> for FooBuilder {
+///     #[allow(unused_mut, unused_variables)]
+///     fn from(source: OldFooBuilder) -> Self {
+///         let mut new = Self::create_empty();
+///         new.foo = source.foo.map(::derive_builder::export::core::convert::Into::into);
+///         new
+///     }
+/// }
+/// #    ).to_string());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ConvertFrom<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// The builder to convert from. No impl is generated if this is `None`.
+    pub source_ty: Option<&'a syn::Type>,
+    /// Name of the builder struct.
+    pub builder_ty: syn::Ident,
+    /// Type parameters and lifetimes attached to the builder struct.
+    pub generics: Option<&'a syn::Generics>,
+    /// Lints to allow on the impl, e.g. `missing_docs`.
+    pub allow_lints: &'a [syn::Path],
+    /// The inherent method which creates an empty builder.
+    pub create_empty: &'a syn::Ident,
+    /// Fields which are taken from the other builder.
+    pub fields: Vec<ConvertedField<'a>>,
+}
+
+/// A field which a `ConvertFrom` takes from the identically named field of the other builder.
+#[derive(Debug)]
+pub struct ConvertedField<'a> {
+    /// Name of the builder field.
+    pub field_ident: &'a syn::Ident,
+    /// Whether the builder field is an `Option` which is `None` until the setter is called,
+    /// so the conversion applies to its contents.
+    pub optional: bool,
+}
+
+impl<'a> ToTokens for ConvertFrom<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let source_ty = match self.source_ty {
+            Some(ty) => ty,
+            None => return,
+        };
+
+        let crate_root = self.crate_root;
+        let builder_ty = &self.builder_ty;
+        let allow_lints = allow_lints_attr(self.allow_lints);
+        let generics = self.generics.cloned().unwrap_or_default();
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let create_empty = self.create_empty;
+        let into = quote!(#crate_root::export::core::convert::Into::into);
+        let conversions = self.fields.iter().map(|f| {
+            let field_ident = f.field_ident;
+            if f.optional {
+                quote!(new.#field_ident = source.#field_ident.map(#into);)
+            } else {
+                quote!(new.#field_ident = #into(source.#field_ident);)
+            }
+        });
+
+        tokens.append_all(quote!(
+            #[automatically_derived]
+            #allow_lints
+            impl #impl_generics #crate_root::export::core::convert::From<#source_ty> for #builder_ty #ty_generics #where_clause {
+                #[allow(unused_mut, unused_variables)]
+                fn from(source: #source_ty) -> Self {
+                    let mut new = Self::#create_empty();
+                    #(#conversions)*
+                    new
+                }
+            }
+        ));
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[cfg(test)] // This contains a Box::leak, so is suitable only for tests
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_convert_from {
+    () => {
+        ConvertFrom {
+            // Deliberately don't use the default value here - make sure
+            // that all test cases are passing crate_root through properly.
+            crate_root: &parse_quote!(::db),
+            source_ty: Some(Box::leak(Box::new(parse_quote!(OldFooBuilder)))),
+            builder_ty: syn::Ident::new("FooBuilder", ::proc_macro2::Span::call_site()),
+            generics: None,
+            allow_lints: &[],
+            create_empty: Box::leak(Box::new(syn::Ident::new(
+                "create_empty",
+                ::proc_macro2::Span::call_site(),
+            ))),
+            fields: vec![ConvertedField {
+                field_ident: Box::leak(Box::new(syn::Ident::new(
+                    "foo",
+                    ::proc_macro2::Span::call_site(),
+                ))),
+                optional: true,
+            }],
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn simple() {
+        let convert_from = default_convert_from!();
+        let core = quote!(::db::export::core);
+
+        assert_eq!(
+            quote!(#convert_from).to_string(),
+            quote!(
+                #[automatically_derived]
+                impl #core::convert::From<OldFooBuilder> for FooBuilder {
+                    #[allow(unused_mut, unused_variables)]
+                    fn from(source: OldFooBuilder) -> Self {
+                        let mut new = Self::create_empty();
+                        new.foo = source.foo.map(#core::convert::Into::into);
+                        new
+                    }
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn custom_field_type() {
+        let mut convert_from = default_convert_from!();
+        convert_from.fields[0].optional = false;
+        let core = quote!(::db::export::core);
+
+        let tokens = quote!(#convert_from).to_string();
+        let expected = quote!(new.foo = #core::convert::Into::into(source.foo);).to_string();
+
+        assert!(tokens.contains(&expected), "{}", tokens);
+    }
+
+    #[test]
+    fn generic() {
+        let generics: syn::Generics = parse_quote!(<T: Clone>);
+        let source_ty: syn::Type = parse_quote!(OldFooBuilder<T>);
+        let mut convert_from = default_convert_from!();
+        convert_from.generics = Some(&generics);
+        convert_from.source_ty = Some(&source_ty);
+        let core = quote!(::db::export::core);
+
+        let tokens = quote!(#convert_from).to_string();
+        let expected_header = quote!(
+            impl<T: Clone> #core::convert::From<#source_ty> for FooBuilder<T>
+        )
+        .to_string();
+
+        assert!(tokens.contains(&expected_header), "{}", tokens);
+    }
+
+    #[test]
+    fn disabled() {
+        let mut convert_from = default_convert_from!();
+        convert_from.source_ty = None;
+
+        assert_eq!(quote!(#convert_from).to_string(), quote!().to_string());
+    }
+}
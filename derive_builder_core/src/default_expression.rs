@@ -1,12 +1,44 @@
 use crate::BlockContents;
-use proc_macro2::Span;
+use darling::util::Flag;
+use darling::FromMeta;
+use proc_macro2::{Span, TokenStream, TokenTree};
 use quote::ToTokens;
 
 /// A `DefaultExpression` can be either explicit or refer to the canonical trait.
 #[derive(Debug, Clone)]
 pub enum DefaultExpression {
+    /// Explicit expression, e.g. `default = "42"`.
     Explicit(BlockContents),
+    /// `Default::default()` of the field type, e.g. `default`.
     Trait,
+    /// The current time of the field type, e.g. `SystemTime` or `Instant`, evaluated at build,
+    /// e.g. `default(now)`.
+    Now,
+    /// The time since the Unix epoch, evaluated at build, e.g.
+    /// `default(since_epoch = "millis")`.
+    SinceEpoch(EpochUnit),
+}
+
+/// Unit of a `default(since_epoch = "...")` value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromMeta)]
+pub enum EpochUnit {
+    /// Whole seconds, e.g. for a `u64` field.
+    Secs,
+    /// Whole milliseconds.
+    Millis,
+    /// Whole microseconds.
+    Micros,
+    /// Whole nanoseconds.
+    Nanos,
+    /// The `Duration` itself.
+    Duration,
+}
+
+/// Options of a list of time defaults, e.g. `default(now)`.
+#[derive(Debug, Default, FromMeta)]
+struct TimeDefault {
+    now: Flag,
+    since_epoch: Option<EpochUnit>,
 }
 
 impl DefaultExpression {
@@ -23,13 +55,41 @@ impl DefaultExpression {
         }
     }
 
+    /// The span of the explicit expression, or the call site for `Trait`.
     pub fn span(&self) -> Span {
         match self {
             DefaultExpression::Explicit(block) => block.span(),
-            DefaultExpression::Trait => Span::call_site(),
+            _ => Span::call_site(),
         }
     }
 
+    /// Check if the expression references `self`, e.g. `default = "self.other()"`, which only
+    /// exists in the build method.
+    pub fn references_self(&self) -> bool {
+        fn contains_self(tokens: TokenStream) -> bool {
+            tokens.into_iter().any(|token| match token {
+                TokenTree::Ident(ident) => ident == "self",
+                TokenTree::Group(group) => contains_self(group.stream()),
+                _ => false,
+            })
+        }
+
+        match self {
+            DefaultExpression::Explicit(block) => contains_self(block.to_token_stream()),
+            _ => false,
+        }
+    }
+
+    /// Check if the default is the time of the build, which can't be evaluated in a `const fn`
+    /// and needs the `std` feature.
+    pub fn is_time(&self) -> bool {
+        matches!(
+            self,
+            DefaultExpression::Now | DefaultExpression::SinceEpoch(_)
+        )
+    }
+
+    /// Create an explicit default expression.
     #[cfg(test)]
     pub fn explicit<I: Into<BlockContents>>(content: I) -> Self {
         DefaultExpression::Explicit(content.into())
@@ -44,6 +104,26 @@ impl darling::FromMeta for DefaultExpression {
     fn from_value(value: &syn::Lit) -> darling::Result<Self> {
         Ok(Self::Explicit(BlockContents::from_value(value)?))
     }
+
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        Ok(Self::Explicit(BlockContents::from_expr(expr)?))
+    }
+
+    fn from_list(items: &[darling::ast::NestedMeta]) -> darling::Result<Self> {
+        match TimeDefault::from_list(items)? {
+            TimeDefault {
+                now,
+                since_epoch: None,
+            } if now.is_present() => Ok(DefaultExpression::Now),
+            TimeDefault {
+                now,
+                since_epoch: Some(unit),
+            } if !now.is_present() => Ok(DefaultExpression::SinceEpoch(unit)),
+            _ => Err(darling::Error::custom(
+                "Expected exactly one of `now` or `since_epoch = \"...\"`",
+            )),
+        }
+    }
 }
 
 /// Wrapper for `DefaultExpression`
@@ -61,6 +141,20 @@ impl<'a> ToTokens for DefaultExpressionWithCrateRoot<'a> {
                 #crate_root::export::core::default::Default::default()
             )
             .to_tokens(tokens),
+            DefaultExpression::Now => {
+                quote!(#crate_root::export::time::Now::now()).to_tokens(tokens)
+            }
+            DefaultExpression::SinceEpoch(unit) => {
+                let since_epoch = quote!(#crate_root::export::time::since_epoch());
+                match unit {
+                    EpochUnit::Secs => quote!(#since_epoch.as_secs() as _),
+                    EpochUnit::Millis => quote!(#since_epoch.as_millis() as _),
+                    EpochUnit::Micros => quote!(#since_epoch.as_micros() as _),
+                    EpochUnit::Nanos => quote!(#since_epoch.as_nanos() as _),
+                    EpochUnit::Duration => since_epoch,
+                }
+                .to_tokens(tokens)
+            }
         }
     }
 }
@@ -0,0 +1,122 @@
+use std::borrow::Cow;
+
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+
+/// Method which gives mutable access to the nested builder of a `delegate` field,
+/// implementing `quote::ToTokens`.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::DelegateAccessor;
+/// # fn main() {
+/// #    let accessor = default_delegate_accessor!();
+/// #
+/// #    assert_eq!(quote!(#accessor).to_string(), quote!(
+/// #[doc = "..."]
+/// pub fn database(&mut self) -> &mut DatabaseBuilder {
+///     &mut self.database
+/// }
+/// #    ).to_string());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct DelegateAccessor<'a> {
+    /// Enables code generation for this method.
+    pub enabled: bool,
+    /// Name of this method.
+    pub ident: syn::Ident,
+    /// Visibility of the method, e.g. `syn::Visibility::Public`.
+    pub visibility: Cow<'a, syn::Visibility>,
+    /// Name of the builder field which stores the nested builder.
+    pub field_ident: &'a syn::Ident,
+    /// Name of the field in errors of the nested builder.
+    pub field_name: String,
+    /// Type of the nested builder.
+    pub builder_ty: &'a syn::Type,
+}
+
+impl<'a> ToTokens for DelegateAccessor<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if !self.enabled {
+            return;
+        }
+
+        let vis = &self.visibility;
+        let ident = &self.ident;
+        let field_ident = self.field_ident;
+        let builder_ty = self.builder_ty;
+        let doc = format!(
+            "Access the builder of `{0}`, which is built together with this builder.\n\n\
+             Its errors name the field as `{0}.<field>`.",
+            self.field_name
+        );
+
+        tokens.append_all(quote!(
+            #[doc = #doc]
+            #vis fn #ident(&mut self) -> &mut #builder_ty {
+                &mut self.#field_ident
+            }
+        ));
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[cfg(test)] // This contains a Box::leak, so is suitable only for tests
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_delegate_accessor {
+    () => {
+        DelegateAccessor {
+            enabled: true,
+            ident: syn::Ident::new("database", ::proc_macro2::Span::call_site()),
+            visibility: ::std::borrow::Cow::Owned(parse_quote!(pub)),
+            field_ident: Box::leak(Box::new(syn::Ident::new(
+                "database",
+                ::proc_macro2::Span::call_site(),
+            ))),
+            field_name: "database".to_string(),
+            builder_ty: Box::leak(Box::new(parse_quote!(DatabaseBuilder))),
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn accessor() {
+        let accessor = default_delegate_accessor!();
+
+        assert_eq!(
+            quote!(#accessor).to_string(),
+            quote!(
+                #[doc = "Access the builder of `database`, which is built together with this builder.\n\nIts errors name the field as `database.<field>`."]
+                pub fn database(&mut self) -> &mut DatabaseBuilder {
+                    &mut self.database
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn disabled() {
+        let mut accessor = default_delegate_accessor!();
+        accessor.enabled = false;
+
+        assert_eq!(quote!(#accessor).to_string(), quote!().to_string());
+    }
+}
@@ -36,6 +36,9 @@ use quote::{ToTokens, TokenStreamExt};
 #[derive(Debug, Default, Clone)]
 pub struct DeprecationNotes(Vec<String>);
 
+/// Empty collection of notes, for components which borrow their notes.
+pub(crate) static NO_DEPRECATION_NOTES: DeprecationNotes = DeprecationNotes(Vec::new());
+
 impl ToTokens for DeprecationNotes {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         for note in &self.0 {
@@ -60,7 +63,7 @@ impl DeprecationNotes {
     }
 
     /// Create a view of these deprecation notes that can annotate a struct.
-    pub const fn as_item(&self) -> DeprecationNotesAsItem {
+    pub const fn as_item(&self) -> DeprecationNotesAsItem<'_> {
         DeprecationNotesAsItem(self)
     }
 }
@@ -5,6 +5,9 @@
 //! - {struct_name}
 //! - {builder_name}
 //! - {field_name}
+//! - {setter_calls}
+//! - {build_fn}
+//! - {required_fields}
 //!
 //! Templates are used like this:
 //!
@@ -0,0 +1,361 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+use syn::spanned::Spanned;
+
+use crate::builder::allow_lints_attr;
+use crate::BuilderPattern;
+
+/// `DynSet` impl for the builder, which sets fields by name with values of a runtime type,
+/// implementing `quote::ToTokens`.
+///
+/// Values are downcast to the type the setter takes and passed to the setter.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::{DynSetImpl, DynSetField};
+/// # fn main() {
+/// #    let dyn_set = default_dyn_set_impl!();
+/// #
+/// #    assert_eq!(quote!(#dyn_set).to_string(), quote!(
+/// #[automatically_derived]
+/// impl ::derive_builder::DynSet for FooBuilder
+/// where
+///     u32: 'static,
+/// {
+///     fn set(
+///         &mut self,
+///         field: &str,
+///         value: ::derive_builder::export::core::boxed::Box<dyn ::derive_builder::export::core::any::Any>,
+///     ) -> ::derive_builder::export::core::result::Result<(), ::derive_builder::DynSetError> {
+///         match field {
+///             "foo" => {
+///                 let value: u32 = match value.downcast::<u32>() {
+///                     ::derive_builder::export::core::result::Result::Ok(value) => *value,
+///                     ::derive_builder::export::core::result::Result::Err(_) => {
+///                         return ::derive_builder::export::core::result::Result::Err(
+///                             ::derive_builder::DynSetError::type_mismatch(
+///                                 "foo",
+///                                 ::derive_builder::export::core::any::type_name::<u32>()
+///                             )
+///                         );
+///                     }
+///                 };
+///                 self.foo(value);
+///                 ::derive_builder::export::core::result::Result::Ok(())
+///             }
+///             _ => ::derive_builder::export::core::result::Result::Err(
+///                 ::derive_builder::DynSetError::unknown_field(field)
+///             ),
+///         }
+///     }
+///
+///     fn field_type(&self, field: &str) -> ::derive_builder::export::core::option::Option<::derive_builder::export::core::any::TypeId> {
+///         match field {
+///             "foo" => ::derive_builder::export::core::option::Option::Some(
+///                 ::derive_builder::export::core::any::TypeId::of::<u32>()
+///             ),
+///             _ => ::derive_builder::export::core::option::Option::None,
+///         }
+///     }
+/// }
+/// #    ).to_string());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct DynSetImpl<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Enables code generation for this impl.
+    pub enabled: bool,
+    /// Name of the builder struct.
+    pub builder_ty: syn::Ident,
+    /// Type parameters and lifetimes attached to the builder struct.
+    pub generics: Option<&'a syn::Generics>,
+    /// Whether the type parameters must be `Clone`, because the inherent `impl` of the builder
+    /// requires it.
+    pub clone_bounds: bool,
+    /// Lints to allow on the impl, e.g. `missing_docs`.
+    pub allow_lints: &'a [syn::Path],
+    /// The inherent method which creates an empty builder.
+    pub create_empty: &'a syn::Ident,
+    /// Fields which can be set by name.
+    pub fields: Vec<DynSetField<'a>>,
+}
+
+/// A field which is set by name by a `DynSetImpl`.
+#[derive(Debug)]
+pub struct DynSetField<'a> {
+    /// Name of the field.
+    pub name: String,
+    /// Name of the setter which is called with the value.
+    pub setter: syn::Ident,
+    /// How the setter takes and returns `self` (e.g. mutably).
+    pub pattern: BuilderPattern,
+    /// Type of the value the setter takes, which the value is downcast to.
+    pub value_ty: &'a syn::Type,
+    /// `#[cfg(...)]` attributes of the setter, which are applied to the match arms as well.
+    pub cfg_attrs: Vec<&'a syn::Attribute>,
+}
+
+impl<'a> ToTokens for DynSetImpl<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if !self.enabled {
+            return;
+        }
+
+        let crate_root = self.crate_root;
+        let core = quote!(#crate_root::export::core);
+        let builder_ty = &self.builder_ty;
+        let allow_lints = allow_lints_attr(self.allow_lints);
+        let generics = self.generics.cloned().unwrap_or_default();
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let mut predicates: Vec<TokenStream> = where_clause
+            .map(|w| w.predicates.iter().map(|p| quote!(#p,)).collect())
+            .unwrap_or_default();
+        if self.clone_bounds {
+            predicates.extend(generics.type_params().map(|param| {
+                let ident = &param.ident;
+                quote!(#ident: #core::clone::Clone,)
+            }));
+        }
+        // Point errors about types which aren't `'static` at the field.
+        predicates.extend(self.fields.iter().map(|f| {
+            let ty = f.value_ty;
+            quote_spanned!(ty.span()=> #ty: 'static,)
+        }));
+        let where_clause = if predicates.is_empty() {
+            quote!()
+        } else {
+            quote!(where #(#predicates)*)
+        };
+
+        let create_empty = self.create_empty;
+        let set_arms = self.fields.iter().map(|f| {
+            let name = &f.name;
+            let setter = &f.setter;
+            let ty = f.value_ty;
+            let cfg_attrs = &f.cfg_attrs;
+            let set = match f.pattern {
+                BuilderPattern::Mutable => quote!(self.#setter(value);),
+                BuilderPattern::Immutable => quote!(*self = self.#setter(value);),
+                BuilderPattern::Owned => quote!(
+                    *self = #core::mem::replace(self, Self::#create_empty()).#setter(value);
+                ),
+            };
+            quote!(
+                #(#cfg_attrs)*
+                #name => {
+                    let value: #ty = match value.downcast::<#ty>() {
+                        #core::result::Result::Ok(value) => *value,
+                        #core::result::Result::Err(_) => {
+                            return #core::result::Result::Err(
+                                #crate_root::DynSetError::type_mismatch(
+                                    #name,
+                                    #core::any::type_name::<#ty>()
+                                )
+                            );
+                        }
+                    };
+                    #set
+                    #core::result::Result::Ok(())
+                }
+            )
+        });
+        let type_arms = self.fields.iter().map(|f| {
+            let name = &f.name;
+            let ty = f.value_ty;
+            let cfg_attrs = &f.cfg_attrs;
+            quote!(
+                #(#cfg_attrs)*
+                #name => #core::option::Option::Some(
+                    #core::any::TypeId::of::<#ty>()
+                ),
+            )
+        });
+
+        tokens.append_all(quote!(
+            #[automatically_derived]
+            #allow_lints
+            impl #impl_generics #crate_root::DynSet for #builder_ty #ty_generics #where_clause {
+                fn set(
+                    &mut self,
+                    field: &str,
+                    value: #core::boxed::Box<dyn #core::any::Any>,
+                ) -> #core::result::Result<(), #crate_root::DynSetError> {
+                    match field {
+                        #(#set_arms)*
+                        _ => #core::result::Result::Err(
+                            #crate_root::DynSetError::unknown_field(field)
+                        ),
+                    }
+                }
+
+                fn field_type(&self, field: &str) -> #core::option::Option<#core::any::TypeId> {
+                    match field {
+                        #(#type_arms)*
+                        _ => #core::option::Option::None,
+                    }
+                }
+            }
+        ));
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[cfg(test)] // This contains a Box::leak, so is suitable only for tests
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_dyn_set_impl {
+    () => {
+        DynSetImpl {
+            // Deliberately don't use the default value here - make sure
+            // that all test cases are passing crate_root through properly.
+            crate_root: &parse_quote!(::db),
+            enabled: true,
+            builder_ty: syn::Ident::new("FooBuilder", ::proc_macro2::Span::call_site()),
+            generics: None,
+            clone_bounds: true,
+            allow_lints: &[],
+            create_empty: Box::leak(Box::new(syn::Ident::new(
+                "create_empty",
+                ::proc_macro2::Span::call_site(),
+            ))),
+            fields: vec![DynSetField {
+                name: "foo".to_string(),
+                setter: syn::Ident::new("foo", ::proc_macro2::Span::call_site()),
+                pattern: BuilderPattern::Mutable,
+                value_ty: Box::leak(Box::new(parse_quote!(u32))),
+                cfg_attrs: vec![],
+            }],
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn simple() {
+        let dyn_set = default_dyn_set_impl!();
+        let core = quote!(::db::export::core);
+
+        assert_eq!(
+            quote!(#dyn_set).to_string(),
+            quote!(
+                #[automatically_derived]
+                impl ::db::DynSet for FooBuilder
+                where
+                    u32: 'static,
+                {
+                    fn set(
+                        &mut self,
+                        field: &str,
+                        value: #core::boxed::Box<dyn #core::any::Any>,
+                    ) -> #core::result::Result<(), ::db::DynSetError> {
+                        match field {
+                            "foo" => {
+                                let value: u32 = match value.downcast::<u32>() {
+                                    #core::result::Result::Ok(value) => *value,
+                                    #core::result::Result::Err(_) => {
+                                        return #core::result::Result::Err(
+                                            ::db::DynSetError::type_mismatch(
+                                                "foo",
+                                                #core::any::type_name::<u32>()
+                                            )
+                                        );
+                                    }
+                                };
+                                self.foo(value);
+                                #core::result::Result::Ok(())
+                            }
+                            _ => #core::result::Result::Err(
+                                ::db::DynSetError::unknown_field(field)
+                            ),
+                        }
+                    }
+
+                    fn field_type(&self, field: &str) -> #core::option::Option<#core::any::TypeId> {
+                        match field {
+                            "foo" => #core::option::Option::Some(
+                                #core::any::TypeId::of::<u32>()
+                            ),
+                            _ => #core::option::Option::None,
+                        }
+                    }
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn owned() {
+        let mut dyn_set = default_dyn_set_impl!();
+        dyn_set.fields[0].pattern = BuilderPattern::Owned;
+        let core = quote!(::db::export::core);
+
+        let tokens = quote!(#dyn_set).to_string();
+        let expected =
+            quote!(*self = #core::mem::replace(self, Self::create_empty()).foo(value);).to_string();
+
+        assert!(tokens.contains(&expected), "{}", tokens);
+    }
+
+    #[test]
+    fn generic() {
+        let generics: syn::Generics = parse_quote!(<T: Copy>);
+        let mut dyn_set = default_dyn_set_impl!();
+        dyn_set.generics = Some(&generics);
+        dyn_set.fields[0].value_ty = Box::leak(Box::new(parse_quote!(T)));
+        let core = quote!(::db::export::core);
+
+        let tokens = quote!(#dyn_set).to_string();
+        let expected_header = quote!(
+            impl<T: Copy> ::db::DynSet for FooBuilder<T>
+            where
+                T: #core::clone::Clone,
+                T: 'static,
+        )
+        .to_string();
+
+        assert!(tokens.contains(&expected_header), "{}", tokens);
+    }
+
+    #[test]
+    fn cfg_attrs() {
+        let cfg: syn::Attribute = parse_quote!(#[cfg(feature = "tls")]);
+        let mut dyn_set = default_dyn_set_impl!();
+        dyn_set.fields[0].cfg_attrs = vec![&cfg];
+        let core = quote!(::db::export::core);
+
+        let tokens = quote!(#dyn_set).to_string();
+        let expected_type_arm = quote!(
+            #[cfg(feature = "tls")]
+            "foo" => #core::option::Option::Some(#core::any::TypeId::of::<u32>()),
+        )
+        .to_string();
+
+        assert!(tokens.contains(&expected_type_arm), "{}", tokens);
+        assert_eq!(tokens.matches("# [cfg (feature = \"tls\")]").count(), 2);
+    }
+
+    #[test]
+    fn disabled() {
+        let mut dyn_set = default_dyn_set_impl!();
+        dyn_set.enabled = false;
+
+        assert_eq!(quote!(#dyn_set).to_string(), quote!().to_string());
+    }
+}
@@ -0,0 +1,153 @@
+use std::borrow::Cow;
+
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+
+/// Map of the keys which don't belong to any field when a builder is deserialized, with an
+/// accessor method implementing `quote::ToTokens`.
+///
+/// The map is a builder field with `#[serde(flatten)]`, which the builder pushes along with its
+/// initializer, so serde collects the unknown keys into it.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::ExtraFields;
+/// # fn main() {
+/// #    let extra = default_extra_fields!();
+/// #
+/// #    assert_eq!(quote!(#extra).to_string(), quote!(
+/// /// Keys of the deserialized input which don't belong to any field, with their values.
+/// pub fn extra(&self) -> &::derive_builder::export::core::collections::HashMap<
+///     ::derive_builder::export::core::string::String,
+///     Value
+/// > {
+///     &self.extra
+/// }
+/// #    ).to_string());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ExtraFields<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Name of the builder field and its accessor.
+    pub ident: syn::Ident,
+    /// Visibility of the accessor, e.g. `syn::Visibility::Public`.
+    pub visibility: Cow<'a, syn::Visibility>,
+    /// Type of the values in the map, e.g. `serde_json::Value`.
+    pub value_ty: &'a syn::Type,
+}
+
+impl<'a> ExtraFields<'a> {
+    fn map_ty(&self) -> TokenStream {
+        let crate_root = self.crate_root;
+        let value_ty = self.value_ty;
+        quote!(#crate_root::export::core::collections::HashMap<
+            #crate_root::export::core::string::String,
+            #value_ty
+        >)
+    }
+
+    /// Emits the builder field, e.g. `#[serde(flatten)] extra: HashMap<String, Value>,`.
+    pub fn field_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        let map_ty = self.map_ty();
+        quote!(
+            #[serde(flatten)]
+            #ident: #map_ty,
+        )
+    }
+
+    /// Emits the initializer of the builder field, which is an empty map.
+    pub fn initializer_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        let crate_root = self.crate_root;
+        quote!(#ident: #crate_root::export::core::default::Default::default(),)
+    }
+}
+
+impl<'a> ToTokens for ExtraFields<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let ident = &self.ident;
+        let vis = &self.visibility;
+        let map_ty = self.map_ty();
+
+        tokens.append_all(quote!(
+            /// Keys of the deserialized input which don't belong to any field, with their values.
+            #vis fn #ident(&self) -> &#map_ty {
+                &self.#ident
+            }
+        ));
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[cfg(test)] // This contains a Box::leak, so is suitable only for tests
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_extra_fields {
+    () => {
+        ExtraFields {
+            // Deliberately don't use the default value here - make sure
+            // that all test cases are passing crate_root through properly.
+            crate_root: &parse_quote!(::db),
+            ident: syn::Ident::new("extra", ::proc_macro2::Span::call_site()),
+            visibility: ::std::borrow::Cow::Owned(parse_quote!(pub)),
+            value_ty: Box::leak(Box::new(parse_quote!(Value))),
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn accessor() {
+        let extra = default_extra_fields!();
+        let map = quote!(::db::export::core::collections::HashMap);
+        let string = quote!(::db::export::core::string::String);
+
+        assert_eq!(
+            quote!(#extra).to_string(),
+            quote!(
+                /// Keys of the deserialized input which don't belong to any field, with their values.
+                pub fn extra(&self) -> &#map<#string, Value> {
+                    &self.extra
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn field_and_initializer() {
+        let extra = default_extra_fields!();
+        let map = quote!(::db::export::core::collections::HashMap);
+        let string = quote!(::db::export::core::string::String);
+
+        assert_eq!(
+            extra.field_tokens().to_string(),
+            quote!(
+                #[serde(flatten)]
+                extra: #map<#string, Value>,
+            )
+            .to_string()
+        );
+        assert_eq!(
+            extra.initializer_tokens().to_string(),
+            quote!(extra: ::db::export::core::default::Default::default(),).to_string()
+        );
+    }
+}
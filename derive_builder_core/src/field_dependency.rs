@@ -0,0 +1,198 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+use syn::ext::IdentExt;
+
+/// Check of a `requires` or `conflicts_with` dependency between two builder fields,
+/// implementing `quote::ToTokens`.
+///
+/// Lives in the body of `BuildMethod`.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::{FieldDependency, FieldDependencyKind};
+/// # fn main() {
+/// #    let dependency = default_field_dependency!();
+/// #
+/// #    assert_eq!(quote!(#dependency).to_string(), quote!(
+/// if self.foo.is_some() && self.bar.is_none() {
+///     return ::derive_builder::export::core::result::Result::Err(
+///         ::derive_builder::export::core::convert::Into::into(
+///             ::derive_builder::FieldDependencyError::requires("foo", "bar")
+///         )
+///     );
+/// }
+/// #    ).to_string());
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct FieldDependency<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Name of the field which declares the dependency.
+    pub field_ident: &'a syn::Ident,
+    /// Name of the field which is required or conflicting.
+    pub other_ident: &'a syn::Ident,
+    /// Name of the field which declares the dependency in the error.
+    pub field_name: String,
+    /// Name of the field which is required or conflicting in the error.
+    pub other_name: String,
+    /// Whether the other field is required or conflicting.
+    pub kind: FieldDependencyKind,
+    /// Whether the check lives in a `const fn` build method, which panics instead of returning
+    /// an error.
+    pub const_fn: bool,
+}
+
+/// The kind of dependency between two fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldDependencyKind {
+    /// The other field must be set if this field is set.
+    Requires,
+    /// The other field must not be set if this field is set.
+    ConflictsWith,
+}
+
+impl<'a> FieldDependency<'a> {
+    /// Create the check that `other_ident` is set or unset, according to `kind`, if
+    /// `field_ident` is set. The fields are named by their idents in the error.
+    pub fn new(
+        crate_root: &'a syn::Path,
+        field_ident: &'a syn::Ident,
+        other_ident: &'a syn::Ident,
+        kind: FieldDependencyKind,
+    ) -> Self {
+        FieldDependency {
+            crate_root,
+            field_ident,
+            other_ident,
+            field_name: field_ident.unraw().to_string(),
+            other_name: other_ident.unraw().to_string(),
+            kind,
+            const_fn: false,
+        }
+    }
+}
+
+impl<'a> ToTokens for FieldDependency<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let crate_root = self.crate_root;
+        let field_ident = self.field_ident;
+        let other_ident = self.other_ident;
+        let field_name = &self.field_name;
+        let other_name = &self.other_name;
+
+        let (other_check, constructor, message) = match self.kind {
+            FieldDependencyKind::Requires => (
+                quote!(is_none),
+                quote!(requires),
+                format!("`{}` requires `{}` to be set", field_name, other_name),
+            ),
+            FieldDependencyKind::ConflictsWith => (
+                quote!(is_some),
+                quote!(conflicts_with),
+                format!("`{}` conflicts with `{}`", field_name, other_name),
+            ),
+        };
+
+        let fail = if self.const_fn {
+            quote!(#crate_root::export::core::panic!(#message))
+        } else {
+            quote!(
+                return #crate_root::export::core::result::Result::Err(
+                    #crate_root::export::core::convert::Into::into(
+                        #crate_root::FieldDependencyError::#constructor(#field_name, #other_name)
+                    )
+                );
+            )
+        };
+
+        tokens.append_all(quote!(
+            if self.#field_ident.is_some() && self.#other_ident.#other_check() {
+                #fail
+            }
+        ));
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_field_dependency {
+    () => {
+        FieldDependency {
+            // Deliberately don't use the default value here - make sure
+            // that all test cases are passing crate_root through properly.
+            crate_root: &parse_quote!(::db),
+            field_ident: &syn::Ident::new("foo", ::proc_macro2::Span::call_site()),
+            other_ident: &syn::Ident::new("bar", ::proc_macro2::Span::call_site()),
+            field_name: "foo".to_string(),
+            other_name: "bar".to_string(),
+            kind: FieldDependencyKind::Requires,
+            const_fn: false,
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn requires() {
+        let dependency = default_field_dependency!();
+        let error = quote!(::db::FieldDependencyError::requires("foo", "bar"));
+
+        assert_eq!(
+            quote!(#dependency).to_string(),
+            quote!(if self.foo.is_some() && self.bar.is_none() {
+                return ::db::export::core::result::Result::Err(
+                    ::db::export::core::convert::Into::into(#error)
+                );
+            })
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn conflicts_with() {
+        let mut dependency = default_field_dependency!();
+        dependency.kind = FieldDependencyKind::ConflictsWith;
+        let error = quote!(::db::FieldDependencyError::conflicts_with("foo", "bar"));
+
+        assert_eq!(
+            quote!(#dependency).to_string(),
+            quote!(if self.foo.is_some() && self.bar.is_some() {
+                return ::db::export::core::result::Result::Err(
+                    ::db::export::core::convert::Into::into(#error)
+                );
+            })
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn const_fn() {
+        let mut dependency = default_field_dependency!();
+        dependency.const_fn = true;
+
+        assert_eq!(
+            quote!(#dependency).to_string(),
+            quote!(if self.foo.is_some() && self.bar.is_none() {
+                ::db::export::core::panic!("`foo` requires `bar` to be set")
+            })
+            .to_string()
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use std::borrow::Cow;
+
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+
+use crate::builder::allow_lints_attr;
+
+/// Enum with a variant for each field of the target struct, implementing `quote::ToTokens`.
+///
+/// It tells build errors about fields apart without comparing names, e.g. to highlight the
+/// field in a form.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::{FieldEnum, FieldEnumVariant};
+/// # fn main() {
+/// #    let field_enum = default_field_enum!();
+/// #
+/// #    assert_eq!(quote!(#field_enum).to_string(), quote!(
+/// #[doc = "Fields of [`Foo`], e.g. to tell which field a build error is about."]
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// pub enum FooField {
+///     #[doc = "The `foo_bar` field."]
+///     FooBar,
+/// }
+///
+/// impl FooField {
+///     /// Name of the field, as in build errors.
+///     pub const fn name(self) -> &'static str {
+///         match self {
+///             Self::FooBar => "foo_bar",
+///         }
+///     }
+///
+///     /// Look up the field with the given name.
+///     pub fn from_name(name: &str) -> ::derive_builder::export::core::option::Option<Self> {
+///         match name {
+///             "foo_bar" => ::derive_builder::export::core::option::Option::Some(Self::FooBar),
+///             _ => ::derive_builder::export::core::option::Option::None,
+///         }
+///     }
+/// }
+///
+/// #[automatically_derived]
+/// impl ::derive_builder::export::core::fmt::Display for FooField {
+///     fn fmt(&self, f: &mut ::derive_builder::export::core::fmt::Formatter) -> ::derive_builder::export::core::fmt::Result {
+///         f.write_str(self.name())
+///     }
+/// }
+/// #    ).to_string());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FieldEnum<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Enables code generation for this enum.
+    pub enabled: bool,
+    /// Name of the enum.
+    pub ident: syn::Ident,
+    /// Visibility of the enum, e.g. `syn::Visibility::Public`.
+    pub visibility: Cow<'a, syn::Visibility>,
+    /// Name of the target struct, which is linked from the documentation.
+    pub target_ty: &'a syn::Ident,
+    /// Lints to allow on the enum and its impls, e.g. `missing_docs`.
+    pub allow_lints: &'a [syn::Path],
+    /// Variants of the enum, in declaration order of the fields.
+    pub variants: Vec<FieldEnumVariant>,
+}
+
+/// A variant of a `FieldEnum`.
+#[derive(Debug)]
+pub struct FieldEnumVariant {
+    /// Name of the variant, i.e. the field's name in `PascalCase`.
+    pub ident: syn::Ident,
+    /// Name of the field in errors, which the variant is converted from and to.
+    pub name: String,
+}
+
+impl<'a> ToTokens for FieldEnum<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if !self.enabled {
+            return;
+        }
+
+        let crate_root = self.crate_root;
+        let core = quote!(#crate_root::export::core);
+        let ident = &self.ident;
+        let vis = &self.visibility;
+        let allow_lints = allow_lints_attr(self.allow_lints);
+        let enum_doc = format!(
+            "Fields of [`{}`], e.g. to tell which field a build error is about.",
+            self.target_ty
+        );
+        let variants = self.variants.iter().map(|v| {
+            let ident = &v.ident;
+            let doc = format!("The `{}` field.", v.name);
+            quote!(#[doc = #doc] #ident,)
+        });
+        let name_arms = self.variants.iter().map(|v| {
+            let ident = &v.ident;
+            let name = &v.name;
+            quote!(Self::#ident => #name,)
+        });
+        let from_name_arms = self.variants.iter().map(|v| {
+            let ident = &v.ident;
+            let name = &v.name;
+            quote!(#name => #core::option::Option::Some(Self::#ident),)
+        });
+
+        tokens.append_all(quote!(
+            #[doc = #enum_doc]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            #allow_lints
+            #vis enum #ident {
+                #(#variants)*
+            }
+
+            #allow_lints
+            impl #ident {
+                /// Name of the field, as in build errors.
+                pub const fn name(self) -> &'static str {
+                    match self {
+                        #(#name_arms)*
+                    }
+                }
+
+                /// Look up the field with the given name.
+                pub fn from_name(name: &str) -> #core::option::Option<Self> {
+                    match name {
+                        #(#from_name_arms)*
+                        _ => #core::option::Option::None,
+                    }
+                }
+            }
+
+            #[automatically_derived]
+            #allow_lints
+            impl #core::fmt::Display for #ident {
+                fn fmt(&self, f: &mut #core::fmt::Formatter) -> #core::fmt::Result {
+                    f.write_str(self.name())
+                }
+            }
+        ));
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[cfg(test)] // This contains a Box::leak, so is suitable only for tests
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_field_enum {
+    () => {
+        FieldEnum {
+            // Deliberately don't use the default value here - make sure
+            // that all test cases are passing crate_root through properly.
+            crate_root: &parse_quote!(::db),
+            enabled: true,
+            ident: syn::Ident::new("FooField", ::proc_macro2::Span::call_site()),
+            visibility: ::std::borrow::Cow::Owned(parse_quote!(pub)),
+            target_ty: Box::leak(Box::new(syn::Ident::new(
+                "Foo",
+                ::proc_macro2::Span::call_site(),
+            ))),
+            allow_lints: &[],
+            variants: vec![FieldEnumVariant {
+                ident: syn::Ident::new("FooBar", ::proc_macro2::Span::call_site()),
+                name: "foo_bar".to_string(),
+            }],
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn simple() {
+        let field_enum = default_field_enum!();
+        let option = quote!(::db::export::core::option::Option);
+        let fmt = quote!(::db::export::core::fmt);
+
+        assert_eq!(
+            quote!(#field_enum).to_string(),
+            quote!(
+                #[doc = "Fields of [`Foo`], e.g. to tell which field a build error is about."]
+                #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+                pub enum FooField {
+                    #[doc = "The `foo_bar` field."]
+                    FooBar,
+                }
+
+                impl FooField {
+                    /// Name of the field, as in build errors.
+                    pub const fn name(self) -> &'static str {
+                        match self {
+                            Self::FooBar => "foo_bar",
+                        }
+                    }
+
+                    /// Look up the field with the given name.
+                    pub fn from_name(name: &str) -> #option<Self> {
+                        match name {
+                            "foo_bar" => #option::Some(Self::FooBar),
+                            _ => #option::None,
+                        }
+                    }
+                }
+
+                #[automatically_derived]
+                impl #fmt::Display for FooField {
+                    fn fmt(&self, f: &mut #fmt::Formatter) -> #fmt::Result {
+                        f.write_str(self.name())
+                    }
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn disabled() {
+        let mut field_enum = default_field_enum!();
+        field_enum.enabled = false;
+
+        assert_eq!(quote!(#field_enum).to_string(), quote!().to_string());
+    }
+}
@@ -0,0 +1,131 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+use syn::spanned::Spanned;
+
+/// Call of the `validate` function of a single builder field, implementing `quote::ToTokens`.
+///
+/// Lives in the body of `BuildMethod`, before the checks of field dependencies. Fields which
+/// are unset aren't validated.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::FieldValidation;
+/// # fn main() {
+/// #    let validation = default_field_validation!();
+/// #
+/// #    assert_eq!(quote!(#validation).to_string(), quote!(
+/// if let ::derive_builder::export::core::option::Option::Some(ref value) = self.foo {
+///     validate_foo(value)?;
+/// }
+/// #    ).to_string());
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct FieldValidation<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Name of the builder field.
+    pub field_ident: &'a syn::Ident,
+    /// Validation function with signature `&T -> Result<(), E>`, where `T` is the type of the
+    /// value in the builder field.
+    pub validate_fn: &'a syn::Path,
+    /// Whether the builder field is an `Option` which is `None` until the setter is called,
+    /// so the validation applies to its contents.
+    pub optional: bool,
+}
+
+impl<'a> FieldValidation<'a> {
+    /// Create the call of `validate_fn` for the optional builder field `field_ident`.
+    pub fn new(
+        crate_root: &'a syn::Path,
+        field_ident: &'a syn::Ident,
+        validate_fn: &'a syn::Path,
+    ) -> Self {
+        FieldValidation {
+            crate_root,
+            field_ident,
+            validate_fn,
+            optional: true,
+        }
+    }
+}
+
+impl<'a> ToTokens for FieldValidation<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let crate_root = self.crate_root;
+        let field_ident = self.field_ident;
+        let validate_fn = self.validate_fn;
+
+        if self.optional {
+            let call = quote_spanned!(validate_fn.span()=> #validate_fn(value)?;);
+            tokens.append_all(quote!(
+                if let #crate_root::export::core::option::Option::Some(ref value) = self.#field_ident {
+                    #call
+                }
+            ));
+        } else {
+            tokens.append_all(
+                quote_spanned!(validate_fn.span()=> #validate_fn(&self.#field_ident)?;),
+            );
+        }
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_field_validation {
+    () => {
+        FieldValidation {
+            // Deliberately don't use the default value here - make sure
+            // that all test cases are passing crate_root through properly.
+            crate_root: &parse_quote!(::db),
+            field_ident: &syn::Ident::new("foo", ::proc_macro2::Span::call_site()),
+            validate_fn: &parse_quote!(validate_foo),
+            optional: true,
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn optional() {
+        let validation = default_field_validation!();
+
+        assert_eq!(
+            quote!(#validation).to_string(),
+            quote!(
+                if let ::db::export::core::option::Option::Some(ref value) = self.foo {
+                    validate_foo(value)?;
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn custom_field_type() {
+        let mut validation = default_field_validation!();
+        validation.optional = false;
+
+        assert_eq!(
+            quote!(#validation).to_string(),
+            quote!(validate_foo(&self.foo)?;).to_string()
+        );
+    }
+}
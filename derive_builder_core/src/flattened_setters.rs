@@ -0,0 +1,143 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+
+use crate::builder::allow_lints_attr;
+
+/// Impl of the setters trait of a nested builder for the outer builder, which stores the nested
+/// builder in a field, implementing `quote::ToTokens`.
+///
+/// This gives the outer builder all setters of the nested builder.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::FlattenedSetters;
+/// # fn main() {
+/// #    let flattened = default_flattened_setters!();
+/// #
+/// #    assert_eq!(quote!(#flattened).to_string(), quote!(
+/// #[automatically_derived]
+/// impl BarBuilderSetters for FooBuilder {
+///     fn bar_builder_mut(&mut self) -> &mut BarBuilder {
+///         &mut self.bar
+///     }
+/// }
+/// #    ).to_string());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FlattenedSetters<'a> {
+    /// Name of the outer builder struct.
+    pub builder_ty: syn::Ident,
+    /// Type parameters and lifetimes attached to the outer builder struct.
+    pub generics: Option<&'a syn::Generics>,
+    /// Lints to allow on the impl, e.g. `missing_docs`.
+    pub allow_lints: &'a [syn::Path],
+    /// Name of the field of the outer builder which stores the nested builder.
+    pub field_ident: &'a syn::Ident,
+    /// Type of the nested builder, e.g. `BarBuilder`.
+    pub nested_builder_ty: &'a syn::Type,
+    /// Path to the setters trait of the nested builder, e.g. `BarBuilderSetters`.
+    pub setters_trait: syn::Path,
+    /// Name of the required method of the setters trait, e.g. `bar_builder_mut`.
+    pub accessor: syn::Ident,
+}
+
+impl<'a> ToTokens for FlattenedSetters<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let builder_ty = &self.builder_ty;
+        let allow_lints = allow_lints_attr(self.allow_lints);
+        let generics = self.generics.cloned().unwrap_or_default();
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let field_ident = self.field_ident;
+        let nested_builder_ty = self.nested_builder_ty;
+        let setters_trait = &self.setters_trait;
+        let accessor = &self.accessor;
+
+        tokens.append_all(quote!(
+            #[automatically_derived]
+            #allow_lints
+            impl #impl_generics #setters_trait for #builder_ty #ty_generics #where_clause {
+                fn #accessor(&mut self) -> &mut #nested_builder_ty {
+                    &mut self.#field_ident
+                }
+            }
+        ));
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[cfg(test)] // This contains a Box::leak, so is suitable only for tests
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_flattened_setters {
+    () => {
+        FlattenedSetters {
+            builder_ty: syn::Ident::new("FooBuilder", ::proc_macro2::Span::call_site()),
+            generics: None,
+            allow_lints: &[],
+            field_ident: Box::leak(Box::new(syn::Ident::new(
+                "bar",
+                ::proc_macro2::Span::call_site(),
+            ))),
+            nested_builder_ty: Box::leak(Box::new(parse_quote!(BarBuilder))),
+            setters_trait: parse_quote!(BarBuilderSetters),
+            accessor: syn::Ident::new("bar_builder_mut", ::proc_macro2::Span::call_site()),
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn simple() {
+        let flattened = default_flattened_setters!();
+
+        assert_eq!(
+            quote!(#flattened).to_string(),
+            quote!(
+                #[automatically_derived]
+                impl BarBuilderSetters for FooBuilder {
+                    fn bar_builder_mut(&mut self) -> &mut BarBuilder {
+                        &mut self.bar
+                    }
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn generic() {
+        let generics: syn::Generics = parse_quote!(<'a, T: Clone>);
+        let mut flattened = default_flattened_setters!();
+        flattened.generics = Some(&generics);
+        flattened.nested_builder_ty = Box::leak(Box::new(parse_quote!(inner::BarBuilder<'a, T>)));
+        flattened.setters_trait = parse_quote!(inner::BarBuilderSetters<'a, T>);
+
+        assert_eq!(
+            quote!(#flattened).to_string(),
+            quote!(
+                #[automatically_derived]
+                impl<'a, T: Clone> inner::BarBuilderSetters<'a, T> for FooBuilder<'a, T> {
+                    fn bar_builder_mut(&mut self) -> &mut inner::BarBuilder<'a, T> {
+                        &mut self.bar
+                    }
+                }
+            )
+            .to_string()
+        );
+    }
+}
@@ -0,0 +1,253 @@
+use std::borrow::Cow;
+
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+use syn::spanned::Spanned;
+
+use crate::{change_span, BuilderPattern};
+
+/// Associated function which creates a builder from the `key=value` pairs of a URL query
+/// string, implementing `quote::ToTokens`.
+///
+/// Values are parsed with `FromStr` and passed to the setters. Unknown keys are ignored.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::{FromQueryMethod, ParsedField};
+/// # fn main() {
+/// #    let from_query = default_from_query_method!();
+/// #
+/// #    assert_eq!(quote!(#from_query).to_string(), quote!(
+/// #[doc = "..."]
+/// #[allow(unused_mut)]
+/// pub fn from_query(query: &str) -> ::derive_builder::export::core::result::Result<Self, ::derive_builder::QueryError>
+/// where
+///     u32: ::derive_builder::export::core::str::FromStr,
+///     <u32 as ::derive_builder::export::core::str::FromStr>::Err: ::derive_builder::export::core::fmt::Display,
+/// {
+///     let mut new = Self::create_empty();
+///     for pair in ::derive_builder::export::query::pairs(query) {
+///         let (key, value) = pair?;
+///         match (key.as_str(), value) {
+///             ("foo", value) => {
+///                 let value: u32 = ::derive_builder::export::query::parse("foo", &value)?;
+///                 new.set_foo(value);
+///             }
+///             _ => {}
+///         }
+///     }
+///     ::derive_builder::export::core::result::Result::Ok(new)
+/// }
+/// #    ).to_string());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FromQueryMethod<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Enables code generation for this function.
+    pub enabled: bool,
+    /// Visibility of the function, e.g. `syn::Visibility::Public`.
+    pub visibility: Cow<'a, syn::Visibility>,
+    /// The inherent method which creates an empty builder.
+    pub create_empty: &'a syn::Ident,
+    /// Fields which can be set from the query string.
+    pub fields: Vec<ParsedField<'a>>,
+}
+
+/// A field which is parsed from a `key=value` pair by a `FromQueryMethod` or a `FromStrImpl`.
+#[derive(Debug)]
+pub struct ParsedField<'a> {
+    /// Key of the field.
+    pub key: String,
+    /// Alternative keys of the field, which are accepted as well.
+    pub aliases: &'a [String],
+    /// Name of the setter which is called with the parsed value.
+    pub setter: syn::Ident,
+    /// How the setter takes and returns `self` (e.g. mutably).
+    pub pattern: BuilderPattern,
+    /// Type of the value the setter takes, which is parsed with `FromStr`.
+    pub value_ty: &'a syn::Type,
+    /// `#[cfg(...)]` attributes of the setter, which are applied to the match arm as well.
+    pub cfg_attrs: Vec<&'a syn::Attribute>,
+}
+
+impl<'a> ParsedField<'a> {
+    /// Where predicates which require the value type to be parsable.
+    pub(crate) fn bounds(&self, crate_root: &syn::Path) -> TokenStream {
+        let ty = self.value_ty;
+        // Point errors about types which can't be parsed at the field.
+        let crate_root = change_span(crate_root.into_token_stream(), ty.span());
+        quote_spanned!(ty.span()=>
+            #ty: #crate_root::export::core::str::FromStr,
+            <#ty as #crate_root::export::core::str::FromStr>::Err: #crate_root::export::core::fmt::Display,
+        )
+    }
+
+    /// Match arm for `(key, value)`, which parses the value with `parse_fn` and passes it to
+    /// the setter of the builder `new`.
+    pub(crate) fn arm(&self, parse_fn: &TokenStream) -> TokenStream {
+        let key = &self.key;
+        let aliases = self.aliases;
+        let setter = &self.setter;
+        let ty = self.value_ty;
+        let cfg_attrs = &self.cfg_attrs;
+        let set = match self.pattern {
+            BuilderPattern::Mutable => quote!(new.#setter(value);),
+            BuilderPattern::Owned | BuilderPattern::Immutable => {
+                quote!(new = new.#setter(value);)
+            }
+        };
+        quote!(
+            #(#cfg_attrs)*
+            (#key, value) #(| (#aliases, value))* => {
+                let value: #ty = #parse_fn(#key, &value)?;
+                #set
+            }
+        )
+    }
+}
+
+impl<'a> ToTokens for FromQueryMethod<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if !self.enabled {
+            return;
+        }
+
+        let crate_root = self.crate_root;
+        let vis = &self.visibility;
+        let create_empty = self.create_empty;
+        let bounds = self.fields.iter().map(|f| f.bounds(crate_root));
+        let parse_fn = quote!(#crate_root::export::query::parse);
+        let arms = self.fields.iter().map(|f| f.arm(&parse_fn));
+
+        tokens.append_all(quote!(
+            #[doc = "Create a builder from the `key=value` pairs of a URL query string.\n\nValues are parsed with `FromStr` and passed to the setters; unknown keys are ignored."]
+            #[allow(unused_mut)]
+            #vis fn from_query(query: &str) -> #crate_root::export::core::result::Result<Self, #crate_root::QueryError>
+            where
+                #(#bounds)*
+            {
+                let mut new = Self::#create_empty();
+                for pair in #crate_root::export::query::pairs(query) {
+                    let (key, value) = pair?;
+                    match (key.as_str(), value) {
+                        #(#arms)*
+                        _ => {}
+                    }
+                }
+                #crate_root::export::core::result::Result::Ok(new)
+            }
+        ));
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[cfg(test)] // This contains a Box::leak, so is suitable only for tests
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_from_query_method {
+    () => {
+        FromQueryMethod {
+            // Deliberately don't use the default value here - make sure
+            // that all test cases are passing crate_root through properly.
+            crate_root: &parse_quote!(::db),
+            enabled: true,
+            visibility: ::std::borrow::Cow::Owned(parse_quote!(pub)),
+            create_empty: Box::leak(Box::new(syn::Ident::new(
+                "create_empty",
+                ::proc_macro2::Span::call_site(),
+            ))),
+            fields: vec![ParsedField {
+                key: "foo".to_string(),
+                aliases: &[],
+                setter: syn::Ident::new("set_foo", ::proc_macro2::Span::call_site()),
+                pattern: BuilderPattern::Mutable,
+                value_ty: Box::leak(Box::new(parse_quote!(u32))),
+                cfg_attrs: vec![],
+            }],
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    fn expected(set: TokenStream) -> TokenStream {
+        quote!(
+            #[doc = "Create a builder from the `key=value` pairs of a URL query string.\n\nValues are parsed with `FromStr` and passed to the setters; unknown keys are ignored."]
+            #[allow(unused_mut)]
+            pub fn from_query(query: &str) -> ::db::export::core::result::Result<Self, ::db::QueryError>
+            where
+                u32: ::db::export::core::str::FromStr,
+                <u32 as ::db::export::core::str::FromStr>::Err: ::db::export::core::fmt::Display,
+            {
+                let mut new = Self::create_empty();
+                for pair in ::db::export::query::pairs(query) {
+                    let (key, value) = pair?;
+                    match (key.as_str(), value) {
+                        ("foo", value) => {
+                            let value: u32 = ::db::export::query::parse("foo", &value)?;
+                            #set
+                        }
+                        _ => {}
+                    }
+                }
+                ::db::export::core::result::Result::Ok(new)
+            }
+        )
+    }
+
+    #[test]
+    fn mutable() {
+        let from_query = default_from_query_method!();
+
+        assert_eq!(
+            quote!(#from_query).to_string(),
+            expected(quote!(new.set_foo(value);)).to_string()
+        );
+    }
+
+    #[test]
+    fn owned() {
+        let mut from_query = default_from_query_method!();
+        from_query.fields[0].pattern = BuilderPattern::Owned;
+
+        assert_eq!(
+            quote!(#from_query).to_string(),
+            expected(quote!(new = new.set_foo(value);)).to_string()
+        );
+    }
+
+    #[test]
+    fn aliases() {
+        let aliases = ["bar".to_string(), "baz".to_string()];
+        let mut from_query = default_from_query_method!();
+        from_query.fields[0].aliases = &aliases;
+
+        let tokens = quote!(#from_query).to_string();
+        let expected = quote!(("foo", value) | ("bar", value) | ("baz", value) =>).to_string();
+
+        assert!(tokens.contains(&expected), "{}", tokens);
+    }
+
+    #[test]
+    fn disabled() {
+        let mut from_query = default_from_query_method!();
+        from_query.enabled = false;
+
+        assert_eq!(quote!(#from_query).to_string(), quote!().to_string());
+    }
+}
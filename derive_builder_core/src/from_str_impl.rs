@@ -0,0 +1,247 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+
+use crate::builder::allow_lints_attr;
+use crate::ParsedField;
+
+/// `FromStr` impl for the builder, which parses a comma-separated list of `key=value` pairs,
+/// implementing `quote::ToTokens`.
+///
+/// Values are parsed with `FromStr` and passed to the setters. Unknown keys are errors.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::{FromStrImpl, ParsedField};
+/// # fn main() {
+/// #    let from_str = default_from_str_impl!();
+/// #
+/// #    assert_eq!(quote!(#from_str).to_string(), quote!(
+/// #[automatically_derived]
+/// impl ::derive_builder::export::core::str::FromStr for FooBuilder
+/// where
+///     u32: ::derive_builder::export::core::str::FromStr,
+///     <u32 as ::derive_builder::export::core::str::FromStr>::Err: ::derive_builder::export::core::fmt::Display,
+/// {
+///     type Err = ::derive_builder::KeyValueError;
+///
+///     #[allow(unused_mut)]
+///     fn from_str(s: &str) -> ::derive_builder::export::core::result::Result<Self, Self::Err> {
+///         let mut new = Self::create_empty();
+///         for pair in ::derive_builder::export::key_value::pairs(s) {
+///             let (key, value) = pair?;
+///             match (key, value) {
+///                 ("foo", value) => {
+///                     let value: u32 = ::derive_builder::export::key_value::parse("foo", &value)?;
+///                     new.foo(value);
+///                 }
+///                 (key, _) => {
+///                     return ::derive_builder::export::core::result::Result::Err(
+///                         ::derive_builder::export::key_value::unknown_key(key, &["foo"])
+///                     );
+///                 }
+///             }
+///         }
+///         ::derive_builder::export::core::result::Result::Ok(new)
+///     }
+/// }
+/// #    ).to_string());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FromStrImpl<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Enables code generation for this impl.
+    pub enabled: bool,
+    /// Name of the builder struct.
+    pub builder_ty: syn::Ident,
+    /// Type parameters and lifetimes attached to the builder struct.
+    pub generics: Option<&'a syn::Generics>,
+    /// Whether the type parameters must be `Clone`, because the inherent `impl` of the builder
+    /// requires it.
+    pub clone_bounds: bool,
+    /// Lints to allow on the impl, e.g. `missing_docs`.
+    pub allow_lints: &'a [syn::Path],
+    /// The inherent method which creates an empty builder.
+    pub create_empty: &'a syn::Ident,
+    /// Fields which can be set from `key=value` pairs.
+    pub fields: Vec<ParsedField<'a>>,
+}
+
+impl<'a> ToTokens for FromStrImpl<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if !self.enabled {
+            return;
+        }
+
+        let crate_root = self.crate_root;
+        let builder_ty = &self.builder_ty;
+        let allow_lints = allow_lints_attr(self.allow_lints);
+        let generics = self.generics.cloned().unwrap_or_default();
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let mut predicates: Vec<TokenStream> = where_clause
+            .map(|w| w.predicates.iter().map(|p| quote!(#p,)).collect())
+            .unwrap_or_default();
+        if self.clone_bounds {
+            predicates.extend(generics.type_params().map(|param| {
+                let ident = &param.ident;
+                quote!(#ident: #crate_root::export::core::clone::Clone,)
+            }));
+        }
+        predicates.extend(self.fields.iter().map(|f| f.bounds(crate_root)));
+        let where_clause = if predicates.is_empty() {
+            quote!()
+        } else {
+            quote!(where #(#predicates)*)
+        };
+
+        let create_empty = self.create_empty;
+        let parse_fn = quote!(#crate_root::export::key_value::parse);
+        let arms = self.fields.iter().map(|f| f.arm(&parse_fn));
+        let keys = self.fields.iter().map(|f| &f.key);
+
+        tokens.append_all(quote!(
+            #[automatically_derived]
+            #allow_lints
+            impl #impl_generics #crate_root::export::core::str::FromStr for #builder_ty #ty_generics #where_clause {
+                type Err = #crate_root::KeyValueError;
+
+                #[allow(unused_mut)]
+                fn from_str(s: &str) -> #crate_root::export::core::result::Result<Self, Self::Err> {
+                    let mut new = Self::#create_empty();
+                    for pair in #crate_root::export::key_value::pairs(s) {
+                        let (key, value) = pair?;
+                        match (key, value) {
+                            #(#arms)*
+                            (key, _) => {
+                                return #crate_root::export::core::result::Result::Err(
+                                    #crate_root::export::key_value::unknown_key(key, &[#(#keys),*])
+                                );
+                            }
+                        }
+                    }
+                    #crate_root::export::core::result::Result::Ok(new)
+                }
+            }
+        ));
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[cfg(test)] // This contains a Box::leak, so is suitable only for tests
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_from_str_impl {
+    () => {
+        FromStrImpl {
+            // Deliberately don't use the default value here - make sure
+            // that all test cases are passing crate_root through properly.
+            crate_root: &parse_quote!(::db),
+            enabled: true,
+            builder_ty: syn::Ident::new("FooBuilder", ::proc_macro2::Span::call_site()),
+            generics: None,
+            clone_bounds: true,
+            allow_lints: &[],
+            create_empty: Box::leak(Box::new(syn::Ident::new(
+                "create_empty",
+                ::proc_macro2::Span::call_site(),
+            ))),
+            fields: vec![ParsedField {
+                key: "foo".to_string(),
+                aliases: &[],
+                setter: syn::Ident::new("foo", ::proc_macro2::Span::call_site()),
+                pattern: BuilderPattern::Mutable,
+                value_ty: Box::leak(Box::new(parse_quote!(u32))),
+                cfg_attrs: vec![],
+            }],
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::BuilderPattern;
+
+    #[test]
+    fn simple() {
+        let from_str = default_from_str_impl!();
+        let core = quote!(::db::export::core);
+        let key_value = quote!(::db::export::key_value);
+
+        assert_eq!(
+            quote!(#from_str).to_string(),
+            quote!(
+                #[automatically_derived]
+                impl #core::str::FromStr for FooBuilder
+                where
+                    u32: #core::str::FromStr,
+                    <u32 as #core::str::FromStr>::Err: #core::fmt::Display,
+                {
+                    type Err = ::db::KeyValueError;
+
+                    #[allow(unused_mut)]
+                    fn from_str(s: &str) -> #core::result::Result<Self, Self::Err> {
+                        let mut new = Self::create_empty();
+                        for pair in #key_value::pairs(s) {
+                            let (key, value) = pair?;
+                            match (key, value) {
+                                ("foo", value) => {
+                                    let value: u32 = #key_value::parse("foo", &value)?;
+                                    new.foo(value);
+                                }
+                                (key, _) => {
+                                    return #core::result::Result::Err(
+                                        #key_value::unknown_key(key, &["foo"])
+                                    );
+                                }
+                            }
+                        }
+                        #core::result::Result::Ok(new)
+                    }
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn generic() {
+        let generics: syn::Generics = parse_quote!(<T: Copy>);
+        let mut from_str = default_from_str_impl!();
+        from_str.generics = Some(&generics);
+        from_str.fields[0].value_ty = Box::leak(Box::new(parse_quote!(T)));
+        let core = quote!(::db::export::core);
+
+        let tokens = quote!(#from_str).to_string();
+        let expected_header = quote!(
+            impl<T: Copy> #core::str::FromStr for FooBuilder<T>
+            where
+                T: #core::clone::Clone,
+                T: #core::str::FromStr,
+                <T as #core::str::FromStr>::Err: #core::fmt::Display,
+        )
+        .to_string();
+
+        assert!(tokens.contains(&expected_header), "{}", tokens);
+    }
+
+    #[test]
+    fn disabled() {
+        let mut from_str = default_from_str_impl!();
+        from_str.enabled = false;
+
+        assert_eq!(quote!(#from_str).to_string(), quote!().to_string());
+    }
+}
@@ -0,0 +1,284 @@
+use std::borrow::Cow;
+
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+
+use crate::builder::allow_lints_attr;
+
+/// `From` impl which seeds the builder with the fields of an existing instance of the target
+/// struct, and `to_builder` method of the target struct, implementing `quote::ToTokens`.
+///
+/// Each builder field is set to the identically named field of the instance, so a single field
+/// can be changed before building again.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::{FromTarget, FromTargetField};
+/// # fn main() {
+/// #    let from_target = default_from_target!();
+/// #
+/// #    assert_eq!(quote!(#from_target).to_string(), quote!(
+/// #[automatically_derived]
+/// impl ::derive_builder::export::core::convert::From<Foo> for FooBuilder {
+///     #[allow(unused_mut, unused_variables)]
+///     fn from(target: Foo) -> Self {
+///         let mut new = Self::create_empty();
+///         new.foo = ::derive_builder::export::core::option::Option::Some(target.foo);
+///         new
+///     }
+/// }
+///
+/// impl Foo {
+///     #[doc = "..."]
+///     pub fn to_builder(&self) -> FooBuilder
+///     where
+///         Self: ::derive_builder::export::core::clone::Clone,
+///     {
+///         ::derive_builder::export::core::convert::From::from(
+///             ::derive_builder::export::core::clone::Clone::clone(self)
+///         )
+///     }
+/// }
+/// #    ).to_string());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FromTarget<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Enables code generation for the impl and the method.
+    pub enabled: bool,
+    /// Name of the builder struct.
+    pub builder_ty: syn::Ident,
+    /// Name of the target struct.
+    pub target_ty: &'a syn::Ident,
+    /// Type parameters and lifetimes attached to the builder and target struct.
+    pub generics: Option<&'a syn::Generics>,
+    /// Whether the type parameters must be `Clone`, because the inherent `impl` of the builder
+    /// requires it.
+    pub clone_bounds: bool,
+    /// Visibility of the `to_builder` method, which is that of the builder.
+    pub visibility: Cow<'a, syn::Visibility>,
+    /// Lints to allow on the impls, e.g. `missing_docs`.
+    pub allow_lints: &'a [syn::Path],
+    /// The inherent method which creates an empty builder.
+    pub create_empty: &'a syn::Ident,
+    /// Fields which are taken from the target struct.
+    pub fields: Vec<FromTargetField<'a>>,
+}
+
+/// A field which a `FromTarget` takes from the identically named field of the target struct.
+#[derive(Debug)]
+pub struct FromTargetField<'a> {
+    /// Name of the builder field.
+    pub field_ident: &'a syn::Ident,
+    /// Whether the builder field is an `Option` of the field's type. Otherwise, it is converted
+    /// with `From`, e.g. into the nested builder of a `flatten` field.
+    pub optional: bool,
+}
+
+impl<'a> ToTokens for FromTarget<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if !self.enabled {
+            return;
+        }
+
+        let crate_root = self.crate_root;
+        let builder_ty = &self.builder_ty;
+        let target_ty = self.target_ty;
+        let vis = &self.visibility;
+        let allow_lints = allow_lints_attr(self.allow_lints);
+        let generics = self.generics.cloned().unwrap_or_default();
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let mut predicates: Vec<TokenStream> = where_clause
+            .map(|w| w.predicates.iter().map(|p| quote!(#p,)).collect())
+            .unwrap_or_default();
+        if self.clone_bounds {
+            predicates.extend(generics.type_params().map(|param| {
+                let ident = &param.ident;
+                quote!(#ident: #crate_root::export::core::clone::Clone,)
+            }));
+        }
+        let from_where_clause = if predicates.is_empty() {
+            quote!()
+        } else {
+            quote!(where #(#predicates)*)
+        };
+        predicates.push(quote!(Self: #crate_root::export::core::clone::Clone,));
+        let create_empty = self.create_empty;
+        let conversions = self.fields.iter().map(|f| {
+            let field_ident = f.field_ident;
+            if f.optional {
+                quote!(new.#field_ident = #crate_root::export::core::option::Option::Some(target.#field_ident);)
+            } else {
+                quote!(new.#field_ident = #crate_root::export::core::convert::From::from(target.#field_ident);)
+            }
+        });
+        let doc = format!(
+            "Create a [`{}`] with all fields set to the values of this [`{}`], e.g. to build a \
+             copy with some fields changed.",
+            builder_ty, target_ty
+        );
+
+        tokens.append_all(quote!(
+            #[automatically_derived]
+            #allow_lints
+            impl #impl_generics #crate_root::export::core::convert::From<#target_ty #ty_generics> for #builder_ty #ty_generics #from_where_clause {
+                #[allow(unused_mut, unused_variables)]
+                fn from(target: #target_ty #ty_generics) -> Self {
+                    let mut new = Self::#create_empty();
+                    #(#conversions)*
+                    new
+                }
+            }
+
+            #allow_lints
+            impl #impl_generics #target_ty #ty_generics #where_clause {
+                #[doc = #doc]
+                #vis fn to_builder(&self) -> #builder_ty #ty_generics
+                where
+                    #(#predicates)*
+                {
+                    #crate_root::export::core::convert::From::from(
+                        #crate_root::export::core::clone::Clone::clone(self)
+                    )
+                }
+            }
+        ));
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[cfg(test)] // This contains a Box::leak, so is suitable only for tests
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_from_target {
+    () => {
+        FromTarget {
+            // Deliberately don't use the default value here - make sure
+            // that all test cases are passing crate_root through properly.
+            crate_root: &parse_quote!(::db),
+            enabled: true,
+            builder_ty: syn::Ident::new("FooBuilder", ::proc_macro2::Span::call_site()),
+            target_ty: Box::leak(Box::new(syn::Ident::new(
+                "Foo",
+                ::proc_macro2::Span::call_site(),
+            ))),
+            generics: None,
+            clone_bounds: false,
+            visibility: ::std::borrow::Cow::Owned(parse_quote!(pub)),
+            allow_lints: &[],
+            create_empty: Box::leak(Box::new(syn::Ident::new(
+                "create_empty",
+                ::proc_macro2::Span::call_site(),
+            ))),
+            fields: vec![FromTargetField {
+                field_ident: Box::leak(Box::new(syn::Ident::new(
+                    "foo",
+                    ::proc_macro2::Span::call_site(),
+                ))),
+                optional: true,
+            }],
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn from_target() {
+        let from_target = default_from_target!();
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#from_target).to_string(),
+            quote!(
+                #[automatically_derived]
+                impl ::db::export::core::convert::From<Foo> for FooBuilder {
+                    #[allow(unused_mut, unused_variables)]
+                    fn from(target: Foo) -> Self {
+                        let mut new = Self::create_empty();
+                        new.foo = ::db::export::core::option::Option::Some(target.foo);
+                        new
+                    }
+                }
+
+                impl Foo {
+                    #[doc = "Create a [`FooBuilder`] with all fields set to the values of this [`Foo`], e.g. to build a copy with some fields changed."]
+                    pub fn to_builder(&self) -> FooBuilder
+                    where
+                        Self: ::db::export::core::clone::Clone,
+                    {
+                        ::db::export::core::convert::From::from(
+                            ::db::export::core::clone::Clone::clone(self)
+                        )
+                    }
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn nested_generic() {
+        let generics: syn::Generics = parse_quote!(<T>);
+        let mut from_target = default_from_target!();
+        from_target.generics = Some(&generics);
+        from_target.clone_bounds = true;
+        from_target.fields[0].optional = false;
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#from_target).to_string(),
+            quote!(
+                #[automatically_derived]
+                impl<T> ::db::export::core::convert::From<Foo<T> > for FooBuilder<T>
+                where
+                    T: ::db::export::core::clone::Clone,
+                {
+                    #[allow(unused_mut, unused_variables)]
+                    fn from(target: Foo<T>) -> Self {
+                        let mut new = Self::create_empty();
+                        new.foo = ::db::export::core::convert::From::from(target.foo);
+                        new
+                    }
+                }
+
+                impl<T> Foo<T> {
+                    #[doc = "Create a [`FooBuilder`] with all fields set to the values of this [`Foo`], e.g. to build a copy with some fields changed."]
+                    pub fn to_builder(&self) -> FooBuilder<T>
+                    where
+                        T: ::db::export::core::clone::Clone,
+                        Self: ::db::export::core::clone::Clone,
+                    {
+                        ::db::export::core::convert::From::from(
+                            ::db::export::core::clone::Clone::clone(self)
+                        )
+                    }
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn disabled() {
+        let mut from_target = default_from_target!();
+        from_target.enabled = false;
+
+        assert_eq!(quote!(#from_target).to_string(), quote!().to_string());
+    }
+}
@@ -0,0 +1,200 @@
+use std::borrow::Cow;
+
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+use syn::spanned::Spanned;
+
+use crate::change_span;
+
+/// Function which constructs a builder from arbitrary bytes and builds it, implementing
+/// `quote::ToTokens`.
+///
+/// It only exists with `cfg(fuzzing)`, which `cargo fuzz` sets, so a fuzz target for the
+/// build pipeline of a struct is a single call. The cfg is checked by the `fuzzing!` macro
+/// of derive_builder.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::{FuzzBuildMethod, FuzzField};
+/// # fn main() {
+/// #    let fuzz_build = default_fuzz_build_method!();
+/// #
+/// #    assert_eq!(quote!(#fuzz_build).to_string(), quote!(
+/// ::derive_builder::export::arbitrary::fuzzing! {
+///     #[doc = "..."]
+///     pub fn fuzz_build(data: &[u8])
+///     where
+///         u32: for<'fuzz> ::derive_builder::export::arbitrary::Arbitrary<'fuzz>,
+///     {
+///         let mut u = ::derive_builder::export::arbitrary::Unstructured::new(data);
+///         let mut builder = Self::create_empty();
+///         builder.foo = match ::derive_builder::export::arbitrary::Arbitrary::arbitrary(&mut u) {
+///             ::derive_builder::export::core::result::Result::Ok(value) => value,
+///             ::derive_builder::export::core::result::Result::Err(_) => return,
+///         };
+///         let _ = builder.build();
+///     }
+/// }
+/// #    ).to_string());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FuzzBuildMethod<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Enables code generation for this function.
+    pub enabled: bool,
+    /// Visibility of the function, e.g. `syn::Visibility::Public`.
+    pub visibility: Cow<'a, syn::Visibility>,
+    /// Name of the function which creates an empty builder.
+    pub create_empty: &'a syn::Ident,
+    /// Name of the build method which is called.
+    pub build_fn: &'a syn::Ident,
+    /// Fields of the builder which are filled from the bytes, in declaration order.
+    pub fields: Vec<FuzzField<'a>>,
+}
+
+/// A builder field which is filled by a `FuzzBuildMethod`.
+#[derive(Debug)]
+pub struct FuzzField<'a> {
+    /// Name of the builder field.
+    pub field_ident: &'a syn::Ident,
+    /// Type which must implement `Arbitrary`, i.e. the contents of the builder field's
+    /// `Option`, or the builder field itself if it has a custom type.
+    pub ty: &'a syn::Type,
+}
+
+impl<'a> ToTokens for FuzzBuildMethod<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if !self.enabled {
+            return;
+        }
+
+        let crate_root = self.crate_root;
+        let core = quote!(#crate_root::export::core);
+        let arbitrary = quote!(#crate_root::export::arbitrary);
+        let vis = &self.visibility;
+        let create_empty = self.create_empty;
+        let build_fn = self.build_fn;
+        // Point errors about types which aren't `Arbitrary` at the field.
+        let bounds = self.fields.iter().map(|f| {
+            let ty = f.ty;
+            let crate_root = change_span(crate_root.into_token_stream(), ty.span());
+            quote_spanned!(ty.span()=> #ty: for<'fuzz> #crate_root::export::arbitrary::Arbitrary<'fuzz>,)
+        });
+        let assignments = self.fields.iter().map(|f| {
+            let field_ident = f.field_ident;
+            quote!(
+                builder.#field_ident = match #arbitrary::Arbitrary::arbitrary(&mut u) {
+                    #core::result::Result::Ok(value) => value,
+                    #core::result::Result::Err(_) => return,
+                };
+            )
+        });
+        let doc = format!(
+            "Fill a builder from arbitrary `data` and call `{}`, as a fuzz target.",
+            build_fn
+        );
+
+        // `cfg(fuzzing)` is checked by derive_builder, so the deriving crate doesn't have to
+        // declare it as an expected cfg.
+        tokens.append_all(quote!(
+            #arbitrary::fuzzing! {
+                #[doc = #doc]
+                #vis fn fuzz_build(data: &[u8])
+                where
+                    #(#bounds)*
+                {
+                    let mut u = #arbitrary::Unstructured::new(data);
+                    let mut builder = Self::#create_empty();
+                    #(#assignments)*
+                    let _ = builder.#build_fn();
+                }
+            }
+        ));
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[cfg(test)] // This contains a Box::leak, so is suitable only for tests
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_fuzz_build_method {
+    () => {
+        FuzzBuildMethod {
+            // Deliberately don't use the default value here - make sure
+            // that all test cases are passing crate_root through properly.
+            crate_root: &parse_quote!(::db),
+            enabled: true,
+            visibility: ::std::borrow::Cow::Owned(parse_quote!(pub)),
+            create_empty: Box::leak(Box::new(syn::Ident::new(
+                "create_empty",
+                ::proc_macro2::Span::call_site(),
+            ))),
+            build_fn: Box::leak(Box::new(syn::Ident::new(
+                "build",
+                ::proc_macro2::Span::call_site(),
+            ))),
+            fields: vec![FuzzField {
+                field_ident: Box::leak(Box::new(syn::Ident::new(
+                    "foo",
+                    ::proc_macro2::Span::call_site(),
+                ))),
+                ty: Box::leak(Box::new(parse_quote!(u32))),
+            }],
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn simple() {
+        let fuzz_build = default_fuzz_build_method!();
+        let arbitrary = quote!(::db::export::arbitrary);
+        let result = quote!(::db::export::core::result::Result);
+
+        assert_eq!(
+            quote!(#fuzz_build).to_string(),
+            quote!(
+                #arbitrary::fuzzing! {
+                    #[doc = "Fill a builder from arbitrary `data` and call `build`, as a fuzz target."]
+                    pub fn fuzz_build(data: &[u8])
+                    where
+                        u32: for<'fuzz> ::db::export::arbitrary::Arbitrary<'fuzz>,
+                    {
+                        let mut u = #arbitrary::Unstructured::new(data);
+                        let mut builder = Self::create_empty();
+                        builder.foo = match #arbitrary::Arbitrary::arbitrary(&mut u) {
+                            #result::Ok(value) => value,
+                            #result::Err(_) => return,
+                        };
+                        let _ = builder.build();
+                    }
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn disabled() {
+        let mut fuzz_build = default_fuzz_build_method!();
+        fuzz_build.enabled = false;
+
+        assert_eq!(quote!(#fuzz_build).to_string(), quote!().to_string());
+    }
+}
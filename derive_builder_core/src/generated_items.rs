@@ -0,0 +1,276 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+
+use crate::builder::allow_lints_attr;
+
+/// Constant on the builder which lists the names of the generated items in order,
+/// implementing `quote::ToTokens`.
+///
+/// Semver tooling and FFI layers can compare it between releases, and tests can assert it.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::GeneratedItems;
+/// # fn main() {
+/// #    let generated_items = default_generated_items!();
+/// #
+/// #    assert_eq!(quote!(#generated_items).to_string(), quote!(
+/// impl FooBuilder {
+///     /// Names of the items generated for the builder, in order.
+///     #[doc(hidden)]
+///     pub const GENERATED_ITEMS: &'static [&'static str] = &["FooBuilder", "FooBuilder::foo"];
+/// }
+/// #    ).to_string());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct GeneratedItems<'a> {
+    /// Enables code generation for this constant.
+    pub enabled: bool,
+    /// Name of the builder struct.
+    pub builder_ty: syn::Ident,
+    /// Type parameters and lifetimes attached to the builder struct.
+    pub generics: Option<&'a syn::Generics>,
+    /// Lints to allow on the impl, e.g. `missing_docs`.
+    pub allow_lints: &'a [syn::Path],
+    /// Names of the generated items, as returned by `item_names`.
+    pub items: Vec<String>,
+}
+
+impl<'a> ToTokens for GeneratedItems<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if !self.enabled {
+            return;
+        }
+
+        let builder_ty = &self.builder_ty;
+        let allow_lints = allow_lints_attr(self.allow_lints);
+        let generics = self.generics.cloned().unwrap_or_default();
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let items = &self.items;
+
+        tokens.append_all(quote!(
+            #allow_lints
+            impl #impl_generics #builder_ty #ty_generics #where_clause {
+                /// Names of the items generated for the builder, in order.
+                #[doc(hidden)]
+                pub const GENERATED_ITEMS: &'static [&'static str] = &[#(#items),*];
+            }
+        ));
+    }
+}
+
+/// Names of the items in the generated `tokens`, in order.
+///
+/// Types, traits, macros and functions are named as they are, e.g. `FooBuilder`, and the
+/// functions and constants of inherent impls and traits are prefixed with the name of the
+/// type, e.g. `FooBuilder::foo`. Items marked `#[doc(hidden)]`, trait impls and items generated
+/// by macros are left out.
+pub(crate) fn item_names(tokens: &TokenStream) -> Vec<String> {
+    let mut names = vec![];
+    if let Ok(file) = syn::parse2::<syn::File>(tokens.clone()) {
+        push_item_names(&mut names, &file.items);
+    }
+    names
+}
+
+fn push_item_names(names: &mut Vec<String>, items: &[syn::Item]) {
+    for item in items {
+        match item {
+            syn::Item::Struct(item) if !is_hidden(&item.attrs) => {
+                names.push(item.ident.to_string())
+            }
+            syn::Item::Enum(item) if !is_hidden(&item.attrs) => names.push(item.ident.to_string()),
+            syn::Item::Fn(item) if !is_hidden(&item.attrs) => {
+                names.push(item.sig.ident.to_string())
+            }
+            syn::Item::Macro(item) if !is_hidden(&item.attrs) => {
+                if let Some(ident) = &item.ident {
+                    names.push(format!("{}!", ident));
+                }
+            }
+            syn::Item::Trait(item) if !is_hidden(&item.attrs) => {
+                let ty = item.ident.to_string();
+                names.push(ty.clone());
+                for trait_item in &item.items {
+                    match trait_item {
+                        syn::TraitItem::Fn(f) if !is_hidden(&f.attrs) => {
+                            names.push(format!("{}::{}", ty, f.sig.ident))
+                        }
+                        syn::TraitItem::Const(c) if !is_hidden(&c.attrs) => {
+                            names.push(format!("{}::{}", ty, c.ident))
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            syn::Item::Impl(item) if item.trait_.is_none() && !is_hidden(&item.attrs) => {
+                let ty = match &*item.self_ty {
+                    syn::Type::Path(path) => match path.path.segments.last() {
+                        Some(segment) => segment.ident.to_string(),
+                        None => continue,
+                    },
+                    _ => continue,
+                };
+                for impl_item in &item.items {
+                    match impl_item {
+                        syn::ImplItem::Fn(f) if !is_hidden(&f.attrs) => {
+                            names.push(format!("{}::{}", ty, f.sig.ident))
+                        }
+                        syn::ImplItem::Const(c) if !is_hidden(&c.attrs) => {
+                            names.push(format!("{}::{}", ty, c.ident))
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            // Items which are wrapped in `const _: () = { ... };`, e.g. for `uses`.
+            syn::Item::Const(item) if item.ident == "_" => {
+                if let syn::Expr::Block(block) = &*item.expr {
+                    let items: Vec<syn::Item> = block
+                        .block
+                        .stmts
+                        .iter()
+                        .filter_map(|stmt| match stmt {
+                            syn::Stmt::Item(item) => Some(item.clone()),
+                            _ => None,
+                        })
+                        .collect();
+                    push_item_names(names, &items);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn is_hidden(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path().is_ident("doc")
+            && matches!(attr.parse_args::<syn::Ident>(), Ok(arg) if arg == "hidden")
+    })
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[cfg(test)] // This contains a Box::leak, so is suitable only for tests
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_generated_items {
+    () => {
+        GeneratedItems {
+            enabled: true,
+            builder_ty: syn::Ident::new("FooBuilder", ::proc_macro2::Span::call_site()),
+            generics: None,
+            allow_lints: &[],
+            items: vec!["FooBuilder".to_string(), "FooBuilder::foo".to_string()],
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn simple() {
+        let generated_items = default_generated_items!();
+
+        assert_eq!(
+            quote!(#generated_items).to_string(),
+            quote!(
+                impl FooBuilder {
+                    /// Names of the items generated for the builder, in order.
+                    #[doc(hidden)]
+                    pub const GENERATED_ITEMS: &'static [&'static str] = &["FooBuilder", "FooBuilder::foo"];
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn generic() {
+        let generics: syn::Generics = parse_quote!(<T: Clone>);
+        let mut generated_items = default_generated_items!();
+        generated_items.generics = Some(&generics);
+        generated_items.items.clear();
+
+        assert_eq!(
+            quote!(#generated_items).to_string(),
+            quote!(
+                impl<T: Clone> FooBuilder<T> {
+                    /// Names of the items generated for the builder, in order.
+                    #[doc(hidden)]
+                    pub const GENERATED_ITEMS: &'static [&'static str] = &[];
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn disabled() {
+        let mut generated_items = default_generated_items!();
+        generated_items.enabled = false;
+
+        assert_eq!(quote!(#generated_items).to_string(), quote!().to_string());
+    }
+
+    #[test]
+    fn names_in_order() {
+        let names = item_names(&quote!(
+            pub struct FooBuilder {}
+
+            impl FooBuilder {
+                pub fn foo(&mut self) {}
+                #[doc(hidden)]
+                fn __build_unnormalized(&self) {}
+                pub fn build(&self) {}
+            }
+
+            impl Default for FooBuilder {
+                fn default() -> Self {
+                    Self {}
+                }
+            }
+
+            const _: () = {
+                impl Foo {
+                    pub fn builder() {}
+                }
+            };
+
+            pub trait FooBuilderSetters {
+                fn bar(self);
+            }
+
+            macro_rules! foo {
+                () => {};
+            }
+        ));
+
+        assert_eq!(
+            names,
+            vec![
+                "FooBuilder",
+                "FooBuilder::foo",
+                "FooBuilder::build",
+                "Foo::builder",
+                "FooBuilderSetters",
+                "FooBuilderSetters::bar",
+                "foo!",
+            ]
+        );
+    }
+}
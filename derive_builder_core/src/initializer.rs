@@ -1,7 +1,11 @@
 use proc_macro2::{Span, TokenStream};
 use quote::{ToTokens, TokenStreamExt};
+use syn::ext::IdentExt;
 
-use crate::{change_span, BlockContents, BuilderPattern, DefaultExpression, DEFAULT_STRUCT_NAME};
+use crate::{
+    change_span, lazy_field_ident, BlockContents, BuilderPattern, DefaultExpression,
+    DEFAULT_STRUCT_NAME,
+};
 
 /// Initializer for the target struct fields, implementing `quote::ToTokens`.
 ///
@@ -33,11 +37,14 @@ use crate::{change_span, BlockContents, BuilderPattern, DefaultExpression, DEFAU
 /// # }
 /// ```
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct Initializer<'a> {
     /// Path to the root of the derive_builder crate.
     pub crate_root: &'a syn::Path,
     /// Name of the target field.
     pub field_ident: &'a syn::Ident,
+    /// Name of the field in the error if it is uninitialized.
+    pub field_name: String,
     /// Whether the builder implements a setter for this field.
     pub field_enabled: bool,
     /// How the build method takes and returns `self` (e.g. mutably).
@@ -62,6 +69,38 @@ pub struct Initializer<'a> {
     ///
     /// For sub-builder fields, this will be `build` (or similar)
     pub conversion: FieldConversion<'a>,
+    /// Whether the initializer lives in a `const fn` build method, which panics on uninitialized
+    /// fields instead of returning an error.
+    pub const_fn: bool,
+    /// Whether the initializer lives in a build method which takes a `fallback` instance of the
+    /// target type, whose value is used instead of defaults if the field is not set.
+    pub fallback: bool,
+    /// Bind the value to a local variable named like the field, instead of initializing the
+    /// field in the struct literal, so initializers which run later can read it.
+    pub bind_local: bool,
+    /// Whether the build method checks all fields with `missing_field_check` before the
+    /// initializers run, so an unset field without default can't be reached here.
+    pub collect_errors: bool,
+    /// Whether the builder field stores the value passed to the setter, which is converted into
+    /// the target field's type with `TryFrom`.
+    ///
+    /// Conversion errors are converted into the validation error of the build method.
+    pub convert_at_build: bool,
+    /// Whether the value is computed by the closure of the `_with` setter of `setter(lazy)` if
+    /// the builder field isn't set.
+    pub lazy: bool,
+    /// Whether the target struct is `#[repr(packed)]`, so its fields can't be borrowed.
+    ///
+    /// Values of the `fallback` instance are copied instead of cloned, which requires the field
+    /// to be `Copy`.
+    pub packed: bool,
+    /// Whether the initializer lives in a build method which doesn't require the field, so it
+    /// falls back to `Default::default()` if the field has no other default.
+    pub optional: bool,
+    /// Whether the initializer lives in a build method which assigns the fields of an existing
+    /// `target` instance of the target type. If `reuses_target_value` holds, a set value is
+    /// assigned with `Clone::clone_from`, which keeps the allocations of the target's value.
+    pub reuse_target: bool,
 }
 
 impl<'a> ToTokens for Initializer<'a> {
@@ -82,9 +121,53 @@ impl<'a> ToTokens for Initializer<'a> {
                         conv.to_tokens(tokens);
                     }
                     FieldConversion::Move => tokens.append_all(quote!( self.#builder_field )),
+                    FieldConversion::Clone => {
+                        let crate_root = self.crate_root;
+                        tokens.append_all(quote!(
+                            #crate_root::export::core::clone::Clone::clone(&self.#builder_field)
+                        ))
+                    }
+                    FieldConversion::Flatten => {
+                        let crate_root = self.crate_root;
+                        let result = quote!(#crate_root::export::core::result::Result);
+                        tokens.append_all(quote!(
+                            match self.#builder_field.build() {
+                                #result::Ok(value) => value,
+                                #result::Err(error) => return #result::Err(
+                                    #crate_root::export::core::convert::Into::into(
+                                        #crate_root::export::core::string::ToString::to_string(&error)
+                                    )
+                                ),
+                            }
+                        ));
+                    }
+                    FieldConversion::Delegate => {
+                        let crate_root = self.crate_root;
+                        let field_name = &self.field_name;
+                        let result = quote!(#crate_root::export::core::result::Result);
+                        tokens.append_all(quote!(
+                            match self.#builder_field.build() {
+                                #result::Ok(value) => value,
+                                #result::Err(error) => return #result::Err(
+                                    #crate_root::export::core::convert::Into::into(
+                                        #crate_root::NestedFieldError::new(#field_name, &error)
+                                    )
+                                ),
+                            }
+                        ));
+                    }
                     FieldConversion::OptionOrDefault => {
                         let match_some = self.match_some();
                         let match_none = self.match_none();
+                        let match_none = match self.lazy_field() {
+                            Some(lazy_field) => quote!(
+                                None => match self.#lazy_field {
+                                    Some(ref lazy) => lazy.get(),
+                                    #match_none,
+                                }
+                            ),
+                            None => quote!(#match_none),
+                        };
                         tokens.append_all(quote!(
                             match self.#builder_field {
                                 #match_some,
@@ -96,15 +179,153 @@ impl<'a> ToTokens for Initializer<'a> {
             }
         };
 
-        tokens.append_all(quote!(#struct_field:));
-        append_rhs(tokens);
-        tokens.append_all(quote!(,));
+        if self.reuse_target && self.reuses_target_value() {
+            let crate_root = self.crate_root;
+            let none = if self.fails_if_unset() {
+                quote!(#crate_root::export::core::unreachable!())
+            } else {
+                let default = self.default();
+                quote!(target.#struct_field = #default)
+            };
+            tokens.append_all(quote!(
+                match self.#builder_field {
+                    Some(ref value) => #crate_root::export::core::clone::Clone::clone_from(
+                        &mut target.#struct_field,
+                        value
+                    ),
+                    None => #none,
+                }
+            ));
+        } else if self.bind_local {
+            tokens.append_all(quote!(let #struct_field =));
+            append_rhs(tokens);
+            tokens.append_all(quote!(;));
+        } else {
+            tokens.append_all(quote!(#struct_field:));
+            append_rhs(tokens);
+            tokens.append_all(quote!(,));
+        }
     }
 }
 
 impl<'a> Initializer<'a> {
+    /// Create an initializer for the target field `field_ident`, which fails the build if the
+    /// builder field is not set.
+    pub fn new(crate_root: &'a syn::Path, field_ident: &'a syn::Ident) -> Self {
+        Initializer {
+            crate_root,
+            field_ident,
+            field_name: field_ident.unraw().to_string(),
+            field_enabled: true,
+            builder_pattern: BuilderPattern::Mutable,
+            default_value: None,
+            use_default_struct: false,
+            conversion: FieldConversion::OptionOrDefault,
+            custom_error_type_span: None,
+            const_fn: false,
+            fallback: false,
+            bind_local: false,
+            collect_errors: false,
+            convert_at_build: false,
+            lazy: false,
+            packed: false,
+            optional: false,
+            reuse_target: false,
+        }
+    }
+
+    /// Check which records the field name in `__missing` if the builder field is not set and
+    /// the initializer would fail the build, for build methods which collect all uninitialized
+    /// fields before failing.
+    ///
+    /// Returns `None` if the initializer can't fail.
+    pub fn missing_field_check(&self) -> Option<TokenStream> {
+        if !self.fails_if_unset() {
+            return None;
+        }
+
+        let crate_root = self.crate_root;
+        let builder_field = self.field_ident;
+        let field_name = &self.field_name;
+        let lazy_unset = self.lazy_field().map(|lazy_field| {
+            quote!(&& #crate_root::export::core::option::Option::is_none(&self.#lazy_field))
+        });
+        Some(quote!(
+            if #crate_root::export::core::option::Option::is_none(&self.#builder_field) #lazy_unset {
+                __missing.push(#field_name);
+            }
+        ))
+    }
+
+    /// Check which fails the build if the builder field is not set, for build methods which
+    /// assign the fields of an existing `target` instance only after all checks passed.
+    ///
+    /// Returns `None` if the initializer can't fail, or if `missing_field_check` is used instead.
+    pub fn uninitialized_check(&self) -> Option<TokenStream> {
+        if !self.fails_if_unset() || self.collect_errors {
+            return None;
+        }
+
+        let builder_field = self.field_ident;
+        let match_none = self.match_none();
+        let match_none = match self.lazy_field() {
+            Some(lazy_field) => quote!(
+                None => match self.#lazy_field {
+                    Some(_) => {}
+                    #match_none,
+                }
+            ),
+            None => quote!(#match_none),
+        };
+        Some(quote!(
+            match self.#builder_field {
+                Some(_) => {}
+                #match_none,
+            }
+        ))
+    }
+
+    /// Check if the field is assigned to an existing `target` instance in place, with
+    /// `Clone::clone_from` if it is set, instead of through a local binding of its value.
+    pub fn reuses_target_value(&self) -> bool {
+        self.field_enabled
+            && matches!(self.conversion, FieldConversion::OptionOrDefault)
+            && self.builder_pattern != BuilderPattern::Owned
+            && !self.convert_at_build
+            && !self.lazy
+            && !self.bind_local
+            && !self.packed
+    }
+
+    /// Name of the hidden builder field which holds the closure of `setter(lazy)`, if any.
+    fn lazy_field(&self) -> Option<syn::Ident> {
+        if self.lazy {
+            Some(lazy_field_ident(self.field_ident))
+        } else {
+            None
+        }
+    }
+
+    /// Check if the initializer fails the build if the builder field is not set.
+    fn fails_if_unset(&self) -> bool {
+        self.field_enabled
+            && matches!(self.conversion, FieldConversion::OptionOrDefault)
+            && !self.fallback
+            && self.default_value.is_none()
+            && !self.use_default_struct
+            && !self.const_fn
+            && !self.optional
+    }
+
     /// To be used inside of `#struct_field: match self.#builder_field { ... }`
     fn match_some(&'a self) -> MatchSome<'a> {
+        if self.convert_at_build {
+            return MatchSome::Convert {
+                crate_root: self.crate_root,
+                owned: self.builder_pattern == BuilderPattern::Owned,
+            };
+        }
+
         match self.builder_pattern {
             BuilderPattern::Owned => MatchSome::Move,
             BuilderPattern::Mutable | BuilderPattern::Immutable => MatchSome::Clone {
@@ -115,6 +336,14 @@ impl<'a> Initializer<'a> {
 
     /// To be used inside of `#struct_field: match self.#builder_field { ... }`
     fn match_none(&'a self) -> MatchNone<'a> {
+        if self.fallback {
+            return MatchNone::Fallback {
+                crate_root: self.crate_root,
+                field_ident: self.field_ident,
+                packed: self.packed,
+            };
+        }
+
         match self.default_value {
             Some(expr) => MatchNone::DefaultTo {
                 expr,
@@ -123,10 +352,23 @@ impl<'a> Initializer<'a> {
             None => {
                 if self.use_default_struct {
                     MatchNone::UseDefaultStructField(self.field_ident)
+                } else if self.optional {
+                    MatchNone::DefaultTrait {
+                        crate_root: self.crate_root,
+                    }
+                } else if self.const_fn {
+                    MatchNone::Panic {
+                        crate_root: self.crate_root,
+                        field_name: self.field_name.clone(),
+                    }
+                } else if self.collect_errors {
+                    MatchNone::Unreachable {
+                        crate_root: self.crate_root,
+                    }
                 } else {
                     MatchNone::ReturnError {
                         crate_root: self.crate_root,
-                        field_name: self.field_ident.to_string(),
+                        field_name: self.field_name.clone(),
                         span: self.custom_error_type_span,
                     }
                 }
@@ -136,6 +378,14 @@ impl<'a> Initializer<'a> {
 
     fn default(&'a self) -> TokenStream {
         let crate_root = self.crate_root;
+        if self.fallback {
+            let field_ident = self.field_ident;
+            if self.packed {
+                return quote!({ fallback.#field_ident });
+            }
+            return quote!(#crate_root::export::core::clone::Clone::clone(&fallback.#field_ident));
+        }
+
         match self.default_value {
             Some(expr) => expr.with_crate_root(crate_root).into_token_stream(),
             None if self.use_default_struct => {
@@ -150,6 +400,7 @@ impl<'a> Initializer<'a> {
     }
 }
 
+/// How an `Initializer` turns the builder field into the value of the target field.
 #[derive(Debug, Clone)]
 pub enum FieldConversion<'a> {
     /// Usual conversion: unwrap the Option from the builder, or (hope to) use a default value
@@ -158,6 +409,16 @@ pub enum FieldConversion<'a> {
     Block(&'a BlockContents),
     /// Custom conversion is just to move the field from the builder
     Move,
+    /// Clone the field of the builder, which the build method only borrows.
+    Clone,
+    /// The builder field is the builder of the field's type, which is built in turn.
+    ///
+    /// Its errors are converted into the validation error of the outer builder.
+    Flatten,
+    /// The builder field is the builder of the field's type, which is built in turn.
+    ///
+    /// Its errors are wrapped in a `NestedFieldError` naming the field.
+    Delegate,
 }
 
 /// To be used inside of `#struct_field: match self.#builder_field { ... }`
@@ -171,12 +432,31 @@ enum MatchNone<'a> {
     ///
     /// The default struct must be in scope in the build_method.
     UseDefaultStructField(&'a syn::Ident),
+    /// `Default::default()` of the field's type.
+    DefaultTrait { crate_root: &'a syn::Path },
     /// Inner value must be the field name
     ReturnError {
         crate_root: &'a syn::Path,
         field_name: String,
         span: Option<Span>,
     },
+    /// Inner value must be the field name
+    ///
+    /// Errors can't be converted in a `const fn`, so this panics instead.
+    Panic {
+        crate_root: &'a syn::Path,
+        field_name: String,
+    },
+    /// The build method already failed if the field is unset.
+    Unreachable { crate_root: &'a syn::Path },
+    /// Inner value must be the field identifier
+    ///
+    /// The `fallback` instance of the target type must be in scope in the build method.
+    Fallback {
+        crate_root: &'a syn::Path,
+        field_ident: &'a syn::Ident,
+        packed: bool,
+    },
 }
 
 impl<'a> ToTokens for MatchNone<'a> {
@@ -186,6 +466,9 @@ impl<'a> ToTokens for MatchNone<'a> {
                 let expr = expr.with_crate_root(crate_root);
                 tokens.append_all(quote!(None => #expr));
             }
+            MatchNone::DefaultTrait { crate_root } => tokens.append_all(quote!(
+                None => #crate_root::export::core::default::Default::default()
+            )),
             MatchNone::UseDefaultStructField(field_ident) => {
                 let struct_ident = syn::Ident::new(DEFAULT_STRUCT_NAME, Span::call_site());
                 tokens.append_all(quote!(
@@ -210,6 +493,32 @@ impl<'a> ToTokens for MatchNone<'a> {
                     None => return #crate_root::export::core::result::Result::Err(#err_conv)
                 ));
             }
+            MatchNone::Panic {
+                ref field_name,
+                crate_root,
+            } => {
+                let message = format!("`{}` must be initialized", field_name);
+                tokens.append_all(quote!(
+                    None => #crate_root::export::core::panic!(#message)
+                ));
+            }
+            MatchNone::Unreachable { crate_root } => tokens.append_all(quote!(
+                None => #crate_root::export::core::unreachable!()
+            )),
+            MatchNone::Fallback {
+                field_ident,
+                packed: true,
+                ..
+            } => tokens.append_all(quote!(
+                None => { fallback.#field_ident }
+            )),
+            MatchNone::Fallback {
+                crate_root,
+                field_ident,
+                packed: false,
+            } => tokens.append_all(quote!(
+                None => #crate_root::export::core::clone::Clone::clone(&fallback.#field_ident)
+            )),
         }
     }
 }
@@ -217,7 +526,14 @@ impl<'a> ToTokens for MatchNone<'a> {
 /// To be used inside of `#struct_field: match self.#builder_field { ... }`
 enum MatchSome<'a> {
     Move,
-    Clone { crate_root: &'a syn::Path },
+    Clone {
+        crate_root: &'a syn::Path,
+    },
+    /// Move or clone the value, and convert it with `TryFrom`.
+    Convert {
+        crate_root: &'a syn::Path,
+        owned: bool,
+    },
 }
 
 impl ToTokens for MatchSome<'_> {
@@ -229,6 +545,25 @@ impl ToTokens for MatchSome<'_> {
             Self::Clone { crate_root } => tokens.append_all(quote!(
                 Some(ref value) => #crate_root::export::core::clone::Clone::clone(value)
             )),
+            Self::Convert { crate_root, owned } => {
+                let core = quote!(#crate_root::export::core);
+                let (pattern, value) = if owned {
+                    (quote!(Some(value)), quote!(value))
+                } else {
+                    (
+                        quote!(Some(ref value)),
+                        quote!(#core::clone::Clone::clone(value)),
+                    )
+                };
+                tokens.append_all(quote!(
+                    #pattern => match #core::convert::TryFrom::try_from(#value) {
+                        #core::result::Result::Ok(value) => value,
+                        #core::result::Result::Err(error) => return #core::result::Result::Err(
+                            #core::convert::Into::into(#core::string::ToString::to_string(&error))
+                        ),
+                    }
+                ))
+            }
         }
     }
 }
@@ -244,12 +579,22 @@ macro_rules! default_initializer {
             // that all test cases are passing crate_root through properly.
             crate_root: &parse_quote!(::db),
             field_ident: &syn::Ident::new("foo", ::proc_macro2::Span::call_site()),
+            field_name: "foo".to_string(),
             field_enabled: true,
             builder_pattern: BuilderPattern::Mutable,
             default_value: None,
             use_default_struct: false,
             conversion: FieldConversion::OptionOrDefault,
             custom_error_type_span: None,
+            const_fn: false,
+            fallback: false,
+            bind_local: false,
+            collect_errors: false,
+            convert_at_build: false,
+            lazy: false,
+            packed: false,
+            optional: false,
+            reuse_target: false,
         }
     };
 }
@@ -316,6 +661,121 @@ mod tests {
         );
     }
 
+    #[test]
+    fn const_fn() {
+        let mut initializer = default_initializer!();
+        initializer.builder_pattern = BuilderPattern::Owned;
+        initializer.const_fn = true;
+
+        assert_eq!(
+            quote!(#initializer).to_string(),
+            quote!(
+                foo: match self.foo {
+                    Some(value) => value,
+                    None => ::db::export::core::panic!("`foo` must be initialized"),
+                },
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn fallback() {
+        let mut initializer = default_initializer!();
+        let default_value = DefaultExpression::explicit::<syn::Expr>(parse_quote!(42));
+        initializer.default_value = Some(&default_value);
+        initializer.fallback = true;
+
+        assert_eq!(
+            quote!(#initializer).to_string(),
+            quote!(
+                foo: match self.foo {
+                    Some(ref value) => ::db::export::core::clone::Clone::clone(value),
+                    None => ::db::export::core::clone::Clone::clone(&fallback.foo),
+                },
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn reuse_target() {
+        let mut initializer = default_initializer!();
+        initializer.reuse_target = true;
+
+        assert_eq!(
+            quote!(#initializer).to_string(),
+            quote!(match self.foo {
+                Some(ref value) =>
+                    ::db::export::core::clone::Clone::clone_from(&mut target.foo, value),
+                None => ::db::export::core::unreachable!(),
+            })
+            .to_string()
+        );
+
+        let check = initializer.uninitialized_check().unwrap();
+        assert_eq!(
+            check.to_string(),
+            quote!(match self.foo {
+                Some(_) => {}
+                None =>
+                    return ::db::export::core::result::Result::Err(
+                        ::db::export::core::convert::Into::into(
+                            ::db::UninitializedFieldError::from("foo")
+                        )
+                    ),
+            })
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn reuse_target_default() {
+        let mut initializer = default_initializer!();
+        let default_value = DefaultExpression::explicit::<syn::Expr>(parse_quote!(42));
+        initializer.default_value = Some(&default_value);
+        initializer.reuse_target = true;
+
+        assert!(initializer.uninitialized_check().is_none());
+        assert_eq!(
+            quote!(#initializer).to_string(),
+            quote!(match self.foo {
+                Some(ref value) =>
+                    ::db::export::core::clone::Clone::clone_from(&mut target.foo, value),
+                None => target.foo = { 42 },
+            })
+            .to_string()
+        );
+
+        // Moved values can't be assigned in place, so they are bound first.
+        initializer.builder_pattern = BuilderPattern::Owned;
+        assert!(!initializer.reuses_target_value());
+    }
+
+    #[test]
+    fn fallback_packed() {
+        let mut initializer = default_initializer!();
+        initializer.fallback = true;
+        initializer.packed = true;
+
+        assert_eq!(
+            quote!(#initializer).to_string(),
+            quote!(
+                foo: match self.foo {
+                    Some(ref value) => ::db::export::core::clone::Clone::clone(value),
+                    None => { fallback.foo },
+                },
+            )
+            .to_string()
+        );
+
+        initializer.field_enabled = false;
+        assert_eq!(
+            quote!(#initializer).to_string(),
+            quote!(foo: { fallback.foo },).to_string()
+        );
+    }
+
     #[test]
     fn default_value() {
         let mut initializer = default_initializer!();
@@ -334,6 +794,78 @@ mod tests {
         );
     }
 
+    #[test]
+    fn missing_field_check() {
+        let initializer = default_initializer!();
+
+        assert_eq!(
+            initializer.missing_field_check().unwrap().to_string(),
+            quote!(if ::db::export::core::option::Option::is_none(&self.foo) {
+                __missing.push("foo");
+            })
+            .to_string()
+        );
+
+        let mut initializer = default_initializer!();
+        let default_value = DefaultExpression::explicit::<syn::Expr>(parse_quote!(42));
+        initializer.default_value = Some(&default_value);
+        assert!(initializer.missing_field_check().is_none());
+    }
+
+    #[test]
+    fn optional() {
+        let mut initializer = default_initializer!();
+        initializer.optional = true;
+
+        assert_eq!(
+            quote!(#initializer).to_string(),
+            quote!(
+                foo: match self.foo {
+                    Some(ref value) => ::db::export::core::clone::Clone::clone(value),
+                    None => ::db::export::core::default::Default::default(),
+                },
+            )
+            .to_string()
+        );
+        assert!(initializer.missing_field_check().is_none());
+    }
+
+    #[test]
+    fn collect_errors() {
+        let mut initializer = default_initializer!();
+        initializer.collect_errors = true;
+
+        assert_eq!(
+            quote!(#initializer).to_string(),
+            quote!(
+                foo: match self.foo {
+                    Some(ref value) => ::db::export::core::clone::Clone::clone(value),
+                    None => ::db::export::core::unreachable!(),
+                },
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn bind_local() {
+        let mut initializer = default_initializer!();
+        let default_value = DefaultExpression::explicit::<syn::Expr>(parse_quote!(42));
+        initializer.default_value = Some(&default_value);
+        initializer.bind_local = true;
+
+        assert_eq!(
+            quote!(#initializer).to_string(),
+            quote!(
+                let foo = match self.foo {
+                    Some(ref value) => ::db::export::core::clone::Clone::clone(value),
+                    None => { 42 },
+                };
+            )
+            .to_string()
+        );
+    }
+
     #[test]
     fn default_struct() {
         let mut initializer = default_initializer!();
@@ -362,6 +894,124 @@ mod tests {
         );
     }
 
+    #[test]
+    fn convert_at_build() {
+        let mut initializer = default_initializer!();
+        initializer.convert_at_build = true;
+        let core = quote!(::db::export::core);
+
+        assert_eq!(
+            quote!(#initializer).to_string(),
+            quote!(
+                foo: match self.foo {
+                    Some(ref value) => match #core::convert::TryFrom::try_from(#core::clone::Clone::clone(value)) {
+                        #core::result::Result::Ok(value) => value,
+                        #core::result::Result::Err(error) => return #core::result::Result::Err(
+                            #core::convert::Into::into(#core::string::ToString::to_string(&error))
+                        ),
+                    },
+                    None => return #core::result::Result::Err(#core::convert::Into::into(
+                        ::db::UninitializedFieldError::from("foo")
+                    )),
+                },
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn lazy() {
+        let mut initializer = default_initializer!();
+        initializer.lazy = true;
+        let core = quote!(::db::export::core);
+
+        assert_eq!(
+            quote!(#initializer).to_string(),
+            quote!(
+                foo: match self.foo {
+                    Some(ref value) => #core::clone::Clone::clone(value),
+                    None => match self.__lazy_foo {
+                        Some(ref lazy) => lazy.get(),
+                        None => return #core::result::Result::Err(#core::convert::Into::into(
+                            ::db::UninitializedFieldError::from("foo")
+                        )),
+                    },
+                },
+            )
+            .to_string()
+        );
+        assert_eq!(
+            initializer.missing_field_check().unwrap().to_string(),
+            quote!(
+                if #core::option::Option::is_none(&self.foo)
+                    && #core::option::Option::is_none(&self.__lazy_foo)
+                {
+                    __missing.push("foo");
+                }
+            )
+            .to_string()
+        );
+        assert!(!initializer.reuses_target_value());
+    }
+
+    #[test]
+    fn clone_conversion() {
+        let mut initializer = default_initializer!();
+        initializer.conversion = FieldConversion::Clone;
+
+        assert_eq!(
+            quote!(#initializer).to_string(),
+            quote!(
+                foo: ::db::export::core::clone::Clone::clone(&self.foo),
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn flatten() {
+        let mut initializer = default_initializer!();
+        initializer.conversion = FieldConversion::Flatten;
+        let result = quote!(::db::export::core::result::Result);
+
+        assert_eq!(
+            quote!(#initializer).to_string(),
+            quote!(
+                foo: match self.foo.build() {
+                    #result::Ok(value) => value,
+                    #result::Err(error) => return #result::Err(
+                        ::db::export::core::convert::Into::into(
+                            ::db::export::core::string::ToString::to_string(&error)
+                        )
+                    ),
+                },
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn delegate() {
+        let mut initializer = default_initializer!();
+        initializer.conversion = FieldConversion::Delegate;
+        let result = quote!(::db::export::core::result::Result);
+
+        assert_eq!(
+            quote!(#initializer).to_string(),
+            quote!(
+                foo: match self.foo.build() {
+                    #result::Ok(value) => value,
+                    #result::Err(error) => return #result::Err(
+                        ::db::export::core::convert::Into::into(
+                            ::db::NestedFieldError::new("foo", &error)
+                        )
+                    ),
+                },
+            )
+            .to_string()
+        );
+    }
+
     #[test]
     fn no_std() {
         let initializer = default_initializer!();
@@ -11,6 +11,48 @@
 //! all the logic of builder creation to be decoupled from the proc-macro entry
 //! point.
 //!
+//! ## Composition API
+//!
+//! Other proc-macros can embed the code generation of [`derive_builder`] by
+//! assembling a [`Builder`] from its components: a [`BuilderField`], a [`Setter`]
+//! and an [`Initializer`] per field, and a [`BuildMethod`]. Each component has a
+//! `new` constructor which applies the defaults of `#[derive(Builder)]`; all
+//! other options are public fields which can be changed before the component is
+//! pushed. The components are `#[non_exhaustive]`, since new options add fields
+//! to them, so they can only be created with their constructors.
+//!
+//! ```rust
+//! # extern crate derive_builder_core;
+//! # extern crate quote;
+//! # extern crate syn;
+//! use derive_builder_core::{
+//!     BuildMethod, Builder, BuilderField, BuilderFieldType, Initializer, Setter,
+//! };
+//! use syn::parse_quote;
+//!
+//! # fn main() {
+//! let crate_root: syn::Path = parse_quote!(::derive_builder);
+//! let target: syn::Ident = parse_quote!(Lorem);
+//! let build: syn::Ident = parse_quote!(build);
+//! let field: syn::Ident = parse_quote!(ipsum);
+//! let ty: syn::Type = parse_quote!(u32);
+//!
+//! let mut builder = Builder::new(&crate_root, parse_quote!(LoremBuilder));
+//! let mut build_fn = BuildMethod::new(&crate_root, &build, &target, parse_quote!(LoremBuilderError));
+//!
+//! builder.push_field(BuilderField::new(&crate_root, &field, BuilderFieldType::Optional(&ty)));
+//! let mut setter = Setter::new(&crate_root, field.clone(), &field, BuilderFieldType::Optional(&ty));
+//! setter.generic_into = true;
+//! builder.push_setter_fn(setter);
+//! build_fn.push_initializer(Initializer::new(&crate_root, &field));
+//! builder.push_build_fn(build_fn);
+//!
+//! let tokens = quote::quote!(#builder).to_string();
+//! assert!(tokens.contains("pub struct LoremBuilder"));
+//! assert!(tokens.contains("pub fn ipsum"));
+//! assert!(tokens.contains("pub fn build"));
+//! # }
+//! ```
 //!
 //! [`derive_builder`]: https://!crates.io/crates/derive_builder
 //! [`derive_builder_core`]: https://!crates.io/crates/derive_builder_core
@@ -33,32 +75,102 @@ extern crate pretty_assertions;
 
 mod block;
 mod build_method;
+mod build_or_panic;
+mod build_wrapped;
 mod builder;
+mod builder_display;
 mod builder_field;
 mod change_span;
+mod config_key_impl;
+mod constructor_macro;
+mod convert_from;
 mod default_expression;
+mod delegate_accessor;
 mod deprecation_notes;
 mod doc_comment;
+mod dyn_set_impl;
+mod extra_fields;
+mod field_dependency;
+mod field_enum;
+mod field_validation;
+mod flattened_setters;
+mod from_query;
+mod from_str_impl;
+mod from_target;
+mod fuzz_build;
+mod generated_items;
 mod initializer;
 mod macro_options;
+mod no_alloc_build_test;
 mod options;
+mod pretty;
+mod reset_method;
 mod setter;
+mod to_map;
+mod try_from_impl;
 
-pub(crate) use block::BlockContents;
-pub(crate) use build_method::BuildMethod;
-pub(crate) use builder::Builder;
-pub(crate) use builder_field::{BuilderField, BuilderFieldType};
+pub use block::BlockContents;
+pub use build_method::BuildMethod;
+pub(crate) use build_or_panic::BuildOrPanicMethod;
+pub(crate) use build_wrapped::BuildWrappedMethod;
+pub use builder::{Builder, SettersTrait, SplitImpls};
+pub(crate) use builder_display::{BuilderDisplay, BuilderDisplayField};
+pub use builder_field::{BuilderField, BuilderFieldType};
 pub(crate) use change_span::change_span;
+pub(crate) use config_key_impl::{ConfigKeyField, ConfigKeyImpl};
+pub(crate) use constructor_macro::{ConstructorMacro, ConstructorMacroField};
+pub(crate) use convert_from::{ConvertFrom, ConvertedField};
 use darling::FromDeriveInput;
-pub(crate) use default_expression::DefaultExpression;
-pub(crate) use deprecation_notes::DeprecationNotes;
+pub use default_expression::{DefaultExpression, EpochUnit};
+pub(crate) use delegate_accessor::DelegateAccessor;
+pub use deprecation_notes::DeprecationNotes;
 pub(crate) use doc_comment::doc_comment_from;
-pub(crate) use initializer::{FieldConversion, Initializer};
-pub(crate) use options::{BuilderPattern, Each};
-pub(crate) use setter::Setter;
+pub(crate) use dyn_set_impl::{DynSetField, DynSetImpl};
+pub(crate) use extra_fields::ExtraFields;
+pub use field_dependency::{FieldDependency, FieldDependencyKind};
+pub(crate) use field_enum::{FieldEnum, FieldEnumVariant};
+pub use field_validation::FieldValidation;
+pub(crate) use flattened_setters::FlattenedSetters;
+pub(crate) use from_query::{FromQueryMethod, ParsedField};
+pub(crate) use from_str_impl::FromStrImpl;
+pub(crate) use from_target::{FromTarget, FromTargetField};
+pub(crate) use fuzz_build::{FuzzBuildMethod, FuzzField};
+pub(crate) use generated_items::{item_names, GeneratedItems};
+pub use initializer::{FieldConversion, Initializer};
+pub(crate) use no_alloc_build_test::NoAllocBuildTest;
+pub use options::{BuildWrapper, BuilderPattern, Each, EachBuilder, OnSet, PostBuildArg};
+pub use pretty::pretty_print;
+pub use reset_method::{ResetField, ResetKind, ResetMethod};
+pub use setter::Setter;
+pub(crate) use to_map::{ToMapField, ToMapMethod};
+pub(crate) use try_from_impl::TryFromImpl;
 
 const DEFAULT_STRUCT_NAME: &str = "__default";
 
+/// Name of the builder field which holds the first error passed to a `setter(defer_err)`.
+const DEFERRED_ERROR_FIELD: &str = "__deferred_error";
+
+/// Name of the builder field which holds the closure passed to the `_with` setter of a
+/// `setter(lazy)` field.
+fn lazy_field_ident(field_ident: &syn::Ident) -> syn::Ident {
+    format_ident!("__lazy_{}", syn::ext::IdentExt::unraw(field_ident))
+}
+
+/// Helper attributes which are only meaningful to `derive_builder`.
+///
+/// The derive registers these as inert attributes; the attribute macro has to strip them
+/// from the struct it re-emits.
+const HELPER_ATTRS: &[&str] = &[
+    "builder",
+    "builder_build_impl_attr",
+    "builder_field_attr",
+    "builder_impl_attr",
+    "builder_setter_attr",
+    "builder_setters_impl_attr",
+    "builder_struct_attr",
+    "builder_try_setters_impl_attr",
+];
+
 /// Derive a builder for a struct
 pub fn builder_for_struct(ast: syn::DeriveInput) -> proc_macro2::TokenStream {
     let opts = match macro_options::Options::from_derive_input(&ast) {
@@ -68,25 +180,341 @@ pub fn builder_for_struct(ast: syn::DeriveInput) -> proc_macro2::TokenStream {
         }
     };
 
+    let builder = assemble_builder(&opts, &ast.ident);
+    let constructor_macro = opts.as_constructor_macro();
+    let builder_display = opts.as_builder_display();
+    let config_key_impl = opts.as_config_key_impl();
+    let field_enum = opts.as_field_enum();
+    let from_str_impl = opts.as_from_str_impl();
+    let try_from_impl = opts.as_try_from_impl();
+    let convert_from = opts.as_convert_from();
+    let from_target = opts.as_from_target();
+    let dyn_set_impl = opts.as_dyn_set_impl();
+    let no_alloc_build_test = opts.as_no_alloc_build_test();
+    let flattened_setters = opts.as_flattened_setters();
+    let generated = quote!(#builder #(#flattened_setters)* #builder_display #config_key_impl #field_enum #from_str_impl #try_from_impl #convert_from #from_target #dyn_set_impl #constructor_macro #no_alloc_build_test);
+    let generated_items = opts.as_generated_items(&generated);
+    let output = apply_cfg(&opts, quote!(#generated #generated_items));
+    dump(&opts, &ast.ident, &output);
+    output
+}
+
+/// Generate a builder for a struct annotated with the `builder_attr` attribute macro.
+///
+/// `args` are the contents of the attribute, and accept the same options as a struct-level
+/// `#[builder(...)]` attribute. Unlike the derive, the struct itself is re-emitted, with all
+/// `derive_builder` helper attributes removed and an associated `builder()` function added.
+pub fn builder_for_struct_attr(
+    args: proc_macro2::TokenStream,
+    mut ast: syn::DeriveInput,
+) -> proc_macro2::TokenStream {
+    if !args.is_empty() {
+        ast.attrs.insert(0, parse_quote!(#[builder(#args)]));
+    }
+
+    let opts = match macro_options::Options::from_derive_input(&ast) {
+        Ok(val) => val,
+        Err(err) => {
+            // The attribute replaces the struct, which must survive to avoid follow-on errors.
+            strip_helper_attrs(&mut ast);
+            let errors = err.write_errors();
+            return quote!(#ast #errors);
+        }
+    };
+
+    strip_helper_attrs(&mut ast);
+    if opts.cfg().is_none() {
+        link_builder_from_target(&opts, &mut ast);
+    }
+
+    let mut builder = assemble_builder(&opts, &ast.ident);
+    builder.target_ty = Some(&ast.ident);
+    let constructor_macro = opts.as_constructor_macro();
+    let builder_display = opts.as_builder_display();
+    let config_key_impl = opts.as_config_key_impl();
+    let field_enum = opts.as_field_enum();
+    let from_str_impl = opts.as_from_str_impl();
+    let try_from_impl = opts.as_try_from_impl();
+    let convert_from = opts.as_convert_from();
+    let from_target = opts.as_from_target();
+    let dyn_set_impl = opts.as_dyn_set_impl();
+    let no_alloc_build_test = opts.as_no_alloc_build_test();
+    let flattened_setters = opts.as_flattened_setters();
+    let generated = quote!(#builder #(#flattened_setters)* #builder_display #config_key_impl #field_enum #from_str_impl #try_from_impl #convert_from #from_target #dyn_set_impl #constructor_macro #no_alloc_build_test);
+    let generated_items = opts.as_generated_items(&generated);
+    let generated = apply_cfg(&opts, quote!(#generated #generated_items));
+    let output = quote!(#ast #generated);
+    dump(&opts, &ast.ident, &output);
+    output
+}
+
+fn assemble_builder<'a>(opts: &'a macro_options::Options, struct_name: &syn::Ident) -> Builder<'a> {
     let mut builder = opts.as_builder();
     let mut build_fn = opts.as_build_method();
+    let mut const_build_fn = opts.as_const_build_method();
+    let mut fallback_build_fn = opts.as_fallback_build_method();
+    let mut build_into_fn = opts.as_build_into_method();
+    let mut all_errors_build_fn = opts.as_all_errors_build_method();
+    let mut subset_build_fns = opts.as_subset_build_methods();
 
-    builder.doc_comment(format!(
+    let mut builder_doc = format!(
         include_str!("doc_tpl/builder_struct.md"),
-        struct_name = ast.ident
-    ));
+        struct_name = struct_name
+    );
+    if let Some(example) = opts.usage_example() {
+        builder_doc.push_str(&example);
+    }
+    builder.doc_comment(builder_doc);
     build_fn.doc_comment(format!(
         include_str!("doc_tpl/builder_method.md"),
-        struct_name = ast.ident
+        struct_name = struct_name
+    ));
+    const_build_fn.doc_comment(format!(
+        include_str!("doc_tpl/builder_method_const.md"),
+        struct_name = struct_name
     ));
+    fallback_build_fn.doc_comment(format!(
+        include_str!("doc_tpl/builder_method_fallback.md"),
+        struct_name = struct_name
+    ));
+    build_into_fn.doc_comment(format!(
+        include_str!("doc_tpl/builder_method_into.md"),
+        struct_name = struct_name
+    ));
+    all_errors_build_fn.doc_comment(format!(
+        include_str!("doc_tpl/builder_method_all_errors.md"),
+        struct_name = struct_name
+    ));
+    for (subset, subset_build_fn) in &mut subset_build_fns {
+        subset_build_fn.doc_comment(format!(
+            include_str!("doc_tpl/builder_method_subset.md"),
+            struct_name = struct_name,
+            required_fields = subset.required_fields_doc()
+        ));
+    }
 
     for field in opts.fields() {
         builder.push_field(field.as_builder_field());
         builder.push_setter_fn(field.as_setter());
+        if let Some(accessor) = field.as_delegate_accessor() {
+            builder.push_delegate_accessor(accessor);
+        }
+        if let Some(validation) = field.as_field_validation() {
+            for (_, subset_build_fn) in &mut subset_build_fns {
+                subset_build_fn.push_field_validation(validation.clone());
+            }
+            build_fn.push_field_validation(validation.clone());
+            build_into_fn.push_field_validation(validation.clone());
+            all_errors_build_fn.push_field_validation(validation.clone());
+            fallback_build_fn.push_field_validation(validation);
+        }
+        for dependency in field.as_field_dependencies() {
+            for (_, subset_build_fn) in &mut subset_build_fns {
+                subset_build_fn.push_dependency_check(dependency.clone());
+            }
+            build_fn.push_dependency_check(dependency.clone());
+            build_into_fn.push_dependency_check(dependency.clone());
+            all_errors_build_fn.push_dependency_check(dependency.clone());
+            fallback_build_fn.push_dependency_check(dependency.clone());
+            const_build_fn.push_dependency_check(FieldDependency {
+                const_fn: true,
+                ..dependency
+            });
+        }
+    }
+
+    for field in opts.initialization_order() {
         build_fn.push_initializer(field.as_initializer());
+        const_build_fn.push_initializer(field.as_const_initializer());
+        fallback_build_fn.push_initializer(field.as_fallback_initializer());
+        build_into_fn.push_initializer(field.as_initializer());
+        all_errors_build_fn.push_initializer(field.as_initializer());
+        for (subset, subset_build_fn) in &mut subset_build_fns {
+            subset_build_fn.push_initializer(field.as_subset_initializer(subset));
+        }
     }
 
     builder.push_build_fn(build_fn);
+    builder.push_build_fn(const_build_fn);
+    builder.push_build_fn(fallback_build_fn);
+    builder.push_build_fn(build_into_fn);
+    builder.push_build_fn(all_errors_build_fn);
+    for (_, subset_build_fn) in subset_build_fns {
+        builder.push_build_fn(subset_build_fn);
+    }
+    builder.push_build_or_panic_fn(opts.as_build_or_panic_method());
+    for build_wrapped_fn in opts.as_build_wrapped_methods() {
+        builder.push_build_wrapped_fn(build_wrapped_fn);
+    }
+    if let Some(extra_fields) = opts.as_extra_fields() {
+        builder.push_extra_fields(extra_fields);
+    }
+    for mutator in opts.mutators() {
+        builder.push_mutator_fn(mutator);
+    }
+    builder.push_reset_fn(opts.as_reset_method());
+    builder.push_from_query_fn(opts.as_from_query_method());
+    builder.push_to_map_fn(opts.as_to_map_method());
+    builder.push_fuzz_build_fn(opts.as_fuzz_build_method());
+    builder
+}
 
-    quote!(#builder)
+fn dump(
+    opts: &macro_options::Options,
+    struct_name: &syn::Ident,
+    output: &proc_macro2::TokenStream,
+) {
+    if opts.dump() {
+        eprintln!(
+            "derive_builder: expansion for `{}`\n{}",
+            struct_name,
+            pretty_print(output)
+        );
+    }
+}
+
+/// Put each generated item behind the `#[builder(cfg(...))]` predicate, if there is one.
+fn apply_cfg(
+    opts: &macro_options::Options,
+    generated: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let predicate = match opts.cfg() {
+        Some(predicate) => predicate,
+        None => return generated,
+    };
+
+    // The generated tokens are a sequence of items, each of which needs its own attribute.
+    // Items which can't be gated are an error, since they would be emitted unconditionally.
+    let ungated = || {
+        quote!(::core::compile_error!(
+            "derive_builder could not apply `#[builder(cfg(...))]` to all generated items"
+        );)
+    };
+    let mut file: syn::File = match syn::parse2(generated) {
+        Ok(file) => file,
+        Err(_) => return ungated(),
+    };
+    for item in &mut file.items {
+        let attrs = match item {
+            syn::Item::Const(item) => &mut item.attrs,
+            syn::Item::Enum(item) => &mut item.attrs,
+            syn::Item::ExternCrate(item) => &mut item.attrs,
+            syn::Item::Fn(item) => &mut item.attrs,
+            syn::Item::ForeignMod(item) => &mut item.attrs,
+            syn::Item::Impl(item) => &mut item.attrs,
+            syn::Item::Macro(item) => &mut item.attrs,
+            syn::Item::Mod(item) => &mut item.attrs,
+            syn::Item::Static(item) => &mut item.attrs,
+            syn::Item::Struct(item) => &mut item.attrs,
+            syn::Item::Trait(item) => &mut item.attrs,
+            syn::Item::TraitAlias(item) => &mut item.attrs,
+            syn::Item::Type(item) => &mut item.attrs,
+            syn::Item::Union(item) => &mut item.attrs,
+            syn::Item::Use(item) => &mut item.attrs,
+            _ => return ungated(),
+        };
+        attrs.insert(0, parse_quote!(#[cfg(#predicate)]));
+    }
+    quote!(#file)
+}
+
+/// Document the struct with a link to its builder.
+///
+/// The link is omitted if the builder is less visible than the struct, since rustdoc warns about
+/// public documentation linking to private items.
+fn link_builder_from_target(opts: &macro_options::Options, ast: &mut syn::DeriveInput) {
+    let builder_vis = opts.builder_vis();
+    let target_vis = &ast.vis;
+    if !matches!(*builder_vis, syn::Visibility::Public(_))
+        && quote!(#builder_vis).to_string() != quote!(#target_vis).to_string()
+    {
+        return;
+    }
+
+    let doc = format!(
+        include_str!("doc_tpl/target_struct.md"),
+        builder_name = opts.builder_ident(),
+        struct_name = ast.ident
+    );
+
+    if ast.attrs.iter().any(|attr| attr.path().is_ident("doc")) {
+        ast.attrs.push(parse_quote!(#[doc = ""]));
+    }
+    ast.attrs.push(doc_comment_from(doc));
+}
+
+/// Remove `derive_builder` helper attributes from the struct and its fields.
+fn strip_helper_attrs(ast: &mut syn::DeriveInput) {
+    fn retain(attrs: &mut Vec<syn::Attribute>) {
+        attrs.retain(|attr| !HELPER_ATTRS.iter().any(|name| attr.path().is_ident(name)));
+    }
+
+    retain(&mut ast.attrs);
+    if let syn::Data::Struct(data) = &mut ast.data {
+        for field in data.fields.iter_mut() {
+            retain(&mut field.attrs);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_attr_keeps_struct_on_error() {
+        let ast: syn::DeriveInput = parse_quote! {
+            struct Lorem {
+                #[builder(setter(unknown_option))]
+                ipsum: u32,
+            }
+        };
+        let output = builder_for_struct_attr(quote!(), ast);
+        let expected = quote!(
+            struct Lorem {
+                ipsum: u32,
+            }
+        );
+
+        assert!(output.to_string().starts_with(&expected.to_string()));
+        assert!(output.to_string().contains("compile_error"));
+    }
+
+    #[test]
+    fn cfg_gates_every_item() {
+        let ast: syn::DeriveInput = parse_quote! {
+            #[builder(cfg(feature = "lorem"))]
+            struct Lorem {
+                ipsum: u32,
+            }
+        };
+        let opts = macro_options::Options::from_derive_input(&ast).unwrap();
+
+        let output = apply_cfg(
+            &opts,
+            quote!(
+                const _: () = {};
+                mod dolor {}
+                fn sit() {}
+                use amet::Consectetur;
+            ),
+        );
+        assert_eq!(
+            output.to_string(),
+            quote!(
+                #[cfg(feature = "lorem")]
+                const _: () = {};
+                #[cfg(feature = "lorem")]
+                mod dolor {}
+                #[cfg(feature = "lorem")]
+                fn sit() {}
+                #[cfg(feature = "lorem")]
+                use amet::Consectetur;
+            )
+            .to_string()
+        );
+
+        let output = apply_cfg(&opts, quote!(let x = 1;));
+        assert!(output.to_string().contains("compile_error"));
+    }
 }
@@ -1,16 +1,28 @@
 use std::convert::TryFrom;
 use std::{borrow::Cow, vec::IntoIter};
 
+use crate::setter::{
+    extract_borrowed_type_from_cow, extract_key_value_types_from_map, extract_type_from_box,
+    extract_type_from_named_wrapper, extract_type_from_option,
+};
 use crate::BuildMethod;
 
 use darling::util::{Flag, PathList, SpannedValue};
 use darling::{Error, FromMeta};
-use proc_macro2::Span;
-use syn::{spanned::Spanned, Attribute, Generics, Ident, Meta, Path};
+use proc_macro2::{Span, TokenStream};
+use syn::punctuated::Punctuated;
+use syn::{ext::IdentExt, spanned::Spanned, Attribute, Generics, Ident, Meta, Path, Token};
 
 use crate::{
-    BlockContents, Builder, BuilderField, BuilderFieldType, BuilderPattern, DefaultExpression,
-    DeprecationNotes, Each, FieldConversion, Initializer, Setter,
+    item_names, BlockContents, BuildOrPanicMethod, BuildWrappedMethod, BuildWrapper, Builder,
+    BuilderDisplay, BuilderDisplayField, BuilderField, BuilderFieldType, BuilderPattern,
+    ConfigKeyField, ConfigKeyImpl, ConstructorMacro, ConstructorMacroField, ConvertFrom,
+    ConvertedField, DefaultExpression, DelegateAccessor, DeprecationNotes, DynSetField, DynSetImpl,
+    Each, EachBuilder, ExtraFields, FieldConversion, FieldDependency, FieldDependencyKind,
+    FieldEnum, FieldEnumVariant, FieldValidation, FlattenedSetters, FromQueryMethod, FromStrImpl,
+    FromTarget, FromTargetField, FuzzBuildMethod, FuzzField, GeneratedItems, Initializer,
+    NoAllocBuildTest, OnSet, ParsedField, PostBuildArg, ResetField, ResetKind, ResetMethod, Setter,
+    SettersTrait, SplitImpls, ToMapField, ToMapMethod, TryFromImpl,
 };
 
 #[derive(Debug, Clone)]
@@ -25,7 +37,7 @@ enum VisibilityAttr {
 }
 
 impl VisibilityAttr {
-    pub fn to_explicit_visibility(&self) -> Option<Cow<syn::Visibility>> {
+    pub fn to_explicit_visibility(&self) -> Option<Cow<'_, syn::Visibility>> {
         match self {
             Self::Public(span) => Some(Cow::Owned(syn::Visibility::Public(
                 parse_quote_spanned!(*span=> pub),
@@ -37,6 +49,8 @@ impl VisibilityAttr {
     }
 }
 
+// `#[derive(Default)]` on enums requires Rust 1.62, which is above our MSRV.
+#[allow(clippy::derivable_impls)]
 impl Default for VisibilityAttr {
     fn default() -> Self {
         Self::None
@@ -136,7 +150,17 @@ impl FromMeta for BuildFnError {
 pub struct BuildFn {
     skip: bool,
     name: Ident,
-    validate: Option<Path>,
+    /// Paths to functions which check the builder before the struct is built, called in the
+    /// order of declaration.
+    #[darling(multiple)]
+    validate: Vec<Path>,
+    /// Path to a function which receives a mutable copy of the builder to normalize, called
+    /// before all validations.
+    normalize: Option<Path>,
+    /// Function which checks the built struct before it is returned, called after all
+    /// validations of the builder.
+    #[darling(with = parse_post_build)]
+    post_build: Option<PostBuild>,
     #[darling(flatten)]
     visibility: VisibilityAttr,
     /// Either the path to an existing error type that the build method should return or a meta
@@ -157,22 +181,83 @@ pub struct BuildFn {
     /// This type's bounds depend on other settings of the builder.
     ///
     /// * If uninitialized fields cause `build()` to fail, then this type
-    ///   must `impl From<UninitializedFieldError>`. Uninitialized fields do not cause errors
+    ///   must `impl From<UninitializedFieldError>`, or `impl From<UninitializedFieldsError>` with
+    ///   `collect_errors`. Uninitialized fields do not cause errors
     ///   when default values are provided for every field or at the struct level.
     /// * If `validate` is specified, then this type must provide a conversion from the specified
     ///   function's error type.
     error: Option<BuildFnError>,
+    /// Path to a function which produces the message of uninitialized field errors in the
+    /// generated error type, given the struct name, field name and error kind.
+    message_fn: Option<Path>,
+    /// Whether to generate `build_with_fallback`, which takes the values of unset fields from an
+    /// existing instance of the target type.
+    fallback: Flag,
+    /// Whether to generate `build_into`, which assigns the fields of an existing instance of the
+    /// target type instead of returning a new one.
+    reuse: Flag,
+    /// Whether to generate `build_all_errors`, which runs every check and returns the errors of
+    /// all failing ones at once.
+    all_errors: Flag,
+    /// Type which the build methods convert the built struct into with `Into`, and return
+    /// instead, e.g. `into = "PublicConfig"`.
+    into: Option<syn::Type>,
+    /// Whether `build()` collects all uninitialized fields into one `UninitializedFieldsError`,
+    /// instead of failing at the first one.
+    collect_errors: Flag,
+    /// Whether to generate `build_or_panic`, which unwraps the result of `build()`.
+    panic_variant: Flag,
+    /// Whether the build method of an owned builder returns the builder with the error, so it
+    /// can be fixed and built again.
+    return_builder: Flag,
+    /// Whether to implement `TryFrom` of the builder for the built struct, which calls the build
+    /// method.
+    try_from: Flag,
+    /// Smart pointers which additional build methods put the built struct into.
+    #[darling(default, with = parse_build_wrappers)]
+    wrappers: Vec<BuildWrapper>,
+    /// Additional build methods which only require some of the fields.
+    #[darling(multiple)]
+    subset: Vec<BuildSubset>,
+}
+
+/// Options for a build method which only requires the listed fields, and defaults the others,
+/// e.g. `build_fn(subset(name = "build_minimal", requires(ipsum, dolor)))`.
+#[derive(Debug, Clone, FromMeta)]
+pub struct BuildSubset {
+    /// Name of the build method.
+    name: Ident,
+    /// Fields which must be set, unless they have a default.
+    #[darling(default, with = parse_field_list)]
+    requires: Vec<Ident>,
+}
+
+impl BuildSubset {
+    /// List the required fields for the documentation of the build method, e.g.
+    /// "`ipsum` and `dolor`".
+    pub fn required_fields_doc(&self) -> String {
+        let names: Vec<String> = self
+            .requires
+            .iter()
+            .map(|ident| format!("`{}`", ident.unraw()))
+            .collect();
+        match names.split_last() {
+            None => "no fields".to_string(),
+            Some((last, [])) => last.clone(),
+            Some((last, rest)) => format!("{} and {}", rest.join(", "), last),
+        }
+    }
 }
 
 impl BuildFn {
     fn validation_needs_error(self) -> darling::Result<Self> {
         let mut acc = Error::accumulator();
-        if self.validate.is_some() {
+        if !self.validate.is_empty() || self.post_build.is_some() {
             if let Some(BuildFnError::Generated(e)) = &self.error {
                 if !*e.validation_error {
                     acc.push(
                         Error::custom(
-                            "Cannot set `error(validation_error = false)` when using `validate`",
+                            "Cannot set `error(validation_error = false)` when using `validate` or `post_build`",
                         )
                         .with_span(&e.validation_error.span()),
                     )
@@ -189,10 +274,181 @@ impl Default for BuildFn {
         BuildFn {
             skip: false,
             name: Ident::new("build", Span::call_site()),
-            validate: None,
+            validate: vec![],
+            normalize: None,
+            post_build: None,
             visibility: Default::default(),
             error: None,
+            message_fn: None,
+            fallback: Default::default(),
+            reuse: Default::default(),
+            all_errors: Default::default(),
+            into: None,
+            collect_errors: Default::default(),
+            panic_variant: Default::default(),
+            return_builder: Default::default(),
+            try_from: Default::default(),
+            wrappers: vec![],
+            subset: vec![],
+        }
+    }
+}
+
+/// Options for the `constructor_macro` property in struct-level builder options.
+#[derive(Debug, Clone, Default, FromMeta)]
+pub struct ConstructorMacroOpts {
+    /// The name of the generated macro. Defaults to the struct name in snake case.
+    name: Option<Ident>,
+}
+
+/// Create `ConstructorMacroOpts` from an attribute's `Meta`.
+///
+/// Three formats are supported:
+///
+/// * `constructor_macro`, which names the macro after the struct
+/// * `constructor_macro = "..."`, which provides the name of the macro
+/// * `constructor_macro(name = "...")`
+fn parse_constructor_macro(meta: &Meta) -> darling::Result<Option<ConstructorMacroOpts>> {
+    match meta {
+        Meta::Path(_) => Ok(Some(ConstructorMacroOpts::default())),
+        Meta::NameValue(mnv) => Ident::from_meta(meta)
+            .map(|name| Some(ConstructorMacroOpts { name: Some(name) }))
+            .map_err(|e| e.with_span(&mnv.value)),
+        Meta::List(_) => ConstructorMacroOpts::from_meta(meta).map(Some),
+    }
+}
+
+/// Options for the `post_build` property of `build_fn`.
+#[derive(Debug, Clone, FromMeta)]
+struct PostBuild {
+    /// Path to the function which receives the built struct.
+    path: Path,
+    /// Whether the function receives `&Foo`, `&mut Foo` or `Foo`.
+    #[darling(default)]
+    arg: PostBuildArg,
+}
+
+/// Create `PostBuild` from an attribute's `Meta`.
+///
+/// Two formats are supported:
+///
+/// * `post_build = "..."`, which provides the path of a function receiving `&Foo`
+/// * `post_build(path = "...", arg = "ref" | "mut" | "owned")`
+fn parse_post_build(meta: &Meta) -> darling::Result<Option<PostBuild>> {
+    match meta {
+        Meta::Path(_) => Err(Error::unsupported_format("word").with_span(meta)),
+        Meta::NameValue(_) => Path::from_meta(meta).map(|path| {
+            Some(PostBuild {
+                path,
+                arg: PostBuildArg::Ref,
+            })
+        }),
+        Meta::List(_) => PostBuild::from_meta(meta).map(Some),
+    }
+}
+
+/// Options for the `field_enum` property in struct-level builder options.
+#[derive(Debug, Clone, Default, FromMeta)]
+pub struct FieldEnumOpts {
+    /// The name of the generated enum. Defaults to the struct name followed by `Field`.
+    name: Option<Ident>,
+}
+
+/// Create `FieldEnumOpts` from an attribute's `Meta`.
+///
+/// Three formats are supported:
+///
+/// * `field_enum`, which names the enum after the struct
+/// * `field_enum = "..."`, which provides the name of the enum
+/// * `field_enum(name = "...")`
+fn parse_field_enum(meta: &Meta) -> darling::Result<Option<FieldEnumOpts>> {
+    match meta {
+        Meta::Path(_) => Ok(Some(FieldEnumOpts::default())),
+        Meta::NameValue(mnv) => Ident::from_meta(meta)
+            .map(|name| Some(FieldEnumOpts { name: Some(name) }))
+            .map_err(|e| e.with_span(&mnv.value)),
+        Meta::List(_) => FieldEnumOpts::from_meta(meta).map(Some),
+    }
+}
+
+/// Parse the comma-separated `use` trees of `uses = "..."`, e.g. `crate::units::{Meters, Seconds}`.
+fn parse_uses(meta: &Meta) -> darling::Result<Vec<syn::UseTree>> {
+    use syn::parse::Parser;
+
+    let uses = String::from_meta(meta)?;
+    Punctuated::<syn::UseTree, Token![,]>::parse_terminated
+        .parse_str(&uses)
+        .map(|uses| uses.into_iter().collect())
+        .map_err(|e| Error::custom(e).with_span(meta))
+}
+
+/// Parse the methods of `mutators(...)`, e.g. `mutators(fn preset_fast(&mut self) { ... })`,
+/// which are written like methods of an `impl` block.
+fn parse_mutators(meta: &Meta) -> darling::Result<Vec<syn::ImplItemFn>> {
+    let list = match meta {
+        Meta::List(list) => list,
+        _ => {
+            return Err(
+                Error::custom("`mutators` expects methods, e.g. `mutators(fn ...)`")
+                    .with_span(meta),
+            )
+        }
+    };
+
+    list.parse_args_with(|input: syn::parse::ParseStream| {
+        let mut mutators = Vec::new();
+        while !input.is_empty() {
+            mutators.push(input.parse()?);
         }
+        Ok(mutators)
+    })
+    .map_err(Error::from)
+}
+
+/// Create the `#[deprecated]` attribute of a deprecated builder from an attribute's `Meta`.
+///
+/// The arguments are passed through, so all formats of `#[deprecated]` are supported:
+///
+/// * `deprecated`
+/// * `deprecated = "..."`, which provides the note
+/// * `deprecated(note = "...", since = "...")`
+fn parse_deprecated(meta: &Meta) -> darling::Result<Option<Attribute>> {
+    Ok(Some(parse_quote!(#[#meta])))
+}
+
+/// Options for the `convert_from` property in struct-level builder options.
+#[derive(Debug, Clone, FromMeta)]
+pub struct ConvertFromOpts {
+    /// The builder to convert from.
+    ty: syn::Type,
+    /// Fields which the other builder doesn't have, and which are left unset.
+    #[darling(default, with = parse_field_list)]
+    skip: Vec<Ident>,
+}
+
+/// Create `ConvertFromOpts` from an attribute's `Meta`.
+///
+/// Two formats are supported:
+///
+/// * `convert_from = "..."`, which provides the builder to convert from
+/// * `convert_from(ty = "...", skip(a, b))`, which also lists the fields to leave unset
+fn parse_convert_from(meta: &Meta) -> darling::Result<Option<ConvertFromOpts>> {
+    match meta {
+        Meta::NameValue(mnv) => syn::Type::from_meta(meta)
+            .map(|ty| Some(ConvertFromOpts { ty, skip: vec![] }))
+            .map_err(|e| e.with_span(&mnv.value)),
+        _ => ConvertFromOpts::from_meta(meta).map(Some),
+    }
+}
+
+/// Extract the predicate of `cfg(...)`, e.g. `feature = "builder"`.
+fn parse_cfg(meta: &Meta) -> darling::Result<Option<TokenStream>> {
+    match meta {
+        Meta::List(list) if !list.tokens.is_empty() => Ok(Some(list.tokens.clone())),
+        _ => Err(
+            Error::custom("Expected a predicate, e.g. `cfg(feature = \"builder\")`")
+                .with_span(meta),
+        ),
     }
 }
 
@@ -208,13 +464,32 @@ pub struct FieldLevelFieldMeta {
     builder_type: Option<syn::Type>,
     /// Custom builder field method, for making target struct field value
     build: Option<BlockContents>,
+    /// Initial capacity of the collection created by the `each` setter
+    capacity: Option<syn::Expr>,
+    /// Store the value in the builder field without an `Option`, starting out with the default
+    bare: Flag,
 }
 
 #[derive(Debug, Clone, Default, FromMeta)]
 pub struct StructLevelSetter {
+    /// Visibility of the setters, which defaults to the visibility of the builder.
+    #[darling(flatten)]
+    visibility: VisibilityAttr,
     prefix: Option<Ident>,
+    #[darling(default, with = parse_suffix)]
+    suffix: Option<String>,
+    rename_all: Option<RenameRule>,
     into: Option<bool>,
+    into_impl: Option<bool>,
+    generic: Option<Ident>,
     strip_option: Option<bool>,
+    /// Make the setters of `bool` fields take no parameter and set them to `true`.
+    strip_bool: Option<bool>,
+    maybe: Option<bool>,
+    conditional: Option<bool>,
+    boxed: Option<bool>,
+    borrowed: Option<bool>,
+    cow_owned: Option<bool>,
     skip: Option<bool>,
 }
 
@@ -226,6 +501,117 @@ impl StructLevelSetter {
     }
 }
 
+/// A case convention applied to the names of all setters by `setter(rename_all = "...")`,
+/// or to the field names in errors and other generated strings by `field_name_case = "..."`.
+///
+/// Names start out in `snake_case`, so the conventions follow serde's `rename_all` for fields.
+/// The kebab-case conventions don't produce identifiers and are only valid for field names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenameRule {
+    /// `lowercase`
+    Lower,
+    /// `UPPERCASE`
+    Upper,
+    /// `PascalCase`
+    Pascal,
+    /// `camelCase`
+    Camel,
+    /// `snake_case`
+    Snake,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnake,
+    /// `kebab-case`
+    Kebab,
+    /// `SCREAMING-KEBAB-CASE`
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    /// Apply the convention to a name. Names which aren't in `snake_case`, e.g. the `camelCase`
+    /// fields of FFI-derived structs, are split into words first, except for `lowercase` and
+    /// `UPPERCASE`, which only change the case.
+    pub fn apply(self, name: &str) -> String {
+        match self {
+            RenameRule::Lower => name.to_ascii_lowercase(),
+            RenameRule::Upper => name.to_ascii_uppercase(),
+            RenameRule::Snake => to_snake_case(name),
+            RenameRule::ScreamingSnake => to_snake_case(name).to_ascii_uppercase(),
+            RenameRule::Pascal => {
+                let mut pascal = String::with_capacity(name.len());
+                let mut capitalize = true;
+                for c in to_snake_case(name).chars() {
+                    if c == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(c.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(c);
+                    }
+                }
+                pascal
+            }
+            RenameRule::Camel => {
+                let pascal = RenameRule::Pascal.apply(name);
+                let mut chars = pascal.chars();
+                match chars.next() {
+                    Some(first) => first.to_lowercase().chain(chars).collect(),
+                    None => pascal,
+                }
+            }
+            RenameRule::Kebab => RenameRule::Snake.apply(name).replace('_', "-"),
+            RenameRule::ScreamingKebab => RenameRule::ScreamingSnake.apply(name).replace('_', "-"),
+        }
+    }
+
+    /// Check if the convention produces valid identifiers.
+    pub fn is_ident_case(self) -> bool {
+        !matches!(self, RenameRule::Kebab | RenameRule::ScreamingKebab)
+    }
+}
+
+impl FromMeta for RenameRule {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "lowercase" => Ok(RenameRule::Lower),
+            "UPPERCASE" => Ok(RenameRule::Upper),
+            "PascalCase" => Ok(RenameRule::Pascal),
+            "camelCase" => Ok(RenameRule::Camel),
+            "snake_case" => Ok(RenameRule::Snake),
+            "SCREAMING_SNAKE_CASE" => Ok(RenameRule::ScreamingSnake),
+            "kebab-case" => Ok(RenameRule::Kebab),
+            "SCREAMING-KEBAB-CASE" => Ok(RenameRule::ScreamingKebab),
+            _ => Err(Error::unknown_value(value)),
+        }
+    }
+}
+
+/// Parameter names of an `untuple` setter, e.g. `setter(untuple(start, end))`.
+///
+/// Given as a word, the names default to `value_0`, `value_1` and so on.
+#[derive(Debug, Clone, Default)]
+pub struct Untuple {
+    names: Vec<Ident>,
+}
+
+impl FromMeta for Untuple {
+    fn from_word() -> darling::Result<Self> {
+        Ok(Self::default())
+    }
+
+    fn from_list(items: &[darling::ast::NestedMeta]) -> darling::Result<Self> {
+        let names = PathList::from_list(items)?
+            .iter()
+            .map(|path| {
+                path.get_ident()
+                    .cloned()
+                    .ok_or_else(|| Error::custom("Expected a parameter name").with_span(path))
+            })
+            .collect::<darling::Result<_>>()?;
+        Ok(Self { names })
+    }
+}
+
 /// Create `Each` from an attribute's `Meta`.
 ///
 /// Two formats are supported:
@@ -243,19 +629,239 @@ fn parse_each(meta: &Meta) -> darling::Result<Option<Each>> {
     }
 }
 
+/// Create `EachBuilder` from an attribute's `Meta`.
+///
+/// Two formats are supported:
+///
+/// * `each_builder = "..."`, which names the setter
+/// * `each_builder(name = "...", builder = "...")`, which also names the builder of the items
+fn parse_each_builder(meta: &Meta) -> darling::Result<Option<EachBuilder>> {
+    if let Meta::NameValue(mnv) = meta {
+        Ident::from_meta(meta)
+            .map(EachBuilder::from)
+            .map(Some)
+            .map_err(|e| e.with_span(&mnv.value))
+    } else {
+        EachBuilder::from_meta(meta).map(Some)
+    }
+}
+
+/// Create the closure of `setter(transform = ...)` from an attribute's `Meta`.
+///
+/// The closure can be written directly, e.g. `transform = |w: u32, h: u32| Size { w, h }`,
+/// or as a string.
+fn parse_transform(meta: &Meta) -> darling::Result<Option<syn::ExprClosure>> {
+    match syn::Expr::from_meta(meta)? {
+        syn::Expr::Closure(closure) => Ok(Some(closure)),
+        other => Err(
+            darling::Error::custom("#[builder(setter(transform))] must be a closure")
+                .with_span(&other),
+        ),
+    }
+}
+
+/// Create the `suffix` of setter names from an attribute's `Meta`.
+///
+/// The suffix is a string rather than an `Ident`, so keywords like `mut` can be appended.
+fn parse_suffix(meta: &Meta) -> darling::Result<Option<String>> {
+    let suffix = String::from_meta(meta)?;
+    if suffix.is_empty() || !suffix.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return Err(darling::Error::custom(
+            "#[builder(setter(suffix))] must be a part of an identifier",
+        )
+        .with_span(meta));
+    }
+    Ok(Some(suffix))
+}
+
+/// Create `OnSet` from an attribute's `Meta`.
+///
+/// Two formats are supported:
+///
+/// * `on_set = "..."`, which names the function
+/// * `on_set(path = "...", value)`, which also passes the builder field to the function
+fn parse_on_set(meta: &Meta) -> darling::Result<Option<OnSet>> {
+    if let Meta::NameValue(mnv) = meta {
+        Path::from_meta(meta)
+            .map(OnSet::from)
+            .map(Some)
+            .map_err(|e| e.with_span(&mnv.value))
+    } else {
+        OnSet::from_meta(meta).map(Some)
+    }
+}
+
+/// The traits in `#[builder(derive(...))]`.
+///
+/// `Display` and `ConfigKey` can't be derived by the compiler, so they are taken out of the
+/// list and generated by `derive_builder` instead. `Copy` stays in the list, but requires
+/// `Clone` to be derived even for owned builders.
+#[derive(Debug, Clone, Default)]
+pub struct BuilderDerives {
+    traits: Vec<Path>,
+    display: bool,
+    config_key: bool,
+    copy: bool,
+}
+
+impl FromMeta for BuilderDerives {
+    fn from_list(items: &[darling::ast::NestedMeta]) -> darling::Result<Self> {
+        let mut derives = BuilderDerives::default();
+        for path in PathList::from_list(items)?.iter() {
+            if is_std_trait_path(path, "fmt", "Display") {
+                derives.display = true;
+            } else if is_config_key_path(path) {
+                derives.config_key = true;
+            } else {
+                derives.copy |= is_std_trait_path(path, "marker", "Copy");
+                derives.traits.push(path.clone());
+            }
+        }
+
+        // `ConfigKey` comes with its own `PartialEq`, `Eq` and `Hash` impls.
+        if derives.config_key {
+            let mut errors = Error::accumulator();
+            for path in &derives.traits {
+                if is_std_trait_path(path, "cmp", "PartialEq")
+                    || is_std_trait_path(path, "cmp", "Eq")
+                    || is_std_trait_path(path, "hash", "Hash")
+                {
+                    errors.push(
+                        Error::custom("`ConfigKey` already implements this trait for the builder")
+                            .with_span(path),
+                    );
+                }
+            }
+            errors.finish()?;
+        }
+
+        Ok(derives)
+    }
+}
+
+/// Options for builders which derive `Deserialize`, in `#[builder(deserialize(...))]`.
+#[derive(Debug, Clone, Default, FromMeta)]
+pub struct BuilderDeserialize {
+    /// Fail to deserialize input with keys which don't belong to any field.
+    deny_unknown_fields: Flag,
+    /// Collect the keys which don't belong to any field into an `extra` map with values of
+    /// this type, e.g. `serde_json::Value`.
+    extra: Option<syn::Type>,
+}
+
+impl BuilderDeserialize {
+    fn is_present(&self) -> bool {
+        self.deny_unknown_fields.is_present() || self.extra.is_some()
+    }
+}
+
+/// Check if `path` names the `ConfigKey` trait, i.e. `ConfigKey` or `derive_builder::ConfigKey`.
+fn is_config_key_path(path: &Path) -> bool {
+    let segments: Vec<String> = path.segments.iter().map(|s| s.ident.to_string()).collect();
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+    matches!(
+        segments.as_slice(),
+        ["ConfigKey"] | ["derive_builder", "ConfigKey"]
+    )
+}
+
+/// Check if `path` names the trait `name` of the std module `module`, e.g. `Display`,
+/// `fmt::Display` or `std::fmt::Display`.
+fn is_std_trait_path(path: &Path, module: &str, name: &str) -> bool {
+    let segments: Vec<String> = path.segments.iter().map(|s| s.ident.to_string()).collect();
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+    match segments.as_slice() {
+        [ident] => *ident == name,
+        [m, ident] => *m == module && *ident == name,
+        [krate, m, ident] => matches!(*krate, "std" | "core") && *m == module && *ident == name,
+        _ => false,
+    }
+}
+
+/// The `parse` meta item on fields in the input type, for builders which are parsed from
+/// `key=value` pairs with `#[builder(from_query)]` or `#[builder(from_str)]`.
+#[derive(Debug, Clone, Default, FromMeta)]
+pub struct FieldParse {
+    /// Key of the field, instead of the field name.
+    rename: Option<String>,
+    /// Don't set the field from parsed pairs, e.g. if its type can't be parsed.
+    skip: Flag,
+}
+
 /// The `setter` meta item on fields in the input type.
 /// Unlike the `setter` meta item at the struct level, this allows specific
 /// name overrides.
 #[derive(Debug, Clone, Default, FromMeta)]
 pub struct FieldLevelSetter {
     prefix: Option<Ident>,
+    /// Appended to the field name, after an underscore, like `prefix` is prepended.
+    #[darling(default, with = parse_suffix)]
+    suffix: Option<String>,
     name: Option<Ident>,
+    /// Old names of the setter, which are emitted as deprecated copies of it.
+    #[darling(multiple)]
+    alias: Vec<Ident>,
     into: Option<bool>,
+    /// Like `into`, but take an `impl Into<T>` instead of adding a type parameter.
+    into_impl: Option<bool>,
+    /// Name of the type parameter of generic setters.
+    generic: Option<Ident>,
     strip_option: Option<bool>,
+    /// Make the setter of a `bool` field take no parameter and set it to `true`.
+    strip_bool: Option<bool>,
+    /// Add a `maybe_` setter which takes an `Option` and only sets the field if it is `Some`, or
+    /// stores it as is with `strip_option`.
+    maybe: Option<bool>,
+    /// Add a `_if` setter which takes a condition and only sets the field if it is `true`.
+    conditional: Option<bool>,
+    boxed: Option<bool>,
+    /// Take a `&'a T` for a `Cow<'a, T>` field and store it as `Cow::Borrowed`.
+    borrowed: Option<bool>,
+    /// With `borrowed`, add an `_owned` setter which takes the owned value of the `Cow` and
+    /// stores it as `Cow::Owned`.
+    cow_owned: Option<bool>,
+    /// Take a closure or other value implementing the traits of a `Box<dyn Trait>` field and
+    /// box it. `fn` is a keyword, which can't be used as the name of an option.
+    closure: Option<bool>,
+    /// Take the type argument of a field of this wrapper type, e.g. `Arc<dyn Trait>`, and wrap
+    /// it with the wrapper's `new`.
+    wrap: Option<Path>,
+    nullable: Option<bool>,
+    /// Add setters which set an array field from an iterator or set one of its items.
+    array: Option<bool>,
+    untuple: Option<Untuple>,
+    /// Make the setter take the parameters of this closure, and store the value it returns.
+    #[darling(default, with = parse_transform)]
+    transform: Option<syn::ExprClosure>,
     skip: Option<bool>,
     custom: Option<bool>,
     #[darling(with = parse_each)]
     each: Option<Each>,
+    /// Add a setter which builds an item of the collection with the builder of the item type.
+    #[darling(with = parse_each_builder)]
+    each_builder: Option<EachBuilder>,
+    /// Add a `_extend` setter which extends the collection with the items of an iterator.
+    extend: Option<bool>,
+    /// Add a `_with` setter which takes a closure, which the build method calls to compute the
+    /// value if the field isn't set.
+    lazy: Option<bool>,
+    /// Only emit the setter if this cargo feature is enabled.
+    feature: Option<syn::LitStr>,
+    /// When the value is converted into the type of the field.
+    convert: Option<ConvertAt>,
+    /// Type of the value stored until the build method converts it, for `convert = "at_build"`.
+    from: Option<syn::Type>,
+    /// Take a `Result` and store its error until the build method returns it.
+    defer_err: Option<bool>,
+}
+
+/// When the value passed to a setter is converted into the type of the field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromMeta)]
+enum ConvertAt {
+    /// The setter converts the value, e.g. with `into`, and stores the field's type.
+    AtSet,
+    /// The setter stores the value as it is, and the build method converts it with `TryFrom`.
+    AtBuild,
 }
 
 impl FieldLevelSetter {
@@ -278,17 +884,44 @@ impl FieldLevelSetter {
             return self.skip.map(|x| !x);
         }
 
-        if self.prefix.is_some()
-            || self.name.is_some()
-            || self.into.is_some()
-            || self.strip_option.is_some()
-            || self.each.is_some()
-        {
+        if self.has_options() {
             return Some(true);
         }
 
         None
     }
+
+    /// Check if any option of the setter is set, other than `skip` and `custom`.
+    fn has_options(&self) -> bool {
+        self.prefix.is_some()
+            || self.suffix.is_some()
+            || self.name.is_some()
+            || !self.alias.is_empty()
+            || self.into.is_some()
+            || self.into_impl.is_some()
+            || self.generic.is_some()
+            || self.strip_option.is_some()
+            || self.strip_bool.is_some()
+            || self.maybe.is_some()
+            || self.conditional.is_some()
+            || self.boxed.is_some()
+            || self.borrowed.is_some()
+            || self.cow_owned.is_some()
+            || self.closure.is_some()
+            || self.wrap.is_some()
+            || self.nullable.is_some()
+            || self.array.is_some()
+            || self.untuple.is_some()
+            || self.transform.is_some()
+            || self.each.is_some()
+            || self.each_builder.is_some()
+            || self.extend.is_some()
+            || self.lazy.is_some()
+            || self.feature.is_some()
+            || self.convert.is_some()
+            || self.from.is_some()
+            || self.defer_err.is_some()
+    }
 }
 
 /// `derive_builder` allows the calling code to use `setter` as a word to enable
@@ -306,10 +939,22 @@ fn field_setter(meta: &Meta) -> darling::Result<FieldLevelSetter> {
     }
 }
 
+/// Attributes on a field which are always forwarded to the builder.
+const FIELD_FORWARDED_ATTRS: &[&str] = &[
+    "doc",
+    "cfg",
+    "allow",
+    "builder_field_attr",
+    "builder_setter_attr",
+];
+
 #[derive(Debug, Clone, Default)]
 struct FieldForwardedAttrs {
     pub field: Vec<Attribute>,
     pub setter: Vec<Attribute>,
+    /// Attributes of other macros, which are only forwarded to the builder field if
+    /// requested with `#[builder(forward_attrs(...))]`.
+    pub foreign: Vec<Attribute>,
 }
 
 impl TryFrom<Vec<Attribute>> for FieldForwardedAttrs {
@@ -317,6 +962,15 @@ impl TryFrom<Vec<Attribute>> for FieldForwardedAttrs {
 
     fn try_from(value: Vec<Attribute>) -> Result<Self, Self::Error> {
         let mut result = Self::default();
+        let (value, foreign): (Vec<_>, Vec<_>) = value
+            .into_iter()
+            .filter(|attr| !attr.path().is_ident("builder"))
+            .partition(|attr| {
+                FIELD_FORWARDED_ATTRS
+                    .iter()
+                    .any(|name| attr.path().is_ident(name))
+            });
+        result.foreign = foreign;
         distribute_and_unnest_attrs(
             value,
             &mut [
@@ -324,17 +978,38 @@ impl TryFrom<Vec<Attribute>> for FieldForwardedAttrs {
                 ("builder_setter_attr", &mut result.setter),
             ],
         )?;
+
+        if cfg!(feature = "doc_cfg") {
+            let doc_cfgs = doc_cfg_attrs(&result.setter);
+            result.setter.extend(doc_cfgs);
+        }
+
         Ok(result)
     }
 }
 
+/// Create a `#[doc(cfg(...))]` for every `#[cfg(...)]` in `attrs`, so that rustdoc shows which
+/// configuration a setter depends on.
+///
+/// The attributes are only active when documenting with `--cfg docsrs`, as `doc(cfg)` is not
+/// available on stable Rust.
+fn doc_cfg_attrs(attrs: &[Attribute]) -> Vec<Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::List(list) => {
+                let predicate = &list.tokens;
+                Some(parse_quote!(#[cfg_attr(docsrs, doc(cfg(#predicate)))]))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
 /// Data extracted from the fields of the input struct.
 #[derive(Debug, Clone, FromField)]
-#[darling(
-    attributes(builder),
-    forward_attrs(doc, cfg, allow, builder_field_attr, builder_setter_attr),
-    and_then = "Self::resolve"
-)]
+#[darling(attributes(builder), forward_attrs, and_then = "Self::resolve")]
 pub struct Field {
     ident: Option<Ident>,
     #[darling(with = TryFrom::try_from)]
@@ -342,6 +1017,9 @@ pub struct Field {
     ty: syn::Type,
     /// Field-level override for builder pattern.
     /// Note that setting this may force the builder to derive `Clone`.
+    ///
+    /// `owned` on a builder with another pattern adds an owned `with_` setter next to the
+    /// setter of the builder's pattern, since an owned setter can't be chained with the others.
     pattern: Option<BuilderPattern>,
     #[darling(flatten)]
     visibility: VisibilityAttr,
@@ -363,15 +1041,136 @@ pub struct Field {
     try_setter: Flag,
     #[darling(default)]
     field: FieldLevelFieldMeta,
+    /// Attributes of other macros to copy from the field to the builder field,
+    /// e.g. `forward_attrs(serde)`.
+    #[darling(default)]
+    forward_attrs: PathList,
+    /// Print `<redacted>` instead of the value in the builder's `Display` impl.
+    redact: Flag,
+    /// Path to a function which checks the value of the builder field, if it is set.
+    validate: Option<Path>,
+    /// Fields which must be set if this field is set.
+    #[darling(default, with = parse_field_list)]
+    requires: Vec<Ident>,
+    /// Fields which must not be set if this field is set.
+    #[darling(default, with = parse_field_list)]
+    conflicts_with: Vec<Ident>,
+    /// Fields which the build method initializes before this field, so its default can read
+    /// their values.
+    #[darling(default, with = parse_field_list)]
+    after: Vec<Ident>,
+    /// Store the builder of the field's type in the builder field, and give the builder the
+    /// setters of the nested builder.
+    flatten: Flag,
+    /// Store the builder of the field's type in the builder field, and give the builder an
+    /// accessor of the nested builder instead of a setter.
+    delegate: Flag,
+    /// How the field is set by `from_query` and `from_str`.
+    #[darling(default)]
+    parse: FieldParse,
+    /// Alternative keys of the field for `from_query` and `from_str`, e.g. old config keys.
+    #[darling(multiple)]
+    alias: Vec<String>,
+}
+
+/// Replace the name of the type in a field's type path, keeping its module and generic
+/// arguments, e.g. `inner::Lorem<T>` becomes `inner::LoremBuilder<T>` for the suffix `Builder`.
+///
+/// Returns `None` if the field's type is not a path.
+fn flattened_path(ty: &syn::Type, suffix: &str) -> Option<Path> {
+    match ty {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => {
+            let mut path = type_path.path.clone();
+            let last = path.segments.last_mut()?;
+            last.ident = format_ident!("{}{}", last.ident.unraw(), suffix);
+            Some(path)
+        }
+        _ => None,
+    }
+}
+
+/// Create the list of `build_fn(wrappers(box, arc, rc))` from an attribute's `Meta`.
+///
+/// `box` is a keyword, so the list is parsed as identifiers instead of paths.
+fn parse_build_wrappers(meta: &Meta) -> darling::Result<Vec<BuildWrapper>> {
+    let idents = meta
+        .require_list()?
+        .parse_args_with(|input: syn::parse::ParseStream| {
+            Punctuated::<Ident, Token![,]>::parse_terminated_with(input, Ident::parse_any)
+        })?;
+    let mut acc = Error::accumulator();
+    let mut wrappers = Vec::new();
+    for ident in &idents {
+        let wrapper = match ident.to_string().as_str() {
+            "box" => BuildWrapper::Box,
+            "arc" => BuildWrapper::Arc,
+            "rc" => BuildWrapper::Rc,
+            _ => {
+                acc.push(Error::custom("Expected `box`, `arc` or `rc`").with_span(ident));
+                continue;
+            }
+        };
+        if wrappers.contains(&wrapper) {
+            acc.push(Error::duplicate_field(&ident.to_string()).with_span(ident));
+        } else {
+            wrappers.push(wrapper);
+        }
+    }
+    acc.finish_with(wrappers)
+}
+
+/// Create a list of field names from an attribute's `Meta`.
+///
+/// Two formats are supported:
+///
+/// * `requires = "..."`, which names a single field
+/// * `requires(a, b)`, which names several fields
+fn parse_field_list(meta: &Meta) -> darling::Result<Vec<Ident>> {
+    match meta {
+        Meta::NameValue(mnv) => Ident::from_meta(meta)
+            .map(|ident| vec![ident])
+            .map_err(|e| e.with_span(&mnv.value)),
+        _ => PathList::from_meta(meta)?
+            .iter()
+            .map(|path| {
+                path.get_ident()
+                    .cloned()
+                    .ok_or_else(|| Error::custom("Expected a field name").with_span(path))
+            })
+            .collect(),
+    }
 }
 
 impl Field {
     /// Resolve and check (post-parsing) options which come from multiple darling options
     ///
     ///  * Check that we don't have a custom field type or builder *and* a default value
-    fn resolve(self) -> darling::Result<Self> {
+    ///  * Move attributes requested by `forward_attrs` to the builder field
+    ///  * Check that `setter(nullable)` is used on an `Option<Option<T>>` field
+    ///  * Use the builder of the field's type as builder field type of `flatten` and `delegate` fields
+    ///  * Check that `field(capacity)` is only used with `setter(each)`
+    ///  * Check that `field(bare)` has a default value and a plain setter
+    ///  * Gate the setter on the cargo feature of `setter(feature = "...")`
+    fn resolve(mut self) -> darling::Result<Self> {
         let mut errors = darling::Error::accumulator();
 
+        if let Some(feature) = &self.setter.feature {
+            let cfg: Attribute = parse_quote!(#[cfg(feature = #feature)]);
+            if cfg!(feature = "doc_cfg") {
+                let doc_cfgs = doc_cfg_attrs(std::slice::from_ref(&cfg));
+                self.attrs.setter.extend(doc_cfgs);
+            }
+            self.attrs.setter.push(cfg);
+        }
+
+        let forward_attrs = &self.forward_attrs;
+        let forwarded = self
+            .attrs
+            .foreign
+            .drain(..)
+            .filter(|attr| forward_attrs.iter().any(|path| attr.path() == path));
+        self.attrs.field.extend(forwarded);
+
         // `default` can be preempted by properties in `field`. Silently ignoring a
         // `default` could cause the direct user of `derive_builder` to see unexpected
         // behavior from the builder, so instead we require that the deriving struct
@@ -406,34 +1205,231 @@ impl Field {
             }
         };
 
-        errors.finish_with(self)
-    }
-}
+        if self.setter.nullable == Some(true) {
+            let is_nested_option = extract_type_from_option(&self.ty)
+                .and_then(extract_type_from_option)
+                .is_some();
+            if !is_nested_option {
+                errors.push(
+                    darling::Error::custom(
+                        "#[builder(setter(nullable))] requires a field of type `Option<Option<T>>`",
+                    )
+                    .with_span(&self.ty),
+                );
+            }
 
-/// Divide a list of attributes into multiple partially-overlapping output lists.
-///
-/// Some attributes from the macro input will be added to the output in multiple places;
-/// for example, a `cfg` attribute must be replicated to both the struct and its impl block or
-/// the resulting code will not compile.
-///
-/// Other attributes are scoped to a specific output by their path, e.g. `builder_field_attr`.
-/// These attributes will only appear in one output list, but need that outer path removed.
-///
-/// For performance reasons, we want to do this in one pass through the list instead of
-/// first distributing and then iterating through each of the output lists.
-///
-/// Each item in `outputs` contains the attribute name unique to that output, and the `Vec` where all attributes for that output should be inserted.
-/// Attributes whose path matches any value in `outputs` will be added only to the first matching one, and will be "unnested".
-/// Other attributes are not unnested, and simply copied for each decoratee.
-fn distribute_and_unnest_attrs(
-    mut input: Vec<Attribute>,
-    outputs: &mut [(&'static str, &mut Vec<Attribute>)],
-) -> darling::Result<()> {
-    let mut errors = vec![];
+            if self.field.builder_type.is_some() {
+                errors.push(
+                    darling::Error::custom(
+                        r#"#[builder(setter(nullable))] and #[builder(field(ty="..."))] cannot be used together"#,
+                    )
+                    .with_span(&self.ty),
+                );
+            }
+        }
 
-    for (name, list) in &*outputs {
-        assert!(list.is_empty(), "Output Vec for '{}' was not empty", name);
-    }
+        if self.setter.array == Some(true) {
+            if !matches!(self.ty, syn::Type::Array(_)) {
+                errors.push(
+                    darling::Error::custom(
+                        "#[builder(setter(array))] requires a field of type `[T; N]`",
+                    )
+                    .with_span(&self.ty),
+                );
+            }
+
+            if self.field.builder_type.is_some() {
+                errors.push(
+                    darling::Error::custom(
+                        r#"#[builder(setter(array))] and #[builder(field(ty="..."))] cannot be used together"#,
+                    )
+                    .with_span(&self.ty),
+                );
+            }
+        }
+
+        if let Some(capacity) = &self.field.capacity {
+            if self.setter.each.is_none() {
+                errors.push(
+                    darling::Error::custom(
+                        r#"#[builder(field(capacity = ...))] requires #[builder(setter(each = "..."))]"#,
+                    )
+                    .with_span(capacity),
+                );
+            }
+        }
+
+        if self.setter.maybe == Some(true)
+            && (self.setter.nullable == Some(true)
+                || self.setter.untuple.is_some()
+                || self.setter.transform.is_some()
+                || self.setter.strip_bool == Some(true)
+                || self.setter.defer_err == Some(true) && self.setter.strip_option != Some(true))
+        {
+            errors.push(
+                darling::Error::custom(
+                    "#[builder(setter(maybe))] cannot be used with `nullable`, `untuple`, `transform`, `strip_bool` or `defer_err`",
+                )
+                .with_span(&self.ty),
+            );
+        }
+
+        if self.setter.conditional == Some(true)
+            && (self.setter.untuple.is_some()
+                || self.setter.transform.is_some()
+                || self.setter.strip_bool == Some(true)
+                || self.setter.defer_err == Some(true))
+        {
+            errors.push(
+                darling::Error::custom(
+                    "#[builder(setter(conditional))] cannot be used with `untuple`, `transform`, `strip_bool` or `defer_err`",
+                )
+                .with_span(&self.ty),
+            );
+        }
+
+        if self.setter.borrowed == Some(true)
+            && (self.setter.boxed == Some(true)
+                || self.setter.closure == Some(true)
+                || self.setter.each.is_some())
+        {
+            errors.push(
+                darling::Error::custom(
+                    "#[builder(setter(borrowed))] cannot be used with `boxed`, `closure` or `each`",
+                )
+                .with_span(&self.ty),
+            );
+        }
+
+        if self.field.bare.is_present() {
+            let span = self.field.bare.span();
+            if self.default.is_none() {
+                errors.push(
+                    darling::Error::custom("#[builder(field(bare))] requires #[builder(default)]")
+                        .with_span(&span),
+                );
+            }
+
+            if matches!(self.default, Some(ref default) if default.is_time()) {
+                errors.push(
+                    darling::Error::custom(
+                        "#[builder(field(bare))] cannot be used with `default(now)` or `default(since_epoch = \"...\")`, which are evaluated at build",
+                    )
+                    .with_span(&span),
+                );
+            }
+
+            if matches!(self.default, Some(ref default) if default.references_self()) {
+                errors.push(
+                    darling::Error::custom(
+                        "#[builder(field(bare))] cannot be used with a default which references `self`, since it is evaluated when the builder is created",
+                    )
+                    .with_span(&span),
+                );
+            }
+
+            if self.setter.each.is_some()
+                || self.setter.extend == Some(true)
+                || self.setter.nullable == Some(true)
+                || self.setter.array == Some(true)
+            {
+                errors.push(
+                    darling::Error::custom(
+                        "#[builder(field(bare))] cannot be used with `setter(each)`, `setter(extend)`, `setter(nullable)` or `setter(array)`",
+                    )
+                    .with_span(&span),
+                );
+            }
+        }
+
+        if self.flatten.is_present() && self.delegate.is_present() {
+            errors.push(
+                darling::Error::custom("#[builder(flatten)] cannot be used with `delegate`")
+                    .with_span(&self.delegate.span()),
+            );
+        }
+
+        let nested = if self.flatten.is_present() {
+            Some(("flatten", self.flatten.span()))
+        } else if self.delegate.is_present() {
+            Some(("delegate", self.delegate.span()))
+        } else {
+            None
+        };
+        if let Some((option, span)) = nested {
+            if self.default.is_some()
+                || self.field.builder_type.is_some()
+                || self.field.build.is_some()
+            {
+                errors.push(
+                    darling::Error::custom(format!(
+                        "#[builder({})] cannot be used with `default` or `field(...)`",
+                        option
+                    ))
+                    .with_span(&span),
+                );
+            }
+
+            if let Some(feature) = &self.setter.feature {
+                errors.push(
+                    darling::Error::custom(format!(
+                        r#"#[builder({})] cannot be used with `setter(feature = "...")`"#,
+                        option
+                    ))
+                    .with_span(feature),
+                );
+            }
+
+            if let Some(validate) = &self.validate {
+                errors.push(
+                    darling::Error::custom(format!(
+                        "#[builder({})] cannot be used with `validate`, use `build_fn(validate)` of the nested builder instead",
+                        option
+                    ))
+                    .with_span(validate),
+                );
+            }
+
+            match flattened_path(&self.ty, "Builder") {
+                Some(path) => self.field.builder_type = Some(parse_quote!(#path)),
+                None => errors.push(
+                    darling::Error::custom(format!(
+                        "#[builder({})] requires a field whose type derives `Builder`",
+                        option
+                    ))
+                    .with_span(&self.ty),
+                ),
+            }
+        }
+
+        errors.finish_with(self)
+    }
+}
+
+/// Divide a list of attributes into multiple partially-overlapping output lists.
+///
+/// Some attributes from the macro input will be added to the output in multiple places;
+/// for example, a `cfg` attribute must be replicated to both the struct and its impl block or
+/// the resulting code will not compile.
+///
+/// Other attributes are scoped to a specific output by their path, e.g. `builder_field_attr`.
+/// These attributes will only appear in one output list, but need that outer path removed.
+///
+/// For performance reasons, we want to do this in one pass through the list instead of
+/// first distributing and then iterating through each of the output lists.
+///
+/// Each item in `outputs` contains the attribute name unique to that output, and the `Vec` where all attributes for that output should be inserted.
+/// Attributes whose path matches any value in `outputs` will be added only to the first matching one, and will be "unnested".
+/// Other attributes are not unnested, and simply copied for each decoratee.
+fn distribute_and_unnest_attrs(
+    mut input: Vec<Attribute>,
+    outputs: &mut [(&'static str, &mut Vec<Attribute>)],
+) -> darling::Result<()> {
+    let mut errors = vec![];
+
+    for (name, list) in &*outputs {
+        assert!(list.is_empty(), "Output Vec for '{}' was not empty", name);
+    }
 
     for attr in input.drain(..) {
         let destination = outputs
@@ -501,6 +1497,17 @@ fn default_create_empty() -> Ident {
 struct StructForwardedAttrs {
     struct_attrs: Vec<Attribute>,
     impl_attrs: Vec<Attribute>,
+    /// Attributes of the `impl` blocks of `split_impls`, which also receive the attributes
+    /// forwarded to all items, e.g. `cfg`.
+    setters_impl_attrs: Vec<Attribute>,
+    try_setters_impl_attrs: Vec<Attribute>,
+    build_impl_attrs: Vec<Attribute>,
+    /// Paths of the attributes which are only forwarded to the `impl` blocks of `split_impls`,
+    /// e.g. `builder_setters_impl_attr`.
+    split_impl_attr_paths: Vec<Path>,
+    /// Whether the struct is `#[repr(packed)]`, so the generated code must not take references
+    /// to its fields. `repr` itself isn't forwarded to the builder.
+    packed: bool,
 }
 
 impl TryFrom<Vec<Attribute>> for StructForwardedAttrs {
@@ -508,11 +1515,36 @@ impl TryFrom<Vec<Attribute>> for StructForwardedAttrs {
 
     fn try_from(value: Vec<Attribute>) -> Result<Self, Self::Error> {
         let mut result = Self::default();
+        let (repr, value): (Vec<_>, Vec<_>) = value
+            .into_iter()
+            .partition(|attr| attr.path().is_ident("repr"));
+        for attr in repr {
+            let hints = attr
+                .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                .map_err(Error::from)?;
+            result.packed |= hints.iter().any(|hint| hint.path().is_ident("packed"));
+        }
+        result.split_impl_attr_paths = value
+            .iter()
+            .map(|attr| attr.path())
+            .filter(|path| {
+                path.is_ident("builder_setters_impl_attr")
+                    || path.is_ident("builder_try_setters_impl_attr")
+                    || path.is_ident("builder_build_impl_attr")
+            })
+            .cloned()
+            .collect();
         distribute_and_unnest_attrs(
             value,
             &mut [
                 ("builder_struct_attr", &mut result.struct_attrs),
                 ("builder_impl_attr", &mut result.impl_attrs),
+                ("builder_setters_impl_attr", &mut result.setters_impl_attrs),
+                (
+                    "builder_try_setters_impl_attr",
+                    &mut result.try_setters_impl_attrs,
+                ),
+                ("builder_build_impl_attr", &mut result.build_impl_attrs),
             ],
         )?;
 
@@ -523,8 +1555,18 @@ impl TryFrom<Vec<Attribute>> for StructForwardedAttrs {
 #[derive(Debug, Clone, FromDeriveInput)]
 #[darling(
     attributes(builder),
-    forward_attrs(cfg, allow, builder_struct_attr, builder_impl_attr),
-    supports(struct_named)
+    forward_attrs(
+        cfg,
+        allow,
+        repr,
+        builder_struct_attr,
+        builder_impl_attr,
+        builder_setters_impl_attr,
+        builder_try_setters_impl_attr,
+        builder_build_impl_attr
+    ),
+    supports(struct_named),
+    and_then = Self::validate
 )]
 pub struct Options {
     ident: Ident,
@@ -554,7 +1596,11 @@ pub struct Options {
 
     /// Additional traits to derive on the builder.
     #[darling(default)]
-    derive: PathList,
+    derive: BuilderDerives,
+
+    /// Lints to allow on all generated items.
+    #[darling(default)]
+    allow: PathList,
 
     custom_constructor: Flag,
 
@@ -588,6 +1634,111 @@ pub struct Options {
     #[darling(default)]
     field: VisibilityAttr,
 
+    /// When present, print the generated code to stderr during compilation.
+    dump: Flag,
+
+    /// When present, list the names of the generated items in a `GENERATED_ITEMS` constant on
+    /// the builder.
+    abi_stable_names: Flag,
+
+    /// When present, reject options which are ignored or overridden by other options, instead
+    /// of tolerating them.
+    strict: Flag,
+
+    /// When present, put the setters, try setters and build methods into separate `impl` blocks,
+    /// which receive the attributes of `builder_setters_impl_attr`,
+    /// `builder_try_setters_impl_attr` and `builder_build_impl_attr`.
+    split_impls: Flag,
+
+    /// When present, emit a hidden module with a function which calls every public setter with
+    /// a value of the type it takes, so changing a setter's signature breaks the build.
+    public_api_snapshot: Flag,
+
+    /// Function which every setter calls with the name of its field, e.g. to record which
+    /// fields are used.
+    #[darling(default, with = parse_on_set)]
+    on_set: Option<OnSet>,
+
+    /// Deprecate the builder struct, its setters and build method, but not the struct itself.
+    #[darling(default, with = parse_deprecated)]
+    deprecated: Option<Attribute>,
+
+    /// Paths to import into the scope of the builder's methods, so default values and
+    /// `field(build = "...")` expressions can use short names.
+    #[darling(default, with = parse_uses)]
+    uses: Vec<syn::UseTree>,
+
+    /// Methods which are added to the `impl` block of the builder as they are written, e.g. to
+    /// set several fields at once.
+    #[darling(default, with = parse_mutators)]
+    mutators: Vec<syn::ImplItemFn>,
+
+    /// Generate a declarative macro which constructs the struct from `field: value` pairs.
+    #[darling(default, with = parse_constructor_macro)]
+    constructor_macro: Option<ConstructorMacroOpts>,
+
+    /// Generate an enum with a variant for each field, which the generated error's `field`
+    /// method returns.
+    #[darling(default, with = parse_field_enum)]
+    field_enum: Option<FieldEnumOpts>,
+
+    /// When present, emit `const fn` setters and a `const fn` build method, so the struct
+    /// can be built in constant expressions.
+    const_fn: Flag,
+
+    /// Mark the builder struct and its setters `#[must_use]`. Without the option, only setters
+    /// which return the builder by value are marked.
+    must_use: Option<bool>,
+
+    /// When present, declare the setters as default methods of a trait which the builder
+    /// implements, so wrappers of the builder can override them.
+    overridable: Flag,
+
+    /// When present, generate a `reset` method which unsets all fields, keeping the
+    /// allocations of collection fields.
+    reset: Flag,
+
+    /// When present, generate a `from_query` function which creates the builder from a URL
+    /// query string.
+    from_query: Flag,
+
+    /// When present, generate a `to_map` method which serializes the fields set in the builder
+    /// into a JSON object.
+    to_map: Flag,
+
+    /// When present, implement `FromStr` for the builder, which parses a comma-separated list
+    /// of `key=value` pairs.
+    from_str: Flag,
+
+    /// Case convention for the field names in errors, the `Display` impl of the builder and the
+    /// keys of `from_query` and `from_str`.
+    field_name_case: Option<RenameRule>,
+
+    /// Implement `From` for the builder, converting from another builder with identically
+    /// named fields.
+    #[darling(default, with = parse_convert_from)]
+    convert_from: Option<ConvertFromOpts>,
+
+    /// When present, implement `From` of the target struct for the builder, and add a
+    /// `to_builder` method to the target struct, which requires it to be `Clone`.
+    from_target: Flag,
+
+    /// When present, implement `DynSet` for the builder, which sets fields by name with values
+    /// of a runtime type.
+    dyn_set: Flag,
+
+    /// When present, generate a unit test which asserts that building from an empty builder
+    /// doesn't allocate.
+    assert_no_alloc_build: Flag,
+
+    /// Handling of unknown keys when the builder is deserialized.
+    #[darling(default)]
+    deserialize: BuilderDeserialize,
+
+    /// Predicate of a `#[cfg(...)]` attribute which is applied to all generated items.
+    #[darling(default, with = parse_cfg)]
+    cfg: Option<TokenStream>,
+
     #[darling(skip, default)]
     deprecation_notes: DeprecationNotes,
 }
@@ -602,6 +1753,16 @@ impl Options {
         format_ident!("{}Builder", self.ident)
     }
 
+    /// Name of the field `ident` in errors and other generated strings, following
+    /// `field_name_case`.
+    pub fn field_name(&self, ident: &Ident) -> String {
+        let name = ident.unraw().to_string();
+        match self.field_name_case {
+            Some(rule) => rule.apply(&name),
+            None => name,
+        }
+    }
+
     pub fn builder_error_ident(&self) -> Path {
         if let Some(BuildFnError::Existing(existing)) = self.build_fn.error.as_ref() {
             existing.clone()
@@ -615,7 +1776,7 @@ impl Options {
     /// The visibility of the builder struct.
     /// If a visibility was declared in attributes, that will be used;
     /// otherwise the struct's own visibility will be used.
-    pub fn builder_vis(&self) -> Cow<syn::Visibility> {
+    pub fn builder_vis(&self) -> Cow<'_, syn::Visibility> {
         self.visibility
             .to_explicit_visibility()
             .unwrap_or_else(|| Cow::Borrowed(&self.vis))
@@ -623,7 +1784,49 @@ impl Options {
 
     /// Get the visibility of the emitted `build` method.
     /// This defaults to the visibility of the parent builder, but can be overridden.
-    pub fn build_method_vis(&self) -> Cow<syn::Visibility> {
+    /// Check for options which are ignored or overridden by other options, for
+    /// `#[builder(strict)]`.
+    fn validate_strict(&self, acc: &mut darling::error::Accumulator) {
+        for field in self.fields() {
+            let setter = &field.field.setter;
+            if setter.skip == Some(true) && setter.has_options() {
+                acc.push(
+                    Error::custom("`setter(skip)` ignores the other setter options (`strict`)")
+                        .with_span(field.field_ident()),
+                );
+            }
+            if !field.field_enabled()
+                && matches!(field.field.default, Some(DefaultExpression::Trait))
+            {
+                acc.push(
+                    Error::custom(
+                        "`default` is implied for fields without a setter; remove it or give it a value (`strict`)",
+                    )
+                    .with_span(field.field_ident()),
+                );
+            }
+            if let (Some(name), Some(prefix)) = (&setter.name, field.setter_prefix()) {
+                acc.push(
+                    Error::custom(format!(
+                        "`setter(name = \"{}\")` overrides `prefix = \"{}\"` (`strict`)",
+                        name, prefix
+                    ))
+                    .with_span(name),
+                );
+            }
+            if let (Some(name), Some(suffix)) = (&setter.name, field.setter_suffix()) {
+                acc.push(
+                    Error::custom(format!(
+                        "`setter(name = \"{}\")` overrides `suffix = \"{}\"` (`strict`)",
+                        name, suffix
+                    ))
+                    .with_span(name),
+                );
+            }
+        }
+    }
+
+    pub fn build_method_vis(&self) -> Cow<'_, syn::Visibility> {
         self.build_fn
             .visibility
             .to_explicit_visibility()
@@ -639,292 +1842,3667 @@ impl Options {
     }
 
     /// A builder requires `Clone` to be derived if its build method or any of its setters
-    /// use the mutable or immutable pattern.
+    /// use the mutable or immutable pattern, or if it derives `Copy`.
     pub fn requires_clone(&self) -> bool {
-        self.pattern.requires_clone() || self.fields().any(|f| f.pattern().requires_clone())
+        self.pattern.requires_clone()
+            || self.fields().any(|f| f.pattern().requires_clone())
+            || self.derive.copy
     }
 
     /// Get an iterator over the input struct's fields which pulls fallback
     /// values from struct-level settings.
-    pub fn fields(&self) -> FieldIter {
+    pub fn fields(&self) -> FieldIter<'_> {
         FieldIter(self, self.raw_fields().into_iter())
     }
 
     pub fn field_count(&self) -> usize {
         self.raw_fields().len()
     }
-}
 
-/// Converters to codegen structs
-impl Options {
-    pub fn as_builder(&self) -> Builder {
-        Builder {
-            crate_root: &self.crate_root,
-            enabled: true,
-            ident: self.builder_ident(),
-            pattern: self.pattern,
-            derives: &self.derive,
-            struct_attrs: &self.attrs.struct_attrs,
-            impl_attrs: &self.attrs.impl_attrs,
-            impl_default: !self.custom_constructor.is_present(),
-            create_empty: self.create_empty.clone(),
-            generics: Some(&self.generics),
-            visibility: self.builder_vis(),
-            fields: Vec::with_capacity(self.field_count()),
-            field_initializers: Vec::with_capacity(self.field_count()),
-            functions: Vec::with_capacity(self.field_count()),
-            generate_error: self
-                .build_fn
-                .error
-                .as_ref()
-                .and_then(BuildFnError::as_existing)
-                .is_none(),
-            generate_validation_error: self
-                .build_fn
-                .error
-                .as_ref()
-                .and_then(BuildFnError::as_generated)
-                .map(|e| *e.validation_error)
-                .unwrap_or(true),
-            no_alloc: cfg!(not(any(feature = "alloc", feature = "lib_has_std"))),
-            must_derive_clone: self.requires_clone(),
-            doc_comment: None,
-            deprecation_notes: Default::default(),
-            std: !self.no_std.is_present(),
+    /// Get the fields in the order in which the build method initializes them: the order of
+    /// declaration, except that fields with `after` follow the fields they name.
+    ///
+    /// Fields in a cycle of `after` are appended in the order of declaration, and rejected by
+    /// `validate`.
+    pub fn initialization_order(&self) -> Vec<FieldWithDefaults<'_>> {
+        let mut pending: Vec<_> = self.fields().collect();
+        let mut order = Vec::with_capacity(pending.len());
+        while let Some(next) = pending.iter().position(|f| {
+            f.field.after.iter().all(|other| {
+                pending
+                    .iter()
+                    .all(|p| p.field.ident.as_ref() != Some(other))
+            })
+        }) {
+            order.push(pending.remove(next));
         }
+        order.extend(pending);
+        order
     }
 
-    pub fn as_build_method(&self) -> BuildMethod {
-        let (_, ty_generics, _) = self.generics.split_for_impl();
-        BuildMethod {
-            crate_root: &self.crate_root,
-            enabled: !self.build_fn.skip,
-            ident: &self.build_fn.name,
-            visibility: self.build_method_vis(),
-            pattern: self.pattern,
-            target_ty: &self.ident,
-            target_ty_generics: Some(ty_generics),
-            error_ty: self.builder_error_ident(),
-            initializers: Vec::with_capacity(self.field_count()),
-            doc_comment: None,
-            default_struct: self.default.as_ref(),
-            validate_fn: self.build_fn.validate.as_ref(),
-        }
+    /// Check if the build method binds the values of fields to local variables, so the
+    /// defaults of fields with `after` can read them.
+    pub fn binds_fields(&self) -> bool {
+        self.fields().any(|f| !f.field.after.is_empty())
     }
-}
 
-/// Accessor for field data which can pull through options from the parent
-/// struct.
-pub struct FieldWithDefaults<'a> {
-    parent: &'a Options,
-    field: &'a Field,
-}
+    /// Render a usage example for the builder's documentation, which calls the setter of every
+    /// required field and then the build method.
+    ///
+    /// Returns `None` if the builder has no `Default` impl or no build method to call.
+    pub fn usage_example(&self) -> Option<String> {
+        if self.custom_constructor.is_present() || self.build_fn.skip {
+            return None;
+        }
 
-/// Accessors for parsed properties, with transparent pull-through from the
-/// parent struct's configuration.
-impl<'a> FieldWithDefaults<'a> {
-    /// Check if this field should emit a setter.
-    pub fn setter_enabled(&self) -> bool {
-        self.field
-            .setter
-            .setter_enabled()
-            .or_else(|| self.parent.setter.enabled())
-            .unwrap_or(true)
-    }
+        let setter_calls: String = self
+            .fields()
+            .filter(|f| f.is_required() && f.setter_enabled())
+            .map(|f| {
+                let args = match (f.setter_untuple(), f.setter_value_type()) {
+                    _ if f.field.setter.transform.is_some() => f
+                        .field
+                        .setter
+                        .transform
+                        .iter()
+                        .flat_map(|transform| &transform.inputs)
+                        .map(|input| match input {
+                            syn::Pat::Type(pat_type) => {
+                                let pat = &pat_type.pat;
+                                format!("/* {}: {} */", quote!(#pat), type_to_string(&pat_type.ty))
+                            }
+                            _ => String::from("/* _ */"),
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    (Some(names), syn::Type::Tuple(tuple)) => names
+                        .iter()
+                        .zip(&tuple.elems)
+                        .map(|(name, ty)| format!("/* {}: {} */", name, type_to_string(ty)))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    _ => format!("/* {} */", type_to_string(f.setter_value_type())),
+                };
+                format!("\n    .{}({})", f.setter_ident(), args)
+            })
+            .collect();
 
-    pub fn field_enabled(&self) -> bool {
-        self.field
-            .setter
-            .field_enabled()
-            .or_else(|| self.parent.setter.enabled())
-            .unwrap_or(true)
+        Some(format!(
+            include_str!("../doc_tpl/builder_example.md"),
+            builder_name = self.builder_ident(),
+            setter_calls = setter_calls,
+            build_fn = self.build_fn.name,
+        ))
     }
 
-    /// Check if this field should emit a fallible setter.
-    /// This depends on the `TryFrom` trait, which hasn't yet stabilized.
-    pub fn try_setter(&self) -> bool {
-        self.field.try_setter.is_present() || self.parent.try_setter.is_present()
+    /// Predicate of the `#[cfg(...)]` attribute to apply to all generated items, if any.
+    pub fn cfg(&self) -> Option<&TokenStream> {
+        self.cfg.as_ref()
     }
 
-    /// Get the prefix that should be applied to the field name to produce
-    /// the setter ident, if any.
-    pub fn setter_prefix(&self) -> Option<&Ident> {
-        self.field
-            .setter
-            .prefix
-            .as_ref()
-            .or(self.parent.setter.prefix.as_ref())
+    /// Whether the generated code should be printed for inspection.
+    pub fn dump(&self) -> bool {
+        self.dump.is_present()
     }
 
-    /// Get the ident of the emitted setter method
-    pub fn setter_ident(&self) -> syn::Ident {
-        if let Some(ref custom) = self.field.setter.name {
-            return custom.clone();
+    /// The name of the constructor macro, which defaults to the struct name in snake case.
+    pub fn constructor_macro_ident(&self) -> Ident {
+        if let Some(name) = self
+            .constructor_macro
+            .as_ref()
+            .and_then(|opts| opts.name.as_ref())
+        {
+            return name.clone();
         }
 
-        let ident = &self.field.ident;
+        format_ident!("{}", to_snake_case(&self.ident.unraw().to_string()))
+    }
 
-        if let Some(ref prefix) = self.setter_prefix() {
-            return format_ident!("{}_{}", prefix, ident.as_ref().unwrap());
+    /// The name of the field enum, which defaults to the struct name followed by `Field`.
+    pub fn field_enum_ident(&self) -> Ident {
+        if let Some(name) = self.field_enum.as_ref().and_then(|opts| opts.name.as_ref()) {
+            return name.clone();
         }
 
-        ident.clone().unwrap()
+        format_ident!("{}Field", self.ident)
     }
 
-    /// Checks if the emitted setter should be generic over types that impl
-    /// `Into<FieldType>`.
-    pub fn setter_into(&self) -> bool {
-        self.field
-            .setter
-            .into
-            .or(self.parent.setter.into)
-            .unwrap_or_default()
+    /// The name of the trait declaring the setters of an `overridable` builder.
+    pub fn setters_trait_ident(&self) -> Ident {
+        format_ident!("{}Setters", self.builder_ident())
     }
 
-    /// Checks if the emitted setter should strip the wrapper Option over types that impl
-    /// `Option<FieldType>`.
-    pub fn setter_strip_option(&self) -> bool {
-        self.field
-            .setter
-            .strip_option
-            .or(self.parent.setter.strip_option)
-            .unwrap_or_default()
+    /// The name of the method through which the setters trait accesses the builder, e.g.
+    /// `lorem_builder_mut`.
+    pub fn setters_trait_accessor(&self) -> Ident {
+        format_ident!(
+            "{}_mut",
+            to_snake_case(&self.builder_ident().unraw().to_string())
+        )
     }
 
-    /// Get the visibility of the emitted setter, if there will be one.
-    pub fn setter_vis(&self) -> Cow<syn::Visibility> {
-        self.field
-            .visibility
+    /// Reject combinations of options which can't be generated together, and complete options
+    /// which depend on others.
+    fn validate(mut self) -> darling::Result<Self> {
+        let mut acc = Error::accumulator();
+
+        if self.strict.is_present() {
+            self.validate_strict(&mut acc);
+        }
+
+        // The options only add serde attributes to the builder.
+        if self.deserialize.is_present() {
+            let derives_deserialize =
+                self.derive.traits.iter().any(
+                    |path| matches!(path.segments.last(), Some(s) if s.ident == "Deserialize"),
+                );
+            if !derives_deserialize {
+                acc.push(Error::custom(
+                    "`deserialize(...)` requires the builder to derive `Deserialize`",
+                ));
+            }
+
+            if let Some(ref extra) = self.deserialize.extra {
+                // serde doesn't support `deny_unknown_fields` with `flatten`.
+                if self.deserialize.deny_unknown_fields.is_present() {
+                    acc.push(
+                        Error::custom(
+                            "`deserialize(extra)` cannot be used with `deny_unknown_fields`",
+                        )
+                        .with_span(&self.deserialize.deny_unknown_fields.span()),
+                    );
+                }
+
+                if cfg!(not(feature = "lib_has_std")) {
+                    acc.push(
+                        Error::custom(
+                            "`deserialize(extra)` requires the `std` feature of derive_builder",
+                        )
+                        .with_span(extra),
+                    );
+                }
+
+                if self.const_fn.is_present() {
+                    acc.push(
+                        Error::custom("`const_fn` builders cannot use `deserialize(extra)`")
+                            .with_span(extra),
+                    );
+                }
+
+                for field in self.fields() {
+                    if field.field_ident() == "extra" {
+                        acc.push(
+                            Error::custom("`deserialize(extra)` adds a builder field named `extra`, which is already a field of the struct")
+                                .with_span(field.field_ident()),
+                        );
+                    }
+                }
+            }
+        }
+
+        // The constructor macro calls `Default::default` and the build method.
+        if self.constructor_macro.is_some() {
+            if self.custom_constructor.is_present() {
+                acc.push(
+                    Error::custom("`constructor_macro` cannot be used with `custom_constructor`")
+                        .with_span(&self.custom_constructor.span()),
+                );
+            }
+
+            if self.build_fn.skip {
+                acc.push(Error::custom(
+                    "`constructor_macro` cannot be used with `build_fn(skip)`",
+                ));
+            }
+        }
+
+        // A `const fn` can't take `&mut self`, call trait methods or return errors which need
+        // a conversion.
+        if self.const_fn.is_present() {
+            let span = self.const_fn.span();
+            if self.fields().any(|f| f.pattern() != BuilderPattern::Owned) {
+                acc.push(
+                    Error::custom("`const_fn` builders require `pattern = \"owned\"`")
+                        .with_span(&span),
+                );
+            }
+
+            if self.fields().any(|f| f.has_time_default()) {
+                acc.push(
+                    Error::custom(
+                        "`const_fn` builders cannot use `default(now)` or `default(since_epoch = \"...\")`",
+                    )
+                    .with_span(&span),
+                );
+            }
+
+            for field in self.fields() {
+                if field.setter_enabled() && field.setter_into() {
+                    acc.push(
+                        Error::custom("`const_fn` builders cannot use `setter(into)`")
+                            .with_span(field.field_ident()),
+                    );
+                }
+
+                if field.setter_enabled() && (field.setter_boxed() || field.setter_closure()) {
+                    acc.push(
+                        Error::custom(
+                            "`const_fn` builders cannot use `setter(boxed)` or `setter(closure)`",
+                        )
+                        .with_span(field.field_ident()),
+                    );
+                }
+
+                if let Some(DefaultExpression::Trait) = field.field.default {
+                    acc.push(
+                        Error::custom(
+                            "`const_fn` builders cannot use `default` without an expression",
+                        )
+                        .with_span(field.field_ident()),
+                    );
+                }
+            }
+
+            if self.default.is_some() {
+                acc.push(
+                    Error::custom("`const_fn` builders cannot use a struct-level `default`")
+                        .with_span(&span),
+                );
+            }
+
+            if !self.build_fn.validate.is_empty() {
+                acc.push(
+                    Error::custom("`const_fn` builders cannot use `build_fn(validate)`")
+                        .with_span(&span),
+                );
+            }
+
+            if self.build_fn.normalize.is_some() {
+                acc.push(
+                    Error::custom("`const_fn` builders cannot use `build_fn(normalize)`")
+                        .with_span(&span),
+                );
+            }
+
+            if self.build_fn.post_build.is_some() {
+                acc.push(
+                    Error::custom("`const_fn` builders cannot use `build_fn(post_build)`")
+                        .with_span(&span),
+                );
+            }
+
+            for field in self.fields() {
+                if let Some(validate) = &field.field.validate {
+                    acc.push(
+                        Error::custom("`const_fn` builders cannot use `validate` on fields")
+                            .with_span(validate),
+                    );
+                }
+            }
+
+            if self.overridable.is_present() {
+                acc.push(
+                    Error::custom("`const_fn` cannot be used with `overridable`").with_span(&span),
+                );
+            }
+
+            if self.fields().any(|f| f.is_flattened()) {
+                acc.push(
+                    Error::custom("`const_fn` cannot be used with `flatten`").with_span(&span),
+                );
+            }
+
+            if self.fields().any(|f| f.is_delegated()) {
+                acc.push(
+                    Error::custom("`const_fn` cannot be used with `delegate`").with_span(&span),
+                );
+            }
+
+            if let Some(ref on_set) = self.on_set {
+                acc.push(
+                    Error::custom("`const_fn` cannot be used with `on_set`")
+                        .with_span(&on_set.path),
+                );
+            }
+        }
+
+        if let Some(rule) = self.setter.rename_all {
+            if !rule.is_ident_case() {
+                acc.push(Error::custom(
+                    "`setter(rename_all)` must produce identifiers; use `field_name_case` for kebab-case field names",
+                ));
+            }
+        }
+
+        for subset in &self.build_fn.subset {
+            if subset.name == self.build_fn.name {
+                acc.push(
+                    Error::custom(
+                        "`build_fn(subset(...))` must not have the name of the build method",
+                    )
+                    .with_span(&subset.name),
+                );
+            }
+            for ident in &subset.requires {
+                if !self.fields().any(|f| f.field_ident() == ident) {
+                    acc.push(
+                        Error::custom("`build_fn(subset(requires(...)))` names an unknown field")
+                            .with_span(ident),
+                    );
+                }
+            }
+        }
+
+        if let Some(ref convert_from) = self.convert_from {
+            for ident in &convert_from.skip {
+                if !self.fields().any(|f| f.field_ident() == ident) {
+                    acc.push(
+                        Error::custom("`convert_from(skip(...))` names an unknown field")
+                            .with_span(ident),
+                    );
+                }
+            }
+        }
+
+        // The builder field of these fields doesn't store a value of the field's type.
+        if self.from_target.is_present() {
+            for field in self.fields().filter(|f| f.field_enabled()) {
+                if field.converts_at_build() || field.field.field.build.is_some() {
+                    acc.push(
+                        Error::custom(format!(
+                            "`from_target` cannot set `{}`, whose builder field is converted by the build method",
+                            field.field_ident()
+                        ))
+                        .with_span(field.field_ident()),
+                    );
+                }
+            }
+        }
+
+        if self.to_map.is_present() && !cfg!(feature = "json") {
+            acc.push(
+                Error::custom("`to_map` requires the `json` feature of derive_builder")
+                    .with_span(&self.to_map.span()),
+            );
+        }
+
+        if self.from_query.is_present() && !cfg!(feature = "urlencoded") {
+            acc.push(
+                Error::custom("`from_query` requires the `urlencoded` feature of derive_builder")
+                    .with_span(&self.from_query.span()),
+            );
+        }
+
+        if self.from_str.is_present() && cfg!(not(any(feature = "alloc", feature = "lib_has_std")))
+        {
+            acc.push(
+                Error::custom("`from_str` requires the `alloc` or `std` feature of derive_builder")
+                    .with_span(&self.from_str.span()),
+            );
+        }
+
+        if self.build_fn.collect_errors.is_present()
+            && cfg!(not(any(feature = "alloc", feature = "lib_has_std")))
+        {
+            acc.push(
+                Error::custom(
+                    "`collect_errors` requires the `alloc` or `std` feature of derive_builder",
+                )
+                .with_span(&self.build_fn.collect_errors.span()),
+            );
+        }
+
+        if self.build_fn.return_builder.is_present() {
+            let span = self.build_fn.return_builder.span();
+            if self.pattern != BuilderPattern::Owned {
+                acc.push(
+                    Error::custom(
+                        "`build_fn(return_builder)` requires `pattern = \"owned\"`, the other builders are not consumed by `build()`",
+                    )
+                    .with_span(&span),
+                );
+            }
+            if self.const_fn.is_present() || self.constructor_macro.is_some() {
+                acc.push(
+                    Error::custom(
+                        "`build_fn(return_builder)` cannot be used with `const_fn` or `constructor_macro`",
+                    )
+                    .with_span(&span),
+                );
+            }
+        }
+
+        if self.split_impls.is_present() {
+            if self.overridable.is_present() {
+                acc.push(
+                    Error::custom("`split_impls` cannot be used with `overridable`")
+                        .with_span(&self.split_impls.span()),
+                );
+            }
+        } else {
+            for path in &self.attrs.split_impl_attr_paths {
+                acc.push(
+                    Error::custom(format!(
+                        "`{}` requires `#[builder(split_impls)]`",
+                        path.get_ident().unwrap()
+                    ))
+                    .with_span(path),
+                );
+            }
+        }
+
+        if !self.build_fn.wrappers.is_empty()
+            && cfg!(not(any(feature = "alloc", feature = "lib_has_std")))
+        {
+            acc.push(Error::custom(
+                "`build_fn(wrappers(...))` requires the `alloc` or `std` feature of derive_builder",
+            ));
+        }
+
+        if self.build_fn.all_errors.is_present()
+            && cfg!(not(any(feature = "alloc", feature = "lib_has_std")))
+        {
+            acc.push(
+                Error::custom(
+                    "`build_fn(all_errors)` requires the `alloc` or `std` feature of derive_builder",
+                )
+                .with_span(&self.build_fn.all_errors.span()),
+            );
+        }
+
+        if cfg!(not(any(feature = "alloc", feature = "lib_has_std"))) {
+            for field in self.fields().filter(|f| f.is_delegated()) {
+                acc.push(
+                    Error::custom(
+                        "#[builder(delegate)] requires the `alloc` or `std` feature of derive_builder",
+                    )
+                    .with_span(&field.field.delegate.span()),
+                );
+            }
+        }
+
+        if cfg!(not(feature = "lib_has_std")) {
+            for field in self.fields().filter(|f| f.has_time_default()) {
+                acc.push(
+                    Error::custom(
+                        "`default(now)` and `default(since_epoch = \"...\")` require the `std` feature of derive_builder",
+                    )
+                    .with_span(field.field_ident()),
+                );
+            }
+        }
+
+        if let Some(ref default) = self.default {
+            if default.is_time() {
+                acc.push(Error::custom(
+                    "`default(now)` and `default(since_epoch = \"...\")` can only be used on fields",
+                ));
+            }
+        }
+
+        if self.dyn_set.is_present() && cfg!(not(any(feature = "alloc", feature = "lib_has_std"))) {
+            acc.push(
+                Error::custom("`dyn_set` requires the `alloc` or `std` feature of derive_builder")
+                    .with_span(&self.dyn_set.span()),
+            );
+        }
+
+        if self.assert_no_alloc_build.is_present() {
+            let span = self.assert_no_alloc_build.span();
+            if cfg!(not(feature = "lib_has_std")) {
+                acc.push(
+                    Error::custom(
+                        "`assert_no_alloc_build` requires the `std` feature of derive_builder",
+                    )
+                    .with_span(&span),
+                );
+            }
+
+            if self.build_fn.skip {
+                acc.push(
+                    Error::custom("`assert_no_alloc_build` cannot be used with `build_fn(skip)`")
+                        .with_span(&span),
+                );
+            }
+
+            if self.generics.type_params().next().is_some() {
+                acc.push(
+                    Error::custom("`assert_no_alloc_build` cannot be used on generic structs")
+                        .with_span(&span),
+                );
+            }
+        }
+
+        // Errors of nested builders are converted into validation errors.
+        if let Some(BuildFnError::Generated(e)) = &self.build_fn.error {
+            if !*e.validation_error {
+                for field in self.fields().filter(|f| f.is_flattened()) {
+                    acc.push(
+                        Error::custom(
+                            "#[builder(flatten)] requires the `validation_error` of the build error",
+                        )
+                        .with_span(&field.field.flatten.span()),
+                    );
+                }
+                for field in self
+                    .fields()
+                    .filter(|f| f.field.setter.each_builder.is_some() && f.setter_enabled())
+                {
+                    acc.push(
+                        Error::custom(
+                            "`setter(each_builder)` requires the `validation_error` of the build error",
+                        )
+                        .with_span(field.field_ident()),
+                    );
+                }
+            }
+        }
+
+        for field in self.fields() {
+            if field.setter_enabled()
+                && field.setter_closure()
+                && !matches!(field.setter_value_type(), syn::Type::TraitObject(_))
+            {
+                acc.push(
+                    Error::custom(
+                        "#[builder(setter(closure))] requires a field of type `Box<dyn Trait>`",
+                    )
+                    .with_span(&field.field.ty),
+                );
+            }
+        }
+
+        for field in self.fields().filter(|f| f.setter_enabled()) {
+            let wrap = match field.setter_wrap() {
+                Some(wrap) => wrap,
+                None => continue,
+            };
+
+            let mut ty = &field.field.ty;
+            if field.setter_strip_option() {
+                ty = extract_type_from_option(ty).unwrap_or(ty);
+            }
+            if extract_type_from_named_wrapper(ty, wrap).is_none() {
+                acc.push(
+                    Error::custom(format!(
+                        "#[builder(setter(wrap))] requires a field of type `{}<T>`",
+                        wrap.segments
+                            .last()
+                            .map_or(String::new(), |s| s.ident.to_string())
+                    ))
+                    .with_span(&field.field.ty),
+                );
+            }
+
+            let setter = &field.field.setter;
+            if setter.boxed == Some(true)
+                || setter.closure == Some(true)
+                || setter.borrowed == Some(true)
+                || setter.nullable == Some(true)
+                || setter.untuple.is_some()
+                || setter.transform.is_some()
+            {
+                acc.push(
+                    Error::custom(
+                        "#[builder(setter(wrap))] cannot be used with `boxed`, `closure`, `borrowed`, `nullable`, `untuple` or `transform`",
+                    )
+                    .with_span(wrap),
+                );
+            }
+
+            if self.const_fn.is_present() {
+                acc.push(
+                    Error::custom("`const_fn` builders cannot use `setter(wrap)`").with_span(wrap),
+                );
+            }
+        }
+
+        for field in self.fields().filter(|f| f.setter_enabled()) {
+            let transform = match field.field.setter.transform {
+                Some(ref transform) => transform,
+                None => continue,
+            };
+
+            for input in &transform.inputs {
+                if !matches!(input, syn::Pat::Type(_)) {
+                    acc.push(
+                        Error::custom(
+                            "#[builder(setter(transform))] needs the type of each parameter",
+                        )
+                        .with_span(input),
+                    );
+                }
+            }
+
+            if field.field.setter.into == Some(true)
+                || field.field.setter.into_impl == Some(true)
+                || field.field.setter.untuple.is_some()
+                || field.field.setter.closure == Some(true)
+                || field.field.setter.defer_err == Some(true)
+            {
+                acc.push(
+                    Error::custom(
+                        "#[builder(setter(transform))] cannot be used with `into`, `untuple`, `closure` or `defer_err`",
+                    )
+                    .with_span(transform),
+                );
+            }
+
+            // The constructor macro passes a single value to each setter.
+            if self.constructor_macro.is_some() {
+                acc.push(
+                    Error::custom(
+                        "#[builder(setter(transform))] cannot be used with `constructor_macro`",
+                    )
+                    .with_span(transform),
+                );
+            }
+        }
+
+        for field in self.fields().filter(|f| f.setter_enabled()) {
+            let untuple = match field.field.setter.untuple {
+                Some(ref untuple) => untuple,
+                None => continue,
+            };
+
+            match field.setter_value_type() {
+                syn::Type::Tuple(tuple) if !tuple.elems.is_empty() => {
+                    if !untuple.names.is_empty() && untuple.names.len() != tuple.elems.len() {
+                        acc.push(
+                            Error::custom(format!(
+                                "#[builder(setter(untuple(...)))] needs {} parameter names",
+                                tuple.elems.len()
+                            ))
+                            .with_span(&field.field.ty),
+                        );
+                    }
+                }
+                _ => acc.push(
+                    Error::custom("#[builder(setter(untuple))] requires a field of a tuple type")
+                        .with_span(&field.field.ty),
+                ),
+            }
+
+            if field.field.setter.into == Some(true) || field.field.setter.into_impl == Some(true) {
+                acc.push(
+                    Error::custom("#[builder(setter(untuple))] cannot be used with `into`")
+                        .with_span(&field.field.ty),
+                );
+            }
+
+            // The constructor macro passes a single value to each setter.
+            if self.constructor_macro.is_some() {
+                acc.push(
+                    Error::custom(
+                        "#[builder(setter(untuple))] cannot be used with `constructor_macro`",
+                    )
+                    .with_span(&field.field.ty),
+                );
+            }
+        }
+
+        for field in self.fields() {
+            // The `&mut Self` returned by the setter can't be passed to the owned setters or
+            // the owned build method.
+            if self.pattern == BuilderPattern::Owned
+                && field.field.pattern == Some(BuilderPattern::Mutable)
+            {
+                acc.push(
+                    Error::custom(
+                        "`pattern = \"mutable\"` on a field cannot be used with `pattern = \"owned\"` on the builder; use `pattern = \"immutable\"` instead",
+                    )
+                    .with_span(field.field_ident()),
+                );
+            }
+
+            if field.converts_at_build() {
+                if field.field.setter.from.is_none() {
+                    acc.push(
+                        Error::custom(
+                            r#"`setter(convert = "at_build")` requires `from = "..."`, the type of the value stored until the build method converts it"#,
+                        )
+                        .with_span(field.field_ident()),
+                    );
+                }
+                if !matches!(field.conversion(), FieldConversion::OptionOrDefault) {
+                    acc.push(
+                        Error::custom(
+                            r#"`setter(convert = "at_build")` cannot be used with `field(ty = "...")`, `field(build = "...")`, `bare`, `flatten` or `delegate`"#,
+                        )
+                        .with_span(field.field_ident()),
+                    );
+                }
+                if self.const_fn.is_present() {
+                    acc.push(
+                        Error::custom(
+                            r#"`setter(convert = "at_build")` cannot be used with `const_fn`"#,
+                        )
+                        .with_span(field.field_ident()),
+                    );
+                }
+                // Conversion errors are turned into validation errors with `ToString`.
+                if cfg!(not(any(feature = "alloc", feature = "lib_has_std"))) {
+                    acc.push(
+                        Error::custom(
+                            r#"`setter(convert = "at_build")` requires the `alloc` or `std` feature of derive_builder"#,
+                        )
+                        .with_span(field.field_ident()),
+                    );
+                }
+            } else if let Some(ref from) = field.field.setter.from {
+                acc.push(
+                    Error::custom(r#"`setter(from = "...")` requires `convert = "at_build"`"#)
+                        .with_span(from),
+                );
+            }
+
+            if field.setter_lazy() {
+                if !matches!(field.conversion(), FieldConversion::OptionOrDefault)
+                    || field.converts_at_build()
+                {
+                    acc.push(
+                        Error::custom(
+                            r#"`setter(lazy)` cannot be used with `field(ty = "...")`, `field(build = "...")`, `bare`, `flatten`, `delegate` or `convert = "at_build"`"#,
+                        )
+                        .with_span(field.field_ident()),
+                    );
+                }
+                if self.const_fn.is_present() {
+                    acc.push(
+                        Error::custom("`setter(lazy)` cannot be used with `const_fn`")
+                            .with_span(field.field_ident()),
+                    );
+                }
+                // The closure is stored in an `Arc`.
+                if cfg!(not(any(feature = "alloc", feature = "lib_has_std"))) {
+                    acc.push(
+                        Error::custom(
+                            "`setter(lazy)` requires the `alloc` or `std` feature of derive_builder",
+                        )
+                        .with_span(field.field_ident()),
+                    );
+                }
+            }
+
+            if field.defers_err() {
+                if field.setter_untuple().is_some() {
+                    acc.push(
+                        Error::custom("`setter(defer_err)` cannot be used with `untuple`")
+                            .with_span(field.field_ident()),
+                    );
+                }
+                if self.const_fn.is_present() {
+                    acc.push(
+                        Error::custom("`setter(defer_err)` cannot be used with `const_fn`")
+                            .with_span(field.field_ident()),
+                    );
+                }
+            }
+
+            if let Some(ref each_builder) = field.field.setter.each_builder {
+                if field.each_builder().is_none() {
+                    acc.push(
+                        Error::custom(
+                            r#"`setter(each_builder)` requires a collection of a type which derives `Builder`, or `builder = "..."`"#,
+                        )
+                        .with_span(&each_builder.name),
+                    );
+                }
+                if !matches!(field.conversion(), FieldConversion::OptionOrDefault) {
+                    acc.push(
+                        Error::custom(
+                            r#"`setter(each_builder)` cannot be used with `field(ty = "...")`, `field(build = "...")`, `bare`, `flatten` or `delegate`"#,
+                        )
+                        .with_span(&each_builder.name),
+                    );
+                }
+            }
+
+            // The error of an item builder is converted with `ToString`.
+            if field.field.setter.each_builder.is_some()
+                && field.setter_enabled()
+                && cfg!(not(any(feature = "alloc", feature = "lib_has_std")))
+            {
+                acc.push(
+                    Error::custom(
+                        "`setter(each_builder)` requires the `alloc` or `std` feature of derive_builder",
+                    )
+                    .with_span(field.field_ident()),
+                );
+            }
+
+            if field.field.setter.strip_bool == Some(true) && !field.is_bool() {
+                acc.push(
+                    Error::custom("#[builder(setter(strip_bool))] requires a field of type `bool`")
+                        .with_span(&field.field.ty),
+                );
+            }
+
+            // The constructor macro passes a single value to each setter.
+            if field.setter_strip_bool() && self.constructor_macro.is_some() {
+                acc.push(
+                    Error::custom(
+                        "#[builder(setter(strip_bool))] cannot be used with `constructor_macro`",
+                    )
+                    .with_span(&field.field.ty),
+                );
+            }
+
+            if let Some(feature) = &field.field.setter.feature {
+                if field.is_required() {
+                    acc.push(
+                        Error::custom(
+                            r#"`setter(feature = "...")` requires a default value, which is used if the feature is disabled"#,
+                        )
+                        .with_span(feature),
+                    );
+                }
+            }
+
+            if let Some(validate) = &field.field.validate {
+                if !field.field_enabled() {
+                    acc.push(
+                        Error::custom(
+                            "`validate` cannot be used on fields without a builder field",
+                        )
+                        .with_span(validate),
+                    );
+                }
+            }
+        }
+
+        // Defaults of fields with `after` read the values of the other fields.
+        let order = self.initialization_order();
+        for (index, field) in order.iter().enumerate() {
+            let after = &field.field.after;
+            if let Some(first) = after.first() {
+                if field.field.default.is_none() && field.field.field.build.is_none() {
+                    acc.push(
+                        Error::custom(
+                            "`after` requires `default` or `field(build = \"...\")`, which can read the fields initialized before",
+                        )
+                        .with_span(first),
+                    );
+                }
+            }
+
+            for other in after {
+                match order
+                    .iter()
+                    .position(|f| f.field.ident.as_ref() == Some(other))
+                {
+                    None => acc
+                        .push(Error::custom(format!("Unknown field `{}`", other)).with_span(other)),
+                    Some(other_index) if other_index == index => acc.push(
+                        Error::custom("A field cannot be initialized after itself")
+                            .with_span(other),
+                    ),
+                    Some(other_index) if other_index > index => acc.push(
+                        Error::custom(format!(
+                            "`after` forms a cycle between `{}` and `{}`",
+                            field.field_ident(),
+                            other
+                        ))
+                        .with_span(other),
+                    ),
+                    Some(_) => {}
+                }
+            }
+        }
+
+        // Dependencies are checked through the `Option` of the builder fields.
+        for field in self.fields() {
+            for other in field
+                .field
+                .requires
+                .iter()
+                .chain(&field.field.conflicts_with)
+            {
+                let other_field = self
+                    .fields()
+                    .find(|f| f.field.ident.as_ref() == Some(other));
+                match other_field {
+                    None => acc
+                        .push(Error::custom(format!("Unknown field `{}`", other)).with_span(other)),
+                    Some(other_field) if !other_field.is_optional() => acc.push(
+                        Error::custom(format!(
+                            "`{}` has no optional builder field, so it cannot be checked",
+                            other
+                        ))
+                        .with_span(other),
+                    ),
+                    Some(_) if !field.is_optional() => acc.push(
+                        Error::custom(format!(
+                            "`{}` has no optional builder field, so it cannot declare dependencies",
+                            field.field_ident()
+                        ))
+                        .with_span(other),
+                    ),
+                    Some(_) => {}
+                }
+            }
+        }
+
+        // Owned `with_` setters are named after their field, so they can clash with the setters
+        // of other fields, e.g. with `setter(prefix = "with")`. The `_owned` setters of `Cow`
+        // fields can clash with the setter of a field named like them.
+        let setter_idents: Vec<_> = self
+            .fields()
+            .filter(|f| f.setter_enabled())
+            .map(|f| f.setter_ident())
+            .collect();
+        for field in self.fields().filter(|f| f.setter_enabled()) {
+            if let Some(owned_ident) = field.owned_setter_ident() {
+                if setter_idents.contains(&owned_ident) {
+                    acc.push(
+                        Error::custom(format!(
+                            "The owned setter `{}` has the same name as another setter, use a different `setter(name)` or `setter(prefix)`",
+                            owned_ident
+                        ))
+                        .with_span(field.field_ident()),
+                    );
+                }
+            }
+
+            if field.setter_cow_owned() {
+                let cow_owned_ident = field.cow_owned_setter_ident();
+                if setter_idents.contains(&cow_owned_ident) {
+                    acc.push(
+                        Error::custom(format!(
+                            "The setter `{}` of `setter(cow_owned)` has the same name as another setter, use a different `setter(name)`",
+                            cow_owned_ident
+                        ))
+                        .with_span(field.field_ident()),
+                    );
+                }
+            } else if field.field.setter.cow_owned == Some(true) {
+                acc.push(
+                    Error::custom(
+                        "#[builder(setter(cow_owned))] requires `borrowed` on a `Cow` field",
+                    )
+                    .with_span(&field.field.ty),
+                );
+            }
+        }
+
+        for field in self.fields().filter(|f| f.setter_enabled()) {
+            if let Some(ref each) = field.field.setter.each {
+                if each.key_value
+                    && extract_key_value_types_from_map(field.setter_value_type()).is_none()
+                {
+                    acc.push(
+                        Error::custom(
+                            "#[builder(setter(each(key_value)))] requires a `HashMap` or `BTreeMap` field",
+                        )
+                        .with_span(&each.name),
+                    );
+                }
+            }
+        }
+
+        // `build_into` has no built struct to pass to `post_build`.
+        if let Some(ref post_build) = self.build_fn.post_build {
+            if self.build_fn.reuse.is_present() {
+                acc.push(
+                    Error::custom("`build_fn(reuse)` cannot be used with `post_build`")
+                        .with_span(&post_build.path),
+                );
+            }
+        }
+
+        // Only the generated error type has a message to customize.
+        if let (Some(message_fn), Some(BuildFnError::Existing(_))) =
+            (&self.build_fn.message_fn, &self.build_fn.error)
+        {
+            acc.push(
+                Error::custom("`message_fn` cannot be used with a custom `error` type")
+                    .with_span(message_fn),
+            );
+        }
+
+        // The generated items use the deprecated builder themselves, which must not warn.
+        if self.deprecated.is_some() {
+            let mut allow = self.allow.to_vec();
+            allow.push(parse_quote!(deprecated));
+            self.allow = allow.into();
+        }
+
+        acc.finish_with(self)
+    }
+}
+
+/// Converters to codegen structs
+impl Options {
+    pub fn as_builder(&self) -> Builder<'_> {
+        Builder {
+            crate_root: &self.crate_root,
+            enabled: true,
+            ident: self.builder_ident(),
+            pattern: self.pattern,
+            derives: &self.derive.traits,
+            allow_lints: &self.allow,
+            struct_attrs: &self.attrs.struct_attrs,
+            impl_attrs: &self.attrs.impl_attrs,
+            impl_default: !self.custom_constructor.is_present(),
+            create_empty: self.create_empty.clone(),
+            generics: Some(&self.generics),
+            visibility: self.builder_vis(),
+            fields: Vec::with_capacity(self.field_count()),
+            field_initializers: Vec::with_capacity(self.field_count()),
+            functions: Vec::with_capacity(self.field_count()),
+            generate_error: self
+                .build_fn
+                .error
+                .as_ref()
+                .and_then(BuildFnError::as_existing)
+                .is_none(),
+            generate_validation_error: self
+                .build_fn
+                .error
+                .as_ref()
+                .and_then(BuildFnError::as_generated)
+                .map(|e| *e.validation_error)
+                .unwrap_or(true),
+            generate_dependency_error: self
+                .fields()
+                .any(|f| !f.field.requires.is_empty() || !f.field.conflicts_with.is_empty()),
+            generate_uninitialized_fields_error: self.build_fn.collect_errors.is_present(),
+            generate_nested_error: self.fields().any(|f| f.is_delegated()),
+            field_enum: self.field_enum.as_ref().map(|_| self.field_enum_ident()),
+            no_alloc: cfg!(not(any(feature = "alloc", feature = "lib_has_std"))),
+            must_derive_clone: self.requires_clone(),
+            doc_comment: None,
+            deprecated: self.deprecated.as_ref(),
+            must_use: self.must_use == Some(true),
+            deprecation_notes: Default::default(),
+            std: !self.no_std.is_present(),
+            target_ty: None,
+            const_fn: self.const_fn.is_present(),
+            message_fn: self.build_fn.message_fn.as_ref(),
+            target_name: self.ident.unraw().to_string(),
+            deny_unknown_fields: self.deserialize.deny_unknown_fields.is_present(),
+            uses: &self.uses,
+            deferred_error: if self.fields().any(|f| f.stores_deferred_error()) {
+                Some(self.builder_error_ident())
+            } else {
+                None
+            },
+            setters_trait: if self.overridable.is_present() {
+                Some(SettersTrait {
+                    ident: self.setters_trait_ident(),
+                    accessor: self.setters_trait_accessor(),
+                    requires_clone: self
+                        .fields()
+                        .any(|f| f.pattern() == BuilderPattern::Immutable),
+                    functions: Vec::with_capacity(self.field_count()),
+                })
+            } else {
+                None
+            },
+            split_impls: if self.split_impls.is_present() {
+                Some(SplitImpls {
+                    setters_attrs: &self.attrs.setters_impl_attrs,
+                    try_setters_attrs: &self.attrs.try_setters_impl_attrs,
+                    build_attrs: &self.attrs.build_impl_attrs,
+                    ..Default::default()
+                })
+            } else {
+                None
+            },
+            api_snapshot: if self.public_api_snapshot.is_present() {
+                Some(Vec::with_capacity(self.field_count()))
+            } else {
+                None
+            },
+        }
+    }
+
+    pub fn as_build_method(&self) -> BuildMethod<'_> {
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        BuildMethod {
+            crate_root: &self.crate_root,
+            enabled: !self.build_fn.skip,
+            ident: &self.build_fn.name,
+            visibility: self.build_method_vis(),
+            pattern: self.pattern,
+            target_ty: &self.ident,
+            target_ty_generics: Some(ty_generics),
+            error_ty: self.builder_error_ident(),
+            into_ty: self.build_fn.into.as_ref(),
+            initializers: Vec::with_capacity(self.field_count()),
+            doc_comment: None,
+            deprecated: self.deprecated.as_ref(),
+            default_struct: self.default.as_ref(),
+            normalize_fn: self.build_fn.normalize.as_ref(),
+            validate_fns: &self.build_fn.validate,
+            deferred_error: self.fields().any(|f| f.stores_deferred_error()),
+            field_validations: Vec::new(),
+            dependency_checks: Vec::new(),
+            bindings: Vec::new(),
+            collect_errors: self.build_fn.collect_errors.is_present(),
+            missing_field_checks: Vec::new(),
+            post_build_fn: self.build_fn.post_build.as_ref().map(|pb| &pb.path),
+            post_build_arg: self
+                .build_fn
+                .post_build
+                .as_ref()
+                .map_or(PostBuildArg::Ref, |pb| pb.arg),
+            const_fn: false,
+            fallback: false,
+            into_target: false,
+            all_errors: false,
+            uninitialized_checks: Vec::new(),
+            packed: self.attrs.packed,
+            return_builder: self.returns_builder(),
+        }
+    }
+
+    /// Check if the build method of an owned builder returns the builder with the error.
+    pub fn returns_builder(&self) -> bool {
+        self.build_fn.return_builder.is_present() && self.pattern == BuilderPattern::Owned
+    }
+
+    /// Returns the `const fn` counterpart of the build method, which is only enabled for
+    /// `const_fn` builders.
+    pub fn as_const_build_method(&self) -> BuildMethod<'_> {
+        BuildMethod {
+            enabled: self.const_fn.is_present() && !self.build_fn.skip,
+            const_fn: true,
+            ..self.as_build_method()
+        }
+    }
+
+    /// Returns the build methods declared with `build_fn(subset(...))`, each with the name of
+    /// the subset.
+    pub fn as_subset_build_methods(&self) -> Vec<(&BuildSubset, BuildMethod<'_>)> {
+        self.build_fn
+            .subset
+            .iter()
+            .map(|subset| {
+                (
+                    subset,
+                    BuildMethod {
+                        ident: &subset.name,
+                        ..self.as_build_method()
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the counterpart of the build method which takes a `fallback` instance, which is
+    /// only enabled if `build_fn(fallback)` is set.
+    pub fn as_fallback_build_method(&self) -> BuildMethod<'_> {
+        BuildMethod {
+            enabled: self.build_fn.fallback.is_present() && !self.build_fn.skip,
+            fallback: true,
+            ..self.as_build_method()
+        }
+    }
+
+    /// Returns the counterpart of the build method which assigns the fields of an existing
+    /// `target` instance, which is only enabled if `build_fn(reuse)` is set.
+    pub fn as_build_into_method(&self) -> BuildMethod<'_> {
+        BuildMethod {
+            enabled: self.build_fn.reuse.is_present() && !self.build_fn.skip,
+            into_target: true,
+            ..self.as_build_method()
+        }
+    }
+
+    /// Returns the counterpart of the build method which returns the errors of all failing
+    /// checks, which is only enabled if `build_fn(all_errors)` is set.
+    pub fn as_all_errors_build_method(&self) -> BuildMethod<'_> {
+        BuildMethod {
+            enabled: self.build_fn.all_errors.is_present() && !self.build_fn.skip,
+            all_errors: true,
+            ..self.as_build_method()
+        }
+    }
+
+    /// Returns the method which panics if the build fails, which is only enabled if
+    /// `build_fn(panic_variant)` is set.
+    pub fn as_build_or_panic_method(&self) -> BuildOrPanicMethod<'_> {
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        BuildOrPanicMethod {
+            crate_root: &self.crate_root,
+            enabled: self.build_fn.panic_variant.is_present() && !self.build_fn.skip,
+            build_fn: &self.build_fn.name,
+            visibility: self.build_method_vis(),
+            pattern: self.pattern,
+            return_builder: self.returns_builder(),
+            target_ty: &self.ident,
+            target_ty_generics: Some(ty_generics),
+            into_ty: self.build_fn.into.as_ref(),
+            required_fields: self
+                .fields()
+                .filter(|f| f.is_required())
+                .map(|f| (f.field_ident(), f.field_name()))
+                .collect(),
+        }
+    }
+
+    /// Returns the `TryFrom` impl of the built struct for the builder, which is only enabled if
+    /// `build_fn(try_from)` is set.
+    pub fn as_try_from_impl(&self) -> TryFromImpl<'_> {
+        TryFromImpl {
+            crate_root: &self.crate_root,
+            enabled: self.build_fn.try_from.is_present() && !self.build_fn.skip,
+            builder_ty: self.builder_ident(),
+            generics: Some(&self.generics),
+            clone_bounds: self.pattern.requires_clone(),
+            allow_lints: &self.allow,
+            build_fn: &self.build_fn.name,
+            target_ty: &self.ident,
+            into_ty: self.build_fn.into.as_ref(),
+            error_ty: self.builder_error_ident(),
+            return_builder: self.returns_builder(),
+        }
+    }
+
+    /// Returns the methods which put the built struct into the smart pointers of
+    /// `build_fn(wrappers(...))`.
+    pub fn as_build_wrapped_methods(&self) -> Vec<BuildWrappedMethod<'_>> {
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        self.build_fn
+            .wrappers
+            .iter()
+            .map(|&wrapper| BuildWrappedMethod {
+                crate_root: &self.crate_root,
+                enabled: !self.build_fn.skip,
+                build_fn: &self.build_fn.name,
+                wrapper,
+                visibility: self.build_method_vis(),
+                pattern: self.pattern,
+                return_builder: self.returns_builder(),
+                target_ty: &self.ident,
+                target_ty_generics: Some(ty_generics.clone()),
+                into_ty: self.build_fn.into.as_ref(),
+                error_ty: self.builder_error_ident(),
+            })
+            .collect()
+    }
+
+    pub fn as_extra_fields(&self) -> Option<ExtraFields<'_>> {
+        Some(ExtraFields {
+            crate_root: &self.crate_root,
+            ident: format_ident!("extra"),
+            visibility: self.builder_vis(),
+            value_ty: self.deserialize.extra.as_ref()?,
+        })
+    }
+
+    /// Methods of `mutators(...)`, which are added to the builder as they are written.
+    pub fn mutators(&self) -> &[syn::ImplItemFn] {
+        &self.mutators
+    }
+
+    pub fn as_reset_method(&self) -> ResetMethod<'_> {
+        ResetMethod {
+            crate_root: &self.crate_root,
+            enabled: self.reset.is_present(),
+            ident: format_ident!("reset"),
+            visibility: self.builder_vis(),
+            pattern: self.pattern,
+            fields: self
+                .fields()
+                .filter(|f| f.field_enabled())
+                .map(|f| ResetField {
+                    field_ident: f.field_ident(),
+                    kind: if !f.is_optional() {
+                        ResetKind::Default
+                    } else if is_clearable_collection(f.value_type()) {
+                        ResetKind::Clear
+                    } else {
+                        ResetKind::Unset
+                    },
+                    default_value: if f.is_bare() {
+                        f.field.default.as_ref()
+                    } else {
+                        None
+                    },
+                    lazy: f.setter_lazy(),
+                })
+                .collect(),
+            deferred_error: self.fields().any(|f| f.stores_deferred_error()),
+        }
+    }
+
+    pub fn as_from_query_method(&self) -> FromQueryMethod<'_> {
+        FromQueryMethod {
+            crate_root: &self.crate_root,
+            enabled: self.from_query.is_present(),
+            visibility: self.builder_vis(),
+            create_empty: &self.create_empty,
+            fields: self.parsed_fields(),
+        }
+    }
+
+    pub fn as_to_map_method(&self) -> ToMapMethod<'_> {
+        ToMapMethod {
+            crate_root: &self.crate_root,
+            enabled: self.to_map.is_present(),
+            visibility: self.builder_vis(),
+            fields: self
+                .fields()
+                .filter(|f| f.field_enabled())
+                .map(|f| ToMapField {
+                    field_ident: f.field_ident(),
+                    key: f.field_name(),
+                    ty: f
+                        .field
+                        .field
+                        .builder_type
+                        .as_ref()
+                        .unwrap_or(f.value_type()),
+                    optional: f.is_optional(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn as_fuzz_build_method(&self) -> FuzzBuildMethod<'_> {
+        FuzzBuildMethod {
+            crate_root: &self.crate_root,
+            enabled: cfg!(feature = "fuzzing") && !self.build_fn.skip,
+            visibility: self.builder_vis(),
+            create_empty: &self.create_empty,
+            build_fn: &self.build_fn.name,
+            fields: self
+                .fields()
+                .filter(|f| f.field_enabled())
+                .map(|f| FuzzField {
+                    field_ident: f.field_ident(),
+                    ty: f
+                        .field
+                        .field
+                        .builder_type
+                        .as_ref()
+                        .unwrap_or(f.value_type()),
+                })
+                .collect(),
+        }
+    }
+
+    /// Fields which are set from `key=value` pairs by `from_query` and `from_str`.
+    fn parsed_fields(&self) -> Vec<ParsedField<'_>> {
+        self.fields()
+            .filter(|f| f.is_parsable())
+            .map(|f| ParsedField {
+                key: f.parse_key(),
+                aliases: &f.field.alias,
+                setter: f.setter_ident(),
+                pattern: f.pattern(),
+                value_ty: f.setter_value_type(),
+                cfg_attrs: f.setter_cfg_attrs(),
+            })
+            .collect()
+    }
+
+    pub fn as_from_str_impl(&self) -> FromStrImpl<'_> {
+        FromStrImpl {
+            crate_root: &self.crate_root,
+            enabled: self.from_str.is_present(),
+            builder_ty: self.builder_ident(),
+            generics: Some(&self.generics),
+            clone_bounds: self.pattern.requires_clone(),
+            allow_lints: &self.allow,
+            create_empty: &self.create_empty,
+            fields: self.parsed_fields(),
+        }
+    }
+
+    /// Returns the constant which lists the names of the items in `generated`, which is only
+    /// enabled if `abi_stable_names` is set.
+    pub fn as_generated_items(&self, generated: &TokenStream) -> GeneratedItems<'_> {
+        let enabled = self.abi_stable_names.is_present();
+        GeneratedItems {
+            enabled,
+            builder_ty: self.builder_ident(),
+            generics: Some(&self.generics),
+            allow_lints: &self.allow,
+            items: if enabled {
+                item_names(generated)
+            } else {
+                vec![]
+            },
+        }
+    }
+
+    pub fn as_no_alloc_build_test(&self) -> NoAllocBuildTest<'_> {
+        NoAllocBuildTest {
+            crate_root: &self.crate_root,
+            enabled: self.assert_no_alloc_build.is_present(),
+            builder_ty: self.builder_ident(),
+            create_empty: &self.create_empty,
+            build_fn: &self.build_fn.name,
+        }
+    }
+
+    pub fn as_dyn_set_impl(&self) -> DynSetImpl<'_> {
+        DynSetImpl {
+            crate_root: &self.crate_root,
+            enabled: self.dyn_set.is_present(),
+            builder_ty: self.builder_ident(),
+            generics: Some(&self.generics),
+            clone_bounds: self.pattern.requires_clone(),
+            allow_lints: &self.allow,
+            create_empty: &self.create_empty,
+            fields: self
+                .fields()
+                .filter(|f| f.has_value_setter())
+                .map(|f| DynSetField {
+                    name: f.field_name(),
+                    setter: f.setter_ident(),
+                    pattern: f.pattern(),
+                    value_ty: f.setter_value_type(),
+                    cfg_attrs: f.setter_cfg_attrs(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn as_convert_from(&self) -> ConvertFrom<'_> {
+        let skip: &[Ident] = match self.convert_from {
+            Some(ref opts) => &opts.skip,
+            None => &[],
+        };
+        ConvertFrom {
+            crate_root: &self.crate_root,
+            source_ty: self.convert_from.as_ref().map(|opts| &opts.ty),
+            builder_ty: self.builder_ident(),
+            generics: Some(&self.generics),
+            allow_lints: &self.allow,
+            create_empty: &self.create_empty,
+            fields: self
+                .fields()
+                .filter(|f| f.field_enabled() && !skip.contains(f.field_ident()))
+                .map(|f| ConvertedField {
+                    field_ident: f.field_ident(),
+                    optional: f.is_optional(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the conversion of the target struct into the builder, which is only enabled if
+    /// `from_target` is set.
+    pub fn as_from_target(&self) -> FromTarget<'_> {
+        FromTarget {
+            crate_root: &self.crate_root,
+            enabled: self.from_target.is_present(),
+            builder_ty: self.builder_ident(),
+            target_ty: &self.ident,
+            generics: Some(&self.generics),
+            clone_bounds: self.pattern.requires_clone(),
+            visibility: self.builder_vis(),
+            allow_lints: &self.allow,
+            create_empty: &self.create_empty,
+            fields: self
+                .fields()
+                .filter(|f| f.field_enabled())
+                .map(|f| FromTargetField {
+                    field_ident: f.field_ident(),
+                    optional: matches!(f.field_type(), BuilderFieldType::Optional(_)),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn as_builder_display(&self) -> BuilderDisplay<'_> {
+        BuilderDisplay {
+            crate_root: &self.crate_root,
+            enabled: self.derive.display,
+            builder_ty: self.builder_ident(),
+            generics: Some(&self.generics),
+            allow_lints: &self.allow,
+            fields: self
+                .fields()
+                .filter(|f| f.field_enabled())
+                .map(|f| BuilderDisplayField {
+                    field_ident: f.field_ident(),
+                    name: f.field_name(),
+                    ty: f
+                        .field
+                        .field
+                        .builder_type
+                        .as_ref()
+                        .unwrap_or(f.value_type()),
+                    optional: f.is_optional(),
+                    redacted: f.field.redact.is_present(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn as_config_key_impl(&self) -> ConfigKeyImpl<'_> {
+        ConfigKeyImpl {
+            crate_root: &self.crate_root,
+            enabled: self.derive.config_key,
+            builder_ty: self.builder_ident(),
+            generics: Some(&self.generics),
+            allow_lints: &self.allow,
+            fields: self
+                .fields()
+                .filter(|f| f.field_enabled())
+                .map(|f| ConfigKeyField {
+                    field_ident: f.field_ident(),
+                    name: f.field_name(),
+                    ty: f
+                        .field
+                        .field
+                        .builder_type
+                        .as_ref()
+                        .unwrap_or(f.value_type()),
+                    optional: f.is_optional(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn as_flattened_setters(&self) -> Vec<FlattenedSetters<'_>> {
+        self.fields()
+            .filter_map(|f| f.as_flattened_setters())
+            .collect()
+    }
+
+    pub fn as_field_enum(&self) -> FieldEnum<'_> {
+        FieldEnum {
+            crate_root: &self.crate_root,
+            enabled: self.field_enum.is_some(),
+            ident: self.field_enum_ident(),
+            visibility: self.builder_vis(),
+            target_ty: &self.ident,
+            allow_lints: &self.allow,
+            variants: self
+                .fields()
+                .map(|f| FieldEnumVariant {
+                    ident: format_ident!(
+                        "{}",
+                        RenameRule::Pascal.apply(&f.field_ident().unraw().to_string())
+                    ),
+                    name: f.field_name(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn as_constructor_macro(&self) -> ConstructorMacro<'_> {
+        ConstructorMacro {
+            enabled: self.constructor_macro.is_some(),
+            ident: self.constructor_macro_ident(),
+            target_ty: &self.ident,
+            builder_ty: self.builder_ident(),
+            build_fn: &self.build_fn.name,
+            fields: self
+                .fields()
+                .filter(|f| f.field_enabled() && !f.is_flattened() && !f.is_delegated())
+                .map(|f| ConstructorMacroField {
+                    field_ident: f.field_ident(),
+                    setter_ident: f.setter_ident(),
+                    required: f.is_required(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Accessor for field data which can pull through options from the parent
+/// struct.
+pub struct FieldWithDefaults<'a> {
+    parent: &'a Options,
+    field: &'a Field,
+}
+
+/// Accessors for parsed properties, with transparent pull-through from the
+/// parent struct's configuration.
+impl<'a> FieldWithDefaults<'a> {
+    /// Check if this field should emit a setter.
+    pub fn setter_enabled(&self) -> bool {
+        !self.is_flattened()
+            && !self.is_delegated()
+            && self
+                .field
+                .setter
+                .setter_enabled()
+                .or_else(|| self.parent.setter.enabled())
+                .unwrap_or(true)
+    }
+
+    pub fn field_enabled(&self) -> bool {
+        self.field
+            .setter
+            .field_enabled()
+            .or_else(|| self.parent.setter.enabled())
+            .unwrap_or(true)
+    }
+
+    /// Check if the builder field stores the builder of the field's type, whose setters are
+    /// flattened into this builder.
+    pub fn is_flattened(&self) -> bool {
+        self.field.flatten.is_present()
+    }
+
+    /// Check if the builder field stores the builder of the field's type, which is exposed by
+    /// an accessor of this builder.
+    pub fn is_delegated(&self) -> bool {
+        self.field.delegate.is_present()
+    }
+
+    /// Check if the default of the field is the time of the build, e.g. `default(now)`.
+    pub fn has_time_default(&self) -> bool {
+        matches!(self.field.default, Some(ref default) if default.is_time())
+    }
+
+    /// Check if the builder field stores the field's type directly, starting out with the
+    /// default value instead of `None`.
+    pub fn is_bare(&self) -> bool {
+        self.field.field.bare.is_present()
+    }
+
+    /// Key of the field for `from_query` and `from_str`.
+    pub fn parse_key(&self) -> String {
+        match self.field.parse.rename {
+            Some(ref key) => key.clone(),
+            None => self.field_name(),
+        }
+    }
+
+    /// Name of the field in errors and other generated strings, following `field_name_case`.
+    pub fn field_name(&self) -> String {
+        self.parent.field_name(self.field_ident())
+    }
+
+    /// Check if `from_query` and `from_str` set this field, which needs a setter taking a
+    /// single value.
+    pub fn is_parsable(&self) -> bool {
+        !self.field.parse.skip.is_present() && self.has_value_setter()
+    }
+
+    /// Check if the field has a setter which takes a single owned value of a sized type and
+    /// stores it in the builder field's `Option`.
+    pub fn has_value_setter(&self) -> bool {
+        self.setter_enabled()
+            && (self.is_optional() || self.is_bare())
+            && !self.setter_closure()
+            && !(self.setter_wrap().is_some()
+                && matches!(self.setter_value_type(), syn::Type::TraitObject(_)))
+            && !self.setter_borrows_cow()
+            && self.field.setter.untuple.is_none()
+            && self.field.setter.transform.is_none()
+            && !self.setter_strip_bool()
+    }
+
+    /// Returns the impl which gives the builder the setters of the nested builder of a
+    /// `flatten` field.
+    pub fn as_flattened_setters(&self) -> Option<FlattenedSetters<'a>> {
+        if !self.is_flattened() || !self.field_enabled() {
+            return None;
+        }
+
+        let nested_builder = flattened_path(&self.field.ty, "Builder")?;
+        let nested_ident = &nested_builder.segments.last()?.ident;
+        Some(FlattenedSetters {
+            builder_ty: self.parent.builder_ident(),
+            generics: Some(&self.parent.generics),
+            allow_lints: &self.parent.allow,
+            field_ident: self.field_ident(),
+            nested_builder_ty: self.field.field.builder_type.as_ref()?,
+            setters_trait: flattened_path(&self.field.ty, "BuilderSetters")?,
+            accessor: format_ident!("{}_mut", to_snake_case(&nested_ident.to_string())),
+        })
+    }
+
+    /// Returns the accessor of the nested builder of a `delegate` field.
+    pub fn as_delegate_accessor(&'a self) -> Option<DelegateAccessor<'a>> {
+        if !self.is_delegated() {
+            return None;
+        }
+
+        Some(DelegateAccessor {
+            enabled: self.field_enabled(),
+            ident: self.setter_ident(),
+            visibility: self.setter_vis(),
+            field_ident: self.field_ident(),
+            field_name: self.field_name(),
+            builder_ty: self.field.field.builder_type.as_ref()?,
+        })
+    }
+
+    /// Check if this field should emit a fallible setter.
+    /// This depends on the `TryFrom` trait, which hasn't yet stabilized.
+    pub fn try_setter(&self) -> bool {
+        self.field.try_setter.is_present() || self.parent.try_setter.is_present()
+    }
+
+    /// Get the prefix that should be applied to the field name to produce
+    /// the setter ident, if any.
+    pub fn setter_prefix(&self) -> Option<&Ident> {
+        self.field
+            .setter
+            .prefix
+            .as_ref()
+            .or(self.parent.setter.prefix.as_ref())
+    }
+
+    /// Get the suffix that should be appended to the field name to produce
+    /// the setter ident, if any.
+    pub fn setter_suffix(&self) -> Option<&str> {
+        self.field
+            .setter
+            .suffix
+            .as_deref()
+            .or(self.parent.setter.suffix.as_deref())
+    }
+
+    /// Get the ident of the emitted setter method
+    pub fn setter_ident(&self) -> syn::Ident {
+        if let Some(ref custom) = self.field.setter.name {
+            return custom.clone();
+        }
+
+        let ident = &self.field.ident;
+
+        let ident = if let Some(ref prefix) = self.setter_prefix() {
+            format_ident!("{}_{}", prefix, ident.as_ref().unwrap())
+        } else {
+            ident.clone().unwrap()
+        };
+
+        let ident = if let Some(suffix) = self.setter_suffix() {
+            format_ident!("{}_{}", ident, suffix)
+        } else {
+            ident
+        };
+
+        self.apply_rename_rule(ident)
+    }
+
+    /// Apply the struct-level `setter(rename_all = "...")` convention to a setter name.
+    ///
+    /// Names which the convention leaves unchanged are returned as they are, so raw identifiers
+    /// keep working.
+    fn apply_rename_rule(&self, ident: Ident) -> Ident {
+        let rule = match self.parent.setter.rename_all {
+            Some(rule) => rule,
+            None => return ident,
+        };
+
+        let name = ident.unraw().to_string();
+        let renamed = rule.apply(&name);
+        if renamed == name {
+            ident
+        } else {
+            Ident::new(&renamed, ident.span())
+        }
+    }
+
+    /// Checks if the emitted setter should be generic over types that impl
+    /// `Into<FieldType>`.
+    pub fn setter_into(&self) -> bool {
+        self.field
+            .setter
+            .into
+            .or(self.parent.setter.into)
+            .unwrap_or_default()
+            || self.setter_into_impl()
+    }
+
+    /// Get the name of the type parameter of generic setters, which is `setter(generic = "...")`
+    /// or `VALUE`, followed by as many underscores as needed to differ from the generic
+    /// parameters of the struct.
+    pub fn setter_value_param(&self) -> Ident {
+        if let Some(generic) = self.field.setter.generic.as_ref().or(self
+            .parent
+            .setter
+            .generic
+            .as_ref())
+        {
+            return generic.clone();
+        }
+
+        let mut name = String::from("VALUE");
+        while self.parent.generics.params.iter().any(|param| match param {
+            syn::GenericParam::Type(ty) => ty.ident == name,
+            syn::GenericParam::Const(c) => c.ident == name,
+            syn::GenericParam::Lifetime(_) => false,
+        }) {
+            name.push('_');
+        }
+        Ident::new(&name, Span::call_site())
+    }
+
+    /// Checks if the setter should take an `impl Into<FieldType>` rather than being generic,
+    /// which implies `into`.
+    pub fn setter_into_impl(&self) -> bool {
+        self.field
+            .setter
+            .into_impl
+            .or(self.parent.setter.into_impl)
+            .unwrap_or_default()
+    }
+
+    /// Checks if the emitted setter should strip the wrapper Option over types that impl
+    /// `Option<FieldType>`.
+    pub fn setter_strip_option(&self) -> bool {
+        self.field
+            .setter
+            .strip_option
+            .or(self.parent.setter.strip_option)
+            .unwrap_or_default()
+    }
+
+    /// Checks if the emitted setter should take no parameter and set the field to `true`.
+    ///
+    /// At the struct level, this only applies to fields of type `bool`.
+    pub fn setter_strip_bool(&self) -> bool {
+        self.field
+            .setter
+            .strip_bool
+            .or(self.parent.setter.strip_bool)
+            .unwrap_or_default()
+            && self.is_bool()
+    }
+
+    /// Checks if the field is of type `bool`.
+    fn is_bool(&self) -> bool {
+        matches!(self.field.ty, syn::Type::Path(ref ty) if ty.qself.is_none() && ty.path.is_ident("bool"))
+    }
+
+    /// Checks if a `maybe_` setter should be emitted, which takes an `Option` of the setter's value.
+    pub fn setter_maybe(&self) -> bool {
+        self.field
+            .setter
+            .maybe
+            .or(self.parent.setter.maybe)
+            .unwrap_or_default()
+    }
+
+    /// Get the ident of the setter which takes the `Option` stripped by `strip_option`.
+    pub fn maybe_setter_ident(&self) -> syn::Ident {
+        self.apply_rename_rule(format_ident!("maybe_{}", self.field_ident().unraw()))
+    }
+
+    /// Checks if a `_if` setter should be emitted, which only sets the field if its condition is
+    /// `true`.
+    pub fn setter_conditional(&self) -> bool {
+        self.field
+            .setter
+            .conditional
+            .or(self.parent.setter.conditional)
+            .unwrap_or_default()
+    }
+
+    /// Get the ident of the setter which takes a condition next to the value.
+    pub fn conditional_setter_ident(&self) -> syn::Ident {
+        self.apply_rename_rule(format_ident!("{}_if", self.field_ident().unraw()))
+    }
+
+    /// Checks if the emitted setter should take the contents of a `Box` and box them.
+    pub fn setter_boxed(&self) -> bool {
+        self.field
+            .setter
+            .boxed
+            .or(self.parent.setter.boxed)
+            .unwrap_or_default()
+    }
+
+    /// Checks if the emitted setter should take a reference for a `Cow` field and store it as
+    /// `Cow::Borrowed`.
+    pub fn setter_borrowed(&self) -> bool {
+        self.field
+            .setter
+            .borrowed
+            .or(self.parent.setter.borrowed)
+            .unwrap_or_default()
+    }
+
+    /// Checks if the emitted setter takes a reference for a `Cow` field, rather than a value.
+    pub fn setter_borrows_cow(&self) -> bool {
+        self.setter_borrowed() && extract_borrowed_type_from_cow(self.setter_value_type()).is_some()
+    }
+
+    /// Checks if an `_owned` setter should be emitted next to a setter which borrows a `Cow`.
+    pub fn setter_cow_owned(&self) -> bool {
+        self.field
+            .setter
+            .cow_owned
+            .or(self.parent.setter.cow_owned)
+            .unwrap_or_default()
+            && self.setter_borrows_cow()
+    }
+
+    /// Get the ident of the setter which takes the owned value of a borrowing `Cow` setter.
+    pub fn cow_owned_setter_ident(&self) -> syn::Ident {
+        self.apply_rename_rule(format_ident!("{}_owned", self.setter_ident().unraw()))
+    }
+
+    /// Checks if the emitted setter should take a value implementing the traits of a
+    /// `Box<dyn Trait>` field and box it.
+    pub fn setter_closure(&self) -> bool {
+        self.field.setter.closure.unwrap_or_default()
+    }
+
+    /// Get the wrapper type whose type argument the setter takes and wraps.
+    pub fn setter_wrap(&self) -> Option<&Path> {
+        self.field.setter.wrap.as_ref()
+    }
+
+    /// Checks if the field is an `Option<Option<T>>` which can be set to a value or to `None`,
+    /// with a dedicated setter for the latter.
+    pub fn setter_nullable(&self) -> bool {
+        self.field.setter.nullable.unwrap_or_default()
+    }
+
+    /// Get the ident of the setter which sets a nullable field to `None`.
+    pub fn null_setter_ident(&self) -> syn::Ident {
+        self.apply_rename_rule(format_ident!("set_null_{}", self.field_ident().unraw()))
+    }
+
+    /// Get the parameter names of a setter which takes the elements of a tuple field, if it is
+    /// an `untuple` setter.
+    ///
+    /// Returns `None` if the setter does not take a tuple.
+    pub fn setter_untuple(&self) -> Option<Vec<Ident>> {
+        let untuple = self.field.setter.untuple.as_ref()?;
+        let arity = match self.setter_value_type() {
+            syn::Type::Tuple(tuple) if !tuple.elems.is_empty() => tuple.elems.len(),
+            _ => return None,
+        };
+
+        if untuple.names.is_empty() {
+            Some((0..arity).map(|i| format_ident!("value_{}", i)).collect())
+        } else {
+            Some(untuple.names.clone())
+        }
+    }
+
+    /// Checks if a `_with` setter taking a closure which computes the value at build should be
+    /// emitted.
+    pub fn setter_lazy(&self) -> bool {
+        self.field.setter.lazy.unwrap_or_default() && self.setter_enabled()
+    }
+
+    /// Checks if a setter extending the collection with the items of an iterator should be
+    /// emitted.
+    pub fn setter_extend(&self) -> bool {
+        self.field.setter.extend.unwrap_or_default()
+    }
+
+    /// Checks if the field is an array, which gets setters taking an iterator or a single item.
+    pub fn setter_array(&self) -> bool {
+        self.field.setter.array.unwrap_or_default()
+    }
+
+    /// Get the default value of the field, if it has one.
+    ///
+    /// Nullable fields which are never set are left as `None`.
+    pub fn default_value(&self) -> Option<&'a DefaultExpression> {
+        if self.field.default.is_none() && self.setter_nullable() && !self.use_parent_default() {
+            return Some(&DefaultExpression::Trait);
+        }
+
+        // Flags set by `strip_bool` setters are `false` unless they are set.
+        if self.field.default.is_none() && self.setter_strip_bool() && !self.use_parent_default() {
+            return Some(&DefaultExpression::Trait);
+        }
+
+        self.field.default.as_ref()
+    }
+
+    /// Get the visibility of the emitted setter, if there will be one.
+    pub fn setter_vis(&self) -> Cow<'_, syn::Visibility> {
+        self.field
+            .visibility
             .to_explicit_visibility()
+            .or_else(|| self.parent.setter.visibility.to_explicit_visibility())
             .or_else(|| self.parent.visibility.to_explicit_visibility())
             .unwrap_or_else(|| Cow::Owned(syn::parse_quote!(pub)))
     }
 
-    /// Get the ident of the input field. This is also used as the ident of the
-    /// emitted field.
-    pub fn field_ident(&self) -> &syn::Ident {
-        self.field
-            .ident
-            .as_ref()
-            .expect("Tuple structs are not supported")
+    /// Get the ident of the input field. This is also used as the ident of the
+    /// emitted field.
+    pub fn field_ident(&self) -> &'a syn::Ident {
+        self.field
+            .ident
+            .as_ref()
+            .expect("Tuple structs are not supported")
+    }
+
+    pub fn field_vis(&self) -> Cow<'_, syn::Visibility> {
+        self.field
+            .field
+            .visibility
+            .to_explicit_visibility()
+            .or_else(
+                // Disabled fields become a PhantomData in the builder.  We make that field
+                // non-public, even if the rest of the builder is public, since this field is just
+                // there to make sure the struct's generics are properly handled.
+                || {
+                    if self.field_enabled() {
+                        None
+                    } else {
+                        Some(Cow::Owned(syn::Visibility::Inherited))
+                    }
+                },
+            )
+            .or_else(|| self.parent.field.to_explicit_visibility())
+            .unwrap_or(Cow::Owned(syn::Visibility::Inherited))
+    }
+
+    pub fn field_type(&'a self) -> BuilderFieldType<'a> {
+        if !self.field_enabled() {
+            BuilderFieldType::Phantom(&self.field.ty)
+        } else if let Some(custom_ty) = self.field.field.builder_type.as_ref() {
+            BuilderFieldType::Precise(custom_ty)
+        } else if self.is_bare() {
+            BuilderFieldType::Precise(&self.field.ty)
+        } else {
+            BuilderFieldType::Optional(self.value_type())
+        }
+    }
+
+    /// Check if the setter takes a `Result` and stores its error for the build method.
+    pub fn defers_err(&self) -> bool {
+        self.field.setter.defer_err == Some(true) && self.setter_enabled()
+    }
+
+    /// Check if a setter of the field stores errors for the build method, which is the case
+    /// for `defer_err` and `each_builder`.
+    pub fn stores_deferred_error(&self) -> bool {
+        self.defers_err() || (self.field.setter.each_builder.is_some() && self.setter_enabled())
+    }
+
+    /// Get the name of the `each_builder` setter and the builder of the items, which defaults
+    /// to the first type argument of the collection with a `Builder` suffix, e.g.
+    /// `ServerBuilder` for `Vec<Server>`.
+    pub fn each_builder(&self) -> Option<(&'a Ident, Path)> {
+        let each_builder = self.field.setter.each_builder.as_ref()?;
+        if let Some(ref builder) = each_builder.builder {
+            return Some((&each_builder.name, builder.clone()));
+        }
+
+        let item_ty = match self.setter_value_type() {
+            syn::Type::Path(type_path) => match &type_path.path.segments.last()?.arguments {
+                syn::PathArguments::AngleBracketed(args) => {
+                    args.args.iter().find_map(|arg| match arg {
+                        syn::GenericArgument::Type(ty) => Some(ty),
+                        _ => None,
+                    })?
+                }
+                _ => return None,
+            },
+            _ => return None,
+        };
+        Some((&each_builder.name, flattened_path(item_ty, "Builder")?))
+    }
+
+    /// Check if the build method converts the stored value into the type of the field, instead
+    /// of the setter.
+    pub fn converts_at_build(&self) -> bool {
+        self.field.setter.convert == Some(ConvertAt::AtBuild)
+    }
+
+    /// Get the type of the value stored by the setter, which is the type of the field unless it
+    /// is converted at build.
+    pub fn value_type(&self) -> &'a syn::Type {
+        match self.field.setter.from {
+            Some(ref from) if self.converts_at_build() => from,
+            _ => &self.field.ty,
+        }
+    }
+
+    pub fn conversion(&'a self) -> FieldConversion<'a> {
+        if self.is_flattened() {
+            return FieldConversion::Flatten;
+        }
+        if self.is_delegated() {
+            return FieldConversion::Delegate;
+        }
+        if self.is_bare() {
+            // Only owned builders can move the field out of `self`.
+            return match self.parent.pattern {
+                BuilderPattern::Owned => FieldConversion::Move,
+                _ => FieldConversion::Clone,
+            };
+        }
+
+        match (&self.field.field.builder_type, &self.field.field.build) {
+            (_, Some(block)) => FieldConversion::Block(block),
+            (Some(_), None) => FieldConversion::Move,
+            (None, None) => FieldConversion::OptionOrDefault,
+        }
+    }
+
+    /// Get the pattern of the setter, which is also used to move or clone the field in the
+    /// build method.
+    pub fn pattern(&self) -> BuilderPattern {
+        match self.field.pattern {
+            Some(BuilderPattern::Owned) | None => self.parent.pattern,
+            Some(pattern) => pattern,
+        }
+    }
+
+    /// Get the ident of the owned setter emitted for `pattern = "owned"` on a field of a
+    /// builder with another pattern.
+    pub fn owned_setter_ident(&self) -> Option<syn::Ident> {
+        if self.field.pattern == Some(BuilderPattern::Owned)
+            && self.parent.pattern != BuilderPattern::Owned
+        {
+            Some(self.apply_rename_rule(format_ident!("with_{}", self.field_ident().unraw())))
+        } else {
+            None
+        }
+    }
+
+    pub fn use_parent_default(&self) -> bool {
+        self.field.default.is_none() && self.parent.default.is_some()
+    }
+
+    /// Get the `#[cfg(...)]` attributes of the setter, e.g. from `setter(feature = "...")`.
+    pub fn setter_cfg_attrs(&self) -> Vec<&'a syn::Attribute> {
+        self.field
+            .attrs
+            .setter
+            .iter()
+            .filter(|attr| attr.path().is_ident("cfg"))
+            .collect()
+    }
+
+    /// Check if the build method fails when this field has not been set.
+    pub fn is_required(&self) -> bool {
+        self.field_enabled()
+            && self.default_value().is_none()
+            && !self.use_parent_default()
+            && matches!(self.conversion(), FieldConversion::OptionOrDefault)
+    }
+
+    /// Check if the builder field is an `Option`, which is `None` until the setter is called.
+    pub fn is_optional(&self) -> bool {
+        self.field_enabled() && self.field.field.builder_type.is_none() && !self.is_bare()
+    }
+
+    /// Get the type of value the setter takes, after `strip_option` and `boxed` are applied.
+    pub fn setter_value_type(&self) -> &'a syn::Type {
+        let mut ty = self.value_type();
+        if self.setter_nullable() {
+            ty = extract_type_from_option(ty)
+                .and_then(extract_type_from_option)
+                .unwrap_or(ty);
+        } else if self.setter_strip_option() {
+            ty = extract_type_from_option(ty).unwrap_or(ty);
+        }
+        if let Some(wrap) = self.setter_wrap() {
+            ty = extract_type_from_named_wrapper(ty, wrap).unwrap_or(ty);
+        } else if self.setter_boxed() || self.setter_closure() {
+            ty = extract_type_from_box(ty).unwrap_or(ty);
+        }
+        ty
+    }
+
+    pub fn deprecation_notes(&self) -> &DeprecationNotes {
+        &self.parent.deprecation_notes
+    }
+}
+
+/// Converters to codegen structs
+impl<'a> FieldWithDefaults<'a> {
+    /// Returns a `Setter` according to the options.
+    pub fn as_setter(&'a self) -> Setter<'a> {
+        Setter {
+            crate_root: &self.parent.crate_root,
+            setter_enabled: self.setter_enabled(),
+            try_setter: self.try_setter(),
+            visibility: self.setter_vis(),
+            pattern: self.pattern(),
+            attrs: &self.field.attrs.setter,
+            ident: self.setter_ident(),
+            allow_non_snake_case: !matches!(
+                self.parent.setter.rename_all,
+                None | Some(RenameRule::Lower) | Some(RenameRule::Snake)
+            ),
+            field_ident: self.field_ident(),
+            field_name: self.field_name(),
+            field_type: self.field_type(),
+            generic_into: self.setter_into(),
+            into_impl: self.setter_into_impl(),
+            value_param: self.setter_value_param(),
+            strip_option: self.setter_strip_option(),
+            boxed: self.setter_boxed(),
+            borrowed: self.setter_borrowed(),
+            cow_owned_setter: if self.setter_cow_owned() {
+                Some(self.cow_owned_setter_ident())
+            } else {
+                None
+            },
+            closure: self.setter_closure(),
+            wrap: self.setter_wrap(),
+            untuple: self.setter_untuple(),
+            transform: self.field.setter.transform.as_ref(),
+            strip_bool: self.setter_strip_bool(),
+            defer_err: if self.defers_err() {
+                Some(self.parent.builder_error_ident())
+            } else {
+                None
+            },
+            null_setter: if self.setter_nullable() {
+                Some(self.null_setter_ident())
+            } else {
+                None
+            },
+            maybe_setter: if self.setter_maybe() && !self.setter_nullable() {
+                Some(self.maybe_setter_ident())
+            } else {
+                None
+            },
+            conditional_setter: if self.setter_conditional() {
+                Some(self.conditional_setter_ident())
+            } else {
+                None
+            },
+            aliases: &self.field.setter.alias,
+            owned_setter: self.owned_setter_ident(),
+            from_iter_setter: if self.setter_array() {
+                Some(self.apply_rename_rule(format_ident!(
+                    "try_{}_from_iter",
+                    self.field_ident().unraw()
+                )))
+            } else {
+                None
+            },
+            set_nth_setter: if self.setter_array() {
+                Some(
+                    self.apply_rename_rule(format_ident!("set_nth_{}", self.field_ident().unraw())),
+                )
+            } else {
+                None
+            },
+            const_fn: self.parent.const_fn.is_present(),
+            must_use: self.parent.must_use,
+            trait_accessor: if self.parent.overridable.is_present() {
+                Some(self.parent.setters_trait_accessor())
+            } else {
+                None
+            },
+            deprecation_notes: self.deprecation_notes(),
+            each: self.field.setter.each.as_ref(),
+            each_capacity: self.field.field.capacity.as_ref(),
+            each_builder: self.each_builder(),
+            lazy_setter: if self.setter_lazy() {
+                Some(self.apply_rename_rule(format_ident!("{}_with", self.field_ident().unraw())))
+            } else {
+                None
+            },
+            extend_setter: if self.setter_extend() {
+                Some(self.apply_rename_rule(format_ident!("{}_extend", self.field_ident().unraw())))
+            } else {
+                None
+            },
+            on_set: self.parent.on_set.as_ref(),
+            deprecated: self.parent.deprecated.as_ref(),
+        }
+    }
+
+    /// Returns an `Initializer` according to the options.
+    ///
+    /// # Panics
+    ///
+    /// if `default_expression` can not be parsed as `Block`.
+    pub fn as_initializer(&'a self) -> Initializer<'a> {
+        Initializer {
+            crate_root: &self.parent.crate_root,
+            field_enabled: self.field_enabled(),
+            field_ident: self.field_ident(),
+            field_name: self.field_name(),
+            builder_pattern: self.pattern(),
+            default_value: self.default_value(),
+            use_default_struct: self.use_parent_default(),
+            conversion: self.conversion(),
+            custom_error_type_span: self.parent.build_fn.error.as_ref().and_then(|err_ty| {
+                match err_ty {
+                    BuildFnError::Existing(p) => Some(p.span()),
+                    _ => None,
+                }
+            }),
+            const_fn: false,
+            fallback: false,
+            bind_local: self.parent.binds_fields(),
+            collect_errors: self.parent.build_fn.collect_errors.is_present(),
+            convert_at_build: self.converts_at_build(),
+            lazy: self.setter_lazy(),
+            // Set by the build method.
+            packed: false,
+            optional: false,
+            reuse_target: false,
+        }
+    }
+
+    /// Returns the checks of the `requires` and `conflicts_with` dependencies of this field.
+    pub fn as_field_dependencies(&self) -> Vec<FieldDependency<'a>> {
+        let requires = self
+            .field
+            .requires
+            .iter()
+            .map(|other| (other, FieldDependencyKind::Requires));
+        let conflicts_with = self
+            .field
+            .conflicts_with
+            .iter()
+            .map(|other| (other, FieldDependencyKind::ConflictsWith));
+
+        requires
+            .chain(conflicts_with)
+            .map(|(other_ident, kind)| FieldDependency {
+                crate_root: &self.parent.crate_root,
+                field_ident: self.field_ident(),
+                other_ident,
+                field_name: self.field_name(),
+                other_name: self.parent.field_name(other_ident),
+                kind,
+                const_fn: false,
+            })
+            .collect()
+    }
+
+    /// Returns the validation of this field with its `validate` function, if it has one.
+    pub fn as_field_validation(&self) -> Option<FieldValidation<'a>> {
+        let validate_fn = self.field.validate.as_ref()?;
+        Some(FieldValidation {
+            crate_root: &self.parent.crate_root,
+            field_ident: self.field_ident(),
+            validate_fn,
+            optional: self.is_optional(),
+        })
+    }
+
+    /// Returns an `Initializer` for the `const fn` build method.
+    pub fn as_const_initializer(&'a self) -> Initializer<'a> {
+        Initializer {
+            const_fn: true,
+            ..self.as_initializer()
+        }
+    }
+
+    /// Returns an `Initializer` for the build method which takes a `fallback` instance.
+    pub fn as_fallback_initializer(&'a self) -> Initializer<'a> {
+        Initializer {
+            fallback: true,
+            ..self.as_initializer()
+        }
+    }
+
+    /// Returns an `Initializer` for a build method declared with `build_fn(subset(...))`,
+    /// which defaults the field unless the subset requires it.
+    pub fn as_subset_initializer(&'a self, subset: &BuildSubset) -> Initializer<'a> {
+        Initializer {
+            optional: !subset.requires.contains(self.field_ident()),
+            ..self.as_initializer()
+        }
+    }
+
+    pub fn as_builder_field(&'a self) -> BuilderField<'a> {
+        BuilderField {
+            crate_root: &self.parent.crate_root,
+            field_ident: self.field_ident(),
+            field_type: self.field_type(),
+            field_visibility: self.field_vis(),
+            attrs: &self.field.attrs.field,
+            default_value: if self.is_bare() {
+                self.field.default.as_ref()
+            } else {
+                None
+            },
+            lazy: self.setter_lazy(),
+        }
+    }
+}
+
+/// Check if `ty` is a standard collection with a `clear` method which keeps its allocation.
+fn is_clearable_collection(ty: &syn::Type) -> bool {
+    const COLLECTIONS: &[&str] = &[
+        "BinaryHeap",
+        "HashMap",
+        "HashSet",
+        "String",
+        "Vec",
+        "VecDeque",
+    ];
+
+    match ty {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => {
+            matches!(type_path.path.segments.last(), Some(segment) if COLLECTIONS.iter().any(|name| segment.ident == name))
+        }
+        _ => false,
+    }
+}
+
+/// Render a type the way it would be written by hand, e.g. `Vec<String>` rather than the
+/// `Vec < String >` produced by the token printer.
+fn type_to_string(ty: &syn::Type) -> String {
+    let mut rendered = quote!(#ty).to_string();
+    for (from, to) in &[
+        (" < ", "<"),
+        (" <", "<"),
+        (" >", ">"),
+        (" :: ", "::"),
+        (":: ", "::"),
+        (" ,", ","),
+        ("& ", "&"),
+    ] {
+        rendered = rendered.replace(from, to);
+    }
+    rendered
+}
+
+/// Convert a struct name like `HttpRequest` to `http_request`.
+fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = matches!(chars.get(i + 1), Some(n) if n.is_lowercase());
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_is_lower) {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+pub struct FieldIter<'a>(&'a Options, IntoIter<&'a Field>);
+
+impl<'a> Iterator for FieldIter<'a> {
+    type Item = FieldWithDefaults<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.1.next().map(|field| FieldWithDefaults {
+            parent: self.0,
+            field,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use darling::FromDeriveInput;
+
+    use super::*;
+
+    #[test]
+    fn usage_example_lists_required_setters() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            #[builder(setter(strip_option))]
+            struct Lorem<'a> {
+                ipsum: Option<Vec<&'a str>>,
+                #[builder(default)]
+                dolor: u32,
+                #[builder(setter(name = "amet"))]
+                sit: std::collections::HashMap<String, u8>,
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            opts.usage_example().unwrap(),
+            "\n# Examples\n\n```ignore\nlet value = LoremBuilder::default()\n    \
+             .ipsum(/* Vec<&'a str> */)\n    \
+             .amet(/* std::collections::HashMap<String, u8> */)\n    \
+             .build()?;\n```\n"
+        );
+    }
+
+    #[test]
+    fn doc_cfg_from_cfg() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote!(#[doc = "Lorem ipsum"]),
+            parse_quote!(#[cfg(feature = "tls")]),
+            parse_quote!(#[allow(unused)]),
+        ];
+
+        let doc_cfgs = doc_cfg_attrs(&attrs);
+
+        assert_eq!(
+            quote!(#(#doc_cfgs)*).to_string(),
+            quote!(#[cfg_attr(docsrs, doc(cfg(feature = "tls")))]).to_string()
+        );
+    }
+
+    #[test]
+    fn rename_rules() {
+        assert_eq!(RenameRule::Lower.apply("with_ipsum"), "with_ipsum");
+        assert_eq!(RenameRule::Upper.apply("with_ipsum"), "WITH_IPSUM");
+        assert_eq!(RenameRule::Pascal.apply("with_ipsum"), "WithIpsum");
+        assert_eq!(RenameRule::Camel.apply("with_ipsum"), "withIpsum");
+        assert_eq!(RenameRule::Snake.apply("with_ipsum"), "with_ipsum");
+        assert_eq!(RenameRule::ScreamingSnake.apply("with_ipsum"), "WITH_IPSUM");
+        assert_eq!(RenameRule::Camel.apply("ipsum_2d"), "ipsum2d");
+    }
+
+    #[test]
+    fn rename_rules_split_non_snake_case_names() {
+        assert_eq!(RenameRule::Snake.apply("ipsumDolor"), "ipsum_dolor");
+        assert_eq!(RenameRule::Snake.apply("HTTPPort"), "http_port");
+        assert_eq!(
+            RenameRule::ScreamingSnake.apply("ipsumDolor"),
+            "IPSUM_DOLOR"
+        );
+        assert_eq!(RenameRule::Camel.apply("IpsumDolor"), "ipsumDolor");
+        assert_eq!(RenameRule::Pascal.apply("ipsumDolor"), "IpsumDolor");
+        assert_eq!(RenameRule::Kebab.apply("ipsumDolor"), "ipsum-dolor");
+        assert_eq!(RenameRule::Lower.apply("ipsumDolor"), "ipsumdolor");
+    }
+
+    #[test]
+    fn snake_case_macro_names() {
+        assert_eq!(to_snake_case("Lorem"), "lorem");
+        assert_eq!(to_snake_case("HttpRequest"), "http_request");
+        assert_eq!(to_snake_case("URLParser"), "url_parser");
+        assert_eq!(to_snake_case("Vec3D"), "vec3_d");
+    }
+
+    #[test]
+    fn constructor_macro_requires_default_and_build_fn() {
+        let err = Options::from_derive_input(&parse_quote! {
+            #[builder(constructor_macro, custom_constructor, build_fn(skip))]
+            struct Lorem {
+                ipsum: u32,
+            }
+        })
+        .unwrap_err();
+
+        assert_eq!(err.len(), 2);
+    }
+
+    #[test]
+    fn field_enum_names() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            #[builder(field_enum, field_name_case = "kebab-case")]
+            struct Lorem {
+                r#type: u32,
+                dolor_sit: u32,
+            }
+        })
+        .unwrap();
+        let field_enum = opts.as_field_enum();
+
+        assert_eq!(field_enum.ident, "LoremField");
+        assert_eq!(field_enum.variants[0].ident, "Type");
+        assert_eq!(field_enum.variants[0].name, "type");
+        assert_eq!(field_enum.variants[1].ident, "DolorSit");
+        assert_eq!(field_enum.variants[1].name, "dolor-sit");
+
+        let opts = Options::from_derive_input(&parse_quote! {
+            #[builder(field_enum = "Part")]
+            struct Lorem {
+                ipsum: u32,
+            }
+        })
+        .unwrap();
+
+        assert_eq!(opts.as_field_enum().ident, "Part");
+        assert_eq!(opts.as_builder().field_enum.unwrap(), "Part");
+    }
+
+    #[test]
+    fn convert_at_build_stores_from_type() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            struct Lorem {
+                #[builder(setter(into, convert = "at_build", from = "String"))]
+                ipsum: u16,
+                #[builder(setter(convert = "at_set"))]
+                dolor: u16,
+            }
+        })
+        .unwrap();
+        let fields = opts.fields().collect::<Vec<_>>();
+
+        assert!(fields[0].converts_at_build());
+        assert_eq!(fields[0].value_type(), &parse_quote!(String));
+        assert!(!fields[1].converts_at_build());
+        assert_eq!(fields[1].value_type(), &parse_quote!(u16));
+    }
+
+    #[test]
+    fn convert_at_build_rejects_invalid_fields() {
+        let err = Options::from_derive_input(&parse_quote! {
+            #[builder(const_fn, pattern = "owned")]
+            struct Lorem {
+                #[builder(setter(convert = "at_build"))]
+                ipsum: u16,
+                #[builder(setter(convert = "at_build", from = "String"), field(ty = "String"))]
+                dolor: u16,
+                #[builder(setter(from = "String"))]
+                sit: u16,
+            }
+        })
+        .unwrap_err();
+
+        let expected = if cfg!(any(feature = "alloc", feature = "lib_has_std")) {
+            5
+        } else {
+            7
+        };
+        assert_eq!(err.len(), expected);
+    }
+
+    #[test]
+    fn defer_err() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            struct Lorem {
+                #[builder(setter(defer_err))]
+                ipsum: u16,
+                #[builder(setter(defer_err = false))]
+                dolor: u16,
+                #[builder(setter(skip, defer_err))]
+                sit: u16,
+            }
+        })
+        .unwrap();
+
+        let fields: Vec<_> = opts.fields().collect();
+        assert!(fields[0].defers_err());
+        assert!(!fields[1].defers_err());
+        assert!(!fields[2].defers_err());
+        assert!(opts.as_builder().deferred_error.is_some());
+        assert!(opts.as_build_method().deferred_error);
+    }
+
+    #[test]
+    fn each_builder() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            struct Lorem {
+                #[builder(setter(each_builder = "server"))]
+                ipsum: Vec<net::Server<u16>>,
+                #[builder(setter(strip_option, each_builder = "backup"))]
+                dolor: Option<Vec<Server>>,
+                #[builder(setter(each_builder(name = "entry", builder = "EntryBuilder")))]
+                sit: HashMap<String, Entry>,
+            }
+        })
+        .unwrap();
+
+        let fields: Vec<_> = opts.fields().collect();
+        let builders: Vec<Path> = fields.iter().map(|f| f.each_builder().unwrap().1).collect();
+        assert_eq!(
+            builders,
+            vec![
+                parse_quote!(net::ServerBuilder<u16>),
+                parse_quote!(ServerBuilder),
+                parse_quote!(EntryBuilder),
+            ]
+        );
+        assert!(fields.iter().all(|f| f.stores_deferred_error()));
+        assert!(opts.as_build_method().deferred_error);
+    }
+
+    #[test]
+    fn each_builder_rejects_invalid_fields() {
+        let err = Options::from_derive_input(&parse_quote! {
+            struct Lorem {
+                #[builder(setter(each_builder = "item"))]
+                ipsum: [u8; 4],
+                #[builder(setter(each_builder = "server"), field(ty = "Vec<Server>"))]
+                dolor: Vec<Server>,
+            }
+        })
+        .unwrap_err();
+
+        let expected = if cfg!(any(feature = "alloc", feature = "lib_has_std")) {
+            2
+        } else {
+            4
+        };
+        assert_eq!(err.len(), expected);
+    }
+
+    #[test]
+    fn strip_bool() {
+        let opts: Options = Options::from_derive_input(&parse_quote! {
+            #[builder(setter(strip_bool))]
+            struct Lorem {
+                ipsum: bool,
+                dolor: u8,
+                #[builder(setter(strip_bool = false))]
+                sit: bool,
+            }
+        })
+        .unwrap();
+
+        let fields: Vec<_> = opts.fields().collect();
+        assert!(fields[0].setter_strip_bool());
+        assert!(matches!(
+            fields[0].default_value(),
+            Some(DefaultExpression::Trait)
+        ));
+        assert!(!fields[1].setter_strip_bool());
+        assert!(!fields[2].setter_strip_bool());
+    }
+
+    #[test]
+    fn lazy_rejects_invalid_fields() {
+        let err = Options::from_derive_input(&parse_quote! {
+            struct Lorem {
+                #[builder(setter(lazy), field(ty = "String"))]
+                ipsum: String,
+                #[builder(setter(lazy, convert = "at_build", from = "String"))]
+                dolor: u32,
+            }
+        })
+        .unwrap_err();
+
+        let expected = if cfg!(any(feature = "alloc", feature = "lib_has_std")) {
+            2
+        } else {
+            5
+        };
+        assert_eq!(err.len(), expected);
+    }
+
+    #[test]
+    fn maybe_rejects_setters_without_value() {
+        let err = Options::from_derive_input(&parse_quote! {
+            struct Lorem {
+                #[builder(setter(maybe, strip_bool))]
+                ipsum: bool,
+                #[builder(setter(maybe, defer_err))]
+                dolor: u32,
+                #[builder(setter(maybe))]
+                sit: u32,
+            }
+        })
+        .unwrap_err();
+
+        assert_eq!(err.len(), 2);
+    }
+
+    #[test]
+    fn mutators() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            #[builder(mutators(
+                pub fn preset_fast(&mut self) -> &mut Self {
+                    self.retries = Some(0);
+                    self
+                }
+
+                fn clear(&mut self) {}
+            ))]
+            struct Lorem {
+                retries: u8,
+            }
+        })
+        .unwrap();
+
+        let names: Vec<String> = opts
+            .mutators()
+            .iter()
+            .map(|f| f.sig.ident.to_string())
+            .collect();
+        assert_eq!(names, ["preset_fast", "clear"]);
+
+        let err = Options::from_derive_input(&parse_quote! {
+            #[builder(mutators(struct Ipsum;))]
+            struct Lorem {
+                retries: u8,
+            }
+        })
+        .unwrap_err();
+        assert_eq!(err.len(), 1);
+    }
+
+    #[test]
+    fn transform() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            struct Lorem {
+                #[builder(setter(transform = |w: u32, h: u32| Size { w, h }))]
+                ipsum: Size,
+                #[builder(setter(transform = "|x: u8| x.into()"))]
+                dolor: u32,
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            opts.usage_example().unwrap(),
+            "\n# Examples\n\n```ignore\nlet value = LoremBuilder::default()\n    \
+             .ipsum(/* w: u32 */, /* h: u32 */)\n    \
+             .dolor(/* x: u8 */)\n    \
+             .build()?;\n```\n"
+        );
+    }
+
+    #[test]
+    fn transform_rejects_invalid_fields() {
+        let err = Options::from_derive_input(&parse_quote! {
+            #[builder(constructor_macro)]
+            struct Lorem {
+                #[builder(setter(transform = |w, h: u32| Size { w, h }, into))]
+                ipsum: Size,
+            }
+        })
+        .unwrap_err();
+
+        assert_eq!(err.len(), 3);
+
+        let err = Options::from_derive_input(&parse_quote! {
+            struct Lorem {
+                #[builder(setter(transform = "Size::default"))]
+                ipsum: Size,
+            }
+        })
+        .unwrap_err();
+
+        assert_eq!(err.len(), 1);
+    }
+
+    #[test]
+    fn strip_bool_rejects_invalid_fields() {
+        let err = Options::from_derive_input(&parse_quote! {
+            #[builder(constructor_macro)]
+            struct Lorem {
+                #[builder(setter(strip_bool))]
+                ipsum: u8,
+                #[builder(setter(strip_bool))]
+                dolor: bool,
+            }
+        })
+        .unwrap_err();
+
+        assert_eq!(err.len(), 2);
+    }
+
+    #[test]
+    fn defer_err_rejects_invalid_fields() {
+        let err = Options::from_derive_input(&parse_quote! {
+            #[builder(const_fn, pattern = "owned", build_fn(error(validation_error = false)))]
+            struct Lorem {
+                #[builder(setter(defer_err, untuple))]
+                ipsum: (u16, u16),
+                #[builder(setter(each_builder = "server"))]
+                dolor: Vec<Server>,
+            }
+        })
+        .unwrap_err();
+
+        let expected = if cfg!(any(feature = "alloc", feature = "lib_has_std")) {
+            3
+        } else {
+            4
+        };
+        assert_eq!(err.len(), expected);
+    }
+
+    #[test]
+    fn setter_suffix() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            #[builder(setter(suffix = "mut"))]
+            struct Lorem {
+                ipsum: u32,
+                #[builder(setter(prefix = "set", suffix = "value"))]
+                dolor: u32,
+                #[builder(setter(name = "amet"))]
+                sit: u32,
+                r#type: u32,
+            }
+        })
+        .unwrap();
+
+        let names: Vec<String> = opts
+            .fields()
+            .map(|f| f.setter_ident().to_string())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["ipsum_mut", "set_dolor_value", "amet", "type_mut"]
+        );
+    }
+
+    #[test]
+    fn setter_suffix_must_be_part_of_ident() {
+        let err = Options::from_derive_input(&parse_quote! {
+            struct Lorem {
+                #[builder(setter(suffix = "not-an-ident"))]
+                ipsum: u32,
+            }
+        })
+        .unwrap_err();
+
+        assert_eq!(err.len(), 1);
+    }
+
+    #[test]
+    fn strict() {
+        let input: syn::DeriveInput = parse_quote! {
+            #[builder(setter(prefix = "with"))]
+            struct Lorem {
+                #[builder(setter(skip, into))]
+                ipsum: u32,
+                #[builder(setter(skip), default)]
+                dolor: u32,
+                #[builder(setter(name = "sit"))]
+                amet: u32,
+                #[builder(setter(skip), default = "42")]
+                consectetur: u32,
+                #[builder(setter(skip = false, into))]
+                adipiscing: u32,
+            }
+        };
+        assert!(Options::from_derive_input(&input).is_ok());
+
+        let mut strict = input;
+        strict.attrs.push(parse_quote!(#[builder(strict)]));
+        let err = Options::from_derive_input(&strict).unwrap_err();
+        assert_eq!(err.len(), 3);
+    }
+
+    #[test]
+    fn build_fn_visibility() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            #[builder(public, build_fn(vis = "pub(crate)", panic_variant))]
+            pub struct Lorem {
+                ipsum: u32,
+            }
+        })
+        .unwrap();
+
+        let pub_crate: syn::Visibility = parse_quote!(pub(crate));
+        assert_eq!(opts.builder_vis().as_ref(), &parse_quote!(pub));
+        assert_eq!(opts.as_build_method().visibility.as_ref(), &pub_crate);
+        assert_eq!(
+            opts.as_build_or_panic_method().visibility.as_ref(),
+            &pub_crate
+        );
+
+        let opts = Options::from_derive_input(&parse_quote! {
+            #[builder(private, build_fn(public))]
+            pub struct Lorem {
+                ipsum: u32,
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            opts.as_build_method().visibility.as_ref(),
+            &parse_quote!(pub)
+        );
+    }
+
+    #[test]
+    fn setter_visibility() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            #[builder(public, setter(vis = "pub(crate)"))]
+            pub struct Lorem {
+                ipsum: u32,
+                #[builder(public)]
+                dolor: u32,
+            }
+        })
+        .unwrap();
+
+        let pub_crate: syn::Visibility = parse_quote!(pub(crate));
+        let public: syn::Visibility = parse_quote!(pub);
+        assert_eq!(opts.builder_vis().as_ref(), &public);
+        assert_eq!(opts.as_build_method().visibility.as_ref(), &public);
+
+        let vis: Vec<syn::Visibility> =
+            opts.fields().map(|f| f.setter_vis().into_owned()).collect();
+        assert_eq!(vis, vec![pub_crate, public]);
+    }
+
+    #[test]
+    fn setter_value_param() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            struct Lorem<VALUE, const VALUE_: usize> {
+                ipsum: VALUE,
+                #[builder(setter(generic = "V"))]
+                dolor: u32,
+            }
+        })
+        .unwrap();
+
+        let names: Vec<String> = opts
+            .fields()
+            .map(|f| f.setter_value_param().to_string())
+            .collect();
+        assert_eq!(names, vec!["VALUE__", "V"]);
+    }
+
+    #[test]
+    fn wrap_requires_wrapper_type() {
+        let err = Options::from_derive_input(&parse_quote! {
+            struct Lorem {
+                #[builder(setter(wrap = "Arc"))]
+                ipsum: Box<dyn Fn()>,
+                #[builder(setter(wrap = "Rc", boxed))]
+                dolor: Rc<u32>,
+                #[builder(setter(wrap = "std::sync::Arc", strip_option))]
+                sit: Option<Arc<u32>>,
+            }
+        })
+        .unwrap_err();
+
+        assert_eq!(err.len(), 2);
+    }
+
+    #[test]
+    fn return_builder() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            #[builder(pattern = "owned", build_fn(return_builder))]
+            struct Lorem {
+                ipsum: u32,
+            }
+        })
+        .unwrap();
+
+        assert!(opts.as_build_method().return_builder);
+        assert!(opts.as_build_or_panic_method().return_builder);
+
+        let err = Options::from_derive_input(&parse_quote! {
+            #[builder(const_fn, build_fn(return_builder))]
+            struct Lorem {
+                ipsum: u32,
+            }
+        })
+        .unwrap_err();
+
+        // `const_fn` also requires `pattern = "owned"`.
+        assert_eq!(err.len(), 3);
     }
 
-    pub fn field_vis(&self) -> Cow<syn::Visibility> {
-        self.field
-            .field
-            .visibility
-            .to_explicit_visibility()
-            .or_else(
-                // Disabled fields become a PhantomData in the builder.  We make that field
-                // non-public, even if the rest of the builder is public, since this field is just
-                // there to make sure the struct's generics are properly handled.
-                || {
-                    if self.field_enabled() {
-                        None
-                    } else {
-                        Some(Cow::Owned(syn::Visibility::Inherited))
-                    }
-                },
-            )
-            .or_else(|| self.parent.field.to_explicit_visibility())
-            .unwrap_or(Cow::Owned(syn::Visibility::Inherited))
+    #[test]
+    fn on_set() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            #[builder(on_set = "telemetry::record")]
+            struct Lorem {
+                ipsum: u32,
+            }
+        })
+        .unwrap();
+
+        let on_set = opts.on_set.as_ref().unwrap();
+        assert_eq!(on_set.path, parse_quote!(telemetry::record));
+        assert!(!on_set.value);
+
+        let opts = Options::from_derive_input(&parse_quote! {
+            #[builder(on_set(path = "telemetry::record", value))]
+            struct Lorem {
+                ipsum: u32,
+            }
+        })
+        .unwrap();
+
+        assert!(opts.on_set.as_ref().unwrap().value);
+
+        let err = Options::from_derive_input(&parse_quote! {
+            #[builder(pattern = "owned", const_fn, on_set = "telemetry::record")]
+            struct Lorem {
+                ipsum: u32,
+            }
+        })
+        .unwrap_err();
+
+        assert_eq!(err.len(), 1);
     }
 
-    pub fn field_type(&'a self) -> BuilderFieldType<'a> {
-        if !self.field_enabled() {
-            BuilderFieldType::Phantom(&self.field.ty)
-        } else if let Some(custom_ty) = self.field.field.builder_type.as_ref() {
-            BuilderFieldType::Precise(custom_ty)
-        } else {
-            BuilderFieldType::Optional(&self.field.ty)
-        }
+    #[test]
+    fn split_impls() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            #[builder(split_impls, try_setter)]
+            #[builder_try_setters_impl_attr(doc(hidden))]
+            struct Lorem {
+                ipsum: u32,
+            }
+        })
+        .unwrap();
+
+        let builder = opts.as_builder();
+        let split_impls = builder.split_impls.as_ref().unwrap();
+        assert_eq!(
+            split_impls.try_setters_attrs,
+            &[parse_quote!(#[doc(hidden)])][..]
+        );
+        assert!(split_impls.setters_attrs.is_empty());
+
+        let err = Options::from_derive_input(&parse_quote! {
+            #[builder(overridable)]
+            #[builder_setters_impl_attr(doc(hidden))]
+            #[builder_build_impl_attr(doc(hidden))]
+            struct Lorem {
+                ipsum: u32,
+            }
+        })
+        .unwrap_err();
+
+        assert_eq!(err.len(), 2);
+
+        let err = Options::from_derive_input(&parse_quote! {
+            #[builder(split_impls, overridable)]
+            struct Lorem {
+                ipsum: u32,
+            }
+        })
+        .unwrap_err();
+
+        assert_eq!(err.len(), 1);
     }
 
-    pub fn conversion(&'a self) -> FieldConversion<'a> {
-        match (&self.field.field.builder_type, &self.field.field.build) {
-            (_, Some(block)) => FieldConversion::Block(block),
-            (Some(_), None) => FieldConversion::Move,
-            (None, None) => FieldConversion::OptionOrDefault,
+    #[test]
+    fn build_wrappers() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            #[builder(build_fn(name = "finish", wrappers(box, arc, rc)))]
+            struct Lorem {
+                ipsum: u32,
+            }
+        })
+        .unwrap();
+
+        let wrappers: Vec<_> = opts
+            .as_build_wrapped_methods()
+            .iter()
+            .map(|m| (m.build_fn.to_string(), m.wrapper))
+            .collect();
+        assert_eq!(
+            wrappers,
+            vec![
+                ("finish".to_string(), BuildWrapper::Box),
+                ("finish".to_string(), BuildWrapper::Arc),
+                ("finish".to_string(), BuildWrapper::Rc),
+            ]
+        );
+
+        let err = Options::from_derive_input(&parse_quote! {
+            #[builder(build_fn(wrappers(arc, cow, arc)))]
+            struct Lorem {
+                ipsum: u32,
+            }
+        })
+        .unwrap_err();
+
+        assert_eq!(err.len(), 2);
+    }
+
+    #[test]
+    fn build_subset() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            #[builder(build_fn(subset(name = "build_minimal", requires(ipsum))))]
+            struct Lorem {
+                ipsum: u32,
+                dolor: u32,
+            }
+        })
+        .unwrap();
+
+        let build_fns = opts.as_subset_build_methods();
+        assert_eq!(build_fns.len(), 1);
+        let (subset, build_fn) = &build_fns[0];
+        assert_eq!(build_fn.ident, "build_minimal");
+        assert_eq!(subset.required_fields_doc(), "`ipsum`");
+
+        let optional: Vec<bool> = opts
+            .fields()
+            .map(|f| f.as_subset_initializer(subset).optional)
+            .collect();
+        assert_eq!(optional, vec![false, true]);
+
+        let err = Options::from_derive_input(&parse_quote! {
+            #[builder(build_fn(subset(name = "build", requires(ipsum, amet))))]
+            struct Lorem {
+                ipsum: u32,
+            }
+        })
+        .unwrap_err();
+
+        assert_eq!(err.len(), 2);
+    }
+
+    #[test]
+    fn time_defaults() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            struct Lorem {
+                #[builder(default(now))]
+                ipsum: SystemTime,
+                #[builder(default(since_epoch = "millis"))]
+                dolor: u64,
+                #[builder(default)]
+                sit: u64,
+            }
+        })
+        .unwrap();
+
+        let defaults: Vec<String> = opts
+            .fields()
+            .map(|f| {
+                let default = f.default_value().unwrap().with_crate_root(&opts.crate_root);
+                quote!(#default).to_string()
+            })
+            .collect();
+        assert_eq!(
+            defaults,
+            vec![
+                quote!(::derive_builder::export::time::Now::now()).to_string(),
+                quote!(::derive_builder::export::time::since_epoch().as_millis() as _).to_string(),
+                quote!(::derive_builder::export::core::default::Default::default()).to_string(),
+            ]
+        );
+
+        for attr in [
+            quote!(#[builder(default(now, since_epoch = "secs"))]),
+            quote!(#[builder(default(since_epoch = "hours"))]),
+            quote!(#[builder(default(now), field(bare))]),
+        ] {
+            let input: syn::DeriveInput = parse_quote! {
+                struct Lorem {
+                    #attr
+                    ipsum: u64,
+                }
+            };
+            assert!(Options::from_derive_input(&input).is_err(), "{}", attr);
         }
     }
 
-    pub fn pattern(&self) -> BuilderPattern {
-        self.field.pattern.unwrap_or(self.parent.pattern)
+    #[test]
+    fn each_key_value_requires_map() {
+        Options::from_derive_input(&parse_quote! {
+            struct Lorem {
+                #[builder(setter(each(name = "entry", key_value)))]
+                ipsum: std::collections::BTreeMap<String, u32>,
+                #[builder(setter(each = "dolor"))]
+                dolor: HashMap<String, u32>,
+            }
+        })
+        .unwrap();
+
+        let err = Options::from_derive_input(&parse_quote! {
+            struct Lorem {
+                #[builder(setter(each(name = "item", key_value)))]
+                ipsum: Vec<(String, u32)>,
+            }
+        })
+        .unwrap_err();
+        assert_eq!(err.len(), 1);
     }
 
-    pub fn use_parent_default(&self) -> bool {
-        self.field.default.is_none() && self.parent.default.is_some()
+    #[test]
+    fn bare_fields() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            struct Lorem {
+                #[builder(field(bare), default)]
+                ipsum: String,
+            }
+        })
+        .unwrap();
+        let field = opts.fields().next().unwrap();
+        assert!(matches!(field.conversion(), FieldConversion::Clone));
+
+        let opts = Options::from_derive_input(&parse_quote! {
+            #[builder(pattern = "owned")]
+            struct Lorem {
+                #[builder(field(bare), default)]
+                ipsum: String,
+            }
+        })
+        .unwrap();
+        let field = opts.fields().next().unwrap();
+        assert!(matches!(field.conversion(), FieldConversion::Move));
+
+        let err = Options::from_derive_input(&parse_quote! {
+            struct Lorem {
+                #[builder(field(bare), default = "self.dolor.unwrap_or(1)")]
+                ipsum: u32,
+                dolor: u32,
+            }
+        })
+        .unwrap_err();
+        assert_eq!(err.len(), 1);
     }
 
-    pub fn deprecation_notes(&self) -> &DeprecationNotes {
-        &self.parent.deprecation_notes
+    #[test]
+    fn from_target() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            #[builder(from_target)]
+            struct Lorem {
+                ipsum: u32,
+                #[builder(field(bare), default)]
+                dolor: Vec<u32>,
+                #[builder(setter(skip))]
+                sit: u32,
+            }
+        })
+        .unwrap();
+
+        let fields: Vec<_> = opts
+            .as_from_target()
+            .fields
+            .iter()
+            .map(|f| (f.field_ident.to_string(), f.optional))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![("ipsum".to_string(), true), ("dolor".to_string(), false)]
+        );
+
+        let err = Options::from_derive_input(&parse_quote! {
+            #[builder(from_target)]
+            struct Lorem {
+                #[builder(field(ty = "String", build = "self.ipsum.parse().unwrap()"))]
+                ipsum: u32,
+                #[builder(setter(convert = "at_build", from = "String"))]
+                dolor: u32,
+            }
+        })
+        .unwrap_err();
+
+        assert_eq!(err.len(), 2);
     }
-}
 
-/// Converters to codegen structs
-impl<'a> FieldWithDefaults<'a> {
-    /// Returns a `Setter` according to the options.
-    pub fn as_setter(&'a self) -> Setter<'a> {
-        Setter {
-            crate_root: &self.parent.crate_root,
-            setter_enabled: self.setter_enabled(),
-            try_setter: self.try_setter(),
-            visibility: self.setter_vis(),
-            pattern: self.pattern(),
-            attrs: &self.field.attrs.setter,
-            ident: self.setter_ident(),
-            field_ident: self.field_ident(),
-            field_type: self.field_type(),
-            generic_into: self.setter_into(),
-            strip_option: self.setter_strip_option(),
-            deprecation_notes: self.deprecation_notes(),
-            each: self.field.setter.each.as_ref(),
-        }
+    #[test]
+    fn repr_packed() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            #[repr(C, packed(2))]
+            struct Lorem {
+                ipsum: u32,
+            }
+        })
+        .unwrap();
+
+        assert!(opts.as_build_method().packed);
+        assert!(opts.attrs.struct_attrs.is_empty());
+
+        let opts = Options::from_derive_input(&parse_quote! {
+            #[repr(C)]
+            struct Lorem {
+                ipsum: u32,
+            }
+        })
+        .unwrap();
+
+        assert!(!opts.as_build_method().packed);
     }
 
-    /// Returns an `Initializer` according to the options.
-    ///
-    /// # Panics
-    ///
-    /// if `default_expression` can not be parsed as `Block`.
-    pub fn as_initializer(&'a self) -> Initializer<'a> {
-        Initializer {
-            crate_root: &self.parent.crate_root,
-            field_enabled: self.field_enabled(),
-            field_ident: self.field_ident(),
-            builder_pattern: self.pattern(),
-            default_value: self.field.default.as_ref(),
-            use_default_struct: self.use_parent_default(),
-            conversion: self.conversion(),
-            custom_error_type_span: self.parent.build_fn.error.as_ref().and_then(|err_ty| {
-                match err_ty {
-                    BuildFnError::Existing(p) => Some(p.span()),
-                    _ => None,
-                }
-            }),
-        }
+    #[test]
+    fn post_build_forms() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            #[builder(build_fn(post_build = "Lorem::check"))]
+            struct Lorem {
+                ipsum: u32,
+            }
+        })
+        .unwrap();
+
+        let build_method = opts.as_build_method();
+        assert_eq!(
+            build_method.post_build_fn,
+            Some(&parse_quote!(Lorem::check))
+        );
+        assert_eq!(build_method.post_build_arg, PostBuildArg::Ref);
+
+        let opts = Options::from_derive_input(&parse_quote! {
+            #[builder(build_fn(post_build(path = "Lorem::fix", arg = "mut")))]
+            struct Lorem {
+                ipsum: u32,
+            }
+        })
+        .unwrap();
+
+        let build_method = opts.as_build_method();
+        assert_eq!(build_method.post_build_fn, Some(&parse_quote!(Lorem::fix)));
+        assert_eq!(build_method.post_build_arg, PostBuildArg::Mut);
+
+        let err = Options::from_derive_input(&parse_quote! {
+            #[builder(build_fn(post_build(path = "Lorem::finish", arg = "copy")))]
+            struct Lorem {
+                ipsum: u32,
+            }
+        })
+        .unwrap_err();
+
+        assert_eq!(err.len(), 1);
     }
 
-    pub fn as_builder_field(&'a self) -> BuilderField<'a> {
-        BuilderField {
-            crate_root: &self.parent.crate_root,
-            field_ident: self.field_ident(),
-            field_type: self.field_type(),
-            field_visibility: self.field_vis(),
-            attrs: &self.field.attrs.field,
-        }
+    #[test]
+    fn uses() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            #[builder(uses = "crate::units::{Meters, Seconds}, super::net::Host as NetHost")]
+            struct Lorem {
+                ipsum: u32,
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            opts.uses,
+            vec![
+                parse_quote!(crate::units::{Meters, Seconds}),
+                parse_quote!(super::net::Host as NetHost)
+            ]
+        );
+
+        let err = Options::from_derive_input(&parse_quote! {
+            #[builder(uses = "crate::units::")]
+            struct Lorem {
+                ipsum: u32,
+            }
+        })
+        .unwrap_err();
+
+        assert_eq!(err.len(), 1);
     }
-}
 
-pub struct FieldIter<'a>(&'a Options, IntoIter<&'a Field>);
+    #[test]
+    fn field_pattern_overrides() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            struct Lorem {
+                #[builder(pattern = "owned")]
+                ipsum: u32,
+                #[builder(pattern = "immutable")]
+                dolor: u32,
+            }
+        })
+        .unwrap();
+        let fields = opts.fields().collect::<Vec<_>>();
 
-impl<'a> Iterator for FieldIter<'a> {
-    type Item = FieldWithDefaults<'a>;
+        assert_eq!(fields[0].pattern(), BuilderPattern::Mutable);
+        assert_eq!(
+            fields[0].owned_setter_ident(),
+            Some(format_ident!("with_ipsum"))
+        );
+        assert_eq!(fields[1].pattern(), BuilderPattern::Immutable);
+        assert_eq!(fields[1].owned_setter_ident(), None);
 
-    fn next(&mut self) -> Option<Self::Item> {
-        self.1.next().map(|field| FieldWithDefaults {
-            parent: self.0,
-            field,
+        let err = Options::from_derive_input(&parse_quote! {
+            #[builder(pattern = "owned")]
+            struct Lorem {
+                #[builder(pattern = "mutable")]
+                ipsum: u32,
+            }
+        })
+        .unwrap_err();
+
+        assert_eq!(err.len(), 1);
+    }
+
+    #[test]
+    fn owned_setter_clashes_with_setter() {
+        let err = Options::from_derive_input(&parse_quote! {
+            #[builder(setter(prefix = "with"))]
+            struct Lorem {
+                #[builder(pattern = "owned")]
+                ipsum: u32,
+                dolor: u32,
+            }
+        })
+        .unwrap_err();
+
+        assert_eq!(err.len(), 1);
+
+        Options::from_derive_input(&parse_quote! {
+            #[builder(setter(prefix = "with"))]
+            struct Lorem {
+                #[builder(pattern = "owned", setter(prefix = "set"))]
+                ipsum: u32,
+            }
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn cow_owned_setter() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            #[builder(setter(borrowed, cow_owned))]
+            struct Lorem<'a> {
+                #[builder(setter(name = "text"))]
+                ipsum: Cow<'a, str>,
+                #[builder(setter(borrowed = false))]
+                dolor: Cow<'a, str>,
+                sit: u32,
+            }
+        })
+        .unwrap();
+        let fields = opts.fields().collect::<Vec<_>>();
+
+        assert!(fields[0].setter_cow_owned());
+        assert_eq!(
+            fields[0].cow_owned_setter_ident(),
+            format_ident!("text_owned")
+        );
+        assert!(!fields[1].setter_cow_owned());
+        assert!(!fields[2].setter_cow_owned());
+
+        let err = Options::from_derive_input(&parse_quote! {
+            struct Lorem<'a> {
+                #[builder(setter(borrowed, cow_owned))]
+                ipsum: Cow<'a, str>,
+                ipsum_owned: String,
+                #[builder(setter(cow_owned))]
+                dolor: Cow<'a, str>,
+            }
+        })
+        .unwrap_err();
+
+        assert_eq!(err.len(), 2);
+    }
+
+    #[test]
+    fn config_key_conflicts_with_derived_traits() {
+        let err = Options::from_derive_input(&parse_quote! {
+            #[builder(derive(Debug, ConfigKey, PartialEq, std::hash::Hash))]
+            struct Lorem {
+                ipsum: u32,
+            }
+        })
+        .unwrap_err();
+
+        assert_eq!(err.len(), 2);
+    }
+
+    #[test]
+    fn deserialize_requires_derive() {
+        let err = Options::from_derive_input(&parse_quote! {
+            #[builder(deserialize(deny_unknown_fields, extra = "Value"))]
+            struct Lorem {
+                extra: u32,
+            }
+        })
+        .unwrap_err();
+        assert_eq!(err.len(), if cfg!(feature = "lib_has_std") { 3 } else { 4 });
+    }
+
+    #[cfg(feature = "lib_has_std")]
+    #[test]
+    fn deserialize_extra_fields() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            #[builder(derive(serde::Deserialize), deserialize(extra = "Value"))]
+            struct Lorem {
+                ipsum: u32,
+            }
+        })
+        .unwrap();
+
+        assert!(opts.as_extra_fields().is_some());
+    }
+
+    #[test]
+    fn to_map_requires_json_feature() {
+        let result = Options::from_derive_input(&parse_quote! {
+            #[builder(to_map)]
+            struct Lorem {
+                ipsum: u32,
+            }
+        });
+
+        assert_eq!(result.is_ok(), cfg!(feature = "json"));
+    }
+
+    #[test]
+    fn deprecated_builder_allows_deprecated() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            #[builder(deprecated(note = "use IpsumBuilder"), allow(missing_docs))]
+            struct Lorem {
+                ipsum: u32,
+            }
         })
+        .unwrap();
+
+        let deprecated: Attribute = parse_quote!(#[deprecated(note = "use IpsumBuilder")]);
+        assert_eq!(opts.deprecated, Some(deprecated));
+        assert_eq!(opts.allow.to_strings(), vec!["missing_docs", "deprecated"]);
+    }
+
+    #[test]
+    fn initialization_order() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            struct Lorem {
+                #[builder(default = "dolor + 1", after = "dolor")]
+                ipsum: u32,
+                #[builder(default = "sit * 2", after = "sit")]
+                dolor: u32,
+                sit: u32,
+                amet: u32,
+            }
+        })
+        .unwrap();
+
+        let order: Vec<_> = opts
+            .initialization_order()
+            .iter()
+            .map(|f| f.field_ident().to_string())
+            .collect();
+        assert_eq!(order, vec!["sit", "dolor", "ipsum", "amet"]);
+        assert!(opts.binds_fields());
+    }
+
+    #[test]
+    fn after_rejects_invalid_fields() {
+        let err = Options::from_derive_input(&parse_quote! {
+            struct Lorem {
+                #[builder(default, after = "dolor")]
+                ipsum: u32,
+                #[builder(default, after = "ipsum")]
+                dolor: u32,
+                #[builder(default, after = "sit")]
+                sit: u32,
+                #[builder(default, after = "consectetur")]
+                amet: u32,
+                #[builder(after = "amet")]
+                adipiscing: u32,
+            }
+        })
+        .unwrap_err();
+
+        assert_eq!(err.len(), 4);
+    }
+
+    #[test]
+    fn usage_example_requires_default_and_build_fn() {
+        let opts = Options::from_derive_input(&parse_quote! {
+            #[builder(custom_constructor)]
+            struct Lorem {
+                ipsum: u32,
+            }
+        })
+        .unwrap();
+
+        assert_eq!(opts.usage_example(), None);
     }
 }
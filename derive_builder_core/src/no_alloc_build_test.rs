@@ -0,0 +1,144 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+
+/// Unit test which asserts that building from an empty builder doesn't allocate,
+/// implementing `quote::ToTokens`.
+///
+/// The allocations are counted by `count_allocations`, which requires the `CountingAllocator`
+/// to be the global allocator of the test binary.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::NoAllocBuildTest;
+/// # fn main() {
+/// #    let test = default_no_alloc_build_test!();
+/// #
+/// #    assert_eq!(quote!(#test).to_string(), quote!(
+/// #[cfg(test)]
+/// #[test]
+/// #[allow(non_snake_case)]
+/// fn FooBuilder_build_does_not_allocate() {
+///     let builder = FooBuilder::create_empty();
+///     let (result, allocations) = ::derive_builder::count_allocations(|| builder.build());
+///     assert!(result.is_ok(), "`FooBuilder::build` failed, so it can't be checked for allocations");
+///     assert_eq!(allocations, 0, "`FooBuilder::build` allocated");
+/// }
+/// #    ).to_string());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct NoAllocBuildTest<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Enables code generation for this test.
+    pub enabled: bool,
+    /// Name of the builder struct.
+    pub builder_ty: syn::Ident,
+    /// The inherent method which creates an empty builder.
+    pub create_empty: &'a syn::Ident,
+    /// Name of the build method.
+    pub build_fn: &'a syn::Ident,
+}
+
+impl<'a> ToTokens for NoAllocBuildTest<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if !self.enabled {
+            return;
+        }
+
+        let crate_root = self.crate_root;
+        let builder_ty = &self.builder_ty;
+        let create_empty = self.create_empty;
+        let build_fn = self.build_fn;
+        let test_ident = format_ident!("{}_{}_does_not_allocate", builder_ty, build_fn);
+        let failed = format!(
+            "`{}::{}` failed, so it can't be checked for allocations",
+            builder_ty, build_fn
+        );
+        let allocated = format!("`{}::{}` allocated", builder_ty, build_fn);
+
+        tokens.append_all(quote!(
+            #[cfg(test)]
+            #[test]
+            #[allow(non_snake_case)]
+            fn #test_ident() {
+                let builder = #builder_ty::#create_empty();
+                let (result, allocations) = #crate_root::count_allocations(|| builder.#build_fn());
+                assert!(result.is_ok(), #failed);
+                assert_eq!(allocations, 0, #allocated);
+            }
+        ));
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[cfg(test)] // This contains a Box::leak, so is suitable only for tests
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_no_alloc_build_test {
+    () => {
+        NoAllocBuildTest {
+            // Deliberately don't use the default value here - make sure
+            // that all test cases are passing crate_root through properly.
+            crate_root: &parse_quote!(::db),
+            enabled: true,
+            builder_ty: syn::Ident::new("FooBuilder", ::proc_macro2::Span::call_site()),
+            create_empty: Box::leak(Box::new(syn::Ident::new(
+                "create_empty",
+                ::proc_macro2::Span::call_site(),
+            ))),
+            build_fn: Box::leak(Box::new(syn::Ident::new(
+                "build",
+                ::proc_macro2::Span::call_site(),
+            ))),
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn simple() {
+        let test = default_no_alloc_build_test!();
+
+        assert_eq!(
+            quote!(#test).to_string(),
+            quote!(
+                #[cfg(test)]
+                #[test]
+                #[allow(non_snake_case)]
+                fn FooBuilder_build_does_not_allocate() {
+                    let builder = FooBuilder::create_empty();
+                    let (result, allocations) = ::db::count_allocations(|| builder.build());
+                    assert!(
+                        result.is_ok(),
+                        "`FooBuilder::build` failed, so it can't be checked for allocations"
+                    );
+                    assert_eq!(allocations, 0, "`FooBuilder::build` allocated");
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn disabled() {
+        let mut test = default_no_alloc_build_test!();
+        test.enabled = false;
+
+        assert_eq!(quote!(#test).to_string(), quote!().to_string());
+    }
+}
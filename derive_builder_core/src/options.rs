@@ -34,15 +34,122 @@ impl Default for BuilderPattern {
     }
 }
 
+/// Controls how the function of `build_fn(post_build)` receives the built struct.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, FromMeta)]
+pub enum PostBuildArg {
+    /// E.g. `fn check(foo: &Foo) -> Result<(), E>`.
+    Ref,
+    /// E.g. `fn fix(foo: &mut Foo) -> Result<(), E>`.
+    Mut,
+    /// E.g. `fn finish(foo: Foo) -> Result<Foo, E>`.
+    Owned,
+}
+
+/// Defaults to `Ref`.
+impl Default for PostBuildArg {
+    fn default() -> Self {
+        Self::Ref
+    }
+}
+
+/// Smart pointer which an additional build method of `build_fn(wrappers(...))` puts the built
+/// struct into.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum BuildWrapper {
+    /// E.g. `fn build_boxed(&self) -> Result<Box<Foo>, E>`.
+    Box,
+    /// E.g. `fn build_arc(&self) -> Result<Arc<Foo>, E>`.
+    Arc,
+    /// E.g. `fn build_rc(&self) -> Result<Rc<Foo>, E>`.
+    Rc,
+}
+
+impl BuildWrapper {
+    /// Suffix of the build method, which is appended to the name of the build method.
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            Self::Box => "boxed",
+            Self::Arc => "arc",
+            Self::Rc => "rc",
+        }
+    }
+}
+
+/// Options of the setter which extends a collection field by one item at a time.
 #[derive(Debug, Clone, FromMeta)]
+#[non_exhaustive]
 pub struct Each {
+    /// Name of the setter.
     pub name: syn::Ident,
+    /// Whether the setter accepts `impl Into<Item>`.
     #[darling(default)]
     pub into: bool,
+    /// Whether the setter of a `HashMap` or `BTreeMap` field takes the key and the value as two
+    /// parameters, instead of a `(key, value)` tuple.
+    #[darling(default)]
+    pub key_value: bool,
+    /// Additional `where` predicates of the setter, e.g. for generic collection types.
+    #[darling(default, with = parse_where_predicates)]
+    pub bound: Vec<syn::WherePredicate>,
 }
 
 impl From<syn::Ident> for Each {
     fn from(name: syn::Ident) -> Self {
-        Self { name, into: false }
+        Self {
+            name,
+            into: false,
+            key_value: false,
+            bound: Vec::new(),
+        }
     }
 }
+
+/// Options of the setter which builds an item of a collection field with the builder of the
+/// item type, and adds it to the collection.
+#[derive(Debug, Clone, FromMeta)]
+#[non_exhaustive]
+pub struct EachBuilder {
+    /// Name of the setter.
+    pub name: syn::Ident,
+    /// Builder of the items. Defaults to the item type with a `Builder` suffix.
+    #[darling(default)]
+    pub builder: Option<syn::Path>,
+}
+
+impl From<syn::Ident> for EachBuilder {
+    fn from(name: syn::Ident) -> Self {
+        Self {
+            name,
+            builder: None,
+        }
+    }
+}
+
+/// Options of the function which every setter calls after setting its field.
+#[derive(Debug, Clone, FromMeta)]
+#[non_exhaustive]
+pub struct OnSet {
+    /// Path of the function, which takes the name of the field as a `&'static str`.
+    pub path: syn::Path,
+    /// Whether the function also takes the builder field as a `&dyn Debug`.
+    #[darling(default)]
+    pub value: bool,
+}
+
+impl From<syn::Path> for OnSet {
+    fn from(path: syn::Path) -> Self {
+        Self { path, value: false }
+    }
+}
+
+/// Parse a string of comma-separated `where` predicates, e.g. `"C: Clone, T: Default"`.
+fn parse_where_predicates(meta: &syn::Meta) -> darling::Result<Vec<syn::WherePredicate>> {
+    use darling::FromMeta;
+    use syn::parse::Parser;
+
+    let predicates = String::from_meta(meta)?;
+    syn::punctuated::Punctuated::<syn::WherePredicate, syn::Token![,]>::parse_terminated
+        .parse_str(&predicates)
+        .map(|predicates| predicates.into_iter().collect())
+        .map_err(|e| darling::Error::custom(e).with_span(meta))
+}
@@ -0,0 +1,48 @@
+use proc_macro2::TokenStream;
+
+/// Render generated code as human-readable Rust source.
+///
+/// With the `pretty` feature enabled, the tokens are formatted with `prettyplease`,
+/// which produces output close to what `rustfmt` would emit. Without the feature,
+/// or if the tokens do not form a valid file (e.g. because they contain a
+/// `compile_error!` next to partial output), the plain single-line rendering
+/// of the token stream is returned instead.
+pub fn pretty_print(tokens: &TokenStream) -> String {
+    #[cfg(feature = "pretty")]
+    {
+        if let Ok(file) = syn::parse2::<syn::File>(tokens.clone()) {
+            return prettyplease::unparse(&file);
+        }
+    }
+
+    tokens.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_items() {
+        let tokens = quote!(
+            struct FooBuilder {
+                foo: Option<u32>,
+            }
+        );
+
+        let rendered = pretty_print(&tokens);
+
+        if cfg!(feature = "pretty") {
+            assert_eq!(rendered, "struct FooBuilder {\n    foo: Option<u32>,\n}\n");
+        } else {
+            assert_eq!(rendered, tokens.to_string());
+        }
+    }
+
+    #[test]
+    fn fall_back_on_invalid_file() {
+        let tokens = quote!(foo: Option<u32>,);
+
+        assert_eq!(pretty_print(&tokens), tokens.to_string());
+    }
+}
@@ -0,0 +1,305 @@
+use std::borrow::Cow;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, TokenStreamExt};
+
+use crate::{lazy_field_ident, BuilderPattern, DefaultExpression, DEFERRED_ERROR_FIELD};
+
+/// Method which unsets all fields of the builder, so it can be reused, implementing
+/// `quote::ToTokens`.
+///
+/// Collection fields are cleared instead, which keeps their allocations.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::{ResetMethod, ResetField, ResetKind};
+/// # fn main() {
+/// #    let reset_method = default_reset_method!();
+/// #
+/// #    assert_eq!(quote!(#reset_method).to_string(), quote!(
+/// #[doc = "..."]
+/// #[allow(unused_mut)]
+/// pub fn reset(&mut self) -> &mut Self {
+///     let mut new = self;
+///     if let ::derive_builder::export::core::option::Option::Some(ref mut value) = new.foo {
+///         value.clear();
+///     }
+///     new.bar = ::derive_builder::export::core::option::Option::None;
+///     new
+/// }
+/// #    ).to_string());
+/// # }
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ResetMethod<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Enables code generation for this method.
+    pub enabled: bool,
+    /// Name of this method.
+    pub ident: syn::Ident,
+    /// Visibility of the method, e.g. `syn::Visibility::Public`.
+    pub visibility: Cow<'a, syn::Visibility>,
+    /// How the method takes and returns `self` (e.g. mutably).
+    pub pattern: BuilderPattern,
+    /// Fields of the builder which are reset.
+    pub fields: Vec<ResetField<'a>>,
+    /// Also discard the error stored by a `setter(defer_err)`.
+    pub deferred_error: bool,
+}
+
+/// A field which is reset by a `ResetMethod`.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ResetField<'a> {
+    /// Name of the builder field.
+    pub field_ident: &'a syn::Ident,
+    /// How the field is reset.
+    pub kind: ResetKind,
+    /// Value of a field which is reset to its `Default`, instead of `Default::default()`.
+    pub default_value: Option<&'a DefaultExpression>,
+    /// Also discard the closure passed to the `_with` setter of `setter(lazy)`.
+    pub lazy: bool,
+}
+
+impl<'a> ResetMethod<'a> {
+    /// Create an enabled, public method named `ident`, which resets no fields yet.
+    pub fn new(crate_root: &'a syn::Path, ident: syn::Ident) -> Self {
+        ResetMethod {
+            crate_root,
+            enabled: true,
+            ident,
+            visibility: Cow::Owned(parse_quote!(pub)),
+            pattern: BuilderPattern::Mutable,
+            fields: vec![],
+            deferred_error: false,
+        }
+    }
+}
+
+impl<'a> ResetField<'a> {
+    /// Create a field which is reset according to `kind`.
+    pub fn new(field_ident: &'a syn::Ident, kind: ResetKind) -> Self {
+        ResetField {
+            field_ident,
+            kind,
+            default_value: None,
+            lazy: false,
+        }
+    }
+}
+
+/// How a builder field is reset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResetKind {
+    /// Set the field to `None`.
+    Unset,
+    /// Call `clear()` on the collection in the field, if it has been set.
+    Clear,
+    /// Set the field to its `Default`, for custom builder field types.
+    Default,
+}
+
+impl<'a> ToTokens for ResetMethod<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if !self.enabled {
+            return;
+        }
+
+        let crate_root = self.crate_root;
+        let vis = &self.visibility;
+        let ident = &self.ident;
+        let (self_param, return_ty, self_into_return_ty) = match self.pattern {
+            BuilderPattern::Owned => (quote!(self), quote!(Self), quote!(self)),
+            BuilderPattern::Mutable => (quote!(&mut self), quote!(&mut Self), quote!(self)),
+            BuilderPattern::Immutable => (
+                quote!(&self),
+                quote!(Self),
+                quote!(#crate_root::export::core::clone::Clone::clone(self)),
+            ),
+        };
+        let resets = self.fields.iter().map(|f| {
+            let field_ident = f.field_ident;
+            let reset_lazy = if f.lazy {
+                let lazy_field = lazy_field_ident(field_ident);
+                Some(quote!(new.#lazy_field = #crate_root::export::core::option::Option::None;))
+            } else {
+                None
+            };
+            let reset = match f.kind {
+                ResetKind::Unset => quote!(
+                    new.#field_ident = #crate_root::export::core::option::Option::None;
+                ),
+                ResetKind::Clear => quote!(
+                    if let #crate_root::export::core::option::Option::Some(ref mut value) = new.#field_ident {
+                        value.clear();
+                    }
+                ),
+                ResetKind::Default => match f.default_value {
+                    Some(expr) => {
+                        let expr = expr.with_crate_root(crate_root);
+                        quote!(new.#field_ident = #expr;)
+                    }
+                    None => quote!(
+                        new.#field_ident = #crate_root::export::core::default::Default::default();
+                    ),
+                },
+            };
+            quote!(#reset #reset_lazy)
+        });
+
+        let reset_deferred_error = if self.deferred_error {
+            let field = syn::Ident::new(DEFERRED_ERROR_FIELD, Span::call_site());
+            Some(quote!(new.#field = #crate_root::export::core::option::Option::None;))
+        } else {
+            None
+        };
+
+        tokens.append_all(quote!(
+            #[doc = "Unset all fields, so the builder can be reused.\n\nCollection fields which have been set are cleared instead, which keeps their allocations, so they remain set to empty collections."]
+            #[allow(unused_mut)]
+            #vis fn #ident(#self_param) -> #return_ty {
+                let mut new = #self_into_return_ty;
+                #(#resets)*
+                #reset_deferred_error
+                new
+            }
+        ));
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[cfg(test)] // This contains a Box::leak, so is suitable only for tests
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_reset_method {
+    () => {
+        ResetMethod {
+            // Deliberately don't use the default value here - make sure
+            // that all test cases are passing crate_root through properly.
+            crate_root: &parse_quote!(::db),
+            enabled: true,
+            ident: syn::Ident::new("reset", ::proc_macro2::Span::call_site()),
+            visibility: ::std::borrow::Cow::Owned(parse_quote!(pub)),
+            pattern: BuilderPattern::Mutable,
+            fields: vec![
+                ResetField {
+                    field_ident: Box::leak(Box::new(syn::Ident::new(
+                        "foo",
+                        ::proc_macro2::Span::call_site(),
+                    ))),
+                    kind: ResetKind::Clear,
+                    default_value: None,
+                    lazy: false,
+                },
+                ResetField {
+                    field_ident: Box::leak(Box::new(syn::Ident::new(
+                        "bar",
+                        ::proc_macro2::Span::call_site(),
+                    ))),
+                    kind: ResetKind::Unset,
+                    default_value: None,
+                    lazy: false,
+                },
+            ],
+            deferred_error: false,
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn mutable() {
+        let reset_method = default_reset_method!();
+        let option = quote!(::db::export::core::option::Option);
+
+        assert_eq!(
+            quote!(#reset_method).to_string(),
+            quote!(
+                #[doc = "Unset all fields, so the builder can be reused.\n\nCollection fields which have been set are cleared instead, which keeps their allocations, so they remain set to empty collections."]
+                #[allow(unused_mut)]
+                pub fn reset(&mut self) -> &mut Self {
+                    let mut new = self;
+                    if let #option::Some(ref mut value) = new.foo {
+                        value.clear();
+                    }
+                    new.bar = #option::None;
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn owned_with_custom_field() {
+        let mut reset_method = default_reset_method!();
+        reset_method.pattern = BuilderPattern::Owned;
+        reset_method.fields.truncate(1);
+        reset_method.fields[0].kind = ResetKind::Default;
+
+        assert_eq!(
+            quote!(#reset_method).to_string(),
+            quote!(
+                #[doc = "Unset all fields, so the builder can be reused.\n\nCollection fields which have been set are cleared instead, which keeps their allocations, so they remain set to empty collections."]
+                #[allow(unused_mut)]
+                pub fn reset(self) -> Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::default::Default::default();
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn default_value() {
+        let default = DefaultExpression::explicit::<syn::Expr>(parse_quote!(8080));
+        let mut reset_method = default_reset_method!();
+        reset_method.fields.truncate(1);
+        reset_method.fields[0].kind = ResetKind::Default;
+        reset_method.fields[0].default_value = Some(&default);
+
+        let tokens = quote!(#reset_method).to_string();
+        let expected = quote!(new.foo = { 8080 };).to_string();
+
+        assert!(tokens.contains(&expected), "{}", tokens);
+    }
+
+    #[test]
+    fn deferred_error() {
+        let mut reset_method = default_reset_method!();
+        reset_method.fields.clear();
+        reset_method.deferred_error = true;
+
+        let tokens = quote!(#reset_method).to_string();
+        let expected =
+            quote!(new.__deferred_error = ::db::export::core::option::Option::None;).to_string();
+
+        assert!(tokens.contains(&expected), "{}", tokens);
+    }
+
+    #[test]
+    fn disabled() {
+        let mut reset_method = default_reset_method!();
+        reset_method.enabled = false;
+
+        assert_eq!(quote!(#reset_method).to_string(), "");
+    }
+}
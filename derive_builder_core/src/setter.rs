@@ -3,8 +3,13 @@ use std::borrow::Cow;
 
 use proc_macro2::{Span, TokenStream};
 use quote::{ToTokens, TokenStreamExt};
+use syn::ext::IdentExt;
 
-use crate::{BuilderFieldType, BuilderPattern, DeprecationNotes, Each};
+use crate::deprecation_notes::NO_DEPRECATION_NOTES;
+use crate::{
+    lazy_field_ident, BuilderFieldType, BuilderPattern, DeprecationNotes, Each, OnSet,
+    DEFERRED_ERROR_FIELD,
+};
 
 /// Setter for the struct fields in the build method, implementing
 /// `quote::ToTokens`.
@@ -36,6 +41,7 @@ use crate::{BuilderFieldType, BuilderPattern, DeprecationNotes, Each};
 /// # }
 /// ```
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct Setter<'a> {
     /// Path to the root of the derive_builder crate.
     pub crate_root: &'a syn::Path,
@@ -51,33 +57,222 @@ pub struct Setter<'a> {
     pub attrs: &'a [syn::Attribute],
     /// Name of this setter fn.
     pub ident: syn::Ident,
+    /// Allow the `non_snake_case` lint, for setter names which follow another case convention.
+    pub allow_non_snake_case: bool,
     /// Name of the target field.
     pub field_ident: &'a syn::Ident,
+    /// Name of the field in errors, e.g. of the `from_iter_setter`.
+    pub field_name: String,
     /// Type of the builder field.
     ///
     /// The corresonding builder field will be `Option<field_type>`.
     pub field_type: BuilderFieldType<'a>,
     /// Make the setter generic over `Into<T>`, where `T` is the field type.
     pub generic_into: bool,
+    /// With `generic_into`, take an `impl Into<T>` instead of adding a `value_param` type
+    /// parameter.
+    pub into_impl: bool,
+    /// Name of the type parameter of generic setters, `VALUE` by default.
+    pub value_param: syn::Ident,
     /// Make the setter remove the Option wrapper from the setter, remove the need to call Some(...).
     /// when combined with into, the into is used on the content Type of the Option.
     pub strip_option: bool,
+    /// Make the setter take the contents of a `Box` and box it, e.g. for recursive types.
+    /// This is applied after `strip_option`, so `Option<Box<T>>` can be set from a `T`.
+    pub boxed: bool,
+    /// Make the setter of a `Cow<'a, T>` field take a `&'a T` and store it as `Cow::Borrowed`,
+    /// without allocating. This is applied after `strip_option`.
+    pub borrowed: bool,
+    /// With `borrowed`, emit a method with this name which takes the owned type of the `Cow`,
+    /// e.g. a `String` for a `Cow<'a, str>`, and stores it as `Cow::Owned`.
+    pub cow_owned_setter: Option<syn::Ident>,
+    /// Make the setter generic over the traits of a `Box<dyn Trait>` field and box the value,
+    /// e.g. to register callbacks. This is applied after `strip_option`.
+    pub closure: bool,
+    /// Make the setter take the type argument of a field of this wrapper type, e.g. `Arc`, and
+    /// wrap it with the wrapper's `new`. Like with `closure`, a setter of a wrapped trait object
+    /// is generic over its traits. This is applied after `strip_option`.
+    pub wrap: Option<&'a syn::Path>,
+    /// Make the setter of a tuple field take each element as a parameter with these names,
+    /// and pack them into the tuple. This is applied after `strip_option`.
+    pub untuple: Option<Vec<syn::Ident>>,
+    /// Make the setter take the parameters of this closure, and use the value it returns. This
+    /// is applied after `strip_option`.
+    pub transform: Option<&'a syn::ExprClosure>,
+    /// Make the setter of a `bool` field take no parameter and set the field to `true`.
+    pub strip_bool: bool,
+    /// Make the setter take a `Result` of its value, whose error converts into this build
+    /// error. The first error is stored in the builder, and the build method returns it.
+    pub defer_err: Option<syn::Path>,
+    /// Emit a method with this name which sets an `Option<Option<T>>` field to `Some(None)`.
+    /// The regular setter then takes a `T` and wraps it in `Some(Some(...))`.
+    pub null_setter: Option<syn::Ident>,
+    /// Emit a method with this name which takes an `Option` of the setter's value, so an
+    /// `Option` can be passed through without matching on it. It sets the field to the
+    /// `Option` stripped by `strip_option`, or otherwise only sets it if it is `Some`.
+    pub maybe_setter: Option<syn::Ident>,
+    /// Emit a method with this name which takes a `bool` next to the value and only sets the
+    /// field if it is `true`, so a chain of setter calls doesn't need to be broken up.
+    pub conditional_setter: Option<syn::Ident>,
+    /// Emit deprecated methods with these names which do the same as the setter, so it can be
+    /// renamed without breaking its callers.
+    pub aliases: &'a [syn::Ident],
+    /// Emit a method with this name which takes and returns the builder by value, next to the
+    /// setter of `pattern`, e.g. for a field-level `pattern = "owned"` on a mutable builder.
+    pub owned_setter: Option<syn::Ident>,
+    /// Emit a method with this name which sets an array field from an iterator, failing if the
+    /// number of items does not match the length of the array.
+    pub from_iter_setter: Option<syn::Ident>,
+    /// Emit a method with this name which sets one item of an array field, filling the others
+    /// with their defaults if the field has not been set.
+    pub set_nth_setter: Option<syn::Ident>,
+    /// Emit the setter as a `const fn`, unless it is generic over `Into<T>`.
+    pub const_fn: bool,
+    /// Mark the setters `#[must_use]`. By default, only the setters which take and return the
+    /// builder by value are marked, as dropping their return value drops the builder.
+    pub must_use: Option<bool>,
+    /// Emit the setter as a default method of a trait, which reaches the builder through this
+    /// required method of the trait.
+    pub trait_accessor: Option<syn::Ident>,
     /// Emit deprecation notes to the user.
     pub deprecation_notes: &'a DeprecationNotes,
     /// Emit extend method.
     pub each: Option<&'a Each>,
+    /// Create the collection extended by the `each` setter with `with_capacity` and this
+    /// capacity, instead of `Default::default`.
+    pub each_capacity: Option<&'a syn::Expr>,
+    /// Name of the setter which builds an item of the collection, and the builder of the items.
+    /// An error of the item's build method is stored for the build method of this builder.
+    pub each_builder: Option<(&'a syn::Ident, syn::Path)>,
+    /// Emit a method with this name which extends the collection with the items of an iterator.
+    pub extend_setter: Option<syn::Ident>,
+    /// Emit a method with this name which takes a closure, which the build method calls to
+    /// compute the value if the field isn't set.
+    pub lazy_setter: Option<syn::Ident>,
+    /// Function which each setter calls with the name of the field after setting it.
+    pub on_set: Option<&'a OnSet>,
+    /// `#[deprecated]` attribute of a deprecated builder, which is attached to all setter fns.
+    pub deprecated: Option<&'a syn::Attribute>,
+}
+
+impl<'a> Setter<'a> {
+    /// Create an enabled setter named `ident` for the builder field `field_ident`.
+    ///
+    /// All other options are set to the defaults of `#[builder(setter)]`.
+    pub fn new(
+        crate_root: &'a syn::Path,
+        ident: syn::Ident,
+        field_ident: &'a syn::Ident,
+        field_type: BuilderFieldType<'a>,
+    ) -> Self {
+        Setter {
+            crate_root,
+            setter_enabled: true,
+            try_setter: false,
+            visibility: Cow::Owned(parse_quote!(pub)),
+            pattern: BuilderPattern::Mutable,
+            attrs: &[],
+            ident,
+            allow_non_snake_case: false,
+            field_ident,
+            field_name: field_ident.unraw().to_string(),
+            field_type,
+            generic_into: false,
+            into_impl: false,
+            value_param: syn::Ident::new("VALUE", Span::call_site()),
+            strip_option: false,
+            boxed: false,
+            borrowed: false,
+            cow_owned_setter: None,
+            closure: false,
+            wrap: None,
+            untuple: None,
+            transform: None,
+            strip_bool: false,
+            defer_err: None,
+            from_iter_setter: None,
+            set_nth_setter: None,
+            null_setter: None,
+            maybe_setter: None,
+            conditional_setter: None,
+            aliases: &[],
+            owned_setter: None,
+            const_fn: false,
+            must_use: None,
+            trait_accessor: None,
+            deprecation_notes: &NO_DEPRECATION_NOTES,
+            each: None,
+            each_capacity: None,
+            each_builder: None,
+            extend_setter: None,
+            lazy_setter: None,
+            on_set: None,
+            deprecated: None,
+        }
+    }
 }
 
 impl<'a> ToTokens for Setter<'a> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.append_tokens(tokens, None);
+    }
+}
+
+impl<'a> Setter<'a> {
+    /// Returns the tokens of the setters, and separately those of the setter generated by
+    /// `try_setter`, so they can be put into different `impl` blocks.
+    pub fn split_tokens(&self) -> (TokenStream, TokenStream) {
+        let mut tokens = TokenStream::new();
+        let mut try_tokens = TokenStream::new();
+        self.append_tokens(&mut tokens, Some(&mut try_tokens));
+        (tokens, try_tokens)
+    }
+
+    /// Append the setters to `tokens`, except for the setter generated by `try_setter`, which is
+    /// appended to `try_tokens` if it is given.
+    fn append_tokens(&self, tokens: &mut TokenStream, try_tokens: Option<&mut TokenStream>) {
         if self.setter_enabled {
             let crate_root = self.crate_root;
             let pattern = self.pattern;
-            let vis = &self.visibility;
+            let vis = match self.trait_accessor {
+                Some(_) => None,
+                None => Some(&self.visibility),
+            };
             let field_ident = self.field_ident;
             let ident = &self.ident;
             let attrs = self.attrs;
+            let deprecated = self.deprecated;
+            let allow_non_snake_case = if self.allow_non_snake_case {
+                quote!(#[allow(non_snake_case)])
+            } else {
+                quote!()
+            };
             let deprecation_notes = self.deprecation_notes;
+            let value_param = &self.value_param;
+            let must_use = if self.must_use.unwrap_or(pattern != BuilderPattern::Mutable) {
+                quote!(#[must_use])
+            } else {
+                quote!()
+            };
+            let owned_must_use = if self.must_use.unwrap_or(true) {
+                quote!(#[must_use])
+            } else {
+                quote!()
+            };
+            let field_access = match self.trait_accessor {
+                Some(ref accessor) => quote!(new.#accessor().#field_ident),
+                None => quote!(new.#field_ident),
+            };
+
+            let on_set = self.on_set.map(|on_set| {
+                let path = &on_set.path;
+                let field_name = &self.field_name;
+                if on_set.value {
+                    quote!(#path(#field_name, &#field_access as &dyn #crate_root::export::core::fmt::Debug);)
+                } else {
+                    quote!(#path(#field_name);)
+                }
+            });
 
             let self_param: TokenStream;
             let return_ty: TokenStream;
@@ -103,122 +298,619 @@ impl<'a> ToTokens for Setter<'a> {
             };
 
             let ty_params: TokenStream;
-            let param_ty: TokenStream;
+            // Bound of the `value_param` type parameter, if the setter is generic.
+            let value_bound: Option<TokenStream>;
+            let params: TokenStream;
+            // Type of the single parameter, if the setter takes just `value`.
+            let param_ty: Option<TokenStream>;
             let mut into_value: TokenStream;
 
             let (field_type, builder_field_is_option) = self.field_type.setter_type_info();
 
-            let (ty, stripped_option) = {
-                if self.strip_option {
-                    match extract_type_from_option(field_type) {
-                        Some(ty) => (ty, true),
-                        None => (field_type, false),
+            let value_ty = self.value_type();
+            let ty = &*value_ty.ty;
+            let (stripped_option, stripped_box, stripped_cow) =
+                (value_ty.option, value_ty.boxed, value_ty.cow);
+
+            let closure_bounds = match ty {
+                syn::Type::TraitObject(trait_object) if self.closure || self.wrap.is_some() => {
+                    let bounds = &trait_object.bounds;
+                    if bounds
+                        .iter()
+                        .any(|bound| matches!(bound, syn::TypeParamBound::Lifetime(_)))
+                    {
+                        Some(quote!(#bounds))
+                    } else {
+                        Some(quote!(#bounds + 'static))
                     }
-                } else {
-                    (field_type, false)
                 }
+                _ => None,
+            };
+
+            let untuple = match (ty, &self.untuple) {
+                (syn::Type::Tuple(tuple), Some(names)) if tuple.elems.len() == names.len() => {
+                    Some((tuple.elems.iter(), names))
+                }
+                _ => None,
             };
 
-            if self.generic_into {
-                ty_params = quote!(<VALUE: #crate_root::export::core::convert::Into<#ty>>);
-                param_ty = quote!(VALUE);
+            if let Some(ref bounds) = closure_bounds {
+                ty_params = quote!(<#value_param: #bounds>);
+                value_bound = Some(quote!(#value_param: #bounds));
+                params = quote!(value: #value_param);
+                param_ty = Some(quote!(#value_param));
+                into_value = quote!(value);
+            } else if let Some(transform) = self.transform {
+                let inputs = &transform.inputs;
+                let body = &transform.body;
+                ty_params = quote!();
+                value_bound = None;
+                params = quote!(#inputs);
+                param_ty = None;
+                into_value = quote!(#body);
+            } else if let Some((elems, names)) = untuple {
+                ty_params = quote!();
+                value_bound = None;
+                params = quote!(#(#names: #elems),*);
+                param_ty = None;
+                into_value = quote!((#(#names,)*));
+            } else if self.strip_bool {
+                ty_params = quote!();
+                value_bound = None;
+                params = quote!();
+                param_ty = None;
+                into_value = quote!(true);
+            } else if self.generic_into && self.into_impl {
+                ty_params = quote!();
+                value_bound = None;
+                params = quote!(value: impl #crate_root::export::core::convert::Into<#ty>);
+                param_ty = Some(quote!(impl #crate_root::export::core::convert::Into<#ty>));
+                into_value = quote!(value.into());
+            } else if self.generic_into {
+                ty_params = quote!(<#value_param: #crate_root::export::core::convert::Into<#ty>>);
+                value_bound =
+                    Some(quote!(#value_param: #crate_root::export::core::convert::Into<#ty>));
+                params = quote!(value: #value_param);
+                param_ty = Some(quote!(#value_param));
                 into_value = quote!(value.into());
             } else {
                 ty_params = quote!();
-                param_ty = quote!(#ty);
+                value_bound = None;
+                params = quote!(value: #ty);
+                param_ty = Some(quote!(#ty));
                 into_value = quote!(value);
             }
+            if let (true, Some(wrap)) = (stripped_box, self.wrap) {
+                into_value = quote!(#wrap::new(#into_value));
+            } else if stripped_box {
+                into_value = wrap_expression_in_box(crate_root, into_value);
+            }
+            if stripped_cow {
+                into_value = wrap_expression_in_cow(crate_root, into_value);
+            }
+            // The value of the field's `Option`, which the `maybe_setter` maps its argument to.
+            let stripped_value = into_value.clone();
             // If both `stripped_option` and `builder_field_is_option`, the target field is `Option<field_type>`,
             // the builder field is `Option<Option<field_type>>`, and the setter takes `file_type`, so we must wrap it twice.
             if stripped_option {
                 into_value = wrap_expression_in_some(crate_root, into_value);
             }
+            if stripped_option && self.null_setter.is_some() {
+                into_value = wrap_expression_in_some(crate_root, into_value);
+            }
             if builder_field_is_option {
                 into_value = wrap_expression_in_some(crate_root, into_value);
             }
 
+            // With `defer_err`, the setter takes a `Result` and keeps the first error for the
+            // build method instead of setting the field.
+            let (setter_ty_params, setter_params, set_value) = match (&self.defer_err, &param_ty) {
+                (Some(error_ty), Some(param_ty)) => {
+                    let error_field = syn::Ident::new(DEFERRED_ERROR_FIELD, Span::call_site());
+                    let error_access = match self.trait_accessor {
+                        Some(ref accessor) => quote!(new.#accessor().#error_field),
+                        None => quote!(new.#error_field),
+                    };
+                    let bounds = value_bound.iter().cloned().chain(Some(
+                        quote!(ERROR: #crate_root::export::core::convert::Into<#error_ty>),
+                    ));
+                    let result = quote!(#crate_root::export::core::result::Result);
+                    (
+                        quote!(<#(#bounds),*>),
+                        quote!(value: #result<#param_ty, ERROR>),
+                        quote!(
+                            match value {
+                                #result::Ok(value) => {
+                                    #field_access = #into_value;
+                                }
+                                #result::Err(error) => {
+                                    if #error_access.is_none() {
+                                        #error_access = #crate_root::export::core::option::Option::Some(
+                                            #crate_root::export::core::convert::Into::into(error)
+                                        );
+                                    }
+                                }
+                            }
+                        ),
+                    )
+                }
+                _ => (
+                    ty_params.clone(),
+                    params.clone(),
+                    quote!(#field_access = #into_value;),
+                ),
+            };
+
+            let constness = if self.const_fn
+                && !self.generic_into
+                && self.defer_err.is_none()
+                && self.transform.is_none()
+                && self.wrap.is_none()
+                && closure_bounds.is_none()
+            {
+                quote!(const)
+            } else {
+                quote!()
+            };
+
             tokens.append_all(quote!(
                 #(#attrs)*
+                #deprecated
+                #must_use
+                #allow_non_snake_case
                 #[allow(unused_mut)]
-                #vis fn #ident #ty_params (#self_param, value: #param_ty)
+                #vis #constness fn #ident #setter_ty_params (#self_param, #setter_params)
                     -> #return_ty
                 {
                     #deprecation_notes
                     let mut new = #self_into_return_ty;
-                    new.#field_ident = #into_value;
+                    #set_value
+                    #on_set
                     new
                 }
             ));
 
-            if self.try_setter {
+            if let Some(ref owned_ident) = self.owned_setter {
+                tokens.append_all(quote!(
+                    #(#attrs)*
+                    #deprecated
+                    #owned_must_use
+                    #allow_non_snake_case
+                    #[allow(unused_mut)]
+                    #vis fn #owned_ident #setter_ty_params (self, #setter_params) -> Self {
+                        #deprecation_notes
+                        let mut new = self;
+                        #set_value
+                        #on_set
+                        new
+                    }
+                ));
+            }
+
+            if let (Some(cow_owned_ident), true, false, syn::Type::Reference(reference)) = (
+                &self.cow_owned_setter,
+                stripped_cow,
+                self.defer_err.is_some(),
+                ty,
+            ) {
+                let borrowed_ty = &reference.elem;
+                let mut owned_value = quote!(#crate_root::export::core::borrow::Cow::Owned(value));
+                if stripped_option {
+                    owned_value = wrap_expression_in_some(crate_root, owned_value);
+                }
+                if stripped_option && self.null_setter.is_some() {
+                    owned_value = wrap_expression_in_some(crate_root, owned_value);
+                }
+                if builder_field_is_option {
+                    owned_value = wrap_expression_in_some(crate_root, owned_value);
+                }
+
+                tokens.append_all(quote!(
+                    #(#attrs)*
+                    #deprecated
+                    #must_use
+                    #allow_non_snake_case
+                    #[allow(unused_mut)]
+                    #vis fn #cow_owned_ident(
+                        #self_param,
+                        value: <#borrowed_ty as #crate_root::export::core::borrow::ToOwned>::Owned
+                    ) -> #return_ty {
+                        #deprecation_notes
+                        let mut new = #self_into_return_ty;
+                        #field_access = #owned_value;
+                        #on_set
+                        new
+                    }
+                ));
+            }
+
+            for alias in self.aliases {
+                let note = format!("use `{}` instead", ident);
+                tokens.append_all(quote!(
+                    #(#attrs)*
+                    #[deprecated(note = #note)]
+                    #must_use
+                    #allow_non_snake_case
+                    #[allow(unused_mut)]
+                    #vis fn #alias #setter_ty_params (#self_param, #setter_params) -> #return_ty {
+                        #deprecation_notes
+                        let mut new = #self_into_return_ty;
+                        #set_value
+                        #on_set
+                        new
+                    }
+                ));
+            }
+
+            if let Some(ref null_ident) = self.null_setter {
+                let null_constness = if self.const_fn {
+                    quote!(const)
+                } else {
+                    quote!()
+                };
+                let mut null_value = wrap_expression_in_some(
+                    crate_root,
+                    quote!(#crate_root::export::core::option::Option::None),
+                );
+                if builder_field_is_option {
+                    null_value = wrap_expression_in_some(crate_root, null_value);
+                }
+
+                tokens.append_all(quote!(
+                    #(#attrs)*
+                    #deprecated
+                    #must_use
+                    #allow_non_snake_case
+                    #[allow(unused_mut)]
+                    #vis #null_constness fn #null_ident(#self_param) -> #return_ty {
+                        #deprecation_notes
+                        let mut new = #self_into_return_ty;
+                        #field_access = #null_value;
+                        #on_set
+                        new
+                    }
+                ));
+            }
+
+            // With `strip_option`, the `maybe_setter` stores the `Option` as is, otherwise it only
+            // sets the field if it is given `Some` value.
+            if let (Some(maybe_ident), Some(param_ty), true) = (
+                &self.maybe_setter,
+                &param_ty,
+                stripped_option || self.defer_err.is_none(),
+            ) {
+                let option = quote!(#crate_root::export::core::option::Option);
+                let set_maybe = if stripped_option {
+                    let mut maybe_value = quote!(value.map(|value| #stripped_value));
+                    if builder_field_is_option {
+                        maybe_value = wrap_expression_in_some(crate_root, maybe_value);
+                    }
+                    quote!(
+                        #field_access = #maybe_value;
+                        #on_set
+                    )
+                } else {
+                    quote!(
+                        if let #option::Some(value) = value {
+                            #field_access = #into_value;
+                            #on_set
+                        }
+                    )
+                };
+
+                tokens.append_all(quote!(
+                    #(#attrs)*
+                    #deprecated
+                    #must_use
+                    #allow_non_snake_case
+                    #[allow(unused_mut)]
+                    #vis fn #maybe_ident #ty_params (#self_param, value: #option<#param_ty>)
+                        -> #return_ty
+                    {
+                        #deprecation_notes
+                        let mut new = #self_into_return_ty;
+                        #set_maybe
+                        new
+                    }
+                ));
+            }
+
+            if let (Some(conditional_ident), Some(param_ty), false) = (
+                &self.conditional_setter,
+                &param_ty,
+                self.defer_err.is_some(),
+            ) {
+                tokens.append_all(quote!(
+                    #(#attrs)*
+                    #deprecated
+                    #must_use
+                    #allow_non_snake_case
+                    #[allow(unused_mut)]
+                    #vis fn #conditional_ident #ty_params (#self_param, condition: bool, value: #param_ty)
+                        -> #return_ty
+                    {
+                        #deprecation_notes
+                        let mut new = #self_into_return_ty;
+                        if condition {
+                            #field_access = #into_value;
+                            #on_set
+                        }
+                        new
+                    }
+                ));
+            }
+
+            if self.try_setter && closure_bounds.is_none() {
                 let try_ty_params =
-                    quote!(<VALUE: #crate_root::export::core::convert::TryInto<#ty>>);
+                    quote!(<#value_param: #crate_root::export::core::convert::TryInto<#ty>>);
                 let try_ident = syn::Ident::new(&format!("try_{}", ident), Span::call_site());
 
                 let mut converted = quote! {converted};
+                if let (true, Some(wrap)) = (stripped_box, self.wrap) {
+                    converted = quote!(#wrap::new(#converted));
+                } else if stripped_box {
+                    converted = wrap_expression_in_box(crate_root, converted);
+                }
+                if stripped_cow {
+                    converted = wrap_expression_in_cow(crate_root, converted);
+                }
                 if builder_field_is_option {
                     converted = wrap_expression_in_some(crate_root, converted);
                 }
                 if stripped_option {
                     converted = wrap_expression_in_some(crate_root, converted);
                 }
+                if stripped_option && self.null_setter.is_some() {
+                    converted = wrap_expression_in_some(crate_root, converted);
+                }
 
-                tokens.append_all(quote!(
+                let try_tokens = match try_tokens {
+                    Some(try_tokens) => try_tokens,
+                    None => &mut *tokens,
+                };
+                try_tokens.append_all(quote!(
                     #(#attrs)*
-                    #vis fn #try_ident #try_ty_params (#self_param, value: VALUE)
-                        -> #crate_root::export::core::result::Result<#return_ty, VALUE::Error>
+                    #deprecated
+                    #allow_non_snake_case
+                    #vis fn #try_ident #try_ty_params (#self_param, value: #value_param)
+                        -> #crate_root::export::core::result::Result<#return_ty, #value_param::Error>
                     {
                         let converted : #ty = value.try_into()?;
                         let mut new = #self_into_return_ty;
-                        new.#field_ident = #converted;
+                        #field_access = #converted;
+                        #on_set
                         Ok(new)
                     }
                 ));
             }
 
+            if let syn::Type::Array(array) = field_type {
+                let item_ty = &array.elem;
+                let len = &array.len;
+                let field_name = &self.field_name;
+                let result = quote!(#crate_root::export::core::result::Result);
+
+                if let Some(ref from_iter_ident) = self.from_iter_setter {
+                    tokens.append_all(quote!(
+                        #(#attrs)*
+                        #deprecated
+                        #allow_non_snake_case
+                        #[allow(unused_mut)]
+                        #vis fn #from_iter_ident<ITER: #crate_root::export::core::iter::IntoIterator<Item = #item_ty>>(
+                            #self_param,
+                            iter: ITER
+                        ) -> #result<#return_ty, #crate_root::ArrayLengthError>
+                        {
+                            #deprecation_notes
+                            let items: #crate_root::export::core::vec::Vec<#item_ty> =
+                                #crate_root::export::core::iter::Iterator::collect(
+                                    #crate_root::export::core::iter::IntoIterator::into_iter(iter)
+                                );
+                            let actual = items.len();
+                            let array: #field_type = match #crate_root::export::core::convert::TryFrom::try_from(items) {
+                                #result::Ok(array) => array,
+                                #result::Err(_) => return #result::Err(
+                                    #crate_root::ArrayLengthError::new(#field_name, #len, actual)
+                                ),
+                            };
+                            let mut new = #self_into_return_ty;
+                            #field_access = #crate_root::export::core::option::Option::Some(array);
+                            #on_set
+                            #result::Ok(new)
+                        }
+                    ));
+                }
+
+                if let Some(ref set_nth_ident) = self.set_nth_setter {
+                    tokens.append_all(quote!(
+                        #(#attrs)*
+                        #deprecated
+                        #must_use
+                        #allow_non_snake_case
+                        #[allow(unused_mut)]
+                        #vis fn #set_nth_ident(#self_param, index: usize, value: #item_ty) -> #return_ty {
+                            #deprecation_notes
+                            let mut new = #self_into_return_ty;
+                            #field_access.get_or_insert_with(|| {
+                                [(); #len].map(|_| #crate_root::export::core::default::Default::default())
+                            })[index] = value;
+                            #on_set
+                            new
+                        }
+                    ));
+                }
+            }
+
+            let new_collection = match self.each_capacity {
+                Some(capacity) => quote!(|| <#ty>::with_capacity(#capacity)),
+                None => quote!(#crate_root::export::core::default::Default::default),
+            };
+
+            // Access the collection to extend, initialising with default value if necessary.
+            let get_initialized_collection = if stripped_option {
+                // Outer (builder) Option -> Inner (field) Option -> collection.
+                quote!(get_or_insert_with(|| Some(
+                    #crate_root::export::core::default::Default::default()
+                ))
+                .get_or_insert_with(#new_collection))
+            } else {
+                // Outer (builder) Option -> collection.
+                quote!(get_or_insert_with(#new_collection))
+            };
+
             if let Some(each) = self.each {
                 let ident_each = &each.name;
-
-                // Access the collection to extend, initialising with default value if necessary.
-                let get_initialized_collection = if stripped_option {
-                    // Outer (builder) Option -> Inner (field) Option -> collection.
-                    quote!(get_or_insert_with(|| Some(
-                        #crate_root::export::core::default::Default::default()
-                    ))
-                    .get_or_insert_with(#crate_root::export::core::default::Default::default))
-                } else {
-                    // Outer (builder) Option -> collection.
-                    quote!(get_or_insert_with(
-                        #crate_root::export::core::default::Default::default
-                    ))
-                };
+                let bound = &each.bound;
 
                 let ty_params: TokenStream;
-                let param_ty: TokenStream;
+                let params: TokenStream;
                 let into_item: TokenStream;
+                let item_ty: TokenStream;
+
+                let key_value = if each.key_value {
+                    extract_key_value_types_from_map(ty)
+                } else {
+                    None
+                };
 
-                if each.into {
-                    ty_params = quote!(<VALUE, FROM_VALUE: #crate_root::export::core::convert::Into<VALUE>>);
-                    param_ty = quote!(FROM_VALUE);
+                if let Some((key_ty, value_ty)) = key_value {
+                    // Maps are extended with `(key, value)` pairs, which the setter takes as two
+                    // parameters with `key_value`.
+                    item_ty = quote!((#key_ty, #value_ty));
+                    if each.into {
+                        ty_params = quote!(<
+                            KEY: #crate_root::export::core::convert::Into<#key_ty>,
+                            #value_param: #crate_root::export::core::convert::Into<#value_ty>
+                        >);
+                        params = quote!(key: KEY, value: #value_param);
+                        into_item = quote!((
+                            #crate_root::export::core::convert::Into::into(key),
+                            #crate_root::export::core::convert::Into::into(value)
+                        ));
+                    } else {
+                        ty_params = quote!();
+                        params = quote!(key: #key_ty, value: #value_ty);
+                        into_item = quote!((key, value));
+                    }
+                } else if each.into {
+                    ty_params = quote!(<#value_param, FROM_VALUE: #crate_root::export::core::convert::Into<#value_param>>);
+                    params = quote!(item: FROM_VALUE);
                     into_item = quote!(#crate_root::export::core::convert::Into::into(item));
+                    item_ty = quote!(#value_param);
                 } else {
-                    ty_params = quote!(<VALUE>);
-                    param_ty = quote!(VALUE);
+                    ty_params = quote!(<#value_param>);
+                    params = quote!(item: #value_param);
                     into_item = quote!(item);
+                    item_ty = quote!(#value_param);
                 }
 
                 tokens.append_all(quote!(
                     #(#attrs)*
+                    #deprecated
+                    #must_use
                     #[allow(unused_mut)]
-                    #vis fn #ident_each #ty_params(#self_param, item: #param_ty) -> #return_ty
+                    #vis fn #ident_each #ty_params(#self_param, #params) -> #return_ty
                     where
-                        #ty: #crate_root::export::core::default::Default + #crate_root::export::core::iter::Extend<VALUE>,
+                        #ty: #crate_root::export::core::default::Default + #crate_root::export::core::iter::Extend<#item_ty>,
+                        #(#bound,)*
                     {
                         #deprecation_notes
                         let mut new = #self_into_return_ty;
-                        new.#field_ident
+                        #field_access
                             .#get_initialized_collection
                             .extend(#crate_root::export::core::option::Option::Some(#into_item));
+                        #on_set
+                        new
+                    }
+                ));
+            }
+
+            if let Some(ref lazy_ident) = self.lazy_setter {
+                let lazy_field = lazy_field_ident(field_ident);
+                let lazy_access = match self.trait_accessor {
+                    Some(ref accessor) => quote!(new.#accessor().#lazy_field),
+                    None => quote!(new.#lazy_field),
+                };
+                let core = quote!(#crate_root::export::core);
+
+                tokens.append_all(quote!(
+                    #(#attrs)*
+                    #deprecated
+                    #must_use
+                    #allow_non_snake_case
+                    #[allow(unused_mut)]
+                    #vis fn #lazy_ident<FN>(#self_param, f: FN) -> #return_ty
+                    where
+                        FN: #core::ops::Fn() -> #field_type + #core::marker::Send + #core::marker::Sync + 'static,
+                    {
+                        #deprecation_notes
+                        let mut new = #self_into_return_ty;
+                        #field_access = #core::option::Option::None;
+                        #lazy_access = #core::option::Option::Some(#crate_root::LazyValue::new(f));
+                        #on_set
+                        new
+                    }
+                ));
+            }
+
+            if let Some(ref extend_ident) = self.extend_setter {
+                tokens.append_all(quote!(
+                    #(#attrs)*
+                    #deprecated
+                    #must_use
+                    #[allow(unused_mut)]
+                    #vis fn #extend_ident<ITER: #crate_root::export::core::iter::IntoIterator>(#self_param, iter: ITER) -> #return_ty
+                    where
+                        #ty: #crate_root::export::core::default::Default + #crate_root::export::core::iter::Extend<ITER::Item>,
+                    {
+                        #deprecation_notes
+                        let mut new = #self_into_return_ty;
+                        #field_access
+                            .#get_initialized_collection
+                            .extend(iter);
+                        #on_set
+                        new
+                    }
+                ));
+            }
+
+            if let Some((ref ident_each, ref item_builder)) = self.each_builder {
+                let core = quote!(#crate_root::export::core);
+                let error_field = syn::Ident::new(DEFERRED_ERROR_FIELD, Span::call_site());
+                let error_access = match self.trait_accessor {
+                    Some(ref accessor) => quote!(new.#accessor().#error_field),
+                    None => quote!(new.#error_field),
+                };
+
+                tokens.append_all(quote!(
+                    #(#attrs)*
+                    #deprecated
+                    #must_use
+                    #[allow(unused_mut)]
+                    #vis fn #ident_each<FN: #core::ops::FnOnce(&mut #item_builder)>(#self_param, f: FN)
+                        -> #return_ty
+                    {
+                        #deprecation_notes
+                        let mut builder = <#item_builder as #core::default::Default>::default();
+                        f(&mut builder);
+                        let mut new = #self_into_return_ty;
+                        match builder.build() {
+                            #core::result::Result::Ok(item) => {
+                                #field_access
+                                    .#get_initialized_collection
+                                    .extend(#core::option::Option::Some(item));
+                            }
+                            #core::result::Result::Err(error) => {
+                                if #error_access.is_none() {
+                                    #error_access = #core::option::Option::Some(
+                                        #core::convert::Into::into(
+                                            #core::string::ToString::to_string(&error)
+                                        )
+                                    );
+                                }
+                            }
+                        }
+                        #on_set
                         new
                     }
                 ));
@@ -227,62 +919,326 @@ impl<'a> ToTokens for Setter<'a> {
     }
 }
 
-/// Returns expression wrapping `bare_value` in `Some`
-fn wrap_expression_in_some(crate_root: &syn::Path, bare_value: impl ToTokens) -> TokenStream {
-    quote!( #crate_root::export::core::option::Option::Some(#bare_value) )
-}
+impl<'a> Setter<'a> {
+    /// Returns the type of the value which the setter takes, after `strip_option`, `boxed` and
+    /// `borrowed` are applied.
+    fn value_type(&self) -> ValueType<'_> {
+        let (field_type, _) = self.field_type.setter_type_info();
 
-// adapted from https://stackoverflow.com/a/55277337/469066
-// Note that since syn is a parser, it works with tokens.
-// We cannot know for sure that this is an Option.
-// The user could, for example, `type MaybeString = std::option::Option<String>`
-// We cannot handle those arbitrary names.
-fn extract_type_from_option(ty: &syn::Type) -> Option<&syn::Type> {
-    use syn::punctuated::Pair;
-    use syn::token::PathSep;
-    use syn::{GenericArgument, Path, PathArguments, PathSegment};
+        let (ty, option) = {
+            if self.null_setter.is_some() {
+                match extract_type_from_option(field_type).and_then(extract_type_from_option) {
+                    Some(ty) => (ty, true),
+                    None => (field_type, false),
+                }
+            } else if self.strip_option {
+                match extract_type_from_option(field_type) {
+                    Some(ty) => (ty, true),
+                    None => (field_type, false),
+                }
+            } else {
+                (field_type, false)
+            }
+        };
 
-    fn extract_type_path(ty: &syn::Type) -> Option<&Path> {
-        match *ty {
-            syn::Type::Path(ref typepath) if typepath.qself.is_none() => Some(&typepath.path),
-            _ => None,
+        let (ty, boxed) = {
+            if let Some(wrap) = self.wrap {
+                match extract_type_from_named_wrapper(ty, wrap) {
+                    Some(inner) => (inner, true),
+                    None => (ty, false),
+                }
+            } else if self.boxed || self.closure {
+                match extract_type_from_box(ty) {
+                    Some(inner) => (inner, true),
+                    None => (ty, false),
+                }
+            } else {
+                (ty, false)
+            }
+        };
+
+        let borrowed_ty: Option<syn::Type> = if self.borrowed {
+            extract_borrowed_type_from_cow(ty)
+                .map(|(lifetime, inner)| parse_quote!(&#lifetime #inner))
+        } else {
+            None
+        };
+
+        ValueType {
+            cow: borrowed_ty.is_some(),
+            ty: match borrowed_ty {
+                Some(ty) => Cow::Owned(ty),
+                None => Cow::Borrowed(ty),
+            },
+            option,
+            boxed,
         }
     }
 
-    // TODO store (with lazy static) precomputed parsing of Option when support of rust 1.18 will be removed (incompatible with lazy_static)
-    // TODO maybe optimization, reverse the order of segments
-    fn extract_option_segment(path: &Path) -> Option<Pair<&PathSegment, &PathSep>> {
-        let idents_of_path = path.segments.iter().fold(String::new(), |mut acc, v| {
-            acc.push_str(&v.ident.to_string());
-            acc.push('|');
-            acc
-        });
-        vec!["Option|", "std|option|Option|", "core|option|Option|"]
-            .into_iter()
-            .find(|s| idents_of_path == *s)
-            .and_then(|_| path.segments.last().map(Pair::End))
-    }
+    /// Returns a call of the setter on `b: &mut #builder_ty` with a value of the type it takes,
+    /// which fails to compile if the signature of the setter changes.
+    ///
+    /// Setters which aren't `pub`, and setters of trait objects, whose type parameter can't be
+    /// named, have none.
+    pub fn api_assertion(&self, builder_ty: &TokenStream) -> Option<TokenStream> {
+        if !self.setter_enabled
+            || self.closure
+            || !matches!(*self.visibility, syn::Visibility::Public(_))
+        {
+            return None;
+        }
+        if self.wrap.is_some() && matches!(*self.value_type().ty, syn::Type::TraitObject(_)) {
+            return None;
+        }
 
-    extract_type_path(ty)
-        .and_then(extract_option_segment)
-        .and_then(|pair_path_segment| {
-            let type_params = &pair_path_segment.into_value().arguments;
-            // It should have only on angle-bracketed param ("<String>"):
-            match *type_params {
-                PathArguments::AngleBracketed(ref params) => params.args.first(),
-                _ => None,
+        let crate_root = self.crate_root;
+        let ident = &self.ident;
+        let value_ty = self.value_type();
+        let ty = &*value_ty.ty;
+        let value = |ty: &dyn ToTokens| quote!(value::<#ty>());
+        let (receiver, return_ty) = match self.pattern {
+            BuilderPattern::Owned => (value(builder_ty), quote!(#builder_ty)),
+            BuilderPattern::Mutable => (quote!(b), quote!(&mut #builder_ty)),
+            BuilderPattern::Immutable => (quote!(b), quote!(#builder_ty)),
+        };
+
+        let (ty_args, args) = match (ty, &self.untuple) {
+            (syn::Type::Tuple(tuple), Some(names)) if tuple.elems.len() == names.len() => {
+                (vec![], tuple.elems.iter().map(|elem| value(elem)).collect())
             }
-        })
-        .and_then(|generic_arg| match *generic_arg {
-            GenericArgument::Type(ref ty) => Some(ty),
-            _ => None,
-        })
+            _ if self.strip_bool => (vec![], vec![]),
+            _ if self.transform.is_some() => {
+                let inputs = self.transform.iter().flat_map(|t| &t.inputs);
+                let args = inputs
+                    .map(|input| match input {
+                        syn::Pat::Type(pat_type) => value(&pat_type.ty),
+                        _ => value(&quote!(_)),
+                    })
+                    .collect();
+                (vec![], args)
+            }
+            _ => {
+                let mut ty_args = vec![];
+                if self.generic_into && !self.into_impl {
+                    ty_args.push(quote!(#ty));
+                }
+                // Explicit type arguments can't be given to a setter taking an `impl Into<T>`.
+                let arg = if let Some(ref error_ty) = self.defer_err {
+                    if !self.into_impl {
+                        ty_args.push(quote!(#error_ty));
+                    }
+                    value(&quote!(#crate_root::export::core::result::Result<#ty, #error_ty>))
+                } else {
+                    value(ty)
+                };
+                (ty_args, vec![arg])
+            }
+        };
+        let turbofish = if ty_args.is_empty() {
+            quote!()
+        } else {
+            quote!(::<#(#ty_args),*>)
+        };
+
+        Some(quote!(
+            let _: #return_ty = #receiver.#ident #turbofish(#(#args),*);
+        ))
+    }
 }
 
-/// Helper macro for unit tests. This is _only_ public in order to be accessible
-/// from doc-tests too.
-#[doc(hidden)]
-#[macro_export]
+/// Type of the value which a setter takes, and which wrappers of the field type it strips.
+struct ValueType<'a> {
+    ty: Cow<'a, syn::Type>,
+    /// Whether an `Option` is stripped, by `strip_option` or the `null_setter`.
+    option: bool,
+    /// Whether a `Box` is stripped, by `boxed` or `closure`.
+    boxed: bool,
+    /// Whether a `Cow` is replaced by a reference, by `borrowed`.
+    cow: bool,
+}
+
+/// Returns expression wrapping `bare_value` in `Some`
+fn wrap_expression_in_some(crate_root: &syn::Path, bare_value: impl ToTokens) -> TokenStream {
+    quote!( #crate_root::export::core::option::Option::Some(#bare_value) )
+}
+
+fn wrap_expression_in_box(crate_root: &syn::Path, bare_value: impl ToTokens) -> TokenStream {
+    quote!( #crate_root::export::core::boxed::Box::new(#bare_value) )
+}
+
+fn wrap_expression_in_cow(crate_root: &syn::Path, bare_value: impl ToTokens) -> TokenStream {
+    quote!( #crate_root::export::core::borrow::Cow::Borrowed(#bare_value) )
+}
+
+pub(crate) fn extract_type_from_option(ty: &syn::Type) -> Option<&syn::Type> {
+    extract_type_from_wrapper(
+        ty,
+        &["Option|", "std|option|Option|", "core|option|Option|"],
+    )
+}
+
+pub(crate) fn extract_type_from_box(ty: &syn::Type) -> Option<&syn::Type> {
+    extract_type_from_wrapper(ty, &["Box|", "std|boxed|Box|", "alloc|boxed|Box|"])
+}
+
+/// Returns the type argument of `ty` if it is a path to the type `wrapper`, e.g. `T` of
+/// `std::sync::Arc<T>` for the wrapper `Arc`.
+///
+/// Only the last segments of the paths are compared, as the wrapper is usually imported.
+pub(crate) fn extract_type_from_named_wrapper<'a>(
+    ty: &'a syn::Type,
+    wrapper: &syn::Path,
+) -> Option<&'a syn::Type> {
+    use syn::{GenericArgument, PathArguments};
+
+    let segment = match *ty {
+        syn::Type::Path(ref typepath) if typepath.qself.is_none() => typepath.path.segments.last(),
+        _ => None,
+    }?;
+    if segment.ident != wrapper.segments.last()?.ident {
+        return None;
+    }
+    match segment.arguments {
+        PathArguments::AngleBracketed(ref params) if params.args.len() == 1 => {
+            match params.args.first() {
+                Some(GenericArgument::Type(ty)) => Some(ty),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns the lifetime and the borrowed type of a `Cow<'a, T>`.
+///
+/// Like the other wrappers, `Cow` is only recognized by its path.
+pub(crate) fn extract_borrowed_type_from_cow(
+    ty: &syn::Type,
+) -> Option<(&syn::Lifetime, &syn::Type)> {
+    use syn::{GenericArgument, PathArguments};
+
+    let path = match *ty {
+        syn::Type::Path(ref typepath) if typepath.qself.is_none() => &typepath.path,
+        _ => return None,
+    };
+    let idents_of_path = path.segments.iter().fold(String::new(), |mut acc, v| {
+        acc.push_str(&v.ident.to_string());
+        acc.push('|');
+        acc
+    });
+    if !["Cow|", "std|borrow|Cow|", "alloc|borrow|Cow|"].contains(&idents_of_path.as_str()) {
+        return None;
+    }
+
+    match path.segments.last()?.arguments {
+        PathArguments::AngleBracketed(ref params) if params.args.len() == 2 => {
+            match (&params.args[0], &params.args[1]) {
+                (GenericArgument::Lifetime(lifetime), GenericArgument::Type(ty)) => {
+                    Some((lifetime, ty))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns the key and value types of a `HashMap<K, V>` or `BTreeMap<K, V>`.
+///
+/// Like the other wrappers, maps are only recognized by their path.
+pub(crate) fn extract_key_value_types_from_map(ty: &syn::Type) -> Option<(&syn::Type, &syn::Type)> {
+    use syn::{GenericArgument, PathArguments};
+
+    let path = match *ty {
+        syn::Type::Path(ref typepath) if typepath.qself.is_none() => &typepath.path,
+        _ => return None,
+    };
+    let idents_of_path = path.segments.iter().fold(String::new(), |mut acc, v| {
+        acc.push_str(&v.ident.to_string());
+        acc.push('|');
+        acc
+    });
+    if ![
+        "HashMap|",
+        "std|collections|HashMap|",
+        "BTreeMap|",
+        "std|collections|BTreeMap|",
+        "alloc|collections|BTreeMap|",
+    ]
+    .contains(&idents_of_path.as_str())
+    {
+        return None;
+    }
+
+    // `HashMap` may have a third argument, the hasher.
+    match path.segments.last()?.arguments {
+        PathArguments::AngleBracketed(ref params) if params.args.len() >= 2 => {
+            match (&params.args[0], &params.args[1]) {
+                (GenericArgument::Type(key), GenericArgument::Type(value)) => Some((key, value)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+// adapted from https://stackoverflow.com/a/55277337/469066
+// Note that since syn is a parser, it works with tokens.
+// We cannot know for sure that this is an Option.
+// The user could, for example, `type MaybeString = std::option::Option<String>`
+// We cannot handle those arbitrary names.
+fn extract_type_from_wrapper<'a>(
+    ty: &'a syn::Type,
+    wrapper_paths: &[&str],
+) -> Option<&'a syn::Type> {
+    use syn::punctuated::Pair;
+    use syn::token::PathSep;
+    use syn::{GenericArgument, Path, PathArguments, PathSegment};
+
+    fn extract_type_path(ty: &syn::Type) -> Option<&Path> {
+        match *ty {
+            syn::Type::Path(ref typepath) if typepath.qself.is_none() => Some(&typepath.path),
+            _ => None,
+        }
+    }
+
+    // TODO store (with lazy static) precomputed parsing of Option when support of rust 1.18 will be removed (incompatible with lazy_static)
+    // TODO maybe optimization, reverse the order of segments
+    fn extract_wrapper_segment<'a>(
+        path: &'a Path,
+        wrapper_paths: &[&str],
+    ) -> Option<Pair<&'a PathSegment, &'a PathSep>> {
+        let idents_of_path = path.segments.iter().fold(String::new(), |mut acc, v| {
+            acc.push_str(&v.ident.to_string());
+            acc.push('|');
+            acc
+        });
+        wrapper_paths
+            .iter()
+            .find(|s| idents_of_path == **s)
+            .and_then(|_| path.segments.last().map(Pair::End))
+    }
+
+    extract_type_path(ty)
+        .and_then(|path| extract_wrapper_segment(path, wrapper_paths))
+        .and_then(|pair_path_segment| {
+            let type_params = &pair_path_segment.into_value().arguments;
+            // It should have only on angle-bracketed param ("<String>"):
+            match *type_params {
+                PathArguments::AngleBracketed(ref params) => params.args.first(),
+                _ => None,
+            }
+        })
+        .and_then(|generic_arg| match *generic_arg {
+            GenericArgument::Type(ref ty) => Some(ty),
+            _ => None,
+        })
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[doc(hidden)]
+#[macro_export]
 macro_rules! default_setter {
     () => {
         Setter {
@@ -293,14 +1249,43 @@ macro_rules! default_setter {
             try_setter: false,
             visibility: ::std::borrow::Cow::Owned(parse_quote!(pub)),
             pattern: BuilderPattern::Mutable,
-            attrs: &vec![],
+            attrs: &[],
             ident: syn::Ident::new("foo", ::proc_macro2::Span::call_site()),
+            allow_non_snake_case: false,
             field_ident: &syn::Ident::new("foo", ::proc_macro2::Span::call_site()),
+            field_name: "foo".to_string(),
             field_type: BuilderFieldType::Optional(Box::leak(Box::new(parse_quote!(Foo)))),
             generic_into: false,
+            into_impl: false,
+            value_param: syn::Ident::new("VALUE", ::proc_macro2::Span::call_site()),
             strip_option: false,
+            boxed: false,
+            borrowed: false,
+            cow_owned_setter: None,
+            closure: false,
+            wrap: None,
+            untuple: None,
+            transform: None,
+            strip_bool: false,
+            defer_err: None,
+            from_iter_setter: None,
+            set_nth_setter: None,
+            null_setter: None,
+            maybe_setter: None,
+            conditional_setter: None,
+            aliases: &[],
+            owned_setter: None,
+            const_fn: false,
+            must_use: None,
+            trait_accessor: None,
             deprecation_notes: &Default::default(),
             each: None,
+            each_capacity: None,
+            each_builder: None,
+            extend_setter: None,
+            lazy_setter: None,
+            on_set: None,
+            deprecated: None,
         }
     };
 }
@@ -311,16 +1296,790 @@ mod tests {
     use super::*;
 
     #[test]
-    fn immutable() {
+    fn immutable() {
+        let mut setter = default_setter!();
+        setter.pattern = BuilderPattern::Immutable;
+
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[must_use]
+                #[allow(unused_mut)]
+                pub fn foo(&self, value: Foo) -> Self {
+                    let mut new = ::db::export::core::clone::Clone::clone(self);
+                    new.foo = ::db::export::core::option::Option::Some(value);
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn must_use() {
+        let mut setter = default_setter!();
+        setter.must_use = Some(true);
+
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[must_use]
+                #[allow(unused_mut)]
+                pub fn foo(&mut self, value: Foo) -> &mut Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(value);
+                    new
+                }
+            )
+            .to_string()
+        );
+
+        setter.pattern = BuilderPattern::Owned;
+        setter.must_use = Some(false);
+
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo(self, value: Foo) -> Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(value);
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn mutable() {
+        let mut setter = default_setter!();
+        setter.pattern = BuilderPattern::Mutable;
+
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo(&mut self, value: Foo) -> &mut Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(value);
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn owned() {
+        let mut setter = default_setter!();
+        setter.pattern = BuilderPattern::Owned;
+
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[must_use]
+                #[allow(unused_mut)]
+                pub fn foo(self, value: Foo) -> Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(value);
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn private() {
+        let vis = Cow::Owned(syn::Visibility::Inherited);
+
+        let mut setter = default_setter!();
+        setter.visibility = vis;
+
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                fn foo(&mut self, value: Foo) -> &mut Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(value);
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn generic() {
+        let mut setter = default_setter!();
+        setter.generic_into = true;
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo<VALUE: ::db::export::core::convert::Into<Foo>>(
+                    &mut self,
+                    value: VALUE
+                ) -> &mut Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(value.into());
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn generic_into_impl() {
+        let mut setter = default_setter!();
+        setter.generic_into = true;
+        setter.into_impl = true;
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo(
+                    &mut self,
+                    value: impl ::db::export::core::convert::Into<Foo>
+                ) -> &mut Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(value.into());
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn strip_option() {
+        let ty = parse_quote!(Option<Foo>);
+        let mut setter = default_setter!();
+        setter.strip_option = true;
+        setter.field_type = BuilderFieldType::Optional(&ty);
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo(&mut self, value: Foo) -> &mut Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(
+                        ::db::export::core::option::Option::Some(value)
+                    );
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn strip_option_into() {
+        let ty = parse_quote!(Option<Foo>);
+        let mut setter = default_setter!();
+        setter.strip_option = true;
+        setter.generic_into = true;
+        setter.field_type = BuilderFieldType::Optional(&ty);
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo<VALUE: ::db::export::core::convert::Into<Foo>>(
+                    &mut self,
+                    value: VALUE
+                ) -> &mut Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(
+                        ::db::export::core::option::Option::Some(value.into())
+                    );
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+    #[test]
+    fn strip_option_try_setter() {
+        let ty = parse_quote!(Option<Foo>);
+        let mut setter = default_setter!();
+        setter.strip_option = true;
+        setter.try_setter = true;
+        setter.generic_into = true;
+        setter.field_type = BuilderFieldType::Optional(&ty);
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo<VALUE: ::db::export::core::convert::Into<Foo>>(
+                    &mut self,
+                    value: VALUE
+                ) -> &mut Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(
+                        ::db::export::core::option::Option::Some(value.into())
+                    );
+                    new
+                }
+                pub fn try_foo<VALUE: ::db::export::core::convert::TryInto<Foo>>(
+                    &mut self,
+                    value: VALUE
+                ) -> ::db::export::core::result::Result<&mut Self, VALUE::Error> {
+                    let converted: Foo = value.try_into()?;
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(
+                        ::db::export::core::option::Option::Some(converted)
+                    );
+                    Ok(new)
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn strip_option_boxed_try_setter() {
+        let ty = parse_quote!(Option<Box<Foo>>);
+        let mut setter = default_setter!();
+        setter.strip_option = true;
+        setter.boxed = true;
+        setter.try_setter = true;
+        setter.generic_into = true;
+        setter.field_type = BuilderFieldType::Optional(&ty);
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo<VALUE: ::db::export::core::convert::Into<Foo>>(
+                    &mut self,
+                    value: VALUE
+                ) -> &mut Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(
+                        ::db::export::core::option::Option::Some(
+                            ::db::export::core::boxed::Box::new(value.into())
+                        )
+                    );
+                    new
+                }
+                pub fn try_foo<VALUE: ::db::export::core::convert::TryInto<Foo>>(
+                    &mut self,
+                    value: VALUE
+                ) -> ::db::export::core::result::Result<&mut Self, VALUE::Error> {
+                    let converted: Foo = value.try_into()?;
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(
+                        ::db::export::core::option::Option::Some(
+                            ::db::export::core::boxed::Box::new(converted)
+                        )
+                    );
+                    Ok(new)
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn const_fn() {
+        let mut setter = default_setter!();
+        setter.pattern = BuilderPattern::Owned;
+        setter.const_fn = true;
+
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[must_use]
+                #[allow(unused_mut)]
+                pub const fn foo(self, value: Foo) -> Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(value);
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn defer_err() {
+        let mut setter = default_setter!();
+        setter.defer_err = Some(parse_quote!(FooBuilderError));
+        setter.const_fn = true;
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo<ERROR: ::db::export::core::convert::Into<FooBuilderError> >(
+                    &mut self,
+                    value: ::db::export::core::result::Result<Foo, ERROR>
+                ) -> &mut Self {
+                    let mut new = self;
+                    match value {
+                        ::db::export::core::result::Result::Ok(value) => {
+                            new.foo = ::db::export::core::option::Option::Some(value);
+                        }
+                        ::db::export::core::result::Result::Err(error) => {
+                            if new.__deferred_error.is_none() {
+                                new.__deferred_error = ::db::export::core::option::Option::Some(
+                                    ::db::export::core::convert::Into::into(error)
+                                );
+                            }
+                        }
+                    }
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn split_tokens() {
+        let mut setter = default_setter!();
+        setter.try_setter = true;
+
+        let (setters, try_setters) = setter.split_tokens();
+
+        assert!(setters.to_string().contains("fn foo"), "{}", setters);
+        assert!(!setters.to_string().contains("try_foo"), "{}", setters);
+        assert!(
+            try_setters.to_string().contains("fn try_foo"),
+            "{}",
+            try_setters
+        );
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(#setters #try_setters).to_string()
+        );
+    }
+
+    #[test]
+    fn on_set() {
+        let path: syn::Path = parse_quote!(telemetry::record);
+        let on_set = OnSet::from(path);
+        let mut setter = default_setter!();
+        setter.on_set = Some(&on_set);
+
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo(&mut self, value: Foo) -> &mut Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(value);
+                    telemetry::record("foo");
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn on_set_value() {
+        let on_set = OnSet {
+            path: parse_quote!(telemetry::record),
+            value: true,
+        };
+        let mut setter = default_setter!();
+        setter.on_set = Some(&on_set);
+
+        let tokens = quote!(#setter).to_string();
+        let expected =
+            quote!(telemetry::record("foo", &new.foo as &dyn ::db::export::core::fmt::Debug);)
+                .to_string();
+
+        assert!(tokens.contains(&expected), "{}", tokens);
+    }
+
+    #[test]
+    fn defer_err_generic() {
+        let mut setter = default_setter!();
+        setter.defer_err = Some(parse_quote!(FooBuilderError));
+        setter.generic_into = true;
+
+        let tokens = quote!(#setter).to_string();
+        let expected = quote!(
+            pub fn foo<
+                VALUE: ::db::export::core::convert::Into<Foo>,
+                ERROR: ::db::export::core::convert::Into<FooBuilderError>
+            >(
+                &mut self,
+                value: ::db::export::core::result::Result<VALUE, ERROR>
+            ) -> &mut Self
+        )
+        .to_string();
+
+        assert!(tokens.contains(&expected), "{}", tokens);
+    }
+
+    #[test]
+    fn trait_method() {
+        let mut setter = default_setter!();
+        setter.trait_accessor = Some(syn::Ident::new(
+            "foo_builder_mut",
+            ::proc_macro2::Span::call_site(),
+        ));
+
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                fn foo(&mut self, value: Foo) -> &mut Self {
+                    let mut new = self;
+                    new.foo_builder_mut().foo = ::db::export::core::option::Option::Some(value);
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn boxed_without_box() {
+        let mut setter = default_setter!();
+        setter.boxed = true;
+
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo(&mut self, value: Foo) -> &mut Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(value);
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn borrowed() {
+        let ty = parse_quote!(Option<Cow<'a, str>>);
+        let mut setter = default_setter!();
+        setter.field_type = BuilderFieldType::Optional(&ty);
+        setter.strip_option = true;
+        setter.borrowed = true;
+        setter.try_setter = true;
+        let some = quote!(::db::export::core::option::Option::Some);
+        let cow = quote!(::db::export::core::borrow::Cow);
+
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo(&mut self, value: &'a str) -> &mut Self {
+                    let mut new = self;
+                    new.foo = #some(#some(#cow::Borrowed(value)));
+                    new
+                }
+
+                pub fn try_foo<VALUE: ::db::export::core::convert::TryInto<&'a str>>(&mut self, value: VALUE)
+                    -> ::db::export::core::result::Result<&mut Self, VALUE::Error>
+                {
+                    let converted: &'a str = value.try_into()?;
+                    let mut new = self;
+                    new.foo = #some(#some(#cow::Borrowed(converted)));
+                    Ok(new)
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn borrowed_with_owned_setter() {
+        let ty = parse_quote!(Cow<'a, str>);
+        let mut setter = default_setter!();
+        setter.field_type = BuilderFieldType::Optional(&ty);
+        setter.borrowed = true;
+        setter.cow_owned_setter = Some(syn::Ident::new("foo_owned", Span::call_site()));
+        let some = quote!(::db::export::core::option::Option::Some);
+        let cow = quote!(::db::export::core::borrow::Cow);
+
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo(&mut self, value: &'a str) -> &mut Self {
+                    let mut new = self;
+                    new.foo = #some(#cow::Borrowed(value));
+                    new
+                }
+
+                #[allow(unused_mut)]
+                pub fn foo_owned(
+                    &mut self,
+                    value: <str as ::db::export::core::borrow::ToOwned>::Owned
+                ) -> &mut Self {
+                    let mut new = self;
+                    new.foo = #some(#cow::Owned(value));
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn borrowed_without_cow() {
+        let mut setter = default_setter!();
+        setter.borrowed = true;
+
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo(&mut self, value: Foo) -> &mut Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(value);
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn closure() {
+        let ty = parse_quote!(Option<Box<dyn Fn(u8) -> bool + Send>>);
+        let mut setter = default_setter!();
+        setter.field_type = BuilderFieldType::Optional(&ty);
+        setter.strip_option = true;
+        setter.closure = true;
+        setter.try_setter = true;
+        let some = quote!(::db::export::core::option::Option::Some);
+
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo<VALUE: Fn(u8) -> bool + Send + 'static>(&mut self, value: VALUE) -> &mut Self {
+                    let mut new = self;
+                    new.foo = #some(#some(::db::export::core::boxed::Box::new(value)));
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn wrap() {
+        let ty = parse_quote!(Option<Arc<dyn Handler + Send>>);
+        let wrap = parse_quote!(Arc);
+        let mut setter = default_setter!();
+        setter.field_type = BuilderFieldType::Optional(&ty);
+        setter.strip_option = true;
+        setter.wrap = Some(&wrap);
+        let some = quote!(::db::export::core::option::Option::Some);
+
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo<VALUE: Handler + Send + 'static>(&mut self, value: VALUE) -> &mut Self {
+                    let mut new = self;
+                    new.foo = #some(#some(Arc::new(value)));
+                    new
+                }
+            )
+            .to_string()
+        );
+
+        let ty = parse_quote!(std::sync::Arc<Foo>);
+        setter.field_type = BuilderFieldType::Optional(&ty);
+        setter.strip_option = false;
+
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo(&mut self, value: Foo) -> &mut Self {
+                    let mut new = self;
+                    new.foo = #some(Arc::new(value));
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn closure_with_lifetime() {
+        let ty = parse_quote!(Box<dyn FnMut() + 'a>);
+        let mut setter = default_setter!();
+        setter.field_type = BuilderFieldType::Optional(&ty);
+        setter.closure = true;
+        let some = quote!(::db::export::core::option::Option::Some);
+
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo<VALUE: FnMut() + 'a>(&mut self, value: VALUE) -> &mut Self {
+                    let mut new = self;
+                    new.foo = #some(::db::export::core::boxed::Box::new(value));
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn untuple() {
+        let ty = parse_quote!(Option<(u32, String)>);
+        let mut setter = default_setter!();
+        setter.field_type = BuilderFieldType::Optional(&ty);
+        setter.strip_option = true;
+        setter.untuple = Some(vec![
+            syn::Ident::new("start", ::proc_macro2::Span::call_site()),
+            syn::Ident::new("label", ::proc_macro2::Span::call_site()),
+        ]);
+        let some = quote!(::db::export::core::option::Option::Some);
+
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo(&mut self, start: u32, label: String) -> &mut Self {
+                    let mut new = self;
+                    new.foo = #some(#some((start, label,)));
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn array_setters() {
+        let ty = parse_quote!([u8; N]);
+        let mut setter = default_setter!();
+        setter.field_type = BuilderFieldType::Optional(&ty);
+        setter.from_iter_setter = Some(syn::Ident::new(
+            "try_foo_from_iter",
+            ::proc_macro2::Span::call_site(),
+        ));
+        setter.set_nth_setter = Some(syn::Ident::new(
+            "set_nth_foo",
+            ::proc_macro2::Span::call_site(),
+        ));
+        let core = quote!(::db::export::core);
+        let result = quote!(::db::export::core::result::Result);
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo(&mut self, value: [u8; N]) -> &mut Self {
+                    let mut new = self;
+                    new.foo = #core::option::Option::Some(value);
+                    new
+                }
+                #[allow(unused_mut)]
+                pub fn try_foo_from_iter<ITER: #core::iter::IntoIterator<Item = u8>>(
+                    &mut self,
+                    iter: ITER
+                ) -> #result<&mut Self, ::db::ArrayLengthError> {
+                    let items: #core::vec::Vec<u8> =
+                        #core::iter::Iterator::collect(#core::iter::IntoIterator::into_iter(iter));
+                    let actual = items.len();
+                    let array: [u8; N] = match #core::convert::TryFrom::try_from(items) {
+                        #result::Ok(array) => array,
+                        #result::Err(_) => return #result::Err(
+                            ::db::ArrayLengthError::new("foo", N, actual)
+                        ),
+                    };
+                    let mut new = self;
+                    new.foo = #core::option::Option::Some(array);
+                    #result::Ok(new)
+                }
+                #[allow(unused_mut)]
+                pub fn set_nth_foo(&mut self, index: usize, value: u8) -> &mut Self {
+                    let mut new = self;
+                    new.foo.get_or_insert_with(|| {
+                        [(); N].map(|_| #core::default::Default::default())
+                    })[index] = value;
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn null_setter() {
+        let ty = parse_quote!(Option<Option<Foo>>);
+        let mut setter = default_setter!();
+        setter.field_type = BuilderFieldType::Optional(&ty);
+        setter.null_setter = Some(syn::Ident::new(
+            "set_null_foo",
+            ::proc_macro2::Span::call_site(),
+        ));
+        let some = quote!(::db::export::core::option::Option::Some);
+        let none = quote!(::db::export::core::option::Option::None);
+
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[allow(unused_mut)]
+                pub fn foo(&mut self, value: Foo) -> &mut Self {
+                    let mut new = self;
+                    new.foo = #some(#some(#some(value)));
+                    new
+                }
+                #[allow(unused_mut)]
+                pub fn set_null_foo(&mut self) -> &mut Self {
+                    let mut new = self;
+                    new.foo = #some(#some(#none));
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn owned_setter() {
         let mut setter = default_setter!();
-        setter.pattern = BuilderPattern::Immutable;
+        setter.owned_setter = Some(syn::Ident::new(
+            "with_foo",
+            ::proc_macro2::Span::call_site(),
+        ));
 
         assert_eq!(
             quote!(#setter).to_string(),
             quote!(
                 #[allow(unused_mut)]
-                pub fn foo(&self, value: Foo) -> Self {
-                    let mut new = ::db::export::core::clone::Clone::clone(self);
+                pub fn foo(&mut self, value: Foo) -> &mut Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(value);
+                    new
+                }
+
+                #[must_use]
+                #[allow(unused_mut)]
+                pub fn with_foo(self, value: Foo) -> Self {
+                    let mut new = self;
                     new.foo = ::db::export::core::option::Option::Some(value);
                     new
                 }
@@ -330,17 +2089,33 @@ mod tests {
     }
 
     #[test]
-    fn mutable() {
+    fn maybe_setter() {
+        let ty = parse_quote!(Option<Box<Foo>>);
         let mut setter = default_setter!();
-        setter.pattern = BuilderPattern::Mutable;
+        setter.field_type = BuilderFieldType::Optional(&ty);
+        setter.strip_option = true;
+        setter.boxed = true;
+        setter.generic_into = true;
+        setter.maybe_setter = Some(syn::Ident::new(
+            "maybe_foo",
+            ::proc_macro2::Span::call_site(),
+        ));
+        let option = quote!(::db::export::core::option::Option);
+        let boxed = quote!(::db::export::core::boxed::Box::new);
 
         assert_eq!(
             quote!(#setter).to_string(),
             quote!(
                 #[allow(unused_mut)]
-                pub fn foo(&mut self, value: Foo) -> &mut Self {
+                pub fn foo<VALUE: ::db::export::core::convert::Into<Foo>>(&mut self, value: VALUE) -> &mut Self {
                     let mut new = self;
-                    new.foo = ::db::export::core::option::Option::Some(value);
+                    new.foo = #option::Some(#option::Some(#boxed(value.into())));
+                    new
+                }
+                #[allow(unused_mut)]
+                pub fn maybe_foo<VALUE: ::db::export::core::convert::Into<Foo>>(&mut self, value: #option<VALUE>) -> &mut Self {
+                    let mut new = self;
+                    new.foo = #option::Some(value.map(|value| #boxed(value.into())));
                     new
                 }
             )
@@ -349,17 +2124,29 @@ mod tests {
     }
 
     #[test]
-    fn owned() {
+    fn maybe_setter_without_option() {
         let mut setter = default_setter!();
-        setter.pattern = BuilderPattern::Owned;
+        setter.maybe_setter = Some(syn::Ident::new(
+            "maybe_foo",
+            ::proc_macro2::Span::call_site(),
+        ));
+        let option = quote!(::db::export::core::option::Option);
 
         assert_eq!(
             quote!(#setter).to_string(),
             quote!(
                 #[allow(unused_mut)]
-                pub fn foo(self, value: Foo) -> Self {
+                pub fn foo(&mut self, value: Foo) -> &mut Self {
                     let mut new = self;
-                    new.foo = ::db::export::core::option::Option::Some(value);
+                    new.foo = #option::Some(value);
+                    new
+                }
+                #[allow(unused_mut)]
+                pub fn maybe_foo(&mut self, value: #option<Foo>) -> &mut Self {
+                    let mut new = self;
+                    if let #option::Some(value) = value {
+                        new.foo = #option::Some(value);
+                    }
                     new
                 }
             )
@@ -368,17 +2155,23 @@ mod tests {
     }
 
     #[test]
-    fn private() {
-        let vis = Cow::Owned(syn::Visibility::Inherited);
-
+    fn aliases() {
+        let aliases = [syn::Ident::new("bar", ::proc_macro2::Span::call_site())];
         let mut setter = default_setter!();
-        setter.visibility = vis;
+        setter.aliases = &aliases;
 
         assert_eq!(
             quote!(#setter).to_string(),
             quote!(
                 #[allow(unused_mut)]
-                fn foo(&mut self, value: Foo) -> &mut Self {
+                pub fn foo(&mut self, value: Foo) -> &mut Self {
+                    let mut new = self;
+                    new.foo = ::db::export::core::option::Option::Some(value);
+                    new
+                }
+                #[deprecated(note = "use `foo` instead")]
+                #[allow(unused_mut)]
+                pub fn bar(&mut self, value: Foo) -> &mut Self {
                     let mut new = self;
                     new.foo = ::db::export::core::option::Option::Some(value);
                     new
@@ -389,21 +2182,31 @@ mod tests {
     }
 
     #[test]
-    fn generic() {
+    fn conditional_setter() {
+        let ty = parse_quote!(Option<Foo>);
         let mut setter = default_setter!();
+        setter.field_type = BuilderFieldType::Optional(&ty);
+        setter.strip_option = true;
         setter.generic_into = true;
+        setter.conditional_setter =
+            Some(syn::Ident::new("foo_if", ::proc_macro2::Span::call_site()));
+        let option = quote!(::db::export::core::option::Option);
 
-        #[rustfmt::skip]
         assert_eq!(
             quote!(#setter).to_string(),
             quote!(
                 #[allow(unused_mut)]
-                pub fn foo<VALUE: ::db::export::core::convert::Into<Foo>>(
-                    &mut self,
-                    value: VALUE
-                ) -> &mut Self {
+                pub fn foo<VALUE: ::db::export::core::convert::Into<Foo>>(&mut self, value: VALUE) -> &mut Self {
                     let mut new = self;
-                    new.foo = ::db::export::core::option::Option::Some(value.into());
+                    new.foo = #option::Some(#option::Some(value.into()));
+                    new
+                }
+                #[allow(unused_mut)]
+                pub fn foo_if<VALUE: ::db::export::core::convert::Into<Foo>>(&mut self, condition: bool, value: VALUE) -> &mut Self {
+                    let mut new = self;
+                    if condition {
+                        new.foo = #option::Some(#option::Some(value.into()));
+                    }
                     new
                 }
             )
@@ -412,22 +2215,19 @@ mod tests {
     }
 
     #[test]
-    fn strip_option() {
-        let ty = parse_quote!(Option<Foo>);
+    fn allow_non_snake_case() {
         let mut setter = default_setter!();
-        setter.strip_option = true;
-        setter.field_type = BuilderFieldType::Optional(&ty);
+        setter.ident = syn::Ident::new("setFoo", ::proc_macro2::Span::call_site());
+        setter.allow_non_snake_case = true;
 
-        #[rustfmt::skip]
         assert_eq!(
             quote!(#setter).to_string(),
             quote!(
+                #[allow(non_snake_case)]
                 #[allow(unused_mut)]
-                pub fn foo(&mut self, value: Foo) -> &mut Self {
+                pub fn setFoo(&mut self, value: Foo) -> &mut Self {
                     let mut new = self;
-                    new.foo = ::db::export::core::option::Option::Some(
-                        ::db::export::core::option::Option::Some(value)
-                    );
+                    new.foo = ::db::export::core::option::Option::Some(value);
                     new
                 }
             )
@@ -436,69 +2236,264 @@ mod tests {
     }
 
     #[test]
-    fn strip_option_into() {
-        let ty = parse_quote!(Option<Foo>);
+    fn each_with_bound() {
+        let ty = parse_quote!(C);
+        let each = Each {
+            name: syn::Ident::new("item", ::proc_macro2::Span::call_site()),
+            into: false,
+            key_value: false,
+            bound: vec![parse_quote!(VALUE: Clone), parse_quote!(C: Send)],
+        };
+        let mut setter = default_setter!();
+        setter.setter_enabled = true;
+        setter.field_type = BuilderFieldType::Optional(&ty);
+        setter.each = Some(&each);
+        let core = quote!(::db::export::core);
+
+        let tokens = quote!(#setter).to_string();
+        assert!(tokens.ends_with(
+            &quote!(
+                #[allow(unused_mut)]
+                pub fn item<VALUE>(&mut self, item: VALUE) -> &mut Self
+                where
+                    C: #core::default::Default + #core::iter::Extend<VALUE>,
+                    VALUE: Clone,
+                    C: Send,
+                {
+                    let mut new = self;
+                    new.foo
+                        .get_or_insert_with(#core::default::Default::default)
+                        .extend(#core::option::Option::Some(item));
+                    new
+                }
+            )
+            .to_string()
+        ));
+    }
+
+    #[test]
+    fn transform() {
+        let ty: syn::Type = parse_quote!(Size);
+        let transform: syn::ExprClosure = parse_quote!(|w: u32, h: u32| Size { w, h });
         let mut setter = default_setter!();
-        setter.strip_option = true;
-        setter.generic_into = true;
         setter.field_type = BuilderFieldType::Optional(&ty);
+        setter.transform = Some(&transform);
 
         #[rustfmt::skip]
         assert_eq!(
             quote!(#setter).to_string(),
             quote!(
                 #[allow(unused_mut)]
-                pub fn foo<VALUE: ::db::export::core::convert::Into<Foo>>(
-                    &mut self,
-                    value: VALUE
-                ) -> &mut Self {
+                pub fn foo(&mut self, w: u32, h: u32) -> &mut Self {
                     let mut new = self;
-                    new.foo = ::db::export::core::option::Option::Some(
-                        ::db::export::core::option::Option::Some(value.into())
-                    );
+                    new.foo = ::db::export::core::option::Option::Some(Size { w, h });
                     new
                 }
             )
             .to_string()
         );
     }
+
     #[test]
-    fn strip_option_try_setter() {
-        let ty = parse_quote!(Option<Foo>);
+    fn strip_bool() {
+        let ty: syn::Type = parse_quote!(bool);
         let mut setter = default_setter!();
-        setter.strip_option = true;
-        setter.try_setter = true;
-        setter.generic_into = true;
         setter.field_type = BuilderFieldType::Optional(&ty);
+        setter.strip_bool = true;
+
         #[rustfmt::skip]
         assert_eq!(
             quote!(#setter).to_string(),
             quote!(
                 #[allow(unused_mut)]
-                pub fn foo<VALUE: ::db::export::core::convert::Into<Foo>>(
-                    &mut self,
-                    value: VALUE
-                ) -> &mut Self {
+                pub fn foo(&mut self, ) -> &mut Self {
                     let mut new = self;
-                    new.foo = ::db::export::core::option::Option::Some(
-                        ::db::export::core::option::Option::Some(value.into())
-                    );
+                    new.foo = ::db::export::core::option::Option::Some(true);
                     new
                 }
-                pub fn try_foo<VALUE: ::db::export::core::convert::TryInto<Foo>>(
-                    &mut self,
-                    value: VALUE
-                ) -> ::db::export::core::result::Result<&mut Self, VALUE::Error> {
-                    let converted: Foo = value.try_into()?;
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn each_map() {
+        let ty: syn::Type = parse_quote!(HashMap<String, u8>);
+        let each = Each {
+            name: syn::Ident::new("entry", ::proc_macro2::Span::call_site()),
+            into: true,
+            key_value: true,
+            bound: vec![],
+        };
+        let mut setter = default_setter!();
+        setter.field_type = BuilderFieldType::Optional(&ty);
+        setter.each = Some(&each);
+        let core = quote!(::db::export::core);
+
+        let tokens = quote!(#setter).to_string();
+        assert!(tokens.ends_with(
+            &quote!(
+                #[allow(unused_mut)]
+                pub fn entry<
+                    KEY: #core::convert::Into<String>,
+                    VALUE: #core::convert::Into<u8>
+                >(&mut self, key: KEY, value: VALUE) -> &mut Self
+                where
+                    HashMap<String, u8>: #core::default::Default + #core::iter::Extend<(String, u8)>,
+                {
                     let mut new = self;
-                    new.foo = ::db::export::core::option::Option::Some(
-                        ::db::export::core::option::Option::Some(converted)
-                    );
-                    Ok(new)
+                    new.foo
+                        .get_or_insert_with(#core::default::Default::default)
+                        .extend(#core::option::Option::Some((
+                            #core::convert::Into::into(key),
+                            #core::convert::Into::into(value)
+                        )));
+                    new
                 }
             )
             .to_string()
-        );
+        ));
+    }
+
+    #[test]
+    fn lazy_setter() {
+        let ty: syn::Type = parse_quote!(Vec<u8>);
+        let mut setter = default_setter!();
+        setter.field_type = BuilderFieldType::Optional(&ty);
+        setter.lazy_setter = Some(syn::Ident::new(
+            "foo_with",
+            ::proc_macro2::Span::call_site(),
+        ));
+        let core = quote!(::db::export::core);
+
+        let tokens = quote!(#setter).to_string();
+        assert!(tokens.ends_with(
+            &quote!(
+                #[allow(unused_mut)]
+                pub fn foo_with<FN>(&mut self, f: FN) -> &mut Self
+                where
+                    FN: #core::ops::Fn() -> Vec<u8> + #core::marker::Send + #core::marker::Sync + 'static,
+                {
+                    let mut new = self;
+                    new.foo = #core::option::Option::None;
+                    new.__lazy_foo = #core::option::Option::Some(::db::LazyValue::new(f));
+                    new
+                }
+            )
+            .to_string()
+        ));
+    }
+
+    #[test]
+    fn extend_setter() {
+        let ty: syn::Type = parse_quote!(Vec<u8>);
+        let mut setter = default_setter!();
+        setter.field_type = BuilderFieldType::Optional(&ty);
+        setter.extend_setter = Some(syn::Ident::new(
+            "foo_extend",
+            ::proc_macro2::Span::call_site(),
+        ));
+        let core = quote!(::db::export::core);
+
+        let tokens = quote!(#setter).to_string();
+        assert!(tokens.ends_with(
+            &quote!(
+                #[allow(unused_mut)]
+                pub fn foo_extend<ITER: #core::iter::IntoIterator>(&mut self, iter: ITER) -> &mut Self
+                where
+                    Vec<u8>: #core::default::Default + #core::iter::Extend<ITER::Item>,
+                {
+                    let mut new = self;
+                    new.foo
+                        .get_or_insert_with(#core::default::Default::default)
+                        .extend(iter);
+                    new
+                }
+            )
+            .to_string()
+        ));
+    }
+
+    #[test]
+    fn each_builder() {
+        let ty: syn::Type = parse_quote!(Vec<Server>);
+        let mut setter = default_setter!();
+        setter.field_type = BuilderFieldType::Optional(&ty);
+        setter.each_builder = Some((
+            Box::leak(Box::new(syn::Ident::new(
+                "server",
+                ::proc_macro2::Span::call_site(),
+            ))),
+            parse_quote!(ServerBuilder),
+        ));
+        let core = quote!(::db::export::core);
+
+        let tokens = quote!(#setter).to_string();
+        assert!(tokens.ends_with(
+            &quote!(
+                #[allow(unused_mut)]
+                pub fn server<FN: #core::ops::FnOnce(&mut ServerBuilder)>(&mut self, f: FN)
+                    -> &mut Self
+                {
+                    let mut builder = <ServerBuilder as #core::default::Default>::default();
+                    f(&mut builder);
+                    let mut new = self;
+                    match builder.build() {
+                        #core::result::Result::Ok(item) => {
+                            new.foo
+                                .get_or_insert_with(#core::default::Default::default)
+                                .extend(#core::option::Option::Some(item));
+                        }
+                        #core::result::Result::Err(error) => {
+                            if new.__deferred_error.is_none() {
+                                new.__deferred_error = #core::option::Option::Some(
+                                    #core::convert::Into::into(
+                                        #core::string::ToString::to_string(&error)
+                                    )
+                                );
+                            }
+                        }
+                    }
+                    new
+                }
+            )
+            .to_string()
+        ));
+    }
+
+    #[test]
+    fn each_with_capacity() {
+        let ty: syn::Type = parse_quote!(Vec<u8>);
+        let capacity = parse_quote!(16);
+        let each = Each {
+            name: syn::Ident::new("item", ::proc_macro2::Span::call_site()),
+            into: false,
+            key_value: false,
+            bound: vec![],
+        };
+        let mut setter = default_setter!();
+        setter.field_type = BuilderFieldType::Optional(&ty);
+        setter.each = Some(&each);
+        setter.each_capacity = Some(&capacity);
+        let core = quote!(::db::export::core);
+
+        let tokens = quote!(#setter).to_string();
+        assert!(tokens.ends_with(
+            &quote!(
+                #[allow(unused_mut)]
+                pub fn item<VALUE>(&mut self, item: VALUE) -> &mut Self
+                where
+                    Vec<u8>: #core::default::Default + #core::iter::Extend<VALUE>,
+                {
+                    let mut new = self;
+                    new.foo
+                        .get_or_insert_with(|| <#ty>::with_capacity(16))
+                        .extend(#core::option::Option::Some(item));
+                    new
+                }
+            )
+            .to_string()
+        ));
     }
 
     // including try_setter
@@ -549,6 +2544,7 @@ mod tests {
         assert_eq!(
             quote!(#setter).to_string(),
             quote!(
+                #[must_use]
                 #[allow(unused_mut)]
                 pub fn foo(&self, value: Foo) -> Self {
                     let mut new = ::db::export::core::clone::Clone::clone(self);
@@ -583,6 +2579,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn deprecated() {
+        let deprecated: syn::Attribute = parse_quote!(#[deprecated(note = "use BarBuilder")]);
+        let ty = parse_quote!(Option<Option<Foo>>);
+        let mut setter = default_setter!();
+        setter.deprecated = Some(&deprecated);
+        setter.field_type = BuilderFieldType::Optional(&ty);
+        setter.null_setter = Some(parse_quote!(clear_foo));
+        let some = quote!(::db::export::core::option::Option::Some);
+        let none = quote!(::db::export::core::option::Option::None);
+
+        assert_eq!(
+            quote!(#setter).to_string(),
+            quote!(
+                #[deprecated(note = "use BarBuilder")]
+                #[allow(unused_mut)]
+                pub fn foo(&mut self, value: Foo) -> &mut Self {
+                    let mut new = self;
+                    new.foo = #some(#some(#some(value)));
+                    new
+                }
+
+                #[deprecated(note = "use BarBuilder")]
+                #[allow(unused_mut)]
+                pub fn clear_foo(&mut self) -> &mut Self {
+                    let mut new = self;
+                    new.foo = #some(#some(#none));
+                    new
+                }
+            )
+            .to_string()
+        );
+    }
+
     #[test]
     fn setter_disabled() {
         let mut setter = default_setter!();
@@ -622,19 +2652,89 @@ mod tests {
         );
     }
 
+    #[test]
+    fn api_assertion() {
+        let mut setter: Setter = default_setter!();
+        setter.pattern = BuilderPattern::Mutable;
+        let builder_ty = quote!(FooBuilder);
+
+        assert_eq!(
+            setter.api_assertion(&builder_ty).unwrap().to_string(),
+            quote!(
+                let _: &mut FooBuilder = b.foo(value::<Foo>());
+            )
+            .to_string()
+        );
+
+        setter.pattern = BuilderPattern::Owned;
+        setter.generic_into = true;
+        setter.defer_err = Some(parse_quote!(FooBuilderError));
+
+        #[rustfmt::skip]
+        assert_eq!(
+            setter.api_assertion(&builder_ty).unwrap().to_string(),
+            quote!(
+                let _: FooBuilder = value::<FooBuilder>().foo::<Foo, FooBuilderError>(
+                    value::<::db::export::core::result::Result<Foo, FooBuilderError> >()
+                );
+            )
+            .to_string()
+        );
+
+        setter.visibility = Cow::Owned(syn::Visibility::Inherited);
+        assert!(setter.api_assertion(&builder_ty).is_none());
+    }
+
     #[test]
     fn extract_type_from_option_on_simple_type() {
         let ty_foo = parse_quote!(Foo);
         assert_eq!(extract_type_from_option(&ty_foo), None);
 
-        for s in vec![
+        for s in &[
             parse_quote!(Option<Foo>),
             parse_quote!(std::option::Option<Foo>),
             parse_quote!(::std::option::Option<Foo>),
             parse_quote!(core::option::Option<Foo>),
             parse_quote!(::core::option::Option<Foo>),
         ] {
-            assert_eq!(extract_type_from_option(&s), Some(&ty_foo));
+            assert_eq!(extract_type_from_option(s), Some(&ty_foo));
+        }
+    }
+
+    #[test]
+    fn extract_type_from_box_on_simple_type() {
+        let ty_foo = parse_quote!(Foo);
+        assert_eq!(extract_type_from_box(&ty_foo), None);
+        assert_eq!(extract_type_from_box(&parse_quote!(Option<Foo>)), None);
+
+        for s in &[
+            parse_quote!(Box<Foo>),
+            parse_quote!(std::boxed::Box<Foo>),
+            parse_quote!(::alloc::boxed::Box<Foo>),
+        ] {
+            assert_eq!(extract_type_from_box(s), Some(&ty_foo));
+        }
+    }
+
+    #[test]
+    fn extract_borrowed_type_from_cow_on_simple_type() {
+        let ty_foo: syn::Type = parse_quote!([u8]);
+        let lifetime: syn::Lifetime = parse_quote!('a);
+        assert_eq!(extract_borrowed_type_from_cow(&parse_quote!(Foo)), None);
+        assert_eq!(
+            extract_borrowed_type_from_cow(&parse_quote!(Box<[u8]>)),
+            None
+        );
+
+        for s in &[
+            parse_quote!(Cow<'a, [u8]>),
+            parse_quote!(std::borrow::Cow<'a, [u8]>),
+            parse_quote!(::alloc::borrow::Cow<'a, [u8]>),
+        ] {
+            assert_eq!(
+                extract_borrowed_type_from_cow(s),
+                Some((&lifetime, &ty_foo))
+            );
         }
     }
 }
@@ -0,0 +1,215 @@
+use std::borrow::Cow;
+
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+use syn::spanned::Spanned;
+
+use crate::change_span;
+
+/// Method which serializes the fields set in the builder into a JSON object, implementing
+/// `quote::ToTokens`.
+///
+/// Unset fields are left out, so the object describes a partial configuration.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::{ToMapMethod, ToMapField};
+/// # fn main() {
+/// #    let to_map = default_to_map_method!();
+/// #
+/// #    assert_eq!(quote!(#to_map).to_string(), quote!(
+/// #[doc = "..."]
+/// pub fn to_map(&self) -> ::derive_builder::export::core::result::Result<
+///     ::derive_builder::export::json::Map<::derive_builder::export::core::string::String, ::derive_builder::export::json::Value>,
+///     ::derive_builder::export::json::Error,
+/// >
+/// where
+///     u32: ::derive_builder::export::json::Serialize,
+/// {
+///     let mut map = ::derive_builder::export::json::Map::new();
+///     if let ::derive_builder::export::core::option::Option::Some(ref value) = self.foo {
+///         map.insert(
+///             ::derive_builder::export::core::string::String::from("foo"),
+///             ::derive_builder::export::json::to_value(value)?,
+///         );
+///     }
+///     ::derive_builder::export::core::result::Result::Ok(map)
+/// }
+/// #    ).to_string());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ToMapMethod<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Enables code generation for this method.
+    pub enabled: bool,
+    /// Visibility of the method, e.g. `syn::Visibility::Public`.
+    pub visibility: Cow<'a, syn::Visibility>,
+    /// Fields of the builder, in declaration order.
+    pub fields: Vec<ToMapField<'a>>,
+}
+
+/// A field which is serialized by a `ToMapMethod`.
+#[derive(Debug)]
+pub struct ToMapField<'a> {
+    /// Name of the builder field.
+    pub field_ident: &'a syn::Ident,
+    /// Key of the field in the object.
+    pub key: String,
+    /// Type of the value which is serialized, i.e. the contents of the builder field's `Option`,
+    /// or the builder field itself if `optional` is `false`.
+    pub ty: &'a syn::Type,
+    /// Whether the builder field is an `Option` which is `None` until the setter is called.
+    pub optional: bool,
+}
+
+impl<'a> ToTokens for ToMapMethod<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if !self.enabled {
+            return;
+        }
+
+        let crate_root = self.crate_root;
+        let core = quote!(#crate_root::export::core);
+        let json = quote!(#crate_root::export::json);
+        let vis = &self.visibility;
+        // Point errors about types which aren't `Serialize` at the field.
+        let bounds = self.fields.iter().map(|f| {
+            let ty = f.ty;
+            let crate_root = change_span(crate_root.into_token_stream(), ty.span());
+            quote_spanned!(ty.span()=> #ty: #crate_root::export::json::Serialize,)
+        });
+        let inserts = self.fields.iter().map(|f| {
+            let field_ident = f.field_ident;
+            let key = &f.key;
+            let insert = quote!(
+                map.insert(#core::string::String::from(#key), #json::to_value(value)?);
+            );
+            if f.optional {
+                quote!(
+                    if let #core::option::Option::Some(ref value) = self.#field_ident {
+                        #insert
+                    }
+                )
+            } else {
+                quote!({
+                    let value = &self.#field_ident;
+                    #insert
+                })
+            }
+        });
+
+        tokens.append_all(quote!(
+            #[doc = "Serialize the fields which have been set into a JSON object, keyed by field name.\n\nUnset fields are left out."]
+            #vis fn to_map(&self) -> #core::result::Result<#json::Map<#core::string::String, #json::Value>, #json::Error>
+            where
+                #(#bounds)*
+            {
+                let mut map = #json::Map::new();
+                #(#inserts)*
+                #core::result::Result::Ok(map)
+            }
+        ));
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[cfg(test)] // This contains a Box::leak, so is suitable only for tests
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_to_map_method {
+    () => {
+        ToMapMethod {
+            // Deliberately don't use the default value here - make sure
+            // that all test cases are passing crate_root through properly.
+            crate_root: &parse_quote!(::db),
+            enabled: true,
+            visibility: ::std::borrow::Cow::Owned(parse_quote!(pub)),
+            fields: vec![ToMapField {
+                field_ident: Box::leak(Box::new(syn::Ident::new(
+                    "foo",
+                    ::proc_macro2::Span::call_site(),
+                ))),
+                key: "foo".to_string(),
+                ty: Box::leak(Box::new(parse_quote!(u32))),
+                optional: true,
+            }],
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    fn expected(insert: TokenStream) -> TokenStream {
+        let map = quote!(::db::export::json::Map<::db::export::core::string::String, ::db::export::json::Value>);
+        quote!(
+            #[doc = "Serialize the fields which have been set into a JSON object, keyed by field name.\n\nUnset fields are left out."]
+            pub fn to_map(&self) -> ::db::export::core::result::Result<#map, ::db::export::json::Error>
+            where
+                u32: ::db::export::json::Serialize,
+            {
+                let mut map = ::db::export::json::Map::new();
+                #insert
+                ::db::export::core::result::Result::Ok(map)
+            }
+        )
+    }
+
+    #[test]
+    fn optional() {
+        let to_map = default_to_map_method!();
+        let insert = quote!(
+            map.insert(::db::export::core::string::String::from("foo"), ::db::export::json::to_value(value)?);
+        );
+
+        assert_eq!(
+            quote!(#to_map).to_string(),
+            expected(quote!(
+                if let ::db::export::core::option::Option::Some(ref value) = self.foo {
+                    #insert
+                }
+            ))
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn custom_field_type() {
+        let mut to_map = default_to_map_method!();
+        to_map.fields[0].optional = false;
+        let insert = quote!(
+            map.insert(::db::export::core::string::String::from("foo"), ::db::export::json::to_value(value)?);
+        );
+
+        assert_eq!(
+            quote!(#to_map).to_string(),
+            expected(quote!({
+                let value = &self.foo;
+                #insert
+            }))
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn disabled() {
+        let mut to_map = default_to_map_method!();
+        to_map.enabled = false;
+
+        assert_eq!(quote!(#to_map).to_string(), quote!().to_string());
+    }
+}
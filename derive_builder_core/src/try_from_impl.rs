@@ -0,0 +1,210 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+
+use crate::builder::allow_lints_attr;
+
+/// `TryFrom` impl of the built struct for the builder, which calls the build method,
+/// implementing `quote::ToTokens`.
+///
+/// # Examples
+///
+/// Will expand to something like the following (depending on settings):
+///
+/// ```rust,ignore
+/// # extern crate proc_macro2;
+/// # #[macro_use]
+/// # extern crate quote;
+/// # extern crate syn;
+/// # #[macro_use]
+/// # extern crate derive_builder_core;
+/// # use derive_builder_core::TryFromImpl;
+/// # fn main() {
+/// #    let try_from = default_try_from_impl!();
+/// #
+/// #    assert_eq!(quote!(#try_from).to_string(), quote!(
+/// #[automatically_derived]
+/// impl ::derive_builder::export::core::convert::TryFrom<FooBuilder> for Foo {
+///     type Error = FooBuilderError;
+///
+///     fn try_from(builder: FooBuilder) -> ::derive_builder::export::core::result::Result<Self, Self::Error> {
+///         builder.build()
+///     }
+/// }
+/// #    ).to_string());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TryFromImpl<'a> {
+    /// Path to the root of the derive_builder crate.
+    pub crate_root: &'a syn::Path,
+    /// Enables code generation for this impl.
+    pub enabled: bool,
+    /// Name of the builder struct.
+    pub builder_ty: syn::Ident,
+    /// Type parameters and lifetimes attached to the builder struct.
+    pub generics: Option<&'a syn::Generics>,
+    /// Whether the type parameters must be `Clone`, because the inherent `impl` of the builder
+    /// requires it.
+    pub clone_bounds: bool,
+    /// Lints to allow on the impl, e.g. `missing_docs`.
+    pub allow_lints: &'a [syn::Path],
+    /// Name of the build method which is called.
+    pub build_fn: &'a syn::Ident,
+    /// Type of the target struct.
+    pub target_ty: &'a syn::Ident,
+    /// Type which the build method converts the target struct into, and returns instead.
+    pub into_ty: Option<&'a syn::Type>,
+    /// Type of error.
+    pub error_ty: syn::Path,
+    /// Whether the build method returns the builder together with the error.
+    pub return_builder: bool,
+}
+
+impl<'a> ToTokens for TryFromImpl<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if !self.enabled {
+            return;
+        }
+
+        let crate_root = self.crate_root;
+        let builder_ty = &self.builder_ty;
+        let allow_lints = allow_lints_attr(self.allow_lints);
+        let generics = self.generics.cloned().unwrap_or_default();
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let mut predicates: Vec<TokenStream> = where_clause
+            .map(|w| w.predicates.iter().map(|p| quote!(#p,)).collect())
+            .unwrap_or_default();
+        if self.clone_bounds {
+            predicates.extend(generics.type_params().map(|param| {
+                let ident = &param.ident;
+                quote!(#ident: #crate_root::export::core::clone::Clone,)
+            }));
+        }
+        let where_clause = if predicates.is_empty() {
+            quote!()
+        } else {
+            quote!(where #(#predicates)*)
+        };
+
+        let target_ty = self.target_ty;
+        let built_ty = match self.into_ty {
+            Some(into_ty) => quote!(#into_ty),
+            None => quote!(#target_ty #ty_generics),
+        };
+        let error_ty = &self.error_ty;
+        let error_ty = if self.return_builder {
+            quote!((#builder_ty #ty_generics, #error_ty))
+        } else {
+            quote!(#error_ty)
+        };
+        let build_fn = self.build_fn;
+
+        tokens.append_all(quote!(
+            #[automatically_derived]
+            #allow_lints
+            impl #impl_generics #crate_root::export::core::convert::TryFrom<#builder_ty #ty_generics> for #built_ty #where_clause {
+                type Error = #error_ty;
+
+                fn try_from(builder: #builder_ty #ty_generics) -> #crate_root::export::core::result::Result<Self, Self::Error> {
+                    builder.#build_fn()
+                }
+            }
+        ));
+    }
+}
+
+/// Helper macro for unit tests. This is _only_ public in order to be accessible
+/// from doc-tests too.
+#[cfg(test)] // This contains a Box::leak, so is suitable only for tests
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_try_from_impl {
+    () => {
+        TryFromImpl {
+            // Deliberately don't use the default value here - make sure
+            // that all test cases are passing crate_root through properly.
+            crate_root: &parse_quote!(::db),
+            enabled: true,
+            builder_ty: syn::Ident::new("FooBuilder", ::proc_macro2::Span::call_site()),
+            generics: None,
+            clone_bounds: false,
+            allow_lints: &[],
+            build_fn: Box::leak(Box::new(syn::Ident::new(
+                "build",
+                ::proc_macro2::Span::call_site(),
+            ))),
+            target_ty: Box::leak(Box::new(syn::Ident::new(
+                "Foo",
+                ::proc_macro2::Span::call_site(),
+            ))),
+            into_ty: None,
+            error_ty: parse_quote!(FooBuilderError),
+            return_builder: false,
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn try_from() {
+        let try_from = default_try_from_impl!();
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#try_from).to_string(),
+            quote!(
+                #[automatically_derived]
+                impl ::db::export::core::convert::TryFrom<FooBuilder> for Foo {
+                    type Error = FooBuilderError;
+
+                    fn try_from(builder: FooBuilder) -> ::db::export::core::result::Result<Self, Self::Error> {
+                        builder.build()
+                    }
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn generic_return_builder() {
+        let generics: syn::Generics = parse_quote!(<T>);
+        let into_ty: syn::Type = parse_quote!(Bar);
+        let mut try_from = default_try_from_impl!();
+        try_from.generics = Some(&generics);
+        try_from.clone_bounds = true;
+        try_from.into_ty = Some(&into_ty);
+        try_from.return_builder = true;
+
+        #[rustfmt::skip]
+        assert_eq!(
+            quote!(#try_from).to_string(),
+            quote!(
+                #[automatically_derived]
+                impl<T> ::db::export::core::convert::TryFrom<FooBuilder<T> > for Bar
+                where
+                    T: ::db::export::core::clone::Clone,
+                {
+                    type Error = (FooBuilder<T>, FooBuilderError);
+
+                    fn try_from(builder: FooBuilder<T>) -> ::db::export::core::result::Result<Self, Self::Error> {
+                        builder.build()
+                    }
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn disabled() {
+        let mut try_from = default_try_from_impl!();
+        try_from.enabled = false;
+
+        assert_eq!(quote!(#try_from).to_string(), quote!().to_string());
+    }
+}
@@ -17,13 +17,25 @@ use proc_macro::TokenStream;
     Builder,
     attributes(
         builder,
+        builder_build_impl_attr,
         builder_field_attr,
         builder_impl_attr,
         builder_setter_attr,
-        builder_struct_attr
+        builder_setters_impl_attr,
+        builder_struct_attr,
+        builder_try_setters_impl_attr
     )
 )]
 pub fn derive(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as syn::DeriveInput);
     derive_builder_core::builder_for_struct(ast).into()
 }
+
+/// Create a builder struct for the annotated struct, and add a `builder()` function to it.
+///
+/// See the `derive_builder` crate documentation for more details.
+#[proc_macro_attribute]
+pub fn builder_attr(args: TokenStream, input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as syn::DeriveInput);
+    derive_builder_core::builder_for_struct_attr(args.into(), ast).into()
+}